@@ -29,6 +29,9 @@ pub const TEXT_ATTR_SIZE: &str = "size";
 pub const TEXT_ATTR_COLOR: &str = "color";
 pub const TEXT_ATTR_BACK_GROUND: &str = "background";
 
+/// Marks a misspelled word, with value `true`. It shows as a CSS class, not as a style.
+pub const TEXT_ATTR_SPELL_ERROR: &str = "spell-error";
+
 /// At a minimum there shall be support for a paragraph format, and one text format.
 /// These shall have default labels:
 pub static NAME_P_BLOCK: &str = "F_P-BLOCK";
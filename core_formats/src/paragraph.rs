@@ -15,7 +15,7 @@ use delta::operations::DeltaOperation;
 use dom::dom_element::DomElement;
 use node_tree::cursor::Cursor;
 use node_tree::doc_node::DocumentNode;
-use node_tree::format_trait::FormatTait;
+use node_tree::format_trait::{BackspaceEmptyBehavior, FormatTait};
 use std::sync::Arc;
 
 /// # P_BLOCK_TAG
@@ -95,6 +95,11 @@ impl FormatTait for Pblock {
         panic!("{} -- You called: {}()", NAME_P_BLOCK, "split_leaf");
     }
 
+    /// A paragraph has no format to leave, the empty line is removed
+    fn backspace_at_empty_block_behavior(&self) -> BackspaceEmptyBehavior {
+        BackspaceEmptyBehavior::MergeWithPrev
+    }
+
     fn is_same_format(&self, _left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> bool {
         if NAME_P_BLOCK.eq(right.get_formatter().format_name()) {
             return true;
@@ -5,10 +5,13 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::format_const::{TEXT_ATTR_BACK_GROUND, TEXT_ATTR_COLOR, TEXT_ATTR_FONT, TEXT_ATTR_SIZE};
+use crate::format_const::{
+    TEXT_ATTR_BACK_GROUND, TEXT_ATTR_COLOR, TEXT_ATTR_FONT, TEXT_ATTR_SIZE, TEXT_ATTR_SPELL_ERROR,
+};
 use crate::util::lookup::{AttributesLookup, Attributor};
 use anyhow::Result;
 use delta::attributes::Attributes;
+use delta::types::attr_val::AttrVal;
 use dom::dom_element::DomElement;
 /// Text attributes apply an HTML attribute to a given HTML element.
 ///
@@ -24,6 +27,9 @@ use once_cell::sync::OnceCell;
 // font: verdana, times, ...
 // size: 6, 8
 // color: red,green, ..., or rgb(128, 128, 0)
+// spell-error: true
+static SPELL_ERROR_CLASS: &str = "ql-spell-error";
+
 pub static TEXT_ATTRIBUTES: OnceCell<AttributesLookup> = OnceCell::new();
 pub fn initialise() {
     if let Some(_attr) = TEXT_ATTRIBUTES.get() {
@@ -34,6 +40,7 @@ pub fn initialise() {
     attr.fill_one(TEXT_ATTR_SIZE, "font-size");
     attr.fill_one(TEXT_ATTR_COLOR, "color");
     attr.fill_one(TEXT_ATTR_BACK_GROUND, "background-color");
+    attr.fill_one(TEXT_ATTR_SPELL_ERROR, SPELL_ERROR_CLASS);
     TEXT_ATTRIBUTES
         .set(attr)
         .expect("did you call TEXT_ATTRIBUTES::initialise() twice?");
@@ -45,10 +52,17 @@ pub fn initialise() {
 /// The other formats are style elements.
 ///
 /// For that reason we single out the `ql-font-` value which is found for the font selection.
+/// The spell error mark is a class without value, and is set with the value `true`.
 pub(crate) fn apply_text_attributes(element: &DomElement, attr: &Attributes) -> Result<()> {
     let classes = element.get_classes();
     for (format, attr_val) in Attributor::selected(attr, TEXT_ATTRIBUTES.get().unwrap()) {
-        if attr_val.is_string() {
+        if format == SPELL_ERROR_CLASS {
+            if attr_val == &AttrVal::Bool(true) {
+                DomElement::add_class(&classes, format);
+            } else {
+                DomElement::remove_class(&classes, format);
+            }
+        } else if attr_val.is_string() {
             if format == "ql-font-" {
                 let k = [format, attr_val.str_val()?].concat();
                 DomElement::add_class(&classes, &k);
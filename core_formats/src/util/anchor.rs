@@ -0,0 +1,54 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::collections::HashSet;
+
+/// # slugify()
+///
+/// Returns the anchor ID for a header text: lower case, with a hyphen for each run of
+/// spaces and other non alphanumeric characters.
+///
+/// `"C++ & Rust: a tour!"` --> `c-rust-a-tour`
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// # anchor_ids()
+///
+/// Returns the anchor ID for each header text, in the same order. A duplicate ID gets a
+/// suffix: `intro`, `intro-2`, `intro-3`, ...
+///
+/// Only the given texts are deduplicated, so the same document always gets the same IDs.
+/// An empty text gives an empty ID.
+pub fn anchor_ids<'a>(texts: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut used: HashSet<String> = HashSet::new();
+    let mut ids: Vec<String> = Vec::new();
+    for text in texts {
+        let slug = slugify(text);
+        if slug.is_empty() {
+            ids.push(slug);
+            continue;
+        }
+        let mut id = slug.clone();
+        let mut n = 1;
+        while used.contains(&id) {
+            n += 1;
+            id = format!("{}-{}", slug, n);
+        }
+        used.insert(id.clone());
+        ids.push(id);
+    }
+    ids
+}
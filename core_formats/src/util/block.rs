@@ -6,6 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::util::block_format;
+use crate::util::block_format::BlockAttributeRegistry;
 use crate::P_FORMAT;
 use anyhow::Result;
 use delta::attributes::{compose, Attributes};
@@ -124,11 +125,12 @@ pub fn apply_attributes(
 
 /// # drop_attributes()
 ///
-/// drops ALL the attributes from a given doc-node
+/// drops ALL the attributes from a given doc-node. The HTML representation of every
+/// attribute in the `BlockAttributeRegistry` is removed from the DOM element.
 pub fn drop_attributes(doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
     let element = doc_node.get_dom_element().unwrap();
+    block_format::apply(element, &BlockAttributeRegistry::remove_all_attr())?;
     let attr = Attributes::default();
-    block_format::apply(element, &attr)?;
     let operation = DeltaOperation::insert_attr(
         doc_node.get_operation().insert_value().clone(),
         attr.clone(),
@@ -5,42 +5,107 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::util::lookup::{AttributesLookup, Attributor};
 use anyhow::Result;
 use delta::attributes::Attributes;
+use delta::types::attr_val::AttrVal;
 use dom::dom_element::DomElement;
-use once_cell::sync::OnceCell;
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
 
-pub static BLOCK_FORMAT: OnceCell<AttributesLookup> = OnceCell::new();
+/// Registered block attributes: `(key, class prefix)`, see `BlockAttributeRegistry`
+static BLOCK_ATTRIBUTES: Lazy<RwLock<Vec<(String, Option<String>)>>> =
+    Lazy::new(|| RwLock::new(Vec::new()));
 
+/// Registers the common block attributes, which are shown as a class of the block element
 pub fn initialise() {
-    if let Some(_attr) = BLOCK_FORMAT.get() {
-        return;
-    }
-    let mut attr = AttributesLookup::new(3);
-    attr.fill_one("direction", "ql-direction-");
-    attr.fill_one("align", "ql-align-");
-    attr.fill_one("indent", "ql-indent-");
-
-    BLOCK_FORMAT
-        .set(attr)
-        .expect("did you call BLOCK_FORMAT::initialise() twice?");
+    BlockAttributeRegistry::register_class_attr("direction", "ql-direction-");
+    BlockAttributeRegistry::register_class_attr("align", "ql-align-");
+    BlockAttributeRegistry::register_class_attr("indent", "ql-indent-");
 }
 
-/// The attributes are a map: `key -> val`, where the `key` match the `key` in `BLOCK_FORMAT`
+/// The attributes are a map: `key -> val`. Every registered block attribute with a class
+/// prefix, see `BlockAttributeRegistry::register_class_attr()`, sets the class `prefix + val`,
+/// or removes it for a `Null` value.
 pub fn apply(element: &DomElement, attr: &Attributes) -> Result<()> {
     let classes = element.get_classes();
-    for (format, attr_val) in Attributor::selected(attr, BLOCK_FORMAT.get().unwrap()) {
+    for (key, format) in BlockAttributeRegistry::class_attrs() {
+        let Some(attr_val) = attr.get(key.as_str()) else {
+            continue;
+        };
         if !attr_val.is_null() {
             let k = if attr_val.is_string() {
-                [format, attr_val.str_val()?].concat()
+                [format.as_str(), attr_val.str_val()?].concat()
             } else {
-                [format.to_string(), attr_val.number_val()?.to_string()].concat()
+                [format, attr_val.number_val()?.to_string()].concat()
             };
             DomElement::add_class(&classes, &k);
         } else {
-            DomElement::remove_class_starts_with(&classes, format);
+            DomElement::remove_class_starts_with(&classes, &format);
         }
     }
     Ok(())
 }
+
+/// # BlockAttributeRegistry
+///
+/// Keeps the keys of all known block attributes, so that `drop_attributes()` can remove
+/// them in one pass. The common block attributes (`direction`, `align`, `indent`) are
+/// registered by `initialise()`, with the prefix of their class. A format with its own block
+/// attribute, like `heading`, registers it when the format is created. Such an attribute
+/// has no class: the format shows it with its own element.
+pub struct BlockAttributeRegistry {}
+
+impl BlockAttributeRegistry {
+    /// Registering the same key twice is allowed
+    pub fn register_attr(key: &str) {
+        let mut keys = BLOCK_ATTRIBUTES.write().unwrap();
+        if !keys.iter().any(|(k, _prefix)| k == key) {
+            keys.push((key.to_string(), None));
+        }
+    }
+
+    /// Registers a block attribute which `apply()` shows as the class `prefix + value`
+    pub fn register_class_attr(key: &str, prefix: &str) {
+        let mut keys = BLOCK_ATTRIBUTES.write().unwrap();
+        match keys.iter_mut().find(|(k, _prefix)| k == key) {
+            Some((_key, p)) => *p = Some(prefix.to_string()),
+            None => keys.push((key.to_string(), Some(prefix.to_string()))),
+        }
+    }
+
+    pub fn is_registered(key: &str) -> bool {
+        BLOCK_ATTRIBUTES
+            .read()
+            .unwrap()
+            .iter()
+            .any(|(k, _prefix)| k == key)
+    }
+
+    pub fn keys() -> Vec<String> {
+        BLOCK_ATTRIBUTES
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(k, _prefix)| k.clone())
+            .collect()
+    }
+
+    /// Returns `(key, class prefix)` of the attributes shown as a class, in registration order
+    pub fn class_attrs() -> Vec<(String, String)> {
+        BLOCK_ATTRIBUTES
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(|(k, prefix)| Some((k.clone(), prefix.clone()?)))
+            .collect()
+    }
+
+    /// Returns the attributes which remove all known block attributes: `{key: Null}`
+    pub fn remove_all_attr() -> Attributes {
+        let mut attr = Attributes::default();
+        for (key, _prefix) in BLOCK_ATTRIBUTES.read().unwrap().iter() {
+            attr.insert(key.clone(), AttrVal::Null);
+        }
+        attr
+    }
+}
@@ -43,6 +43,11 @@ impl<'a> AttributesLookup<'a> {
         self.order.push(attrib);
         self.map.insert(attrib, html_tag);
     }
+
+    /// Returns the HTML tag, or HTML attribute name, for one key
+    pub fn get(&self, attrib: &str) -> Option<&'a str> {
+        self.map.get(attrib).copied()
+    }
 }
 
 /// This allows the user to loop over all attributes in a given order
@@ -138,4 +143,12 @@ mod test {
             println!("found 'all key': ({})", i);
         }
     }
+
+    #[test]
+    fn test_lookup_get() {
+        initialise();
+        let lookup = LOOKUP.get().unwrap();
+        assert_eq!(lookup.get("key_2"), Some("html_tag_2"));
+        assert_eq!(lookup.get("key_7"), None);
+    }
 }
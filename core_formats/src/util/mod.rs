@@ -8,6 +8,7 @@
 pub mod block; //helper functions for block FormatTrait implementations
 pub mod node_morph; //helper functions to manipulate content in the node
 
+pub mod anchor; //anchor IDs for header blocks
 pub mod block_format;
 pub mod lookup;
 //pub mod delta_op_util;
@@ -5,7 +5,6 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::util::string_util::StringUtils;
 use crate::TEXT_FORMAT;
 use anyhow::Result;
 use delta::operations::DeltaOperation;
@@ -13,9 +12,13 @@ use delta::types::ops_kind::OpKind;
 use dom::dom_text::find_dom_text;
 use node_tree::cursor::{Cursor, CursorLocation};
 use node_tree::doc_node::DocumentNode;
+use node_tree::dom_doc_node::DomDocNode;
 use node_tree::dom_doc_tree_morph::{append, insert_after, insert_at_index, unlink};
+use node_tree::error::Error::{
+    ChildIndexOutOfRange, InvalidParentFormat, MoveDocRoot, MoveIntoDescendant,
+};
 use node_tree::format_trait::FormatTait;
-use node_tree::tree_traverse::{next_sibling, prev_sibling};
+use node_tree::tree_traverse::{first_node, is_doc_root, next_node, next_sibling, prev_sibling};
 ///==============================================================================================
 /// Tree node morphing:
 /// Nodes will deform, by adding/removing text, or splitting the text
@@ -351,6 +354,93 @@ pub fn try_3_way_merge_block(middle_block: &Arc<DocumentNode>) -> Result<()> {
     Ok(())
 }
 
+/// # normalize()
+///
+/// Merges all adjacent nodes which should have been merged, for example after pasting HTML,
+/// or applying several operations. This is one merge pass over the whole document:
+///  - zero length wrappers, like `<UL>` or `<A>`, are merged by `try_3_way_merge_block()`
+///  - text nodes with equal attributes are merged by `try_3_way_merge_text()`
+///
+/// Blocks with a length, like `<P>`, are never merged. Two paragraphs are the same format,
+/// but they are 2 lines of the document. Embeds, like an image, are not merged either.
+///
+/// Calling `normalize()` twice gives the same tree as calling it once.
+pub fn normalize(root: &Arc<DocumentNode>) -> Result<()> {
+    if root.child_count() == 0 {
+        return Ok(());
+    }
+    //a merged wrapper may bring a nested wrapper next to another one
+    while merge_wrappers(root)? {}
+    merge_texts(root)
+}
+
+/// Returns true when a wrapper has been merged
+fn merge_wrappers(root: &Arc<DocumentNode>) -> Result<bool> {
+    let mut changed = false;
+    let mut dn_o = Some(first_node(root));
+    while let Some(doc_node) = dn_o {
+        let mut node = doc_node;
+        if node.op_len() == 0 && node.child_count() > 0 {
+            let parent = node.get_parent().unwrap();
+            loop {
+                let siblings = parent.child_count();
+                let prev = prev_sibling(&node);
+                try_3_way_merge_block(&node)?;
+                if parent.get_child_index(&node).is_none() {
+                    node = prev.unwrap(); //merged into the left hand block
+                }
+                if parent.child_count() == siblings {
+                    break;
+                }
+                changed = true;
+            }
+        }
+        dn_o = next_node(&node);
+    }
+    Ok(changed)
+}
+
+fn merge_texts(root: &Arc<DocumentNode>) -> Result<()> {
+    let cursor = Cursor::new();
+    let mut dn_o = Some(first_node(root));
+    while let Some(doc_node) = dn_o {
+        let mut node = doc_node;
+        while let Some(next) = next_sibling(&node) {
+            if !is_mergeable_text(&node, &next) || !is_mergeable_prev(&node) {
+                break;
+            }
+            cursor.set_after_no_retain_update(&node);
+            try_3_way_merge_text(&cursor)?;
+            node = cursor.get_location().doc_node();
+        }
+        dn_o = next_node(&node);
+    }
+    Ok(())
+}
+
+/// `try_3_way_merge_text()` merges both siblings with equal attributes, so both have to be text
+fn is_mergeable_prev(node: &Arc<DocumentNode>) -> bool {
+    match prev_sibling(node) {
+        Some(prev) => {
+            prev.get_operation().get_attributes() != node.get_operation().get_attributes()
+                || is_mergeable_text(&prev, node)
+        }
+        None => true,
+    }
+}
+
+fn is_mergeable_text(left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> bool {
+    let is_text = |n: &Arc<DocumentNode>| {
+        n.get_formatter().is_text_format()
+            && n.child_count() == 0
+            && n.op_len() > 0
+            && n.get_operation().insert_value().is_string()
+    };
+    is_text(left)
+        && is_text(right)
+        && left.get_operation().get_attributes() == right.get_operation().get_attributes()
+}
+
 /// # split_text_node()
 ///
 /// Splits the text node, into 2.
@@ -408,7 +498,7 @@ pub fn split_text_node(doc_node: &Arc<DocumentNode>, index: usize) -> Result<Arc
 /// hence it contains a string as payload. This precondition is not checked / enforced.
 pub fn split_at(op: &DeltaOperation, index: usize) -> Result<(DeltaOperation, DeltaOperation)> {
     let s = op.insert_value().str_val()?;
-    let (l, r) = s.split_at(index);
+    let (l, r) = split_at_op_index(s, index);
     let mut left = DeltaOperation::insert(l);
     left.set_attributes(op.get_attributes().clone());
     let mut right = DeltaOperation::insert(r);
@@ -527,6 +617,78 @@ pub fn delete_node(doc_node: &Arc<DocumentNode>) {
     unlink(&parent, doc_node);
 }
 
+/// # move_node()
+///
+/// Moves a node, with all its children, to the child `index` of `new_parent`. For example
+/// to drag a list item to another position in its `<UL>`, or to another `<UL>`.
+///
+/// The index is the position in `new_parent` after the node has been unlinked. So moving
+/// the first `<LI>` of 3 items to the end of its own `<UL>` uses index 2.
+///
+/// Errors:
+///  - `MoveDocRoot` when the node is the document root
+///  - `MoveIntoDescendant` when `new_parent` is the node, or in the sub tree of the node
+///  - `ChildIndexOutOfRange` when `index` is beyond the children of `new_parent`
+///  - `InvalidParentFormat` when a block is moved into a node which does not accept block
+///    children, see `FormatTait::accepts_block_children()`, or a text node is moved into
+///    a node which does
+///
+/// A format may have both the wrapper and the blocks, like the `<UL>` and its `<LI>` items.
+/// Only the zero length wrapper takes blocks. The blocks themselves take text nodes, and
+/// the zero length wrapper of a nested block, like a nested `<UL>`.
+///
+/// The tree is not changed when an error is returned. Merging with the new siblings is left
+/// to the caller, see `normalize()`.
+pub fn move_node(
+    node: &Arc<DocumentNode>,
+    new_parent: &Arc<DocumentNode>,
+    index: usize,
+) -> Result<()> {
+    if is_doc_root(node) {
+        return Err(MoveDocRoot.into());
+    }
+    let mut ancestor = Some(new_parent.clone());
+    while let Some(a) = ancestor {
+        if Arc::ptr_eq(&a, node) {
+            return Err(MoveIntoDescendant.into());
+        }
+        ancestor = a.get_parent();
+    }
+
+    let accepts_wrappers = new_parent.get_formatter().accepts_block_children();
+    let accepts_blocks = accepts_wrappers && new_parent.op_len() == 0;
+    let accepted = if node.get_formatter().is_text_format() {
+        !accepts_blocks
+    } else {
+        accepts_blocks || (accepts_wrappers && node.op_len() == 0)
+    };
+    let is_text_parent = matches!(new_parent.get_doc_dom_node(), DomDocNode::TextNode(_));
+    if !accepted || is_text_parent {
+        return Err(InvalidParentFormat {
+            parent: new_parent.get_formatter().format_name().to_string(),
+            child: node.get_formatter().format_name().to_string(),
+        }
+        .into());
+    }
+
+    let parent = node.get_parent();
+    let mut count = new_parent.child_count();
+    if let Some(p) = &parent {
+        if Arc::ptr_eq(p, new_parent) {
+            count -= 1;
+        }
+    }
+    if index > count {
+        return Err(ChildIndexOutOfRange { index, count }.into());
+    }
+
+    if let Some(p) = &parent {
+        unlink(p, node);
+    }
+    insert_at_index(new_parent, index, node.clone());
+    Ok(())
+}
+
 /// # delete_text()
 ///
 /// Deletes a substring in the text in a `DocumentNode`.
@@ -583,7 +745,7 @@ pub fn insert_text(doc_node: &Arc<DocumentNode>, at: usize, txt: &str) -> Result
 /// hence it contains a string as payload. This precondition is not checked / enforced.
 fn insert_at(op: &mut DeltaOperation, at: usize, s: &str) -> Result<()> {
     let txt = op.insert_value().str_val()?;
-    let (l, r) = txt.split_at(at);
+    let (l, r) = split_at_op_index(txt, at);
     let op_s = [l, s, r].concat();
     op.set_op_kind(OpKind::from(op_s));
     Ok(())
@@ -599,13 +761,22 @@ fn insert_at(op: &mut DeltaOperation, at: usize, s: &str) -> Result<()> {
 fn delete_at(op: &mut DeltaOperation, at: usize, len: usize) -> Result<()> {
     assert!(op.op_len() >= at + len);
     let s = op.insert_value().str_val()?;
-    let (l, r) = s.split_at(at);
-    let rr = r.substring(len, r.len());
+    let (l, r) = split_at_op_index(s, at);
+    let (_deleted, rr) = split_at_op_index(r, len);
     let res = [l, rr].concat();
     op.set_op_kind(OpKind::from(res));
     Ok(())
 }
 
+/// # split_at_op_index()
+///
+/// Splits the text at an index of the DeltaOperation. The operation length counts unicode
+/// scalar values, not bytes, and not grapheme clusters like `StringUtils`.
+fn split_at_op_index(s: &str, at: usize) -> (&str, &str) {
+    let byte_index = s.char_indices().nth(at).map_or(s.len(), |(i, _c)| i);
+    s.split_at(byte_index)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -636,6 +807,13 @@ mod test {
         let mut op = DeltaOperation::insert("Hello World");
         delete_at(&mut op, 0, 11)?;
         assert_eq!(op.insert_value().str_val()?, "".to_string());
+
+        //the index counts unicode scalar values, not bytes
+        let mut op = DeltaOperation::insert("Können hören");
+        delete_at(&mut op, 1, 5)?;
+        assert_eq!(op.insert_value().str_val()?, "K hören".to_string());
+        insert_at(&mut op, 3, "ö")?;
+        assert_eq!(op.insert_value().str_val()?, "K höören".to_string());
         Ok(())
     }
 
@@ -664,6 +842,11 @@ mod test {
         let (left, right) = split_at(&op, 11)?;
         assert_eq!(left.insert_value().str_val()?, "Hello World".to_string());
         assert_eq!(right.insert_value().str_val()?, "".to_string());
+
+        let op = DeltaOperation::insert("👨‍👩‍👧 Können");
+        let (left, right) = split_at(&op, 8)?;
+        assert_eq!(left.insert_value().str_val()?, "👨‍👩‍👧 Kö".to_string());
+        assert_eq!(right.insert_value().str_val()?, "nnen".to_string());
         Ok(())
     }
 }
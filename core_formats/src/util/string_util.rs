@@ -6,61 +6,41 @@
 // copied, modified, or distributed except according to those terms.
 
 use std::ops::{Bound, RangeBounds};
+use unicode_segmentation::UnicodeSegmentation;
 
 // I liked this code; from old thread
 // https://users.rust-lang.org/t/how-to-get-a-substring-of-a-string/1351/11
+//
+// All indices and lengths count grapheme clusters, which is what the user sees as one
+// character: an emoji with modifiers, a letter with combining diacritics, a Korean syllable.
 
 // FIXME remove dependency on this functionality only used once ...
 pub trait StringUtils {
     fn substring(&self, start: usize, len: usize) -> &str;
     fn slice(&self, range: impl RangeBounds<usize>) -> &str;
     fn split_at_char(&self, index: usize) -> (&str, &str);
+    fn grapheme_count(&self) -> usize;
 }
 
 impl StringUtils for str {
     /// # substring()
     ///
-    /// Returns a string that starts with first character pointed to by start.
+    /// Returns a string that starts with first grapheme cluster pointed to by start.
     ///
-    /// It will copy 'len' characters in the output. If the original string is
-    /// nog long enough to have the remaining 'len' characters, then only the
+    /// It will copy 'len' grapheme clusters in the output. If the original string is
+    /// nog long enough to have the remaining 'len' grapheme clusters, then only the
     /// string with the remaining length will be returned. No error!
     fn substring(&self, start: usize, len: usize) -> &str {
-        let mut char_pos = 0;
-        let mut byte_start = 0;
-        let mut it = self.chars();
-        loop {
-            if char_pos == start {
-                break;
-            }
-            if let Some(c) = it.next() {
-                char_pos += 1;
-                byte_start += c.len_utf8();
-            } else {
-                break;
-            }
-        }
-        char_pos = 0;
-        let mut byte_end = byte_start;
-        loop {
-            if char_pos == len {
-                break;
-            }
-            if let Some(c) = it.next() {
-                char_pos += 1;
-                byte_end += c.len_utf8();
-            } else {
-                break;
-            }
-        }
+        let byte_start = grapheme_byte_index(self, start);
+        let byte_end = byte_start + grapheme_byte_index(&self[byte_start..], len);
         &self[byte_start..byte_end]
     }
 
-    /// Returns a string that starts with first character pointed to by start.
+    /// Returns a string that starts with first grapheme cluster pointed to by start.
     /// The Start, Length pair is given to the input as type `RangBounds<usize>`
     ///
-    /// It will copy 'len' characters in the output. If the original string is
-    /// nog long enough to have the remaining 'len' characters, then only the
+    /// It will copy 'len' grapheme clusters in the output. If the original string is
+    /// nog long enough to have the remaining 'len' grapheme clusters, then only the
     /// string with the remaining length will be returned. No error!
     fn slice(&self, range: impl RangeBounds<usize>) -> &str {
         let start = match range.start_bound() {
@@ -70,20 +50,32 @@ impl StringUtils for str {
         let len = match range.end_bound() {
             Bound::Included(bound) => *bound + 1,
             Bound::Excluded(bound) => *bound,
-            Bound::Unbounded => self.len(),
-        } - start;
+            Bound::Unbounded => self.grapheme_count(),
+        }
+        .saturating_sub(start);
         self.substring(start, len)
     }
 
     /// Splits the string into 2 sub strings: left, right.
     ///
-    /// The right string starts with as first character the character the 'index'
+    /// The right string starts with as first grapheme cluster the grapheme cluster 'index'
     /// of the original string.
     fn split_at_char(&self, index: usize) -> (&str, &str) {
-        let first = self.substring(0, index);
-        let second = self.substring(index, self.len() - 2 - index);
-        (first, second)
+        self.split_at(grapheme_byte_index(self, index))
     }
+
+    /// Returns the number of grapheme clusters in the string
+    fn grapheme_count(&self) -> usize {
+        self.graphemes(true).count()
+    }
+}
+
+/// Returns the byte index of the grapheme cluster 'index', or the string length when
+/// the string has fewer grapheme clusters.
+fn grapheme_byte_index(s: &str, index: usize) -> usize {
+    s.grapheme_indices(true)
+        .nth(index)
+        .map_or(s.len(), |(byte_index, _grapheme)| byte_index)
 }
 
 #[cfg(test)]
@@ -123,4 +115,34 @@ mod test {
         assert_eq!(s3, "Können");
         assert_eq!(s4, " sie mir hören?");
     }
+
+    #[test]
+    fn grapheme_test() {
+        //emoji with zero width joiners: 5 unicode scalar values, 1 grapheme cluster
+        let family = "👨‍👩‍👧";
+        assert_eq!(family.grapheme_count(), 1);
+        assert_eq!(family.split_at_char(1), (family, ""));
+        assert_eq!(family.split_at_char(0), ("", family));
+
+        //combining characters: e + combining acute accent
+        let s = "cafe\u{301}s";
+        assert_eq!(s.grapheme_count(), 5);
+        assert_eq!(s.substring(3, 1), "e\u{301}");
+        assert_eq!(s.split_at_char(4), ("cafe\u{301}", "s"));
+
+        //Arabic text with diacritics: each letter carries a vowel mark
+        let s = "كَتَبَ";
+        assert_eq!(s.chars().count(), 6);
+        assert_eq!(s.grapheme_count(), 3);
+        assert_eq!(s.substring(1, 1), "تَ");
+        assert_eq!(s.slice(1..), "تَبَ");
+
+        //Korean syllables, precomposed and as conjoining jamo
+        let s = "한국어";
+        assert_eq!(s.grapheme_count(), 3);
+        assert_eq!(s.split_at_char(1), ("한", "국어"));
+        let s = "\u{1112}\u{1161}\u{11AB}\u{1100}\u{116E}\u{11A8}";
+        assert_eq!(s.grapheme_count(), 2);
+        assert_eq!(s.substring(1, 5), "\u{1100}\u{116E}\u{11A8}");
+    }
 }
@@ -57,3 +57,151 @@ fn apply_text_format_test() -> Result<()> {
     assert_eq!(doc.as_html_string(), expect);
     Ok(())
 }
+
+/// Strikethrough is one of the `TEXT_FORMATS`, so the `TextFormat` handles it.
+#[wasm_bindgen_test]
+fn strike_text_format_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("strike_text_format_test");
+    doc.append_to_body();
+    doc.open()?;
+
+    let mut strike = Attributes::default();
+    strike.insert("strike", true);
+
+    let mut delta = Delta::default();
+    delta.insert_attr("TEXT_1", strike.clone());
+    delta.insert("TEXT_2");
+    delta.insert_attr("TEXT_3", strike.clone());
+    delta.insert("\n");
+    doc.apply_delta(delta)?;
+    let expect = r#"<p><s>TEXT_1</s>TEXT_2<s>TEXT_3</s></p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //--------------------------------------------------------------------
+    let mut delta = Delta::default();
+    delta.retain(2);
+    delta.delete(2);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    let expect = r#"<p><s>TE_1</s>TEXT_2<s>TEXT_3</s></p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //--------------------------------------------------------------------
+    //deleting the plain text merges the adjacent strikethrough nodes
+    let mut delta = Delta::default();
+    delta.retain(4);
+    delta.delete(6);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    let expect = r#"<p><s>TE_1TEXT_3</s></p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    assert_eq!(doc.get_root().get_child(0).unwrap().child_count(), 1);
+
+    let mut expect = Delta::default();
+    expect.insert_attr("TE_1TEXT_3", strike);
+    expect.insert("\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
+
+/// The text color is one of the `TEXT_ATTRIBUTES`, and shows as a style of a `<SPAN>`.
+#[wasm_bindgen_test]
+fn color_text_attribute_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("color_text_attribute_test");
+    doc.append_to_body();
+    doc.open()?;
+
+    let mut hex = Attributes::default();
+    hex.insert("color", "#ff0000");
+    let mut named = Attributes::default();
+    named.insert("color", "blue");
+
+    let mut delta = Delta::default();
+    delta.insert("plain ");
+    delta.insert_attr("hex", hex.clone());
+    delta.insert_attr("named", named);
+    delta.insert(" ");
+    delta.insert_attr("red", hex.clone());
+    delta.insert("\n");
+    doc.apply_delta(delta.clone())?;
+
+    //different colors do not merge
+    let expect = r#"<p>plain <span style="color:#ff0000;">hex</span><span style="color:blue;">named</span> <span style="color:#ff0000;">red</span></p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    delta.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+
+    //--------------------------------------------------------------------
+    //the same colors do
+    let mut delta = Delta::default();
+    delta.retain(9);
+    delta.delete(6);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    let expect = r#"<p>plain <span style="color:#ff0000;">hexred</span></p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+/// The background color is one of the `TEXT_ATTRIBUTES`, like the text color. Both show as
+/// a style of one and the same `<SPAN>`.
+#[wasm_bindgen_test]
+fn background_text_attribute_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("background_text_attribute_test");
+    doc.append_to_body();
+    doc.open()?;
+
+    let mut yellow = Attributes::default();
+    yellow.insert("background", "#ffff00");
+    let mut both = yellow.clone();
+    both.insert("color", "red");
+
+    let mut delta = Delta::default();
+    delta.insert_attr("marked", yellow.clone());
+    delta.insert_attr("red", both.clone());
+    delta.insert(" ");
+    delta.insert_attr("too", both.clone());
+    delta.insert("\n");
+    doc.apply_delta(delta.clone())?;
+
+    let expect = r#"<p><span style="background-color:#ffff00;">marked</span><span style="color:red;background-color:#ffff00;">red</span> <span style="color:red;background-color:#ffff00;">too</span></p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    delta.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+
+    //--------------------------------------------------------------------
+    //only nodes with both the same color, and background color merge
+    let mut delta = Delta::default();
+    delta.retain(9);
+    delta.delete(1);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    let expect = r#"<p><span style="background-color:#ffff00;">marked</span><span style="color:red;background-color:#ffff00;">redtoo</span></p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //--------------------------------------------------------------------
+    //removing the background color keeps the text color
+    let mut drop_a = Attributes::default();
+    drop_a.insert("background", AttrVal::Null);
+    let mut delta = Delta::default();
+    delta.retain(6);
+    delta.retain_attr(6, drop_a);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    let expect = r#"<p><span style="background-color:#ffff00;">marked</span><span style="color:red;">redtoo</span></p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    let mut expect = Delta::default();
+    let mut red = Attributes::default();
+    red.insert("color", "red");
+    expect.insert_attr("marked", yellow);
+    expect.insert_attr("redtoo", red);
+    expect.insert("\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
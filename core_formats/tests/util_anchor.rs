@@ -0,0 +1,24 @@
+use core_formats::util::anchor::{anchor_ids, slugify};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn slugify_test() {
+    assert_eq!(slugify("Getting started"), "getting-started");
+    assert_eq!(slugify("C++ & Rust: a tour!"), "c-rust-a-tour");
+    assert_eq!(slugify("a - b"), "a-b");
+    assert_eq!(slugify("  Über  café  "), "über-café");
+    assert_eq!(slugify("?!"), "");
+}
+
+#[wasm_bindgen_test]
+fn anchor_ids_test() {
+    let ids = anchor_ids(["Intro", "Usage", "Intro", "intro!", "", "Intro 2"]);
+    let expect = vec!["intro", "usage", "intro-2", "intro-3", "", "intro-2-2"];
+    assert_eq!(ids, expect);
+
+    //the same texts give the same IDs
+    assert_eq!(anchor_ids(["Intro", "Intro"]), vec!["intro", "intro-2"]);
+    assert_eq!(anchor_ids(["Intro", "Intro"]), vec!["intro", "intro-2"]);
+}
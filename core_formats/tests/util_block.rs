@@ -1,8 +1,10 @@
 use anyhow::Result;
 use core_formats::paragraph::Pblock;
 use core_formats::text_formatter::TextFormat;
-use core_formats::util::block::{block_transform, un_block_transform};
+use core_formats::util::block::{block_transform, drop_attributes, un_block_transform};
+use core_formats::util::block_format::BlockAttributeRegistry;
 use core_formats::util::node_morph::split_text_and_block_at_cursor;
+use core_formats::P_FORMAT;
 use delta::attributes::Attributes;
 use delta::operations::DeltaOperation;
 use dom::dom_element::DomElement;
@@ -124,6 +126,7 @@ fn block_transform_test() -> Result<()> {
 
     let mut attr = Attributes::default();
     attr.insert("heading", 1);
+    attr.insert("cite", "quote");
 
     //------------------------------------------
     // create doc
@@ -232,3 +235,36 @@ fn op_inset_edge_test() -> Result<()> {
     //error!("{}", &cursor);
     Ok(())
 }
+
+#[wasm_bindgen_test]
+fn drop_attributes_test() -> Result<()> {
+    BlockAttributeRegistry::register_attr("heading");
+    BlockAttributeRegistry::register_attr("heading"); //twice is allowed
+    BlockAttributeRegistry::register_class_attr("cite", "ql-cite-");
+
+    let mut attr = Attributes::default();
+    attr.insert("align", "center");
+    attr.insert("indent", 2);
+    attr.insert("direction", "rtl");
+    attr.insert("heading", 1);
+    let op = DeltaOperation::insert_attr("\n", attr);
+    let p_node = P_FORMAT.create(op, P_FORMAT.clone())?;
+
+    let classes = p_node.get_dom_element().unwrap().get_attribute("class");
+    let classes = classes.unwrap_or_default();
+    assert!(classes.contains("ql-align-center"));
+    assert!(classes.contains("ql-direction-rtl"));
+    assert!(classes.contains("ql-indent-"));
+    assert!(classes.contains("ql-cite-quote")); //a registered class attribute
+    assert!(!classes.contains("heading")); //shown by the header format itself
+
+    drop_attributes(&p_node)?;
+    let keys = BlockAttributeRegistry::keys();
+    assert!(keys.iter().filter(|k| k.as_str() == "heading").count() == 1);
+    for key in keys.iter() {
+        assert!(!p_node.get_operation().get_attributes().contains_key(key));
+    }
+    let classes = p_node.get_dom_element().unwrap().get_attribute("class");
+    assert!(!classes.unwrap_or_default().contains("ql-"));
+    Ok(())
+}
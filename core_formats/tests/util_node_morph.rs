@@ -2,10 +2,11 @@ use core_formats::paragraph::Pblock;
 use core_formats::text_formatter::TextFormat;
 use core_formats::util::node_morph::{
     delete_node, insert_empty_block_node_after_cursor, merge_block_node, merge_text_node,
-    split_block_at_cursor, split_block_before_child, split_text_and_block_at_cursor,
+    normalize, split_block_at_cursor, split_block_before_child, split_text_and_block_at_cursor,
     split_text_node, try_3_way_merge_text,
 };
 use delta::attributes::Attributes;
+use delta::delta::Delta;
 use delta::operations::DeltaOperation;
 use node_tree::cursor::{Cursor, CursorLocation};
 use node_tree::dom_doc_tree_morph::append;
@@ -411,3 +412,47 @@ pub fn try_3_way_merge_text_test() -> anyhow::Result<()> {
     assert_eq!(doc.as_html_string(), expect);
     Ok(())
 }
+
+#[wasm_bindgen_test]
+pub fn normalize_test() -> anyhow::Result<()> {
+    let doc = DocumentRoot::new("normalize_test");
+    doc.append_to_body();
+    let p_format = Arc::new(Pblock::new());
+    let t_format = Arc::new(TextFormat::new());
+
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+
+    // <p>[A][B][C][D][E]</p><p>[F]</p> with all text nodes bold
+    let root = doc.get_root();
+    let par = p_format.create(DeltaOperation::insert("\n"), p_format.clone())?;
+    append(root, par.clone());
+    for txt in ["A", "B", "C", "D", "E"] {
+        let delta = DeltaOperation::insert_attr(txt, attr.clone());
+        append(&par, t_format.create(delta, t_format.clone())?);
+    }
+    let next_par = p_format.create(DeltaOperation::insert("\n"), p_format.clone())?;
+    append(root, next_par.clone());
+    let delta = DeltaOperation::insert_attr("F", attr.clone());
+    append(&next_par, t_format.create(delta, t_format.clone())?);
+    assert_eq!(par.child_count(), 5);
+
+    normalize(root)?;
+    assert_eq!(par.child_count(), 1);
+    assert_eq!(root.child_count(), 2); //paragraphs are not merged
+    let expect = r#"<p><strong>ABCDE</strong></p><p><strong>F</strong></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    let mut delta = Delta::default();
+    delta.insert_attr("ABCDE", attr.clone());
+    delta.insert("\n");
+    delta.insert_attr("F", attr);
+    delta.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+
+    //idempotent
+    normalize(root)?;
+    assert_eq!(doc.as_html_string(), expect);
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+    Ok(())
+}
@@ -177,6 +177,23 @@ impl DomElement {
     pub fn blur(elem: &HtmlElement) {
         elem.blur().unwrap();
     }
+
+    /// Sets the `checked` property of an `<INPUT>` element, like a check box
+    pub fn set_checked(&self, checked: bool) {
+        self.element
+            .clone()
+            .dyn_into::<web_sys::HtmlInputElement>()
+            .unwrap()
+            .set_checked(checked);
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.element
+            .clone()
+            .dyn_into::<web_sys::HtmlInputElement>()
+            .unwrap()
+            .checked()
+    }
 }
 
 ///
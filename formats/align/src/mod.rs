@@ -0,0 +1,191 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::util::block::{apply_attributes, drop_attributes};
+use core_formats::util::block_format;
+use core_formats::util::node_morph::delete_node;
+use core_formats::P_FORMAT;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use delta::types::attr_val::AttrVal::Null;
+use node_tree::cursor::Cursor;
+use node_tree::doc_node::DocumentNode;
+use node_tree::format_trait::FormatTait;
+use std::sync::Arc;
+
+pub static NAME_ALIGN: &'static str = "align"; //registry label
+
+static ALIGN_ATTR_KEY: &'static str = "align"; //attribute key
+static ALIGN_LEFT: &'static str = "left"; //default alignment, no class
+static ALIGN_VALUES: [&'static str; 4] = ["left", "center", "right", "justify"];
+
+/// # TextAlignFormat
+///
+/// ``` bash
+///   {insert: "centered"}
+///   {attributes: {align: "center"}, insert: "↵"}
+/// ```
+///
+/// Gives as HTML:
+/// ```bash
+///     <p class="ql-align-center">centered</p>
+/// ```
+///
+/// Alignment is not a block of its own, but modifies the block it is given to. So
+/// `block_transform()` keeps the block node, and only changes its `ql-align-` class, like
+/// `core_formats::util::block::apply_attributes()`. The block keeps its own format, so the
+/// `<P>` stays a paragraph.
+///
+/// Register this format after the other block formats, and before the paragraph. Then a
+/// header, blockquote, or list with an `align` attribute is still created by its own format,
+/// which applies the `ql-align-` class too:
+/// ```bash
+///     <h1 class="ql-align-right">title</h1>
+/// ```
+///
+/// The values are `center`, `right`, and `justify`. `left` is the default, so `left` and
+/// `null` remove the alignment.
+pub struct TextAlignFormat {}
+impl TextAlignFormat {
+    pub fn new() -> Self {
+        block_format::initialise();
+        TextAlignFormat {}
+    }
+}
+
+/// Returns the attributes without the alignment when it is left aligned, or `null`
+fn align_attributes(attr: &Attributes) -> Attributes {
+    let mut attr = attr.clone();
+    let left = match attr.get(ALIGN_ATTR_KEY) {
+        Some(AttrVal::String(s)) => s == ALIGN_LEFT,
+        Some(val) => val.is_null(),
+        None => false,
+    };
+    if left {
+        attr.remove(ALIGN_ATTR_KEY);
+    }
+    attr
+}
+
+impl FormatTait for TextAlignFormat {
+    /// Creates a paragraph, which is aligned
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let mut op = DeltaOperation::insert(operation.insert_value().clone());
+        op.set_attributes(align_attributes(operation.get_attributes()));
+        P_FORMAT.create(op, P_FORMAT.clone())
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_ALIGN
+    }
+
+    fn is_text_format(&self) -> bool {
+        false
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        let mut attr = Attributes::default();
+        attr.insert(ALIGN_ATTR_KEY, Null);
+        attr
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        if delta.insert_value().is_string() && delta.insert_value().str_val()? == "\n" {
+            return Ok(match delta.get_attributes().get(ALIGN_ATTR_KEY) {
+                Some(AttrVal::String(s)) => ALIGN_VALUES.contains(&s.as_str()),
+                Some(val) => val.is_null(),
+                None => false,
+            });
+        }
+        Ok(false)
+    }
+
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        apply_attributes(doc_node, &align_attributes(attr))
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        drop_attributes(doc_node)
+    }
+
+    fn split_leaf(&self, _cursor: &Cursor) -> Result<()> {
+        panic!("TextAlignFormat::split_leaf() - Error. ");
+    }
+
+    //The aligned blocks have their own format, see block_transform()
+    fn is_same_format(&self, _left: &Arc<DocumentNode>, _right: &Arc<DocumentNode>) -> bool {
+        false
+    }
+
+    /// Keeps the block node, and its format, and only replaces the alignment.<br>
+    /// The cursor does not change, since the block node stays in the document.
+    fn block_transform(
+        &self,
+        _cursor: &Cursor,
+        block_node: &Arc<DocumentNode>,
+        delta: DeltaOperation,
+        _format: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let attr = align_attributes(delta.get_attributes());
+        let element = block_node.get_dom_element().unwrap();
+        block_format::apply(element, &self.block_remove_attr())?;
+        block_format::apply(element, &attr)?;
+
+        let mut op = block_node.get_operation();
+        op.set_attributes(attr);
+        block_node.set_operation(op);
+        Ok(block_node.clone())
+    }
+
+    /// Removes the alignment, the block node stays in the document
+    fn un_block_transform(
+        &self,
+        _cursor: &Cursor,
+        block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        let element = block_node.get_dom_element().unwrap();
+        block_format::apply(element, &self.block_remove_attr())?;
+
+        //get_operation() returns a copy, so we need to set the operation again
+        let mut op = block_node.get_operation();
+        op.remove_attribute(ALIGN_ATTR_KEY);
+        block_node.set_operation(op);
+        Ok(block_node.clone())
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        _doc_node: &Arc<DocumentNode>,
+        _at: usize,
+        _length: usize,
+    ) -> Result<()> {
+        panic!("TextAlignFormat::delete() - Error. Block has length 1, so use the other delete function...");
+    }
+
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn try_merge(&self, _cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+use align::{TextAlignFormat, NAME_ALIGN};
+use anyhow::Result;
+use blockquote::{BlockquoteFormat, NAME_BLOCKQUOTE};
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use header::{HeaderBlock, NAME_HEADER};
+use list::{ListBlock, NAME_UL_BLOCK};
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+/// The alignment is registered after the other block formats, and before the paragraph
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_HEADER, Arc::new(HeaderBlock::new()))
+            .unwrap();
+        r.register_block_fmt(NAME_BLOCKQUOTE, Arc::new(BlockquoteFormat::new()))
+            .unwrap();
+        r.register_block_fmt(NAME_UL_BLOCK, Arc::new(ListBlock::new_ul()))
+            .unwrap();
+        r.register_block_fmt(NAME_ALIGN, Arc::new(TextAlignFormat::new()))
+            .unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn align(val: &str) -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert("align", val);
+    attr
+}
+
+#[test]
+fn detect_align_format() -> Result<()> {
+    let format = TextAlignFormat::new();
+    assert_eq!(format.format_name(), NAME_ALIGN);
+    for val in ["left", "center", "right", "justify"] {
+        assert!(format.applies(&DeltaOperation::insert_attr("\n", align(val)))?);
+    }
+    let mut attr = Attributes::default();
+    attr.insert("align", AttrVal::Null);
+    assert!(format.applies(&DeltaOperation::insert_attr("\n", attr))?);
+
+    assert!(!format.applies(&DeltaOperation::insert_attr("\n", align("middle")))?);
+    assert!(!format.applies(&DeltaOperation::insert_attr("text", align("center")))?);
+    assert!(!format.applies(&DeltaOperation::insert("\n"))?);
+    assert!(format.block_remove_attr().get("align").unwrap().is_null());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn align_values_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("align_values_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("left");
+    delta.insert_attr("\n", align("left"));
+    delta.insert("center");
+    delta.insert_attr("\n", align("center"));
+    delta.insert("right");
+    delta.insert_attr("\n", align("right"));
+    delta.insert("justify");
+    delta.insert_attr("\n", align("justify"));
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<p>left</p><p class="ql-align-center">center</p><p class="ql-align-right">right</p><p class="ql-align-justify">justify</p><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    //left is the default, so it does not show in the delta
+    let mut expect = Delta::default();
+    expect.insert("left\ncenter");
+    expect.insert_attr("\n", align("center"));
+    expect.insert("right");
+    expect.insert_attr("\n", align("right"));
+    expect.insert("justify");
+    expect.insert_attr("\n", align("justify"));
+    expect.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+
+    //the paragraph stays a paragraph
+    let p = doc.get_root().get_child(1).unwrap();
+    assert_eq!(p.get_formatter().format_name(), NAME_P_BLOCK);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn align_retain_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("align_retain_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("text\n");
+    doc.apply_delta(delta)?;
+
+    for (val, html_txt) in [
+        (
+            "center",
+            r#"<p class="ql-align-center">text</p><p><br></p>"#,
+        ),
+        (
+            "justify",
+            r#"<p class="ql-align-justify">text</p><p><br></p>"#,
+        ),
+        ("left", r#"<p>text</p><p><br></p>"#),
+        ("right", r#"<p class="ql-align-right">text</p><p><br></p>"#),
+    ] {
+        let mut delta = Delta::default();
+        delta.retain(4);
+        delta.retain_attr(1, align(val));
+        doc.apply_delta(delta)?;
+        assert_eq!(doc.as_html_string(), html_txt);
+    }
+
+    //null removes the alignment
+    let mut attr = Attributes::default();
+    attr.insert("align", AttrVal::Null);
+    let mut delta = Delta::default();
+    delta.retain(4);
+    delta.retain_attr(1, attr);
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), r#"<p>text</p><p><br></p>"#);
+
+    let mut expect = Delta::default();
+    expect.insert("text\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn align_header_blockquote_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("align_header_blockquote_test");
+    doc.open()?;
+
+    let mut heading = align("center");
+    heading.insert("heading", 1);
+    let mut quote = align("right");
+    quote.insert("blockquote", true);
+
+    let mut delta = Delta::default();
+    delta.insert("title");
+    delta.insert_attr("\n", heading);
+    delta.insert("quote");
+    delta.insert_attr("\n", quote);
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<h1 class="ql-align-center">title</h1><blockquote class="ql-align-right">quote</blockquote><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn align_list_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("align_list_test");
+    doc.open()?;
+
+    let mut attr = align("center");
+    attr.insert("list", "bullet");
+
+    let mut delta = Delta::default();
+    delta.insert("first");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("second");
+    delta.insert_attr("\n", attr);
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<ul class="ql-align-center"><li>first</li><li>second</li></ul><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    //a different alignment splits the list
+    let mut delta = Delta::default();
+    delta.retain(12);
+    delta.retain_attr(1, align("right"));
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<ul class="ql-align-center"><li>first</li></ul><ul class="ql-align-right"><li>second</li></ul><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    //and the same alignment merges it again
+    let mut delta = Delta::default();
+    delta.retain(12);
+    delta.retain_attr(1, align("center"));
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<ul class="ql-align-center"><li>first</li><li>second</li></ul><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn align_from_html_round_trip_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("align_from_html_round_trip_test");
+    doc.open()?;
+
+    let mut heading = align("center");
+    heading.insert("heading", 1);
+    let mut quote = Attributes::default();
+    quote.insert("blockquote", true);
+    let mut bullet = align("right");
+    bullet.insert("list", "bullet");
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+
+    let mut delta = Delta::default();
+    delta.insert("title");
+    delta.insert_attr("\n", heading);
+    delta.insert("intro ");
+    delta.insert_attr("text", bold);
+    delta.insert("\nquote");
+    delta.insert_attr("\n", quote);
+    delta.insert("first");
+    delta.insert_attr("\n", bullet.clone());
+    delta.insert("second");
+    delta.insert_attr("\n", bullet);
+    delta.insert("justified");
+    delta.insert_attr("\n", align("justify"));
+    doc.apply_delta(delta)?;
+
+    let html = doc.as_html_string();
+    let loaded = DocumentRoot::from_html("align_from_html_round_trip_loaded_test", &html)?;
+    assert_eq!(loaded.as_html_string(), html);
+    Ok(())
+}
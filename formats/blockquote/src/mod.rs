@@ -0,0 +1,151 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::util::block::{
+    apply_attributes, block_transform, drop_attributes, un_block_transform,
+};
+use core_formats::util::block_format;
+use core_formats::util::block_format::BlockAttributeRegistry;
+use core_formats::util::node_morph::delete_node;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal::Null;
+use dom::dom_element::DomElement;
+use node_tree::cursor::Cursor;
+use node_tree::doc_node::DocumentNode;
+use node_tree::format_trait::FormatTait;
+use std::sync::Arc;
+
+pub static NAME_BLOCKQUOTE: &'static str = "blockquote"; //registry label
+
+static BLOCKQUOTE_TAG: &'static str = "BLOCKQUOTE"; //HTML tag
+static BLOCKQUOTE_ATTR_KEY: &'static str = "blockquote"; //attribute key
+
+/// # BlockquoteFormat
+///
+/// ``` bash
+///   {insert: "quoted text"}
+///   {attributes: {blockquote: true}, insert: "↵"}
+/// ```
+///
+/// Gives as HTML:
+/// ```bash
+///     <blockquote>quoted text</blockquote>
+/// ```
+///
+/// Every line is a `<blockquote>` of its own. Consecutive quoted lines are not merged,
+/// like the `HeaderBlock`; the author typed separate lines.
+pub struct BlockquoteFormat {}
+impl BlockquoteFormat {
+    pub fn new() -> Self {
+        block_format::initialise();
+        BlockAttributeRegistry::register_attr(BLOCKQUOTE_ATTR_KEY);
+        BlockquoteFormat {}
+    }
+}
+
+impl FormatTait for BlockquoteFormat {
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let element = DomElement::new(BLOCKQUOTE_TAG);
+        block_format::apply(&element, operation.get_attributes())?;
+        let doc_node = DocumentNode::new_element(element, formatter);
+        doc_node.set_operation(operation);
+        Ok(Arc::new(doc_node))
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_BLOCKQUOTE
+    }
+
+    fn is_text_format(&self) -> bool {
+        false
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        let mut attr = Attributes::default();
+        attr.insert(BLOCKQUOTE_ATTR_KEY, Null);
+        attr
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        Ok(delta.insert_value().is_string()
+            && delta.insert_value().str_val()? == "\n"
+            && delta.get_attributes().contains_key(BLOCKQUOTE_ATTR_KEY))
+    }
+
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        apply_attributes(doc_node, attr)
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        drop_attributes(doc_node)
+    }
+
+    fn split_leaf(&self, _cursor: &Cursor) -> Result<()> {
+        panic!("BlockquoteFormat::split_leaf() - Error. ");
+    }
+
+    //We do not merge so we always flag it to be not the same, even for equal attributes
+    fn is_same_format(&self, _left: &Arc<DocumentNode>, _right: &Arc<DocumentNode>) -> bool {
+        false
+    }
+
+    fn block_transform(
+        &self,
+        cursor: &Cursor,
+        block_node: &Arc<DocumentNode>,
+        delta: DeltaOperation,
+        format: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        block_transform(block_node, delta, format, cursor)
+    }
+
+    fn un_block_transform(
+        &self,
+        cursor: &Cursor,
+        block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        //get_operation() returns a copy, so we need to set the operation again
+        let mut op = block_node.get_operation();
+        op.remove_attribute(BLOCKQUOTE_ATTR_KEY);
+        block_node.set_operation(op);
+        un_block_transform(block_node, cursor)
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        _doc_node: &Arc<DocumentNode>,
+        _at: usize,
+        _length: usize,
+    ) -> Result<()> {
+        panic!("BlockquoteFormat::delete() - Error. Block has length 1, so use the other delete function...");
+    }
+
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        assert_eq!(doc_node.get_doc_dom_node().get_node_name(), BLOCKQUOTE_TAG);
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    //We do not merge quoted lines, see is_same_format()
+    fn try_merge(&self, _cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::Result;
+use blockquote::{BlockquoteFormat, NAME_BLOCKQUOTE};
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::types::attr_val::AttrVal;
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_BLOCKQUOTE, Arc::new(BlockquoteFormat::new()))
+            .unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn quote_attr() -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert("blockquote", true);
+    attr
+}
+
+#[test]
+fn detect_blockquote_format() -> Result<()> {
+    let mut delta = Delta::default();
+    delta.insert("quoted");
+    delta.insert_attr("\n", quote_attr());
+
+    let format = BlockquoteFormat::new();
+    assert_eq!(format.format_name(), NAME_BLOCKQUOTE);
+    assert!(!format.applies(delta.get_ops_ref().get(0).unwrap())?);
+    assert!(format.applies(delta.get_ops_ref().get(1).unwrap())?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn blockquote_create_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("blockquote_create_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("normal\nfirst quote");
+    delta.insert_attr("\n", quote_attr());
+    delta.insert("second quote");
+    delta.insert_attr("\n", quote_attr());
+    doc.apply_delta(delta.clone())?;
+
+    //consecutive quotes are not merged
+    let html_txt = r##"<p>normal</p><blockquote>first quote</blockquote><blockquote>second quote</blockquote><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    delta.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn blockquote_delete_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("blockquote_delete_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("quoted text");
+    delta.insert_attr("\n", quote_attr());
+    doc.apply_delta(delta)?;
+
+    //-----------------------------------------------------------------
+    let mut delta = Delta::default();
+    delta.retain(6);
+    delta.delete(5);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<blockquote>quoted</blockquote><p><br></p>"
+    );
+
+    //-----------------------------------------------------------------
+    let mut delta = Delta::default();
+    delta.delete(6);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<blockquote><br></blockquote><p><br></p>"
+    );
+
+    //-----------------------------------------------------------------
+    let mut delta = Delta::default();
+    delta.delete(1);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<p><br></p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn blockquote_retain_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("blockquote_retain_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("one\ntwo\n");
+    doc.apply_delta(delta)?;
+
+    //quote the second line
+    let mut delta = Delta::default();
+    delta.retain(7);
+    delta.retain_attr(1, quote_attr());
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>one</p><blockquote>two</blockquote><p><br></p>"
+    );
+
+    //and remove the quote again
+    let mut attr = Attributes::default();
+    attr.insert("blockquote", AttrVal::Null);
+    let mut delta = Delta::default();
+    delta.retain(7);
+    delta.retain_attr(1, attr);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<p>one</p><p>two</p><p><br></p>");
+
+    let mut expect = Delta::default();
+    expect.insert("one\ntwo\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn blockquote_un_block_transform_cursor_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("blockquote_un_block_transform_cursor_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("quoted");
+    delta.insert_attr("\n", quote_attr());
+    delta.insert_attr("\n", quote_attr());
+    doc.apply_delta(delta)?;
+
+    //an empty block: the cursor moves to the new paragraph
+    let empty = doc.get_root().get_child(1).unwrap();
+    doc.get_cursor().set_at(&empty, 0);
+    let p = BlockquoteFormat::new().un_block_transform(doc.get_cursor(), &empty)?;
+    assert_eq!(p.get_formatter().format_name(), NAME_P_BLOCK);
+    assert!(doc.get_cursor().get_doc_node() == p);
+    assert_eq!(doc.get_cursor().get_retain_index(), 7);
+
+    //a block with text: the cursor stays on the text
+    let quote = doc.get_root().get_child(0).unwrap();
+    let text = quote.get_child(0).unwrap();
+    doc.get_cursor().set_at(&text, 2);
+    let p = BlockquoteFormat::new().un_block_transform(doc.get_cursor(), &quote)?;
+    assert!(doc.get_cursor().get_doc_node() == text);
+    assert!(p.get_child(0).unwrap() == text);
+    assert_eq!(doc.get_cursor().calculate_retain_index(), 2);
+    Ok(())
+}
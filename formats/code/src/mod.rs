@@ -10,7 +10,9 @@ use core_formats::util::block::{
     apply_attributes, block_transform, drop_attributes, un_block_transform,
 };
 use core_formats::util::block_format;
-use core_formats::util::node_morph::delete_node;
+use core_formats::util::block_format::BlockAttributeRegistry;
+use core_formats::util::node_morph::{delete_node, merge_block_node, split_block_before_child};
+use core_formats::P_FORMAT;
 use delta::attributes::Attributes;
 use delta::operations::DeltaOperation;
 use delta::types::attr_val::AttrVal;
@@ -18,7 +20,9 @@ use delta::types::attr_val::AttrVal::Null;
 use dom::dom_element::DomElement;
 use node_tree::cursor::Cursor;
 use node_tree::doc_node::DocumentNode;
-use node_tree::format_trait::FormatTait;
+use node_tree::dom_doc_tree_morph::{append, insert_at_index, insert_before, unlink};
+use node_tree::format_trait::{BackspaceEmptyBehavior, EnterKeyBehavior, FormatTait};
+use node_tree::tree_traverse::{next_sibling, prev_sibling};
 use std::sync::Arc;
 
 pub static NAME_CODE: &'static str = "CODE"; //registry label
@@ -26,6 +30,9 @@ pub static NAME_CODE: &'static str = "CODE"; //registry label
 static CODE_TAG: &'static str = "SPAN";
 static CODE_ATTR_KEY: &'static str = "code-block";
 static CODE_CLASS: &'static str = "ql-pre";
+static LANGUAGE_ATTR: &'static str = "data-language";
+static PRE_TAG: &'static str = "PRE";
+static PRE_CLASS: &'static str = "ql-syntax";
 
 /// # CodeBlock
 ///
@@ -45,6 +52,12 @@ static CODE_CLASS: &'static str = "ql-pre";
 ///     <span class="ql-pre" ">world\n</span>
 /// ```
 ///
+/// # LANGUAGE
+///
+/// The attribute value may be the language of the code, like `{code-block: "rust"}`. The
+/// language is shown as `<span class="ql-pre" data-language="rust">`. Lines with a different
+/// language are a different format, so they do not merge.
+///
 /// We then need CSS formatting
 /// ```bash
 ///     span.ql-pre {
@@ -57,25 +70,155 @@ static CODE_CLASS: &'static str = "ql-pre";
 /// that is not as the HTML dom would want it. Normally all the text nodes are merged in to one,
 /// but that would mean that we have no 1:1 relation anymore between `<text>` nodes, doc_nodes, and
 /// delta operations
-pub struct CodeBlock {}
+///
+/// # MERGED CODE BLOCKS
+///
+/// With `CodeBlock::new_merged()` adjacent code lines are collected in one `<pre>` block,
+/// like the `ListBlock` collects the `<LI>` elements in one `<UL>`:
+/// ```bash
+///     <pre class="ql-syntax">
+///         <span class="ql-pre">hello </span>
+///         <span class="ql-pre">sweet</span>
+///         <span class="ql-pre">world</span>
+///     </pre>
+/// ```
+/// The `<pre>` node has an operation of length 0, so it does not appear in the delta.
+/// We do not use `<br>` elements for the new lines in the `<pre>` block, since we keep
+/// the 1:1 relation between the block operations and the doc_nodes.
+///
+/// We then need CSS formatting
+/// ```bash
+///     pre.ql-syntax span.ql-pre {
+///        display: block;
+///     }
+/// ```
+pub struct CodeBlock {
+    merge: bool,
+}
 impl CodeBlock {
     pub fn new() -> Self {
         block_format::initialise();
-        CodeBlock {}
+        BlockAttributeRegistry::register_attr(CODE_ATTR_KEY);
+        CodeBlock { merge: false }
+    }
+
+    pub fn new_merged() -> Self {
+        block_format::initialise();
+        BlockAttributeRegistry::register_attr(CODE_ATTR_KEY);
+        CodeBlock { merge: true }
     }
 }
 
-impl FormatTait for CodeBlock {
-    fn create(
+impl CodeBlock {
+    fn create_line_node(
         &self,
         operation: DeltaOperation,
         formatter: Arc<dyn FormatTait + Send + Sync>,
-    ) -> Result<Arc<DocumentNode>> {
+    ) -> Arc<DocumentNode> {
         let element = DomElement::new(CODE_TAG);
         element.set_class(CODE_CLASS);
+        set_language(&element, operation.get_attributes());
         let doc_node = DocumentNode::new_element(element, formatter);
         doc_node.set_operation(operation);
-        return Ok(Arc::new(doc_node));
+        Arc::new(doc_node)
+    }
+
+    /// The `<pre>` node gets the attributes of the line, with an empty insert
+    fn create_pre_node(
+        &self,
+        attr: &Attributes,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let element = DomElement::new(PRE_TAG);
+        element.set_class(PRE_CLASS);
+        block_format::apply(&element, attr)?;
+        let mut op = DeltaOperation::insert("");
+        op.set_attributes(attr.clone());
+        let doc_node = DocumentNode::new_element(element, formatter);
+        doc_node.set_operation(op);
+        Ok(Arc::new(doc_node))
+    }
+
+    /// Creates a `<pre>` node, with one line in it.<br>
+    /// Returns the pair, so that the calling method may use either one
+    fn create_code_node(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<(Arc<DocumentNode>, Arc<DocumentNode>)> {
+        let pre_node = self.create_pre_node(operation.get_attributes(), formatter.clone())?;
+        let line_node = self.create_line_node(operation, formatter);
+        append(&pre_node, line_node.clone());
+        Ok((pre_node, line_node))
+    }
+
+    /// The `code-block` attribute value, `Bool` or the language `String`
+    fn code_value(doc_node: &Arc<DocumentNode>) -> Option<AttrVal> {
+        match doc_node.get_operation().get_attributes().get(CODE_ATTR_KEY) {
+            Some(val @ (AttrVal::Bool(_) | AttrVal::String(_))) => Some(val.clone()),
+            _ => None,
+        }
+    }
+
+    fn is_pre_node(&self, doc_node: &Arc<DocumentNode>) -> bool {
+        doc_node.get_doc_dom_node().get_node_name() == PRE_TAG
+            && doc_node.get_formatter().format_name() == NAME_CODE
+    }
+
+    /// Splits of a line node such that it is the only line in a `<pre>` block.<br>
+    /// Returned: the `<pre>` parent of this single line
+    fn split_line_in_own_pre(&self, line_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        let mut pre_node = line_node.get_parent().unwrap();
+        if let Some(next) = next_sibling(line_node) {
+            split_block_before_child(&pre_node, &next)?;
+        }
+        if let Some(_prev) = prev_sibling(line_node) {
+            pre_node = split_block_before_child(&pre_node, line_node)?;
+        }
+        assert_eq!(pre_node.child_count(), 1); //expecting isolated node !!
+        Ok(pre_node)
+    }
+
+    /// Merging of `<pre>` nodes, analogous to `ListBlock::merge_ul_nodes()`:
+    ///  - merges the next sibling, and the one after that, if `<pre>`
+    ///  - merges the previous sibling, and the one before that, if `<pre>`
+    /// No merge if the `<pre>` nodes have different attributes (such as indentation)
+    ///
+    /// Returns: resulting merged `<pre>` node.
+    fn merge_pre_nodes(&self, pre_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        let op = pre_node.get_operation();
+        for _ in 0..2 {
+            if let Some(next) = next_sibling(pre_node) {
+                if self.is_pre_node(&next) && op == next.get_operation() {
+                    merge_block_node(pre_node, &next)?;
+                }
+            }
+        }
+
+        let mut ret = pre_node.clone();
+        for _ in 0..2 {
+            if let Some(prev) = prev_sibling(&ret) {
+                if self.is_pre_node(&prev) && op == prev.get_operation() {
+                    merge_block_node(&prev, &ret)?;
+                    ret = prev;
+                }
+            }
+        }
+        Ok(ret)
+    }
+}
+
+impl FormatTait for CodeBlock {
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        if self.merge {
+            let (pre_node, _) = self.create_code_node(operation, formatter)?;
+            return Ok(pre_node);
+        }
+        return Ok(self.create_line_node(operation, formatter));
     }
 
     fn format_name(&self) -> &'static str {
@@ -93,9 +236,14 @@ impl FormatTait for CodeBlock {
     }
 
     fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
-        if delta.insert_value().is_string() && delta.insert_value().str_val()? == "\n" {
-            if delta.get_attributes().contains_key(CODE_ATTR_KEY) {
-                return Ok(true);
+        if delta.insert_value().is_string() {
+            let s = delta.insert_value().str_val()?;
+            //allow "" to detect the format given to a <pre> doc_node
+            if s == "\n" || (self.merge && s == "") {
+                match delta.get_attributes().get(CODE_ATTR_KEY) {
+                    Some(AttrVal::Bool(_)) | Some(AttrVal::String(_)) => return Ok(true),
+                    _ => {}
+                }
             }
         }
         return Ok(false);
@@ -107,28 +255,51 @@ impl FormatTait for CodeBlock {
         attr: &Attributes,
         _formatter: Arc<dyn FormatTait + Send + Sync>,
     ) -> Result<Arc<DocumentNode>> {
-        apply_attributes(doc_node, attr)
+        let doc_node = apply_attributes(doc_node, attr)?;
+        set_language(doc_node.get_dom_element().unwrap(), attr);
+        Ok(doc_node)
     }
 
     fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
-        drop_attributes(doc_node)
+        let doc_node = drop_attributes(doc_node)?;
+        set_language(doc_node.get_dom_element().unwrap(), &Attributes::default());
+        Ok(doc_node)
+    }
+
+    fn clone_doc_node(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        let formatter = doc_node.get_formatter();
+        if self.is_pre_node(doc_node) {
+            // in case we are splitting a merged code block
+            return self.create_pre_node(doc_node.get_operation().get_attributes(), formatter);
+        }
+        Ok(self.create_line_node(doc_node.get_operation(), formatter))
     }
 
     fn split_leaf(&self, _cursor: &Cursor) -> Result<()> {
         panic!("CodeFormat::split_leaf() - Error. ");
     }
 
+    /// The `<PRE>` contains the code lines
+    fn accepts_block_children(&self) -> bool {
+        true
+    }
+
+    /// A new line in code is a `<BR>` in the code line, the code is not split
+    fn enter_key_behavior(&self) -> EnterKeyBehavior {
+        EnterKeyBehavior::InsertSoftBreak
+    }
+
+    /// An empty code line is kept, the code block is left by the arrow keys
+    fn backspace_at_empty_block_behavior(&self) -> BackspaceEmptyBehavior {
+        BackspaceEmptyBehavior::NoOp
+    }
+
+    /// Code lines with the same language, or both without language, are the same format
     fn is_same_format(&self, left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> bool {
-        let left_a = left.get_operation().get_attributes().clone();
-        let right_a = right.get_operation().get_attributes().clone();
-        if left_a.contains_key(CODE_ATTR_KEY) && right_a.contains_key(CODE_ATTR_KEY) {
-            if let Some(AttrVal::Bool(l)) = left_a.get(CODE_ATTR_KEY) {
-                if let Some(AttrVal::Bool(r)) = right_a.get(CODE_ATTR_KEY) {
-                    return l == r;
-                }
-            }
+        match (CodeBlock::code_value(left), CodeBlock::code_value(right)) {
+            (Some(l), Some(r)) => l == r,
+            _ => false,
         }
-        return false;
     }
 
     //Only called for block-formats; the block is inserted in the parent.
@@ -139,16 +310,62 @@ impl FormatTait for CodeBlock {
         delta: DeltaOperation,
         format: Arc<dyn FormatTait + Send + Sync>,
     ) -> Result<Arc<DocumentNode>> {
-        block_transform(block_node, delta, format, cursor)
+        if !self.merge {
+            return block_transform(block_node, delta, format, cursor);
+        }
+        let update_cursor = &cursor.get_doc_node() == block_node;
+        assert!((update_cursor && block_node.child_count() == 0) || !update_cursor);
+
+        let (pre_node, line_node) = self.create_code_node(delta, format)?;
+        let parent = block_node.get_parent().unwrap();
+        insert_before(&parent, block_node, pre_node.clone());
+        unlink(&parent, block_node);
+
+        if update_cursor {
+            cursor.set_at(&line_node, 0);
+        }
+        for c in block_node.get_children().iter() {
+            unlink(block_node, c);
+            append(&line_node, c.clone());
+        }
+        self.merge_pre_nodes(&pre_node)
     }
 
+    /// In merged mode the line is isolated in its own `<pre>` block first, and then
+    /// transformed in to a `<P>` block.
     fn un_block_transform(
         &self,
         cursor: &Cursor,
         block_node: &Arc<DocumentNode>,
     ) -> Result<Arc<DocumentNode>> {
-        block_node.get_operation().remove_attribute(CODE_ATTR_KEY);
-        un_block_transform(block_node, cursor)
+        if !self.merge {
+            block_node.get_operation().remove_attribute(CODE_ATTR_KEY);
+            return un_block_transform(block_node, cursor);
+        }
+        let update_cursor = &cursor.get_doc_node() == block_node;
+        assert!((update_cursor && block_node.child_count() == 0) || !update_cursor);
+
+        let pre_node = self.split_line_in_own_pre(block_node)?;
+        let parent = pre_node.get_parent().unwrap();
+
+        let idx = pre_node.my_index_as_child().unwrap();
+        let mut attr = pre_node.get_operation().get_attributes().clone();
+        attr.remove(CODE_ATTR_KEY);
+        let op = DeltaOperation::insert_attr("\n", attr);
+        let p_node = P_FORMAT.create(op, P_FORMAT.clone())?;
+        insert_at_index(&parent, idx, p_node.clone());
+
+        unlink(&pre_node, block_node);
+        unlink(&parent, &pre_node);
+
+        if update_cursor {
+            cursor.set_at(&p_node, 0);
+        }
+        for c in block_node.get_children().iter() {
+            unlink(block_node, c);
+            append(&p_node, c.clone());
+        }
+        Ok(p_node)
     }
 
     fn delete_leaf_segment(
@@ -167,14 +384,39 @@ impl FormatTait for CodeBlock {
             .get_doc_dom_node()
             .get_node_name()
             .contains(CODE_TAG));
+        let parent = doc_node.get_parent().unwrap();
         delete_node(doc_node);
+        if self.merge && parent.child_count() == 0 {
+            delete_node(&parent);
+        }
     }
 
+    /// Returns the `<pre>` node with the single line, in merged mode.
     fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        if self.merge {
+            return self.split_line_in_own_pre(doc_node);
+        }
         return Ok(doc_node.clone());
     }
 
-    fn try_merge(&self, _cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+    fn try_merge(&self, _cursor: &Cursor, block_node: &Arc<DocumentNode>) -> Result<()> {
+        if !self.merge {
+            return Ok(());
+        }
+        let pre_node = if self.is_pre_node(block_node) {
+            block_node.clone()
+        } else {
+            block_node.get_parent().unwrap()
+        };
+        self.merge_pre_nodes(&pre_node)?;
         Ok(())
     }
 }
+
+/// Sets the `data-language` of a code line, for a `code-block` attribute with a language
+fn set_language(element: &DomElement, attr: &Attributes) {
+    match attr.get(CODE_ATTR_KEY) {
+        Some(AttrVal::String(language)) => element.set_attribute(LANGUAGE_ATTR, language),
+        _ => element.remove_attribute(LANGUAGE_ATTR),
+    }
+}
@@ -4,9 +4,13 @@ use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
 use core_formats::{P_FORMAT, TEXT_FORMAT};
 use delta::attributes::Attributes;
 use delta::delta::Delta;
+use delta::operations::DeltaOperation;
 use delta::types::attr_val::AttrVal;
+use node_tree::format_trait::{BackspaceEmptyBehavior, EnterKeyBehavior, FormatTait};
 use op_transform::doc_root::DocumentRoot;
+use op_transform::keyboard::KeyboardShortcut;
 use op_transform::registry::Registry;
+use soft_break::{SoftBreak, NAME_SOFT_BREAK};
 use std::ops::Deref;
 use std::sync::{Arc, Mutex, OnceLock};
 use wasm_bindgen_test::*;
@@ -23,6 +27,8 @@ fn init_test_registry() {
             .unwrap();
         r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
             .unwrap();
+        r.register_line_fmt(NAME_SOFT_BREAK, Arc::new(SoftBreak::new()))
+            .unwrap();
         r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
             .unwrap();
         Mutex::new(1)
@@ -182,3 +188,145 @@ fn code_double_span_delete_test() -> Result<()> {
     assert_eq!(doc.as_html_string(), html_txt);
     Ok(())
 }
+
+#[wasm_bindgen_test]
+fn code_auto_format_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_auto_format_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+
+    for c in "```let x = 1;".chars() {
+        doc.apply_operation(DeltaOperation::insert(c.to_string()))?;
+    }
+    let html_txt = r##"<span class="ql-pre">let x = 1;</span>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn code_import_markdown_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_import_markdown_test");
+    doc.open()?;
+
+    doc.import_markdown("```rust\nlet x = 1;\n    indented\n```\nafter")?;
+    let html_txt = r##"<span class="ql-pre">let x = 1;</span><span class="ql-pre">    indented</span><p>after</p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn code_split_text_lines_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_split_text_lines_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert("code-block", true);
+    doc.apply_operation(DeltaOperation::insert_attr("one\ntwo\n", attr))?;
+    let html_txt = r##"<span class="ql-pre">one</span><span class="ql-pre">two</span><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    //only the block operations are code blocks
+    for op in doc.to_delta().get_ops() {
+        let is_block = op.insert_value().is_string() && op.insert_value().str_val()? == "\n";
+        if op.op_len() > 0 && !is_block {
+            assert!(!op.get_attributes().contains_key("code-block"));
+        }
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn code_enter_key_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_enter_key_test");
+    doc.open()?;
+    assert_eq!(
+        CodeBlock::new().enter_key_behavior(),
+        EnterKeyBehavior::InsertSoftBreak
+    );
+
+    let mut attr = Attributes::default();
+    attr.insert("code-block", true);
+    let mut delta = Delta::default();
+    delta.insert("hello");
+    delta.insert_attr("\n", attr);
+    doc.apply_delta(delta)?;
+
+    //a soft break in the code line, not a new line
+    doc.get_cursor().set_selection_by_retain_range(2, 2)?;
+    let enter = KeyboardShortcut::new("Enter", false, false, false);
+    assert!(doc.handle_shortcut(&enter));
+    let expect = r#"<span class="ql-pre">he<br>llo</span><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    assert_eq!(doc.get_cursor().get_retain_index(), 3);
+
+    //a paragraph is still split
+    doc.get_cursor().set_selection_by_retain_range(7, 7)?;
+    assert!(doc.handle_shortcut(&enter));
+    let expect = r#"<span class="ql-pre">he<br>llo</span><p><br></p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn code_enter_operation_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_enter_operation_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert("code-block", true);
+    let mut delta = Delta::default();
+    delta.insert("a\nb");
+    delta.insert_attr("\n", attr);
+    doc.apply_delta(delta)?;
+    let html = doc.as_html_string();
+
+    //only the Enter key inserts a soft break, an operation is applied as it is
+    doc.get_cursor().set_selection_by_retain_range(1, 1)?;
+    doc.apply_operation(DeltaOperation::delete(1))?;
+    assert!(doc.undo()?);
+    assert_eq!(doc.as_html_string(), html);
+    for op in doc.to_delta().get_ops() {
+        assert!(op.insert_value().is_string());
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn code_backspace_empty_line_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_backspace_empty_line_test");
+    doc.open()?;
+    assert_eq!(
+        CodeBlock::new().backspace_at_empty_block_behavior(),
+        BackspaceEmptyBehavior::NoOp
+    );
+
+    let mut attr = Attributes::default();
+    attr.insert("code-block", true);
+    let mut delta = Delta::default();
+    delta.insert("a");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert_attr("\n", attr);
+    doc.apply_delta(delta)?;
+    let html = doc.as_html_string();
+
+    //the empty code line is kept
+    doc.get_cursor().set_selection_by_retain_range(2, 2)?;
+    let backspace = KeyboardShortcut::new("Backspace", false, false, false);
+    assert!(doc.handle_shortcut(&backspace));
+    assert_eq!(doc.as_html_string(), html);
+    assert_eq!(doc.get_cursor().get_retain_index(), 2);
+
+    //the Delete key at the end of the line above still deletes its new line
+    doc.get_cursor().set_selection_by_retain_range(1, 1)?;
+    let delete = KeyboardShortcut::new("Delete", false, false, false);
+    assert!(doc.handle_shortcut(&delete));
+    let expect = r#"<span class="ql-pre">a</span><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
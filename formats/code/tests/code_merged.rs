@@ -0,0 +1,194 @@
+use anyhow::Result;
+use code::{CodeBlock, NAME_CODE};
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::types::attr_val::AttrVal;
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers the merged code block format
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_CODE, Arc::new(CodeBlock::new_merged()))
+            .unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn code_delta() -> Delta {
+    let mut attr = Attributes::default();
+    attr.insert("code-block", true);
+
+    let mut delta = Delta::default();
+    delta.insert("hello ");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("sweet");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("world");
+    delta.insert_attr("\n", attr);
+    delta
+}
+
+#[wasm_bindgen_test]
+fn code_merged_format_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_merged_format_test");
+    doc.open()?;
+    doc.apply_delta(code_delta())?;
+
+    let html_txt = r##"<pre class="ql-syntax"><span class="ql-pre">hello </span><span class="ql-pre">sweet</span><span class="ql-pre">world</span></pre><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    //the delta does not contain the <pre> node
+    let mut expect = code_delta();
+    expect.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn code_merged_is_same_format_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_merged_is_same_format_test");
+    doc.open()?;
+    doc.apply_delta(code_delta())?;
+
+    let pre = doc.get_root().get_child(0).unwrap();
+    let first = pre.get_child(0).unwrap();
+    let second = pre.get_child(1).unwrap();
+    assert!(CodeBlock::new_merged().is_same_format(&first, &second));
+
+    let p = doc.get_root().get_child(1).unwrap();
+    assert!(!CodeBlock::new_merged().is_same_format(&first, &p));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn code_merged_split_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_merged_split_test");
+    doc.open()?;
+    doc.apply_delta(code_delta())?;
+
+    //remove the code format from the middle line
+    let mut attr = Attributes::default();
+    attr.insert("code-block", AttrVal::Null);
+    let mut delta = Delta::default();
+    delta.retain(12);
+    delta.retain_attr(1, attr);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<pre class="ql-syntax"><span class="ql-pre">hello </span></pre><p>sweet</p><pre class="ql-syntax"><span class="ql-pre">world</span></pre><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    //and merge them again
+    let mut attr = Attributes::default();
+    attr.insert("code-block", true);
+    let mut delta = Delta::default();
+    delta.retain(12);
+    delta.retain_attr(1, attr);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<pre class="ql-syntax"><span class="ql-pre">hello </span><span class="ql-pre">sweet</span><span class="ql-pre">world</span></pre><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    let mut expect = code_delta();
+    expect.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn code_merged_line_start_end_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_merged_line_start_end_test");
+    doc.open()?;
+    doc.apply_delta(code_delta())?;
+
+    //each line of the code block is a line, the <pre> node is skipped
+    let cursor = doc.get_cursor();
+    for (index, start, end) in [(3, 0, 6), (9, 7, 12), (15, 13, 18)] {
+        set_cursor_selection(cursor, index, 0);
+        cursor.line_end()?;
+        assert_eq!(cursor.get_retain_index(), end);
+        cursor.line_start()?;
+        assert_eq!(cursor.get_retain_index(), start);
+    }
+    Ok(())
+}
+
+fn code_language(language: &str) -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert("code-block", language);
+    attr
+}
+
+#[wasm_bindgen_test]
+fn code_merged_language_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_merged_language_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("fn");
+    delta.insert_attr("\n", code_language("rust"));
+    delta.insert("def");
+    delta.insert_attr("\n", code_language("python"));
+    delta.insert("pass");
+    delta.insert_attr("\n", code_language("python"));
+    doc.apply_delta(delta)?;
+
+    //only the python lines merge
+    let html_txt = r##"<pre class="ql-syntax"><span class="ql-pre" data-language="rust">fn</span></pre><pre class="ql-syntax"><span class="ql-pre" data-language="python">def</span><span class="ql-pre" data-language="python">pass</span></pre><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    let rust = doc.get_root().get_child(0).unwrap().get_child(0).unwrap();
+    let python = doc.get_root().get_child(1).unwrap();
+    let def = python.get_child(0).unwrap();
+    let pass = python.get_child(1).unwrap();
+    assert!(!CodeBlock::new_merged().is_same_format(&rust, &def));
+    assert!(CodeBlock::new_merged().is_same_format(&def, &pass));
+
+    //the language survives the round trip through the delta
+    let mut copy = DocumentRoot::new("code_merged_language_copy_test");
+    copy.load(doc.to_delta())?;
+    assert_eq!(copy.as_html_string(), html_txt);
+    assert_eq!(copy.to_delta().get_ops(), doc.to_delta().get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn code_merged_language_bool_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("code_merged_language_bool_test");
+    doc.open()?;
+    let mut attr = Attributes::default();
+    attr.insert("code-block", true);
+    let mut delta = Delta::default();
+    delta.insert("plain");
+    delta.insert_attr("\n", attr);
+    delta.insert("fn");
+    delta.insert_attr("\n", code_language("rust"));
+    doc.apply_delta(delta)?;
+
+    //a line without language is not the same format as a rust line
+    let html_txt = r##"<pre class="ql-syntax"><span class="ql-pre">plain</span></pre><pre class="ql-syntax"><span class="ql-pre" data-language="rust">fn</span></pre><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    Ok(())
+}
@@ -0,0 +1,146 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::util::node_morph::delete_node;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use dom::dom_element::DomElement;
+use node_tree::cursor::Cursor;
+use node_tree::doc_node::DocumentNode;
+use node_tree::format_trait::FormatTait;
+use std::sync::Arc;
+
+pub static NAME_DIVIDER: &'static str = "divider"; //registry label, and insert::map key
+
+static DIVIDER_TAG: &'static str = "HR"; //html tag
+
+/// # HorizontalRuleFormat
+///
+/// A horizontal rule between two paragraphs:
+/// ```bash
+///   {insert: "above\n"}
+///   {insert: { divider: true }}
+///   {insert: "\nbelow\n"}
+/// ```
+///
+/// Results in:
+/// ```bash
+/// <p>above</p><p><hr></p><p>below</p>
+/// ```
+///
+/// The `<HR>` is a void element, it has no children. Like an image it is an embed with
+/// the length 1, so it can not be split, and two rules are never merged.
+pub struct HorizontalRuleFormat {}
+
+impl HorizontalRuleFormat {
+    pub fn new() -> Self {
+        HorizontalRuleFormat {}
+    }
+}
+
+impl FormatTait for HorizontalRuleFormat {
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let dom_el = DomElement::new(DIVIDER_TAG);
+        let doc_node = DocumentNode::new_element(dom_el, formatter);
+        doc_node.set_operation(operation);
+        Ok(Arc::new(doc_node))
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_DIVIDER
+    }
+
+    fn is_text_format(&self) -> bool {
+        true
+    }
+
+    fn is_void(&self) -> bool {
+        true
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        panic!("Hey you called HorizontalRuleFormat::block_remove_attr() on the TextFormatter format-trait implementation.");
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        let val = delta.insert_value();
+        if val.is_map() {
+            return Ok(val.map_val()?.contains_key(NAME_DIVIDER));
+        }
+        Ok(false)
+    }
+
+    //A horizontal rule has no attributes
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        _attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn split_leaf(&self, _cursor: &Cursor) -> Result<()> {
+        //void element with length 1 (always) so it can not be split
+        panic!("HorizontalRuleFormat::split_leaf() - Error.");
+    }
+
+    fn is_same_format(&self, _left: &Arc<DocumentNode>, _right: &Arc<DocumentNode>) -> bool {
+        false //every rule is a unit of its own
+    }
+
+    fn block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+        _delta: DeltaOperation,
+        _new_transducer: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("HorizontalRuleFormat::block_transform() - Error.");
+    }
+
+    fn un_block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("HorizontalRuleFormat::un_block_transform() - Error.");
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        _doc_node: &Arc<DocumentNode>,
+        _at: usize,
+        _length: usize,
+    ) -> Result<()> {
+        panic!("HorizontalRuleFormat::Delete() - Rule has length 1, so use the other delete function...");
+    }
+
+    //Deletes the rule as a whole
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        delete_node(doc_node);
+    }
+
+    //The <HR> has no children, so it is isolated already
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    //Two rules next to each other stay two rules
+    fn try_merge(&self, _cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        Ok(())
+    }
+}
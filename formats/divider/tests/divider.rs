@@ -0,0 +1,115 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::{DeltaOperation, OpsMap};
+use divider::{HorizontalRuleFormat, NAME_DIVIDER};
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_DIVIDER, Arc::new(HorizontalRuleFormat::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn divider() -> DeltaOperation {
+    let mut hr = OpsMap::default();
+    hr.insert(NAME_DIVIDER, true);
+    DeltaOperation::insert_attr(hr, Attributes::default())
+}
+
+/// Creates the document with a rule between 2 paragraphs
+fn create_test_rule(doc: &mut DocumentRoot) -> Result<Delta> {
+    let mut delta = Delta::default();
+    delta.insert("First\n");
+    delta.push(divider());
+    delta.insert("\nSecond\n");
+
+    doc.open()?;
+    doc.apply_delta(delta.clone())?;
+
+    let expect = r#"<p>First</p><p><hr></p><p>Second</p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(delta)
+}
+
+#[test]
+fn detect_divider_format() -> Result<()> {
+    let format = HorizontalRuleFormat::new();
+    assert_eq!(format.format_name(), NAME_DIVIDER);
+    assert!(format.applies(&divider())?);
+    assert!(!format.applies(&DeltaOperation::insert("---"))?);
+
+    let mut img = OpsMap::default();
+    img.insert("image", "image-source.png");
+    assert!(!format.applies(&DeltaOperation::insert_attr(img, Attributes::default()))?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn divider_create_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("divider_create_test");
+    let mut delta = create_test_rule(&mut doc)?;
+
+    let p = doc.get_root().get_child(1).unwrap();
+    let hr = p.get_child(0).unwrap();
+    assert_eq!(hr.op_len(), 1);
+
+    delta.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn divider_delete_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("divider_delete_test");
+    create_test_rule(&mut doc)?;
+
+    //delete the rule, and the new line of its paragraph
+    set_cursor_selection(doc.get_cursor(), 6, 0);
+    doc.apply_operation(DeltaOperation::delete(2))?;
+    assert_eq!(doc.as_html_string(), "<p>First</p><p>Second</p><p><br></p>");
+
+    let mut expect = Delta::default();
+    expect.insert("First\nSecond\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn divider_no_merge_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("divider_no_merge_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.push(divider());
+    delta.push(divider());
+    delta.insert("\n");
+    doc.apply_delta(delta)?;
+
+    assert_eq!(doc.as_html_string(), "<p><hr><hr></p><p><br></p>");
+    assert_eq!(doc.get_root().get_child(0).unwrap().child_count(), 2);
+    Ok(())
+}
@@ -0,0 +1,191 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::util::block::{
+    apply_attributes, block_transform, drop_attributes, un_block_transform,
+};
+use core_formats::util::block_format;
+use core_formats::util::block_format::BlockAttributeRegistry;
+use core_formats::util::node_morph::delete_node;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal::Null;
+use dom::dom_element::DomElement;
+use node_tree::cursor::Cursor;
+use node_tree::doc_node::DocumentNode;
+use node_tree::format_trait::FormatTait;
+use std::sync::Arc;
+
+pub static NAME_FIGURE: &'static str = "figure"; //registry label
+
+static FIGURE_TAG: &'static str = "FIGURE"; //HTML tag
+static FIGCAPTION_TAG: &'static str = "FIGCAPTION"; //HTML tag of the caption
+static CAPTION_ATTR_KEY: &'static str = "caption"; //attribute key
+
+/// # FigureFormat
+///
+/// An image with a caption. The caption is a block attribute of the line holding the image:
+/// ```bash
+///   { insert: { image: 'cat.png' }, attributes: { alt: "cat" } }
+///   { insert: "\n", attributes: { caption: "A cat" } }
+/// ```
+///
+/// Gives as HTML:
+/// ```bash
+///     <figure><figcaption contenteditable="false">A cat</figcaption><img img="cat.png" alt="cat"></figure>
+/// ```
+///
+/// The `<FIGURE>` is a block, so it is a sibling of the paragraphs, and never inside one.
+/// The `<IMG>` is a text leaf of the block, made by the `ImageFormat`. The `<FIGCAPTION>`
+/// is not a document node. It is the first child of the `<FIGURE>`, so the leaves of the
+/// block are appended after it. Like the check box of a check list, it is not editable in
+/// the browser: the caption is changed with a retain of the `caption` attribute.
+///
+/// Removing the caption, with a retain of `{caption: null}`, gives back a paragraph with the
+/// standalone image. An empty caption is a paragraph too.
+///
+/// Register this format before the paragraph.
+pub struct FigureFormat {}
+
+impl FigureFormat {
+    pub fn new() -> Self {
+        block_format::initialise();
+        BlockAttributeRegistry::register_attr(CAPTION_ATTR_KEY);
+        FigureFormat {}
+    }
+}
+
+/// Returns true when the attributes have a non empty caption
+fn has_caption(attr: &Attributes) -> bool {
+    match attr.get(CAPTION_ATTR_KEY) {
+        Some(val) if val.is_string() => !val.str_val().unwrap().is_empty(),
+        _ => false,
+    }
+}
+
+/// Sets the text of the `<FIGCAPTION>` element, which is made when there is none.
+fn set_caption(figure: &DomElement, attr: &Attributes) -> Result<()> {
+    let text = match attr.get(CAPTION_ATTR_KEY) {
+        Some(val) if val.is_string() => val.str_val()?,
+        _ => "",
+    };
+    if let Some(caption) = figure.find_down(FIGCAPTION_TAG) {
+        caption.set_text_content(Some(text));
+    } else {
+        let caption = DomElement::new(FIGCAPTION_TAG);
+        caption.set_attribute("contenteditable", "false");
+        caption.element().set_text_content(Some(text));
+        figure.insert_child(0, caption.node());
+    }
+    Ok(())
+}
+
+impl FormatTait for FigureFormat {
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let figure = DomElement::new(FIGURE_TAG);
+        block_format::apply(&figure, operation.get_attributes())?;
+        set_caption(&figure, operation.get_attributes())?;
+        let doc_node = DocumentNode::new_element(figure, formatter);
+        doc_node.set_operation(operation);
+        Ok(Arc::new(doc_node))
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_FIGURE
+    }
+
+    fn is_text_format(&self) -> bool {
+        false
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        let mut attr = Attributes::default();
+        attr.insert(CAPTION_ATTR_KEY, Null);
+        attr
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        if delta.insert_value().is_string() && delta.insert_value().str_val()? == "\n" {
+            return Ok(has_caption(delta.get_attributes()));
+        }
+        Ok(false)
+    }
+
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let doc_node = apply_attributes(doc_node, attr)?;
+        set_caption(&doc_node.get_dom_element().unwrap(), attr)?;
+        Ok(doc_node)
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        drop_attributes(doc_node)
+    }
+
+    fn split_leaf(&self, _cursor: &Cursor) -> Result<()> {
+        panic!("FigureFormat::split_leaf() - Error.");
+    }
+
+    fn is_same_format(&self, _left: &Arc<DocumentNode>, _right: &Arc<DocumentNode>) -> bool {
+        false //figures never merge
+    }
+
+    fn block_transform(
+        &self,
+        cursor: &Cursor,
+        block_node: &Arc<DocumentNode>,
+        delta: DeltaOperation,
+        format: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        block_transform(block_node, delta, format, cursor)
+    }
+
+    /// Gives back a paragraph with the standalone `<IMG>`, without the caption
+    fn un_block_transform(
+        &self,
+        cursor: &Cursor,
+        block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        //get_operation() returns a copy, so we need to set the operation again
+        let mut op = block_node.get_operation();
+        op.remove_attribute(CAPTION_ATTR_KEY);
+        block_node.set_operation(op);
+        un_block_transform(block_node, cursor)
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        _doc_node: &Arc<DocumentNode>,
+        _at: usize,
+        _length: usize,
+    ) -> Result<()> {
+        panic!("FigureFormat::delete() - Error. Block has length 1, so use the other delete function...");
+    }
+
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        assert_eq!(doc_node.get_doc_dom_node().get_node_name(), FIGURE_TAG);
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    //figures never merge
+    fn try_merge(&self, _cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::{DeltaOperation, OpsMap};
+use delta::types::attr_val::AttrVal;
+use dom::constants::DOCUMENT;
+use figure::{FigureFormat, NAME_FIGURE};
+use image::{ImageFormat, NAME_IMAGE};
+use node_tree::dom_cursor::DomCursor;
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_FIGURE, Arc::new(FigureFormat::new()))
+            .unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_IMAGE, Arc::new(ImageFormat::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn image_op() -> DeltaOperation {
+    let mut img = OpsMap::default();
+    img.insert(NAME_IMAGE, "cat.png");
+    let mut attr = Attributes::default();
+    attr.insert("alt", "cat");
+    DeltaOperation::insert_attr(img, attr)
+}
+
+fn caption(text: &str) -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert("caption", text);
+    attr
+}
+
+fn create_test_figure(doc: &mut DocumentRoot) -> Result<()> {
+    let mut delta = Delta::default();
+    delta.insert("a\n");
+    delta.push(image_op());
+    delta.insert_attr("\n", caption("A cat"));
+    delta.insert("b");
+    doc.open()?;
+    doc.apply_delta(delta)?;
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn figure_insert_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("figure_insert_test");
+    create_test_figure(&mut doc)?;
+
+    //the figure is a block, next to the paragraphs
+    let expect = r##"<p>a</p><figure><figcaption contenteditable="false">A cat</figcaption><img img="cat.png" alt="cat"></figure><p>b</p>"##;
+    assert_eq!(doc.as_html_string(), expect);
+
+    let figure = doc.get_root().get_children().get(1).unwrap().clone();
+    assert_eq!(figure.op_len(), 1);
+    assert_eq!(figure.get_formatter().format_name(), NAME_FIGURE);
+    assert_eq!(
+        figure.get_operation(),
+        DeltaOperation::insert_attr("\n", caption("A cat"))
+    );
+    let img = figure.get_children().get(0).unwrap().clone();
+    assert_eq!(img.get_formatter().format_name(), NAME_IMAGE);
+    assert_eq!(img.get_operation(), image_op());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn figure_caption_selection_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("figure_caption_selection_test");
+    doc.append_to_body();
+    create_test_figure(&mut doc)?;
+    let figure = doc.get_root().get_child(1).unwrap();
+    let caption = figure
+        .get_dom_element()
+        .unwrap()
+        .find_down("FIGCAPTION")
+        .unwrap();
+    assert_eq!(
+        caption.get_attribute("contenteditable"),
+        Some("false".to_string())
+    );
+
+    //a selection in the caption text is at the start of the figure
+    let text = caption.first_child().unwrap();
+    DOCUMENT.with(|d| {
+        let range = d.create_range().unwrap();
+        range.set_start(&text, 2).unwrap();
+        let selection = d.get_selection().unwrap().unwrap();
+        selection.remove_all_ranges().unwrap();
+        selection.add_range(&range).unwrap();
+    });
+    let cursor = DomCursor::new(doc.get_root()).cursor_from_html_dom();
+    assert_eq!(cursor.calculate_retain_index(), 2);
+    doc.close();
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn figure_no_caption_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("figure_no_caption_test");
+    doc.open()?;
+    doc.apply_operation(image_op())?;
+    doc.apply_operation(DeltaOperation::insert_attr("\n", caption("")))?;
+
+    //an empty caption is a plain image
+    let p = doc.get_root().get_children().get(0).unwrap().clone();
+    assert_eq!(p.get_formatter().format_name(), NAME_P_BLOCK);
+    let img = p.get_children().get(0).unwrap().clone();
+    assert_eq!(img.get_formatter().format_name(), NAME_IMAGE);
+    assert!(!doc.as_html_string().contains("figure"));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn figure_update_caption_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("figure_update_caption_test");
+    create_test_figure(&mut doc)?;
+
+    let mut delta = Delta::default();
+    delta.retain(3);
+    delta.retain_attr(1, caption("A black cat"));
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let expect = r##"<p>a</p><figure><figcaption contenteditable="false">A black cat</figcaption><img img="cat.png" alt="cat"></figure><p>b</p>"##;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //removing the caption gives the standalone image
+    let mut attr = Attributes::default();
+    attr.insert("caption", AttrVal::Null);
+    let mut delta = Delta::default();
+    delta.retain(3);
+    delta.retain_attr(1, attr);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let expect = r##"<p>a</p><p><img img="cat.png" alt="cat"></p><p>b</p>"##;
+    assert_eq!(doc.as_html_string(), expect);
+    let p = doc.get_root().get_children().get(1).unwrap().clone();
+    assert_eq!(p.get_formatter().format_name(), NAME_P_BLOCK);
+    let img = p.get_children().get(0).unwrap().clone();
+    assert_eq!(img.get_formatter().format_name(), NAME_IMAGE);
+
+    //adding a caption to a plain image gives the figure again
+    let mut delta = Delta::default();
+    delta.retain(3);
+    delta.retain_attr(1, caption("A cat"));
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let expect = r##"<p>a</p><figure><figcaption contenteditable="false">A cat</figcaption><img img="cat.png" alt="cat"></figure><p>b</p>"##;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn figure_un_block_transform_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("figure_un_block_transform_test");
+    create_test_figure(&mut doc)?;
+
+    let figure = doc.get_root().get_children().get(1).unwrap().clone();
+    let p = FigureFormat::new().un_block_transform(doc.get_cursor(), &figure)?;
+    assert_eq!(p.get_formatter().format_name(), NAME_P_BLOCK);
+    assert!(p.get_operation().get_attributes().get("caption").is_none());
+    let img = p.get_children().get(0).unwrap().clone();
+    assert_eq!(img.get_formatter().format_name(), NAME_IMAGE);
+
+    let expect = r##"<p>a</p><p><img img="cat.png" alt="cat"></p><p>b</p>"##;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn figure_delete_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("figure_delete_test");
+    create_test_figure(&mut doc)?;
+
+    //the image, and the line of the figure
+    let mut delta = Delta::default();
+    delta.retain(2);
+    delta.delete(2);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let expect = r##"<p>a</p><p>b</p>"##;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
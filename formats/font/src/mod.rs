@@ -0,0 +1,230 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::format_const::TEXT_ATTR_FONT;
+use core_formats::util::lookup::{AttributesLookup, Attributor};
+use core_formats::util::node_morph::{
+    delete_node, delete_text, split_text_node, try_3_way_merge_text,
+};
+use core_formats::TEXT_FORMAT;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use dom::dom_element::DomElement;
+use dom::dom_text::DomText;
+use node_tree::cursor::{Cursor, CursorLocation};
+use node_tree::doc_node::DocumentNode;
+use node_tree::dom_doc_tree_morph::{insert_at_index, unlink};
+use node_tree::format_trait::FormatTait;
+use once_cell::sync::{Lazy, OnceCell};
+use std::sync::Arc;
+
+pub static NAME_FONT: &'static str = "font"; //registry label
+
+static SPAN_TAG: &'static str = "SPAN"; //HTML tag for text without other formats
+
+static FONT_FORMAT: Lazy<Arc<dyn FormatTait + Send + Sync>> =
+    Lazy::new(|| Arc::new(FontFamilyFormat::new()));
+
+static ATTRIBUTES: OnceCell<AttributesLookup> = OnceCell::new();
+pub(crate) fn initialise() {
+    if let Some(_attr) = ATTRIBUTES.get() {
+        return;
+    }
+    let mut attr = AttributesLookup::new(1);
+    attr.fill_one(TEXT_ATTR_FONT, "font-family");
+    ATTRIBUTES
+        .set(attr)
+        .expect("did you call font::initialise() twice?");
+}
+
+/// # FontFamilyFormat
+///
+/// ```bash
+///   {insert: "code", attributes: {font: "monospace"}}
+///   {insert: "fun", attributes: {font: "Comic Sans MS", bold: true}}
+/// ```
+///
+/// Gives as HTML:
+/// ```bash
+///     <span style="font-family:monospace;">code</span>
+///     <strong style="font-family:Comic Sans MS;">fun</strong>
+/// ```
+///
+/// The font is a free form string, which is used as the CSS `font-family` as it is. The
+/// `TextFormat` shows a font as a `ql-font-` class instead, so register this format before
+/// the `TextFormat` to get the `font-family` style.
+///
+/// The other text formats are made by the `TextFormat`. Without any, the text gets a `<SPAN>`.
+pub struct FontFamilyFormat {}
+impl FontFamilyFormat {
+    pub fn new() -> Self {
+        initialise();
+        FontFamilyFormat {}
+    }
+}
+
+/// Returns the font name, `None` when there is no font or it is `null`
+fn font_name(attr: &Attributes) -> Option<&str> {
+    match attr.get(TEXT_ATTR_FONT) {
+        Some(AttrVal::String(s)) => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+/// Replaces the document node with a new one having the given attributes.<br>
+/// Without a font the node is given to the `TextFormat`.
+fn apply_font(doc_node: &Arc<DocumentNode>, attr: &Attributes) -> Result<Arc<DocumentNode>> {
+    assert!(doc_node.get_formatter().is_text_format());
+    let index = doc_node.my_index_as_child().unwrap();
+
+    let format = if font_name(attr).is_some() {
+        FONT_FORMAT.clone()
+    } else {
+        TEXT_FORMAT.clone()
+    };
+    let old_op = doc_node.get_operation();
+    let op = DeltaOperation::insert_attr(old_op.insert_value().str_val()?, attr.clone());
+    let new_doc_node = format.create(op, format.clone())?;
+
+    let parent = doc_node.get_parent().unwrap();
+    unlink(&parent, doc_node);
+    insert_at_index(&parent, index, new_doc_node.clone());
+    Ok(new_doc_node)
+}
+
+impl FormatTait for FontFamilyFormat {
+    /// Lets the `TextFormat` create the text without the font, and then adds the
+    /// `font-family` style.
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let txt = operation.insert_value().str_val()?;
+        let mut attr = operation.get_attributes().clone();
+        attr.remove(TEXT_ATTR_FONT);
+        let doc_node = TEXT_FORMAT.create(DeltaOperation::insert_attr(txt, attr), formatter)?;
+
+        let doc_node = if doc_node.get_dom_element().is_some() {
+            doc_node
+        } else {
+            let span = DomElement::new(SPAN_TAG);
+            span.append_child(DomText::new(txt).node());
+            Arc::new(DocumentNode::new_element(span, doc_node.get_formatter()))
+        };
+        let element = doc_node.get_dom_element().unwrap();
+        let attr = operation.get_attributes();
+        for (style, val) in Attributor::selected(attr, ATTRIBUTES.get().unwrap()) {
+            DomElement::add_style(element, style, val.str_val()?);
+        }
+        doc_node.set_operation(operation);
+        Ok(doc_node)
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_FONT
+    }
+
+    fn is_text_format(&self) -> bool {
+        true
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        panic!("Hey you called FontFamilyFormat::block_remove_attr() on a text format-trait implementation.");
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        if delta.insert_value().is_string() && delta.insert_value().str_val()? != "\n" {
+            return Ok(font_name(delta.get_attributes()).is_some());
+        }
+        Ok(false)
+    }
+
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        apply_font(doc_node, attr)
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        TEXT_FORMAT.drop_line_attributes(doc_node)
+    }
+
+    /// `split_text_node()` creates the right hand node as plain text, so we give it the
+    /// font again.
+    ///
+    /// Post condition: Cursor position is BEFORE the right hand side created node
+    fn split_leaf(&self, cursor: &Cursor) -> Result<()> {
+        if let CursorLocation::At(doc_node, index) = cursor.get_location() {
+            if index == 0 {
+                return Ok(());
+            }
+            let right = split_text_node(&doc_node, index)?;
+            let right = apply_font(&right, doc_node.get_operation().get_attributes())?;
+            cursor.set_before_no_retain_update(&right);
+        }
+        Ok(())
+    }
+
+    //Font names are case-insensitive in CSS, so "Serif" and "serif" are the same font
+    fn is_same_format(&self, left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> bool {
+        let left = left.get_operation();
+        let right = right.get_operation();
+        match (
+            font_name(left.get_attributes()),
+            font_name(right.get_attributes()),
+        ) {
+            (Some(l), Some(r)) => l.eq_ignore_ascii_case(r),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+        _delta: DeltaOperation,
+        _new_transducer: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("FontFamilyFormat::block_transform() - Error.");
+    }
+
+    fn un_block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("FontFamilyFormat::un_block_transform() - Error.");
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        at: usize,
+        length: usize,
+    ) -> Result<()> {
+        delete_text(doc_node, at, length)
+    }
+
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn try_merge(&self, cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        try_3_way_merge_text(cursor)
+    }
+}
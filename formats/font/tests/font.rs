@@ -0,0 +1,108 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use font::{FontFamilyFormat, NAME_FONT};
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_FONT, Arc::new(FontFamilyFormat::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn font(val: &str) -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert("font", val);
+    attr
+}
+
+#[test]
+fn detect_font_format() -> Result<()> {
+    let format = FontFamilyFormat::new();
+    assert_eq!(format.format_name(), NAME_FONT);
+    assert!(format.applies(&DeltaOperation::insert_attr("a", font("serif")))?);
+    assert!(!format.applies(&DeltaOperation::insert("a"))?);
+    assert!(!format.applies(&DeltaOperation::insert_attr("\n", font("serif")))?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn font_bold_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("font_bold_test");
+    doc.open()?;
+
+    let mut bold = font("Comic Sans MS");
+    bold.insert("bold", true);
+
+    let mut delta = Delta::default();
+    delta.insert_attr("code", font("monospace"));
+    delta.insert(" and ");
+    delta.insert_attr("fun", bold.clone());
+    delta.insert_attr("!", font("Comic Sans MS"));
+    delta.insert("\n");
+    doc.apply_delta(delta.clone())?;
+
+    let html_txt = r##"<p><span style="font-family:monospace;">code</span> and <strong style="font-family:Comic Sans MS;">fun</strong><span style="font-family:Comic Sans MS;">!</span></p><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    delta.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+
+    //inserting in the bold text keeps both the font and the <STRONG>
+    let mut delta = Delta::default();
+    delta.retain(10);
+    delta.insert_attr("n", bold);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<p><span style="font-family:monospace;">code</span> and <strong style="font-family:Comic Sans MS;">funn</strong><span style="font-family:Comic Sans MS;">!</span></p><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn font_same_format_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("font_same_format_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert_attr("a", font("Serif"));
+    delta.insert_attr("b", font("serif"));
+    delta.insert_attr("c", font("monospace"));
+    delta.insert("d\n");
+    doc.apply_delta(delta)?;
+
+    let p = doc.get_root().get_child(0).unwrap();
+    let a = p.get_child(0).unwrap();
+    let b = p.get_child(1).unwrap();
+    let c = p.get_child(2).unwrap();
+    let d = p.get_child(3).unwrap();
+
+    let format = FontFamilyFormat::new();
+    assert!(format.is_same_format(&a, &b));
+    assert!(!format.is_same_format(&b, &c));
+    assert!(!format.is_same_format(&c, &d));
+    Ok(())
+}
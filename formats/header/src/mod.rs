@@ -5,28 +5,34 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use crate::error::Error::InvalidHeadingLevel;
 use anyhow::Result;
+use core_formats::util::anchor::slugify;
 use core_formats::util::block::{
     apply_attributes, block_transform, drop_attributes, un_block_transform,
 };
 use core_formats::util::block_format;
+use core_formats::util::block_format::BlockAttributeRegistry;
 use core_formats::util::lookup::AttributesLookup;
 use core_formats::util::node_morph::delete_node;
 use delta::attributes::Attributes;
 use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
 use delta::types::attr_val::AttrVal::Null;
 use dom::dom_element::DomElement;
-use log::error;
 use node_tree::cursor::Cursor;
 use node_tree::doc_node::DocumentNode;
 use node_tree::format_trait::FormatTait;
 use once_cell::sync::OnceCell;
 use std::sync::Arc;
 
+pub mod error;
+
 pub static NAME_HEADER: &'static str = "heading"; //registry label
 
 static HX_TAG: &'static str = "H"; //HTML tag
 static HEADER_ATTR_KEY: &'static str = "heading"; //attribute key
+static HX_CLASS: &'static str = "ql-header-"; //CSS class prefix for H4 .. H6
 
 //FIXME: Default structure, but is it used in this scope?
 static ATTRIBUTES: OnceCell<AttributesLookup> = OnceCell::new();
@@ -47,14 +53,50 @@ pub(crate) fn initialise() {
 ///  - `{ insert(header 1)}, {insert(\n), attributes:{heading:1}}` --> `<H1>header 1</H1>`
 ///  - `{ insert(header 2)}, {insert(\n), attributes:{heading:2}}` --> `<H2>header 2</H2>`
 ///  - `{ insert(header 3)}, {insert(\n), attributes:{heading:3}}` --> `<H3>header 3</H3>`
+///  - `{ insert(header 4)}, {insert(\n), attributes:{heading:4}}` --> `<H4 class="ql-header-4">header 4</H4>`
+///
+/// The attribute in the delta should show: header, and the value should show which one 1 .. 6
+///
+/// Browsers show H5 and H6 smaller than the normal text. So H4 .. H6 get a CSS class,
+/// which a style sheet can use:
+/// ```bash
+///     h5.ql-header-5 {
+///        font-size: 1em;
+///     }
+/// ```
 ///
-/// The attribute in the delta should show: header, and the value should show which one 1,2,3
-pub struct HeaderBlock {}
+/// # ANCHOR IDS
+///
+/// With `HeaderBlock::new_with_anchors()` the header element gets an `id` for deep links,
+/// made from the header text by `generate_id()`: `<H1 id="some-title">Some title</H1>`.
+/// The document sets the IDs after each change, see `FormatTait::has_anchor_id()`, so
+/// editing the header text changes the ID. A duplicate ID gets a suffix within the
+/// document: `intro`, `intro-2`, ... A header without text has no ID.
+pub struct HeaderBlock {
+    anchors: bool,
+}
 impl HeaderBlock {
     pub fn new() -> Self {
         initialise();
         block_format::initialise();
-        HeaderBlock {}
+        BlockAttributeRegistry::register_attr(HEADER_ATTR_KEY);
+        HeaderBlock { anchors: false }
+    }
+
+    pub fn new_with_anchors() -> Self {
+        initialise();
+        block_format::initialise();
+        BlockAttributeRegistry::register_attr(HEADER_ATTR_KEY);
+        HeaderBlock { anchors: true }
+    }
+
+    /// # generate_id()
+    ///
+    /// Returns the anchor ID for a header text: lower case, with a hyphen for each run of
+    /// spaces and other non alphanumeric characters. Duplicates are not numbered here,
+    /// that needs the other headers of the document, see `anchor_ids()` in `core_formats`.
+    pub fn generate_id(text: &str) -> String {
+        slugify(text)
     }
 }
 
@@ -65,9 +107,12 @@ impl FormatTait for HeaderBlock {
         formatter: Arc<dyn FormatTait + Send + Sync>,
     ) -> Result<Arc<DocumentNode>> {
         let val = operation.get_attributes().get(HEADER_ATTR_KEY).unwrap();
-        let idx = val.number_val().unwrap();
-        let name = format!("H{:?}", idx);
+        let level = heading_level(val)?;
+        let name = format!("{}{}", HX_TAG, level);
         let element = DomElement::new(&name);
+        if level > 3 {
+            DomElement::add_class(&element.get_classes(), &format!("{}{}", HX_CLASS, level));
+        }
         block_format::apply(&element, operation.get_attributes())?;
         let doc_node = DocumentNode::new_element(element, formatter);
         doc_node.set_operation(operation);
@@ -82,15 +127,23 @@ impl FormatTait for HeaderBlock {
         false
     }
 
+    fn has_anchor_id(&self) -> bool {
+        self.anchors
+    }
+
     fn block_remove_attr(&self) -> Attributes {
         let mut attr = Attributes::default();
         attr.insert(HEADER_ATTR_KEY, Null);
         attr
     }
 
+    /// Returns an error `InvalidHeadingLevel` when the heading is not in the range 1 .. 6
     fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
         if delta.insert_value().is_string() && delta.insert_value().str_val()? == "\n" {
-            if delta.get_attributes().contains_key(HEADER_ATTR_KEY) {
+            if let Some(val) = delta.get_attributes().get(HEADER_ATTR_KEY) {
+                if !val.is_null() {
+                    heading_level(val)?;
+                }
                 return Ok(true);
             }
         }
@@ -134,11 +187,10 @@ impl FormatTait for HeaderBlock {
         cursor: &Cursor,
         block_node: &Arc<DocumentNode>,
     ) -> Result<Arc<DocumentNode>> {
-        block_node.get_operation().remove_attribute(HEADER_ATTR_KEY);
-        error!(
-            "HEADING un_block_transform() - attr removed = {:?}",
-            block_node.get_operation().get_attributes()
-        );
+        //get_operation() returns a copy, so we need to set the operation again
+        let mut op = block_node.get_operation();
+        op.remove_attribute(HEADER_ATTR_KEY);
+        block_node.set_operation(op);
         un_block_transform(block_node, cursor)
     }
 
@@ -166,3 +218,17 @@ impl FormatTait for HeaderBlock {
         Ok(())
     }
 }
+
+/// # heading_level()
+///
+/// Returns the heading level from the attribute value, which must be a number in the range 1 .. 6
+fn heading_level(val: &AttrVal) -> Result<usize> {
+    let level = val
+        .number_val()
+        .map(|l| l.to_string())
+        .unwrap_or_else(|_| format!("{:?}", val));
+    match level.parse::<usize>() {
+        Ok(l) if (1..=6).contains(&l) => Ok(l),
+        _ => Err(InvalidHeadingLevel { level }.into()),
+    }
+}
@@ -3,11 +3,19 @@ use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
 use core_formats::{P_FORMAT, TEXT_FORMAT};
 use delta::attributes::Attributes;
 use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use delta::types::ops_kind::OpKind;
+use dom::dom_element::DomElement;
 use header::{HeaderBlock, NAME_HEADER};
 use node_tree::format_trait::FormatTait;
+use node_tree::tree_traverse::collect_blocks;
 use op_transform::doc_root::DocumentRoot;
+use op_transform::op_delete;
 use op_transform::registry::Registry;
+use std::cell::RefCell;
 use std::ops::Deref;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex, OnceLock};
 use wasm_bindgen_test::*;
 
@@ -239,3 +247,462 @@ fn header_delete_all_content_test() -> Result<()> {
     assert_eq!(doc.as_html_string(), html_txt);
     Ok(())
 }
+
+#[wasm_bindgen_test]
+fn table_of_contents_no_headers_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("table_of_contents_no_headers_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Just text\nand some more text");
+    doc.apply_delta(delta)?;
+
+    assert!(doc.table_of_contents().is_empty());
+    assert!(doc.table_of_contents_anchors().is_empty());
+    assert!(doc.goto_anchor("just-text").is_err());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn table_of_contents_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("table_of_contents_test");
+    doc.open()?;
+
+    let mut h1 = Attributes::default();
+    h1.insert("heading", 1);
+    let mut h2 = Attributes::default();
+    h2.insert("heading", 2);
+
+    let mut delta = Delta::default();
+    delta.insert("Title");
+    delta.insert_attr("\n", h1);
+    delta.insert("intro text\nSub Title!");
+    delta.insert_attr("\n", h2);
+    delta.insert("more text");
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<h1>Title</h1><p>intro text</p><h2>Sub Title!</h2><p>more text</p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    let toc = doc.table_of_contents();
+    assert_eq!(toc.len(), 2);
+    assert_eq!(toc[0], (0, "Title".to_string(), 1));
+    assert_eq!(toc[1], (17, "Sub Title!".to_string(), 2));
+
+    let anchors = doc.table_of_contents_anchors();
+    assert_eq!(anchors, vec!["title".to_string(), "sub-title".to_string()]);
+
+    doc.goto_anchor("sub-title")?;
+    assert_eq!(doc.get_cursor().get_retain_index(), 17);
+    doc.goto_anchor("title")?;
+    assert_eq!(doc.get_cursor().get_retain_index(), 0);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn table_of_contents_duplicates_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("table_of_contents_duplicates_test");
+    doc.open()?;
+
+    let mut h2 = Attributes::default();
+    h2.insert("heading", 2);
+
+    let mut delta = Delta::default();
+    delta.insert("Usage");
+    delta.insert_attr("\n", h2.clone());
+    delta.insert("Usage");
+    delta.insert_attr("\n", h2.clone());
+    delta.insert("Usage");
+    delta.insert_attr("\n", h2);
+    doc.apply_delta(delta)?;
+
+    let anchors = doc.table_of_contents_anchors();
+    assert_eq!(
+        anchors,
+        vec![
+            "usage".to_string(),
+            "usage-2".to_string(),
+            "usage-3".to_string()
+        ]
+    );
+
+    doc.goto_anchor("usage-3")?;
+    assert_eq!(doc.get_cursor().get_retain_index(), 12);
+    doc.goto_anchor("usage-2")?;
+    assert_eq!(doc.get_cursor().get_retain_index(), 6);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_auto_format_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_auto_format_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+
+    let mut delta = Delta::default();
+    delta.insert("Intro\n");
+    doc.apply_delta(delta)?;
+    doc.cursor_to_end();
+
+    for c in "## Title".chars() {
+        doc.apply_operation(DeltaOperation::insert(c.to_string()))?;
+    }
+    assert_eq!(doc.as_html_string(), "<p>Intro</p><h2>Title</h2>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_auto_format_undo_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_auto_format_undo_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+
+    for c in "# ".chars() {
+        doc.apply_operation(DeltaOperation::insert(c.to_string()))?;
+    }
+    assert_eq!(doc.as_html_string(), "<h1><br></h1>");
+
+    //the auto format is undone, not the typed text
+    assert!(doc.undo()?);
+    assert_eq!(doc.as_html_string(), "<p># </p>");
+    assert!(doc.undo()?);
+    assert_eq!(doc.as_html_string(), "<p>#</p>");
+
+    assert!(doc.redo()?);
+    assert!(doc.redo()?);
+    assert_eq!(doc.as_html_string(), "<h1><br></h1>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_auto_format_secondary_cursor_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_auto_format_secondary_cursor_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+
+    let mut delta = Delta::default();
+    delta.insert("\nEnd");
+    doc.apply_delta(delta)?;
+    doc.reset_cursor();
+
+    //before the "d" of "End"
+    let id = doc.add_cursor(3);
+    for c in "# ".chars() {
+        doc.apply_operation(DeltaOperation::insert(c.to_string()))?;
+    }
+    assert_eq!(doc.as_html_string(), "<h1><br></h1><p>End</p>");
+    assert_eq!(doc.get_all_cursors()[1], (id, 3));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_auto_format_protected_range_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_auto_format_protected_range_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+
+    let mut delta = Delta::default();
+    delta.insert("\nEnd");
+    doc.apply_delta(delta)?;
+    doc.reset_cursor();
+
+    //"End" stays protected
+    doc.protect_range(1, 3);
+    for c in "# ".chars() {
+        doc.apply_operation(DeltaOperation::insert(c.to_string()))?;
+    }
+    assert_eq!(doc.as_html_string(), "<h1><br></h1><p>End</p>");
+    assert_eq!(doc.get_protected_ranges(), vec![(1, 3)]);
+
+    //a protected pattern is not formatted
+    let mut doc = DocumentRoot::new("header_auto_format_protected_pattern_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+    doc.apply_operation(DeltaOperation::insert("#"))?;
+    doc.protect_range(0, 1);
+    doc.apply_operation(DeltaOperation::insert(" "))?;
+    assert_eq!(doc.as_html_string(), "<p># </p>");
+    assert_eq!(doc.get_protected_ranges(), vec![(0, 1)]);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_insert_block_before_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_insert_block_before_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Hello");
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<p>Hello</p>");
+
+    let mut attr = Attributes::default();
+    attr.insert("heading", 1);
+    doc.insert_block_before(2, NAME_HEADER, attr)?;
+    assert_eq!(doc.as_html_string(), "<h1><br></h1><p>Hello</p>");
+    assert_eq!(doc.get_cursor().get_retain_index(), 0);
+
+    let mut attr = Attributes::default();
+    attr.insert("heading", 1);
+    assert!(doc.insert_block_before(0, NAME_P_BLOCK, attr).is_err());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_all_levels_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_all_levels_test");
+    doc.open()?;
+
+    assert_eq!(DomElement::new("H4").node_name(), "H4");
+
+    let mut delta = Delta::default();
+    for level in 1..=6 {
+        let mut attr = Attributes::default();
+        attr.insert("heading", level);
+        delta.insert(format!("h{}", level));
+        delta.insert_attr("\n", attr);
+    }
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<h1>h1</h1><h2>h2</h2><h3>h3</h3><h4 class="ql-header-4">h4</h4><h5 class="ql-header-5">h5</h5><h6 class="ql-header-6">h6</h6><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_retain_h4_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_retain_h4_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Title\nText");
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<p>Title</p><p>Text</p>");
+
+    let mut attr = Attributes::default();
+    attr.insert("heading", 4);
+    let mut delta = Delta::default();
+    delta.retain(5);
+    delta.retain_attr(1, attr);
+
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<h4 class="ql-header-4">Title</h4><p>Text</p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_retain_remove_h5_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_retain_remove_h5_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert("heading", 5);
+    let mut delta = Delta::default();
+    delta.insert("Title");
+    delta.insert_attr("\n", attr);
+    delta.insert("Text");
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<h5 class="ql-header-5">Title</h5><p>Text</p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    let mut attr = Attributes::default();
+    attr.insert("heading", AttrVal::Null);
+    let mut delta = Delta::default();
+    delta.retain(5);
+    delta.retain_attr(1, attr);
+
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    assert_eq!(doc.as_html_string(), "<p>Title</p><p>Text</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_invalid_level_test() -> Result<()> {
+    let hx_block = HeaderBlock::new();
+    for level in [0, 7] {
+        let mut attr = Attributes::default();
+        attr.insert("heading", level);
+        let op = DeltaOperation::insert_attr("\n", attr);
+        assert!(hx_block.applies(&op).is_err());
+    }
+
+    let mut attr = Attributes::default();
+    attr.insert("heading", 6);
+    let op = DeltaOperation::insert_attr("\n", attr);
+    assert_eq!(hx_block.applies(&op)?, true);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_observe_block_change_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_observe_block_change_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Title\nText");
+    doc.apply_delta(delta)?;
+
+    let changes: Rc<RefCell<Vec<Vec<DeltaOperation>>>> = Rc::new(RefCell::new(Vec::new()));
+    let c = changes.clone();
+    doc.observe_subtree(move |ops: &[DeltaOperation]| c.borrow_mut().push(ops.to_vec()));
+
+    let mut attr = Attributes::default();
+    attr.insert("heading", 2);
+    let mut delta = Delta::default();
+    delta.retain(5);
+    delta.retain_attr(1, attr);
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<h2>Title</h2><p>Text</p>");
+
+    //the first retain only moves the cursor
+    let changes = changes.borrow();
+    assert_eq!(changes.len(), 1);
+    let ops = changes.first().unwrap();
+    assert_eq!(ops.len(), 2);
+    assert!(matches!(ops[0].get_op_kind(), OpKind::Retain(5)));
+    assert!(matches!(ops[1].get_op_kind(), OpKind::Retain(1)));
+    assert!(ops[1].get_attributes().contains_key("heading"));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_import_markdown_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_import_markdown_test");
+    doc.open()?;
+
+    doc.import_markdown("# One\n\n### Three ###\ntext\n#hashtag\n####### seven")?;
+    let html_txt = r##"<h1>One</h1><h3>Three</h3><p>text #hashtag ####### seven</p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_accessible_text_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_accessible_text_test");
+    doc.open()?;
+
+    doc.import_markdown("# One\n\ntext\n\n### Three")?;
+    assert_eq!(doc.get_accessible_text(), "[H1: One]\ntext\n[H3: Three]");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_convert_blocks_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_convert_blocks_test");
+    doc.open()?;
+
+    doc.import_markdown("# One\n\ntext\n\n### Three")?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<h1>One</h1><p>text</p><h3>Three</h3>"
+    );
+
+    let count = doc.convert_blocks(NAME_HEADER, NAME_P_BLOCK, Attributes::default())?;
+    assert_eq!(count, 2);
+    assert_eq!(doc.as_html_string(), "<p>One</p><p>text</p><p>Three</p>");
+
+    //nothing left to convert
+    let count = doc.convert_blocks(NAME_HEADER, NAME_P_BLOCK, Attributes::default())?;
+    assert_eq!(count, 0);
+    assert_eq!(doc.as_html_string(), "<p>One</p><p>text</p><p>Three</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_plain_text_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_plain_text_test");
+    doc.open()?;
+
+    doc.import_markdown("# One\n\ntext\n\n### Three")?;
+    assert_eq!(doc.get_plain_text(), "One\ntext\nThree\n");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_collect_blocks_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_collect_blocks_test");
+    doc.open()?;
+
+    doc.import_markdown("# One\n\ntext\n\n### Three")?;
+    let blocks = collect_blocks(doc.get_root());
+    let text: Vec<String> = blocks.iter().map(|b| b.get_text_content()).collect();
+    assert_eq!(text, vec!["One", "text", "Three"]);
+    assert_eq!(blocks[0].get_formatter().format_name(), NAME_HEADER);
+    assert_eq!(blocks[1].get_formatter().format_name(), NAME_P_BLOCK);
+    assert!(blocks.iter().all(|b| b.op_len() > 0));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_delete_selection_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_delete_selection_test");
+    doc.open()?;
+
+    doc.import_markdown("# One\n\ntext")?;
+    assert_eq!(doc.as_html_string(), "<h1>One</h1><p>text</p>");
+
+    //from the header into the paragraph
+    let cursor = doc.get_cursor();
+    cursor.set_selection_by_retain_range(1, 6)?;
+    op_delete::delete_selection(cursor, false)?;
+    assert_eq!(doc.as_html_string(), "<p>Oxt</p>");
+    assert!(!cursor.is_selection());
+    assert_eq!(cursor.get_retain_index(), 1);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_delta_json_round_trip_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_delta_json_round_trip_test");
+    doc.open()?;
+    doc.import_markdown("# One\n\ntext\n\n### Three")?;
+
+    let json = doc.to_delta_json();
+    let loaded = DocumentRoot::from_delta_json("header_delta_json_loaded_test", &json)?;
+    assert_eq!(loaded.as_html_string(), doc.as_html_string());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_delta_at_range_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_delta_at_range_test");
+    doc.open()?;
+    doc.import_markdown("# One\n\ntext")?;
+
+    //the header keeps its block attributes
+    let mut header = Attributes::default();
+    header.insert("heading", 1);
+    let mut expect = Delta::default();
+    expect.insert("ne");
+    expect.insert_attr("\n", header);
+    expect.insert("te");
+    let range = doc.get_delta_at_range(1, 5);
+    assert_eq!(range.get_ops_ref(), expect.get_ops_ref());
+    Ok(())
+}
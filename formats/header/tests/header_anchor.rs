@@ -0,0 +1,183 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use header::{HeaderBlock, NAME_HEADER};
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers the header format with anchor IDs
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_HEADER, Arc::new(HeaderBlock::new_with_anchors()))
+            .unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn heading(level: i32) -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert("heading", level);
+    attr
+}
+
+#[wasm_bindgen_test]
+fn header_generate_id_test() {
+    assert_eq!(
+        HeaderBlock::generate_id("Getting started"),
+        "getting-started"
+    );
+    //no counter, duplicates are numbered within the document
+    assert_eq!(
+        HeaderBlock::generate_id("Getting started"),
+        "getting-started"
+    );
+
+    //special characters
+    assert_eq!(
+        HeaderBlock::generate_id("C++ & Rust: a tour!"),
+        "c-rust-a-tour"
+    );
+    assert_eq!(HeaderBlock::generate_id("  Über  café  "), "über-café");
+    assert_eq!(HeaderBlock::generate_id("a - b"), "a-b");
+}
+
+#[wasm_bindgen_test]
+fn header_anchor_id_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_anchor_id_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("Anchor intro");
+    delta.insert_attr("\n", heading(1));
+    delta.insert("text\n");
+    delta.insert("Anchor intro");
+    delta.insert_attr("\n", heading(2));
+    delta.insert("What's new?");
+    delta.insert_attr("\n", heading(2));
+    doc.apply_delta(delta)?;
+
+    let html_txt = r##"<h1 id="anchor-intro">Anchor intro</h1><p>text</p><h2 id="anchor-intro-2">Anchor intro</h2><h2 id="what-s-new">What's new?</h2><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    let toc = doc.extract_toc();
+    let expect = vec![
+        (1, "Anchor intro".to_string(), "anchor-intro".to_string()),
+        (2, "Anchor intro".to_string(), "anchor-intro-2".to_string()),
+        (2, "What's new?".to_string(), "what-s-new".to_string()),
+    ];
+    assert_eq!(toc, expect);
+
+    //the table of contents uses the same IDs
+    let anchors = doc.table_of_contents_anchors();
+    assert_eq!(
+        anchors,
+        vec!["anchor-intro", "anchor-intro-2", "what-s-new"]
+    );
+    doc.goto_anchor("anchor-intro-2")?;
+    assert_eq!(doc.get_cursor().get_retain_index(), 18);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_anchor_id_edit_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_anchor_id_edit_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("Intro");
+    delta.insert_attr("\n", heading(1));
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        r##"<h1 id="intro">Intro</h1><p><br></p>"##
+    );
+
+    //editing the header text changes the ID
+    let mut delta = Delta::default();
+    delta.retain(5);
+    delta.insert(" text");
+    doc.apply_delta(delta)?;
+    let html_txt = r##"<h1 id="intro-text">Intro text</h1><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    //a header without text has no ID
+    let mut delta = Delta::default();
+    delta.delete(10);
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), r##"<h1><br></h1><p><br></p>"##);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_anchor_id_stable_test() -> Result<()> {
+    init_test_registry();
+    let mut delta = Delta::default();
+    delta.insert("Stable");
+    delta.insert_attr("\n", heading(1));
+    delta.insert("Stable");
+    delta.insert_attr("\n", heading(2));
+
+    //loading the same document again gives the same IDs
+    let html_txt = r##"<h1 id="stable">Stable</h1><h2 id="stable-2">Stable</h2><p><br></p>"##;
+    for _ in 0..2 {
+        let mut doc = DocumentRoot::new("header_anchor_id_stable_test");
+        doc.open()?;
+        doc.apply_delta(delta.clone())?;
+        assert_eq!(doc.as_html_string(), html_txt);
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn header_anchor_id_other_block_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("header_anchor_id_other_block_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("Intro");
+    delta.insert_attr("\n", heading(1));
+    delta.insert("text\n");
+    delta.insert("Intro");
+    delta.insert_attr("\n", heading(2));
+    doc.apply_delta(delta)?;
+
+    //typing in a paragraph keeps the IDs
+    let mut delta = Delta::default();
+    delta.retain(6);
+    delta.insert("more ");
+    doc.apply_delta(delta)?;
+    let html_txt =
+        r##"<h1 id="intro">Intro</h1><p>more text</p><h2 id="intro-2">Intro</h2><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    //a paragraph which becomes a header gets an ID
+    let mut delta = Delta::default();
+    delta.retain(15);
+    delta.retain_attr(1, heading(1));
+    doc.apply_delta(delta)?;
+    let html_txt = r##"<h1 id="intro">Intro</h1><h1 id="more-text">more text</h1><h2 id="intro-2">Intro</h2><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    //removing the text of the first header renumbers the other headers
+    let mut delta = Delta::default();
+    delta.delete(5);
+    doc.apply_delta(delta)?;
+    let html_txt =
+        r##"<h1><br></h1><h1 id="more-text">more text</h1><h2 id="intro">Intro</h2><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    Ok(())
+}
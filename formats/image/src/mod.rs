@@ -9,7 +9,7 @@ use anyhow::Result;
 use core_formats::util::lookup::{AttributesLookup, Attributor};
 use core_formats::util::node_morph::delete_node;
 use delta::attributes::Attributes;
-use delta::operations::DeltaOperation;
+use delta::operations::{DeltaOperation, OpsMap};
 use dom::dom_element::DomElement;
 use node_tree::cursor::Cursor;
 use node_tree::doc_node::DocumentNode;
@@ -20,17 +20,20 @@ use std::sync::Arc;
 pub static NAME_IMAGE: &'static str = "image"; //registry label
 
 static IMAGE_TAG: &'static str = "img"; //html tag
+static SRCSET_ATTR: &'static str = "srcset";
+static SIZES_ATTR: &'static str = "sizes";
 
 static ATTRIBUTES: OnceCell<AttributesLookup> = OnceCell::new();
 pub(crate) fn initialise() {
     if let Some(_attr) = ATTRIBUTES.get() {
         return;
     }
-    let mut attr = AttributesLookup::new(4);
-    attr.fill_one("alt", "alt");
+    let mut attr = AttributesLookup::new(5);
     attr.fill_one("alt", "alt");
     attr.fill_one("height", "height");
     attr.fill_one("width", "width");
+    attr.fill_one(SRCSET_ATTR, "srcset");
+    attr.fill_one(SIZES_ATTR, "sizes");
     ATTRIBUTES
         .set(attr)
         .expect("failed to set config. did you call read_config() twice?");
@@ -50,6 +53,9 @@ pub(crate) fn initialise() {
 /// ```bash
 /// <img src="octodex.github.com/images/labtocat.png" alt="Lab Octocat" width="500" height="600">
 /// ```
+///
+/// Responsive images have the attributes `srcset` and `sizes`, which are copied to the
+/// `<img>` element as they are. See `ImageFormat::new_responsive()`.
 pub struct ImageFormat {}
 
 impl ImageFormat {
@@ -57,6 +63,24 @@ impl ImageFormat {
         initialise();
         ImageFormat {}
     }
+
+    /// # new_responsive()
+    ///
+    /// Returns the insert operation of a responsive image, like:
+    /// ```bash
+    /// {
+    ///   insert: { image: 'cat.png' },
+    ///   attributes: { srcset: "cat-480.png 480w, cat-800.png 800w", sizes: "50vw" }
+    /// }
+    /// ```
+    pub fn new_responsive(src: &str, srcset: &str, sizes: &str) -> DeltaOperation {
+        let mut img = OpsMap::default();
+        img.insert(NAME_IMAGE, src);
+        let mut attr = Attributes::default();
+        attr.insert(SRCSET_ATTR, srcset);
+        attr.insert(SIZES_ATTR, sizes);
+        DeltaOperation::insert_attr(img, attr)
+    }
 }
 
 //These are the transformations we can do to a document ...
@@ -96,6 +120,10 @@ impl FormatTait for ImageFormat {
         true
     }
 
+    fn is_void(&self) -> bool {
+        true
+    }
+
     fn block_remove_attr(&self) -> Attributes {
         panic!("Hey you called ImageFormat::block_remove_attr() on the TextFormatter format-trait implementation.");
     }
@@ -103,9 +131,11 @@ impl FormatTait for ImageFormat {
     fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
         let val = delta.insert_value();
         if val.is_map() {
-            let av = val.map_val()?.get(NAME_IMAGE).unwrap();
-            if av.is_string() {
-                return Ok(true);
+            //other embeds, like a template variable, are a map too
+            if let Some(av) = val.map_val()?.get(NAME_IMAGE) {
+                if av.is_string() {
+                    return Ok(true);
+                }
             }
         }
         return Ok(false);
@@ -3,12 +3,13 @@ use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
 use core_formats::{P_FORMAT, TEXT_FORMAT};
 use delta::attributes::Attributes;
 use delta::delta::Delta;
-use delta::operations::OpsMap;
+use delta::operations::{DeltaOperation, OpsMap};
 use image::{ImageFormat, NAME_IMAGE};
 use node_tree::cursor::Cursor;
 use node_tree::format_trait::FormatTait;
 use node_tree::tree_traverse::last_leaf_node;
 use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
 use op_transform::registry::Registry;
 use std::ops::Deref;
 use std::sync::{Arc, Mutex, OnceLock};
@@ -128,3 +129,213 @@ fn image_delete_test() -> Result<()> {
     assert_eq!(doc.as_html_string(), expect);
     Ok(())
 }
+
+#[wasm_bindgen_test]
+fn image_import_markdown_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("image_import_markdown_test");
+    doc.open()?;
+
+    doc.import_markdown("![alt-text](image-source.png)")?;
+    let expect = r##"<p><img img="image-source.png" alt="alt-text"></p>"##;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn image_format_ranges_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("image_format_ranges_test");
+    create_test_img(&mut doc)?;
+
+    assert_eq!(
+        doc.get_format_ranges(),
+        vec![
+            (0, 1, NAME_IMAGE.to_string()),
+            (1, 1, NAME_P_BLOCK.to_string())
+        ]
+    );
+    assert_eq!(doc.get_attribute_ranges("alt").len(), 1);
+    assert_eq!(doc.get_attribute_ranges("alt")[0].0, 0);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn extract_images_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("extract_images_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("no images\n");
+    doc.apply_delta(delta)?;
+    assert!(doc.extract_images().is_empty());
+
+    let mut img = OpsMap::default();
+    img.insert(NAME_IMAGE, "image-source.png");
+    let mut delta = Delta::default();
+    let mut attr = Attributes::default();
+    attr.insert("alt", "alt-text");
+    delta.retain(3);
+    delta.insert_attr(img, attr);
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.extract_images(),
+        vec![("image-source.png".to_string(), 3)]
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn image_accessible_text_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("image_accessible_text_test");
+    doc.open()?;
+
+    doc.import_markdown("![alt-text](a.png) and ![](b.png)")?;
+    assert_eq!(doc.get_accessible_text(), "[image: alt-text] and [image]");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn image_block_at_retain_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("image_block_at_retain_test");
+    doc.open()?;
+
+    let mut img = OpsMap::default();
+    img.insert(NAME_IMAGE, "image-source.png");
+    let mut delta = Delta::default();
+    delta.insert("ab");
+    delta.insert_attr(img, Attributes::default());
+    delta.insert("c\n");
+    doc.apply_delta(delta)?;
+
+    //the embedded object belongs to the paragraph
+    let block = doc.get_block_at_retain(2).unwrap();
+    assert_eq!(block.get_formatter().format_name(), NAME_P_BLOCK);
+    assert!(Arc::ptr_eq(&block, &doc.get_block_at_retain(0).unwrap()));
+    assert!(doc.get_block_at_retain(6).is_none());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn image_plain_text_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("image_plain_text_test");
+    doc.open()?;
+
+    doc.import_markdown("![alt-text](a.png) and ![](b.png)")?;
+    assert_eq!(doc.get_plain_text(), "  and  \n");
+    assert_eq!(
+        doc.get_plain_text_with_placeholder("[img]"),
+        "[img] and [img]\n"
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn image_word_count_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("image_word_count_test");
+    create_test_img(&mut doc)?;
+
+    //an image is neither a word, nor a character
+    assert_eq!(doc.word_count(), 0);
+    assert_eq!(doc.character_count(true), 0);
+    assert_eq!(doc.character_count(false), 0);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn image_is_void_test() -> Result<()> {
+    init_test_registry();
+    assert!(ImageFormat::new().is_void());
+    assert!(!TEXT_FORMAT.is_void());
+
+    let mut doc = DocumentRoot::new("image_is_void_test");
+    doc.open()?;
+    let mut img = OpsMap::default();
+    img.insert(NAME_IMAGE, "image-source.png");
+    let mut delta = Delta::default();
+    delta.insert("ab");
+    delta.insert_attr(img, Attributes::default());
+    delta.insert("c\n");
+    doc.apply_delta(delta)?;
+    let before = doc.get_plain_text_with_placeholder("[img]");
+
+    //the cursor is before the image, which is not split
+    set_cursor_selection(doc.get_cursor(), 2, 0);
+    doc.apply_operation(DeltaOperation::insert("x"))?;
+    assert_eq!(
+        doc.get_plain_text_with_placeholder("[img]"),
+        before.replacen("[img]", "x[img]", 1)
+    );
+
+    //the image is deleted as a whole
+    set_cursor_selection(doc.get_cursor(), 3, 0);
+    doc.apply_operation(DeltaOperation::delete(1))?;
+    assert_eq!(
+        doc.get_plain_text_with_placeholder("[img]"),
+        before.replacen("[img]", "x", 1)
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn image_delta_json_round_trip_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("image_delta_json_round_trip_test");
+    doc.open()?;
+    doc.import_markdown("text ![alt-text](a.png) and ![](b.png)")?;
+
+    let json = doc.to_delta_json();
+    let loaded = DocumentRoot::from_delta_json("image_delta_json_loaded_test", &json)?;
+    assert_eq!(loaded.as_html_string(), doc.as_html_string());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn image_delta_at_range_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("image_delta_at_range_test");
+    doc.open()?;
+    doc.import_markdown("ab ![alt-text](a.png) cd")?;
+
+    //the image is never cut
+    let range = doc.get_delta_at_range(2, 3);
+    let ops = range.get_ops_ref();
+    assert_eq!(ops.len(), 3);
+    assert_eq!(ops[0].insert_value().str_val()?, " ");
+    assert!(ops[1].insert_value().str_val().is_err());
+    assert_eq!(ops[2].insert_value().str_val()?, " ");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn image_responsive_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("image_responsive_test");
+    doc.open()?;
+    let srcset = "cat-480.png 480w, cat-800.png 800w";
+    let mut op = ImageFormat::new_responsive("cat.png", srcset, "50vw");
+    let mut attr = op.get_attributes().clone();
+    attr.insert("alt", "cat");
+    attr.insert("width", "480");
+    op.set_attributes(attr);
+    doc.apply_operation(op)?;
+
+    let p = doc.get_root().get_child(0).unwrap();
+    let img = p.get_child(0).unwrap();
+    let element = img.get_dom_element().unwrap();
+    assert_eq!(element.get_attribute("img").unwrap(), "cat.png");
+    assert_eq!(element.get_attribute("srcset").unwrap(), srcset);
+    assert_eq!(element.get_attribute("sizes").unwrap(), "50vw");
+    assert_eq!(element.get_attribute("alt").unwrap(), "cat");
+    assert_eq!(element.get_attribute("width").unwrap(), "480");
+
+    let img = ImageFormat::new().drop_line_attributes(&img)?;
+    let element = img.get_dom_element().unwrap();
+    assert!(element.get_attribute("srcset").is_none());
+    assert!(element.get_attribute("sizes").is_none());
+    Ok(())
+}
@@ -0,0 +1,199 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::util::block::{apply_attributes, drop_attributes};
+use core_formats::util::block_format;
+use core_formats::util::node_morph::delete_node;
+use core_formats::P_FORMAT;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use delta::types::attr_val::AttrVal::Null;
+use node_tree::cursor::Cursor;
+use node_tree::doc_node::DocumentNode;
+use node_tree::format_trait::FormatTait;
+use std::sync::Arc;
+
+pub static NAME_INDENT: &'static str = "indent"; //registry label
+
+static INDENT_ATTR_KEY: &'static str = "indent"; //attribute key
+static INDENT_MAX: usize = 8; //deepest indent level
+
+/// # IndentFormat
+///
+/// ``` bash
+///   {insert: "indented"}
+///   {attributes: {indent: 1}, insert: "↵"}
+/// ```
+///
+/// Gives as HTML:
+/// ```bash
+///     <p class="ql-indent-1">indented</p>
+/// ```
+///
+/// Like the `TextAlignFormat`, the indent is not a block of its own, but modifies the block
+/// it is given to. So `block_transform()` keeps the block node and its format, and only
+/// replaces the `ql-indent-N` class using `block_format::apply()`.
+///
+/// Register this format after the other block formats, and before the paragraph. Then a
+/// header with an `indent` attribute is still created by the `HeaderBlock`, which applies
+/// the `ql-indent-N` class too:
+/// ```bash
+///     <h2 class="ql-indent-2">title</h2>
+/// ```
+///
+/// The levels are `1 .. 8`. The level `0`, and `null`, remove the indent.
+pub struct IndentFormat {}
+impl IndentFormat {
+    pub fn new() -> Self {
+        block_format::initialise();
+        IndentFormat {}
+    }
+}
+
+/// Returns the indent level, which may be a number or a string like `"2"`.
+/// `None` when it is not a level in the range `0 .. INDENT_MAX`
+fn indent_level(val: &AttrVal) -> Option<usize> {
+    let level = match val {
+        AttrVal::String(s) => s.clone(),
+        val => val.number_val().ok()?.to_string(),
+    };
+    match level.parse::<usize>() {
+        Ok(l) if l <= INDENT_MAX => Some(l),
+        _ => None,
+    }
+}
+
+/// Returns the attributes without the indent when the level is 0, or `null`
+fn indent_attributes(attr: &Attributes) -> Attributes {
+    let mut attr = attr.clone();
+    let none = match attr.get(INDENT_ATTR_KEY) {
+        Some(val) => val.is_null() || indent_level(val) == Some(0),
+        None => false,
+    };
+    if none {
+        attr.remove(INDENT_ATTR_KEY);
+    }
+    attr
+}
+
+impl FormatTait for IndentFormat {
+    /// Creates a paragraph, which is indented
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let mut op = DeltaOperation::insert(operation.insert_value().clone());
+        op.set_attributes(indent_attributes(operation.get_attributes()));
+        P_FORMAT.create(op, P_FORMAT.clone())
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_INDENT
+    }
+
+    fn is_text_format(&self) -> bool {
+        false
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        let mut attr = Attributes::default();
+        attr.insert(INDENT_ATTR_KEY, Null);
+        attr
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        if delta.insert_value().is_string() && delta.insert_value().str_val()? == "\n" {
+            return Ok(match delta.get_attributes().get(INDENT_ATTR_KEY) {
+                Some(val) => val.is_null() || indent_level(val).is_some(),
+                None => false,
+            });
+        }
+        Ok(false)
+    }
+
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        apply_attributes(doc_node, &indent_attributes(attr))
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        drop_attributes(doc_node)
+    }
+
+    fn split_leaf(&self, _cursor: &Cursor) -> Result<()> {
+        panic!("IndentFormat::split_leaf() - Error. ");
+    }
+
+    //The indented blocks have their own format, see block_transform()
+    fn is_same_format(&self, _left: &Arc<DocumentNode>, _right: &Arc<DocumentNode>) -> bool {
+        false
+    }
+
+    /// Keeps the block node, and its format, and only replaces the indent.<br>
+    /// The cursor does not change, since the block node stays in the document.
+    fn block_transform(
+        &self,
+        _cursor: &Cursor,
+        block_node: &Arc<DocumentNode>,
+        delta: DeltaOperation,
+        _format: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let attr = indent_attributes(delta.get_attributes());
+        let element = block_node.get_dom_element().unwrap();
+        block_format::apply(element, &self.block_remove_attr())?;
+        block_format::apply(element, &attr)?;
+
+        let mut op = block_node.get_operation();
+        op.set_attributes(attr);
+        block_node.set_operation(op);
+        Ok(block_node.clone())
+    }
+
+    /// Removes the indent, the block node stays in the document
+    fn un_block_transform(
+        &self,
+        _cursor: &Cursor,
+        block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        let element = block_node.get_dom_element().unwrap();
+        block_format::apply(element, &self.block_remove_attr())?;
+
+        //get_operation() returns a copy, so we need to set the operation again
+        let mut op = block_node.get_operation();
+        op.remove_attribute(INDENT_ATTR_KEY);
+        block_node.set_operation(op);
+        Ok(block_node.clone())
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        _doc_node: &Arc<DocumentNode>,
+        _at: usize,
+        _length: usize,
+    ) -> Result<()> {
+        panic!("IndentFormat::delete() - Error. Block has length 1, so use the other delete function...");
+    }
+
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn try_merge(&self, _cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use header::{HeaderBlock, NAME_HEADER};
+use indent::{IndentFormat, NAME_INDENT};
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+/// The indent is registered after the header, and before the paragraph
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_HEADER, Arc::new(HeaderBlock::new()))
+            .unwrap();
+        r.register_block_fmt(NAME_INDENT, Arc::new(IndentFormat::new()))
+            .unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn indent(level: usize) -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert("indent", level);
+    attr
+}
+
+#[test]
+fn detect_indent_format() -> Result<()> {
+    let format = IndentFormat::new();
+    assert_eq!(format.format_name(), NAME_INDENT);
+    for level in 0..=8 {
+        assert!(format.applies(&DeltaOperation::insert_attr("\n", indent(level)))?);
+    }
+    let mut attr = Attributes::default();
+    attr.insert("indent", AttrVal::Null);
+    assert!(format.applies(&DeltaOperation::insert_attr("\n", attr))?);
+
+    assert!(!format.applies(&DeltaOperation::insert_attr("\n", indent(9)))?);
+    assert!(!format.applies(&DeltaOperation::insert_attr("text", indent(1)))?);
+    assert!(!format.applies(&DeltaOperation::insert("\n"))?);
+    assert!(format.block_remove_attr().get("indent").unwrap().is_null());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn indent_retain_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("indent_retain_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("text\n");
+    doc.apply_delta(delta)?;
+
+    let mut delta = Delta::default();
+    delta.retain(4);
+    delta.retain_attr(1, indent(1));
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        r#"<p class="ql-indent-1">text</p><p><br></p>"#
+    );
+
+    let mut expect = Delta::default();
+    expect.insert("text");
+    expect.insert_attr("\n", indent(1));
+    expect.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+
+    //the paragraph stays a paragraph
+    let p = doc.get_root().get_child(0).unwrap();
+    assert_eq!(p.get_formatter().format_name(), NAME_P_BLOCK);
+
+    //a new level replaces the old one
+    let mut delta = Delta::default();
+    delta.retain(4);
+    delta.retain_attr(1, indent(3));
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        r#"<p class="ql-indent-3">text</p><p><br></p>"#
+    );
+
+    //null removes the indent
+    let mut attr = Attributes::default();
+    attr.insert("indent", AttrVal::Null);
+    let mut delta = Delta::default();
+    delta.retain(4);
+    delta.retain_attr(1, attr);
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), r#"<p>text</p><p><br></p>"#);
+
+    let mut expect = Delta::default();
+    expect.insert("text\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn indent_header_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("indent_header_test");
+    doc.open()?;
+
+    let mut heading = indent(2);
+    heading.insert("heading", 2);
+
+    let mut delta = Delta::default();
+    delta.insert("title");
+    delta.insert_attr("\n", heading);
+    delta.insert("text");
+    delta.insert_attr("\n", indent(0));
+    doc.apply_delta(delta)?;
+
+    //level 0 is not indented
+    let html_txt = r##"<h2 class="ql-indent-2">title</h2><p>text</p><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    Ok(())
+}
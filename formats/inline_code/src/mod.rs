@@ -0,0 +1,209 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::util::node_morph::{
+    delete_node, delete_text, split_text_node, try_3_way_merge_text,
+};
+use core_formats::TEXT_FORMAT;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use dom::dom_element::DomElement;
+use node_tree::cursor::{Cursor, CursorLocation};
+use node_tree::doc_node::DocumentNode;
+use node_tree::dom_doc_tree_morph::{insert_at_index, unlink};
+use node_tree::format_trait::FormatTait;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub static NAME_INLINE_CODE: &'static str = "inline-code"; //registry label
+
+static INLINE_CODE_TAG: &'static str = "CODE"; //HTML tag
+static INLINE_CODE_ATTR_KEY: &'static str = "code"; //attribute key
+static CODE_BLOCK_ATTR_KEY: &'static str = "code-block"; //attribute key of the CodeBlock
+
+static INLINE_CODE_FORMAT: Lazy<Arc<dyn FormatTait + Send + Sync>> =
+    Lazy::new(|| Arc::new(InlineCodeFormat::new()));
+
+/// # InlineCodeFormat
+///
+/// ```bash
+///   {insert: "call "}
+///   {insert: "main()", attributes: {code: true}}
+///   {insert: " first"}
+/// ```
+///
+/// Gives as HTML:
+/// ```bash
+///     <p>call <code>main()</code> first</p>
+/// ```
+///
+/// This is code inside a line of text. A line of code is a block, made by the `CodeBlock`
+/// for `{code-block: true}`.
+///
+/// Inline code is normally not combined with other text formats. When it is, like with
+/// `bold`, the `TextFormat` makes the `<STRONG>` inside the `<CODE>` element.
+///
+/// Register this format before the `TextFormat`.
+pub struct InlineCodeFormat {}
+impl InlineCodeFormat {
+    pub fn new() -> Self {
+        InlineCodeFormat {}
+    }
+}
+
+/// Returns true when the attributes contain `{code: true}`, and no `code-block`
+fn is_inline_code(attr: &Attributes) -> bool {
+    !attr.contains_key(CODE_BLOCK_ATTR_KEY)
+        && attr.get(INLINE_CODE_ATTR_KEY) == Some(&AttrVal::Bool(true))
+}
+
+/// Returns true when there are other attributes than `code`
+fn has_other_attributes(attr: &Attributes) -> bool {
+    let mut attr = attr.clone();
+    attr.remove(INLINE_CODE_ATTR_KEY);
+    !attr.is_empty()
+}
+
+/// Replaces the document node with a new one having the given attributes.<br>
+/// Without inline code the node is given to the `TextFormat`.
+fn apply_inline_code(doc_node: &Arc<DocumentNode>, attr: &Attributes) -> Result<Arc<DocumentNode>> {
+    assert!(doc_node.get_formatter().is_text_format());
+    let index = doc_node.my_index_as_child().unwrap();
+
+    let format = if is_inline_code(attr) {
+        INLINE_CODE_FORMAT.clone()
+    } else {
+        TEXT_FORMAT.clone()
+    };
+    let old_op = doc_node.get_operation();
+    let op = DeltaOperation::insert_attr(old_op.insert_value().str_val()?, attr.clone());
+    let new_doc_node = format.create(op, format.clone())?;
+
+    let parent = doc_node.get_parent().unwrap();
+    unlink(&parent, doc_node);
+    insert_at_index(&parent, index, new_doc_node.clone());
+    Ok(new_doc_node)
+}
+
+impl FormatTait for InlineCodeFormat {
+    /// Lets the `TextFormat` create the text with the other attributes, and puts that in
+    /// the `<CODE>` element.
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let mut attr = operation.get_attributes().clone();
+        attr.remove(INLINE_CODE_ATTR_KEY);
+        let op = DeltaOperation::insert_attr(operation.insert_value().str_val()?, attr);
+        let text_node = TEXT_FORMAT.create(op, formatter.clone())?;
+
+        let code = DomElement::new(INLINE_CODE_TAG);
+        code.append_child(text_node.get_html_node());
+        let doc_node = DocumentNode::new_element(code, formatter);
+        doc_node.set_operation(operation);
+        Ok(Arc::new(doc_node))
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_INLINE_CODE
+    }
+
+    fn is_text_format(&self) -> bool {
+        true
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        panic!("Hey you called InlineCodeFormat::block_remove_attr() on a text format-trait implementation.");
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        if delta.insert_value().is_string() && delta.insert_value().str_val()? != "\n" {
+            return Ok(is_inline_code(delta.get_attributes()));
+        }
+        Ok(false)
+    }
+
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        apply_inline_code(doc_node, attr)
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        TEXT_FORMAT.drop_line_attributes(doc_node)
+    }
+
+    /// `split_text_node()` creates the right hand node as plain text, so we give it the
+    /// `<CODE>` element again.
+    ///
+    /// Post condition: Cursor position is BEFORE the right hand side created node
+    fn split_leaf(&self, cursor: &Cursor) -> Result<()> {
+        if let CursorLocation::At(doc_node, index) = cursor.get_location() {
+            if index == 0 {
+                return Ok(());
+            }
+            let right = split_text_node(&doc_node, index)?;
+            let right = apply_inline_code(&right, doc_node.get_operation().get_attributes())?;
+            cursor.set_before_no_retain_update(&right);
+        }
+        Ok(())
+    }
+
+    //Code combined with other attributes is never the same, so we do not lose formatting
+    fn is_same_format(&self, left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> bool {
+        let left = left.get_operation();
+        let right = right.get_operation();
+        is_inline_code(left.get_attributes())
+            && !has_other_attributes(left.get_attributes())
+            && left.get_attributes().is_equal(right.get_attributes())
+    }
+
+    fn block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+        _delta: DeltaOperation,
+        _new_transducer: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("InlineCodeFormat::block_transform() - Error.");
+    }
+
+    fn un_block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("InlineCodeFormat::un_block_transform() - Error.");
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        at: usize,
+        length: usize,
+    ) -> Result<()> {
+        delete_text(doc_node, at, length)
+    }
+
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn try_merge(&self, cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        try_3_way_merge_text(cursor)
+    }
+}
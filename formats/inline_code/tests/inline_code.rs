@@ -0,0 +1,164 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use inline_code::{InlineCodeFormat, NAME_INLINE_CODE};
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_INLINE_CODE, Arc::new(InlineCodeFormat::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn code() -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert("code", true);
+    attr
+}
+
+#[test]
+fn detect_inline_code_format() -> Result<()> {
+    let format = InlineCodeFormat::new();
+    assert_eq!(format.format_name(), NAME_INLINE_CODE);
+    assert!(format.applies(&DeltaOperation::insert_attr("main()", code()))?);
+    assert!(!format.applies(&DeltaOperation::insert("main()"))?);
+    assert!(!format.applies(&DeltaOperation::insert_attr("\n", code()))?);
+
+    let mut attr = code();
+    attr.insert("code-block", true);
+    assert!(!format.applies(&DeltaOperation::insert_attr("main()", attr))?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn inline_code_insert_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("inline_code_insert_test");
+    doc.open()?;
+
+    let mut bold = code();
+    bold.insert("bold", true);
+
+    let mut delta = Delta::default();
+    delta.insert("call ");
+    delta.insert_attr("main()", code());
+    delta.insert(" or ");
+    delta.insert_attr("exit()", bold);
+    delta.insert("\n");
+    doc.apply_delta(delta.clone())?;
+
+    let html_txt =
+        r##"<p>call <code>main()</code> or <code><strong>exit()</strong></code></p><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    delta.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+
+    //code combined with other attributes is never the same format
+    let p = doc.get_root().get_child(0).unwrap();
+    let format = InlineCodeFormat::new();
+    assert!(format.is_same_format(&p.get_child(1).unwrap(), &p.get_child(1).unwrap()));
+    assert!(!format.is_same_format(&p.get_child(3).unwrap(), &p.get_child(3).unwrap()));
+
+    //inserting code in code gives one <CODE> element
+    let mut delta = Delta::default();
+    delta.retain(9);
+    delta.insert_attr("_1", code());
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let html_txt =
+        r##"<p>call <code>main_1()</code> or <code><strong>exit()</strong></code></p><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    assert_eq!(doc.get_cursor().get_retain_index(), 11);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn inline_code_delete_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("inline_code_delete_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert_attr("one", code());
+    delta.insert(" ");
+    delta.insert_attr("two", code());
+    delta.insert("\n");
+    doc.apply_delta(delta)?;
+
+    //-----------------------------------------------------------------
+    let mut delta = Delta::default();
+    delta.retain(1);
+    delta.delete(1);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p><code>oe</code> <code>two</code></p><p><br></p>"
+    );
+
+    //-----------------------------------------------------------------
+    //deleting the text in between merges the code
+    let mut delta = Delta::default();
+    delta.retain(2);
+    delta.delete(1);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<p><code>oetwo</code></p><p><br></p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn inline_code_boundary_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("inline_code_boundary_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("a ");
+    delta.insert_attr("code", code());
+    delta.insert(" b\n");
+    doc.apply_delta(delta)?;
+
+    //plain text at the boundaries of the code stays outside the <CODE> element
+    let mut delta = Delta::default();
+    delta.retain(2);
+    delta.insert("x");
+    delta.retain(4);
+    delta.insert("y");
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>a x<code>code</code>y b</p><p><br></p>"
+    );
+    assert_eq!(doc.get_cursor().get_retain_index(), 8);
+
+    let mut expect = Delta::default();
+    expect.insert("a x");
+    expect.insert_attr("code", code());
+    expect.insert("y b\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
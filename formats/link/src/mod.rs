@@ -28,15 +28,22 @@ use std::sync::Arc;
 pub static NAME_LINK: &'static str = "link"; //registry label
 
 static LINK_ATTR: &'static str = "link"; //marker attribute to recognize this format
+static LINK_TARGET_ATTR: &'static str = "link-target";
+static LINK_TITLE_ATTR: &'static str = "link-title";
 pub static LINK_TAG: &'static str = "A"; //HTML tag
+static TARGET_BLANK: &'static str = "_blank";
+static REL_ATTR: &'static str = "rel";
+static REL_NO_OPENER: &'static str = "noopener noreferrer";
 
 static ATTRIBUTES: OnceCell<AttributesLookup> = OnceCell::new();
 pub(crate) fn initialise() {
     if let Some(_attr) = ATTRIBUTES.get() {
         return;
     }
-    let mut attr = AttributesLookup::new(1);
+    let mut attr = AttributesLookup::new(3);
     attr.fill_one(LINK_ATTR, "href");
+    attr.fill_one(LINK_TARGET_ATTR, "target");
+    attr.fill_one(LINK_TITLE_ATTR, "title");
     ATTRIBUTES
         .set(attr)
         .expect("did you call link::initialise() twice?");
@@ -109,6 +116,20 @@ pub(crate) fn initialise() {
 /// Another caveat is that the text nodes may be formatted. Hence the child of some text
 /// may be a `<EM>` or other formatting `html node`. So look for the right parent!
 ///
+/// TARGET AND TITLE<br>
+/// The attributes `link-target` and `link-title` give the `target` and `title` of the link.
+/// A link with `target="_blank"` also gets `rel="noopener noreferrer"`:
+/// ```json
+///        { insert: "sweet", attributes: { link: 'https://www.google.com', link-target: '_blank' } }
+/// ```
+/// results in:
+/// ```html
+///        <A href="https://www.google.com" target="_blank" rel="noopener noreferrer">
+///           sweet
+///        </A>
+///```
+/// Links which differ in any of these attributes are not merged.
+///
 pub struct LinkFormat {}
 
 impl LinkFormat {
@@ -129,9 +150,7 @@ impl FormatTait for LinkFormat {
         let text_node = TEXT_FORMAT.create(operation, formatter.clone())?;
 
         let link_element = DomElement::new(LINK_TAG);
-        for (k, v) in Attributor::selected(&attr, ATTRIBUTES.get().unwrap()) {
-            link_element.set_attribute(&k, &v.str_val()?);
-        }
+        set_link_attributes(&link_element, &attr)?;
 
         let op = DeltaOperation::insert_attr("", attr.clone());
 
@@ -169,16 +188,12 @@ impl FormatTait for LinkFormat {
         _formatter: Arc<dyn FormatTait + Send + Sync>,
     ) -> Result<Arc<DocumentNode>> {
         let dom_el = doc_node.get_dom_element().unwrap();
-        for (k, v) in Attributor::selected(attr, ATTRIBUTES.get().unwrap()) {
-            dom_el.set_attribute(k, &v.str_val()?)
-        }
+        set_link_attributes(dom_el, attr)?;
         if doc_node.get_operation().op_len() > 0 {
             //now do parent with <A> tag too
             let dn = doc_node.get_parent().unwrap();
             let dom_el = dn.get_dom_element().unwrap();
-            for (k, v) in Attributor::selected(attr, ATTRIBUTES.get().unwrap()) {
-                dom_el.set_attribute(k, &v.str_val()?)
-            }
+            set_link_attributes(dom_el, attr)?;
         }
         Ok(doc_node.clone())
     }
@@ -189,6 +204,7 @@ impl FormatTait for LinkFormat {
         for key in Attributor::all_key(lookup) {
             dom_el.remove_attribute(&key);
         }
+        dom_el.remove_attribute(REL_ATTR);
         if doc_node.get_operation().op_len() > 0 {
             //now do parent with <A> tag too
             let dn = doc_node.get_parent().unwrap();
@@ -196,6 +212,7 @@ impl FormatTait for LinkFormat {
             for key in Attributor::all_key(lookup) {
                 dom_el.remove_attribute(&key);
             }
+            dom_el.remove_attribute(REL_ATTR);
         }
         Ok(doc_node.clone())
     }
@@ -228,12 +245,15 @@ impl FormatTait for LinkFormat {
         Ok(())
     }
 
+    /// Same link, target and title
     fn is_same_format(&self, left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> bool {
-        if let Some(left) = left.get_operation().get_attributes().get(LINK_ATTR) {
-            if let Some(right) = right.get_operation().get_attributes().get(LINK_ATTR) {
-                if left.eq(right) {
-                    return true;
-                }
+        let left = left.get_operation().get_attributes().clone();
+        let right = right.get_operation().get_attributes().clone();
+        if let Some(l) = left.get(LINK_ATTR) {
+            if let Some(r) = right.get(LINK_ATTR) {
+                return l.eq(r)
+                    && left.get(LINK_TARGET_ATTR) == right.get(LINK_TARGET_ATTR)
+                    && left.get(LINK_TITLE_ATTR) == right.get(LINK_TITLE_ATTR);
             }
         }
         return false;
@@ -315,6 +335,24 @@ impl FormatTait for LinkFormat {
     }
 }
 
+/// Sets the HTML attributes of the link, a `Null` value removes the attribute.
+/// A link opening in a new tab gets `rel="noopener noreferrer"`.
+fn set_link_attributes(dom_el: &DomElement, attr: &Attributes) -> Result<()> {
+    for (k, v) in Attributor::selected(attr, ATTRIBUTES.get().unwrap()) {
+        if v.is_null() {
+            dom_el.remove_attribute(k);
+        } else {
+            dom_el.set_attribute(k, &v.str_val()?);
+        }
+    }
+    if dom_el.get_attribute("target").as_deref() == Some(TARGET_BLANK) {
+        dom_el.set_attribute(REL_ATTR, REL_NO_OPENER);
+    } else {
+        dom_el.remove_attribute(REL_ATTR);
+    }
+    Ok(())
+}
+
 /// After splitting up the link node, the cursor points to the "LINK_TAG" doc node.
 /// After the merging we should again point to the link tag node remaining??
 fn try_merge_link(cursor: &Cursor) -> Result<()> {
@@ -10,6 +10,7 @@ use link::{LinkFormat, NAME_LINK};
 use node_tree::cursor::Cursor;
 use node_tree::format_trait::FormatTait;
 use op_transform::doc_root::DocumentRoot;
+use op_transform::op_delete;
 use op_transform::registry::Registry;
 use std::ops::Deref;
 use std::sync::{Arc, Mutex, OnceLock};
@@ -450,3 +451,198 @@ fn link_split_and_merge_test() -> Result<()> {
     }
     Ok(())
 }
+
+#[wasm_bindgen_test]
+fn link_import_markdown_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("link_import_markdown_test");
+    doc.open()?;
+
+    doc.import_markdown("go to [**the** site](https://x.y) now")?;
+    let expect = r#"<p>go to <a href="https://x.y"><strong>the</strong> site</a> now</p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn extract_links_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("extract_links_test");
+    doc.open()?;
+    assert!(doc.extract_links().is_empty());
+
+    let mut delta = Delta::default();
+    delta.insert("hello ");
+    let mut attr = Attributes::default();
+    attr.insert(LINK_ATTR, "https://a.b");
+    delta.insert_attr("world", attr);
+    delta.insert(" and ");
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.extract_links(),
+        vec![("https://a.b".to_string(), 6, 11)]
+    );
+
+    //link spanning bold and italic text
+    let mut delta = Delta::default();
+    delta.retain(16);
+    let mut attr = Attributes::default();
+    attr.insert(LINK_ATTR, "https://c.d");
+    delta.insert_attr("go", attr.clone());
+    attr.insert("bold", true);
+    delta.insert_attr("og", attr.clone());
+    attr.insert("italic", true);
+    delta.insert_attr("le", attr);
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.extract_links(),
+        vec![
+            ("https://a.b".to_string(), 6, 11),
+            ("https://c.d".to_string(), 16, 22)
+        ]
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn link_accessible_text_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("link_accessible_text_test");
+    doc.open()?;
+
+    doc.import_markdown("go to [**the** site](https://x.y) now")?;
+    assert_eq!(
+        doc.get_accessible_text(),
+        "go to [link: the site (https://x.y)] now"
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn link_plain_text_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("link_plain_text_test");
+    create_test_link(&mut doc)?;
+
+    //the formatted child of the link is text like any other
+    assert_eq!(doc.get_plain_text(), "google\n");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn link_delete_selection_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("link_delete_selection_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert("link", "https://");
+    let mut delta = Delta::default();
+    delta.insert_attr("google", attr);
+    delta.insert(" plain");
+    doc.apply_delta(delta)?;
+    let expect = r#"<p><a href="https://">google</a> plain</p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //the selection starts in the text node of the link
+    let cursor = doc.get_cursor();
+    cursor.set_selection_by_retain_range(3, 9)?;
+    op_delete::delete_selection(cursor, false)?;
+    let expect = r#"<p><a href="https://">goo</a>ain</p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    assert!(!cursor.is_selection());
+    assert_eq!(cursor.get_retain_index(), 3);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn link_delta_json_round_trip_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("link_delta_json_round_trip_test");
+    create_test_link(&mut doc)?;
+
+    let json = doc.to_delta_json();
+    let loaded = DocumentRoot::from_delta_json("link_delta_json_loaded_test", &json)?;
+    assert_eq!(loaded.as_html_string(), doc.as_html_string());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn link_text_content_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("link_text_content_test");
+    create_test_link(&mut doc)?;
+
+    //the anchor text, also of the bold part
+    let p = doc.get_root().get_child(0).unwrap();
+    let a = p.get_child(0).unwrap();
+    assert_eq!(a.get_operation().op_len(), 0);
+    assert_eq!(a.get_text_content(), "google");
+    assert_eq!(p.get_text_content(), "google");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn link_target_title_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("link_target_title_test");
+    doc.open()?;
+    let mut attr = Attributes::default();
+    attr.insert("link", "https://");
+    attr.insert("link-target", "_blank");
+    attr.insert("link-title", "Home page");
+    let mut delta = Delta::default();
+    delta.insert_attr("home", attr);
+    doc.apply_delta(delta.clone())?;
+
+    //a new tab gets rel="noopener noreferrer"
+    let expect = r#"<p><a href="https://" target="_blank" title="Home page" rel="noopener noreferrer">home</a></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    assert_eq!(doc.get_delta_at_range(0, 4).get_ops(), delta.get_ops());
+
+    //only for a new tab
+    let mut doc = DocumentRoot::new("link_target_self_test");
+    doc.open()?;
+    let mut attr = Attributes::default();
+    attr.insert("link", "https://");
+    attr.insert("link-target", "_self");
+    let mut delta = Delta::default();
+    delta.insert_attr("home", attr);
+    doc.apply_delta(delta)?;
+    let expect = r#"<p><a href="https://" target="_self">home</a></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn link_target_no_merge_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("link_target_no_merge_test");
+    doc.open()?;
+    let mut blank = Attributes::default();
+    blank.insert("link", "https://");
+    blank.insert("link-target", "_blank");
+    let mut plain = Attributes::default();
+    plain.insert("link", "https://");
+    let mut titled = Attributes::default();
+    titled.insert("link", "https://");
+    titled.insert("link-title", "tip");
+    let mut delta = Delta::default();
+    delta.insert_attr("ab", blank);
+    delta.insert_attr("cd", plain);
+    delta.insert_attr("ef", titled);
+    doc.apply_delta(delta)?;
+
+    //the same URL, but a different target or title
+    let expect = r#"<p><a href="https://" target="_blank" rel="noopener noreferrer">ab</a><a href="https://">cd</a><a href="https://" title="tip">ef</a></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    let p = doc.get_root().get_child(0).unwrap();
+    let first = p.get_child(0).unwrap();
+    let second = p.get_child(1).unwrap();
+    let third = p.get_child(2).unwrap();
+    assert!(!LinkFormat::new().is_same_format(&first, &second));
+    assert!(!LinkFormat::new().is_same_format(&second, &third));
+    assert!(LinkFormat::new().is_same_format(&second, &second));
+    Ok(())
+}
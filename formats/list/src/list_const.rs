@@ -9,5 +9,16 @@ pub const LIST_ATTR_KEY: &'static str = "list";
 pub const LIST_BULLET: &'static str = "bullet";
 pub const LIST_ORDERED: &'static str = "ordered";
 
+//State of a checklist item, both are items of the same checklist
+pub const LIST_CHECKED: &'static str = "checked";
+pub const LIST_UNCHECKED: &'static str = "unchecked";
+
+//Numbering of an ordered list, given to the first line of the list
+pub const LIST_START_ATTR_KEY: &'static str = "list-start";
+pub const LIST_CONTINUE_ATTR_KEY: &'static str = "list-continue";
+
+//Nesting level of a list item, 0 is the outer list
+pub const LIST_INDENT_ATTR_KEY: &'static str = "indent";
+
 //FIXME: update code to include this option ... (css already describes it)
 pub const LIST_CHECK_BOX: &'static str = "data-checked";
@@ -5,10 +5,14 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::list_const::{LIST_ATTR_KEY, LIST_BULLET, LIST_ORDERED};
+use crate::list_const::{
+    LIST_ATTR_KEY, LIST_BULLET, LIST_CHECKED, LIST_CONTINUE_ATTR_KEY, LIST_INDENT_ATTR_KEY,
+    LIST_ORDERED, LIST_START_ATTR_KEY, LIST_UNCHECKED,
+};
 use anyhow::Result;
 use core_formats::util::block::{apply_attributes, drop_attributes};
 use core_formats::util::block_format;
+use core_formats::util::block_format::BlockAttributeRegistry;
 use core_formats::util::node_morph::{delete_node, merge_block_node, split_block_before_child};
 use core_formats::P_FORMAT;
 use delta::attributes::Attributes;
@@ -27,10 +31,16 @@ pub mod list_const;
 
 pub static NAME_UL_BLOCK: &str = "UL_BLOCK";
 pub static NAME_OL_BLOCK: &str = "OL_BLOCK";
+pub static NAME_CHECKLIST_BLOCK: &str = "CHECKLIST_BLOCK";
 
 static UL_TAG: &str = "UL";
 static OL_TAG: &str = "OL";
 static LI_TAG: &str = "LI";
+static OL_START: &str = "start";
+static INPUT_TAG: &str = "INPUT";
+static CHECKLIST_CLASS: &str = "ql-checklist";
+static CHECKED_CLASS: &str = "ql-checked";
+static UNCHECKED_CLASS: &str = "ql-unchecked";
 
 /// # ListBlock
 ///
@@ -147,6 +157,44 @@ static LI_TAG: &str = "LI";
 ///    </UL>
 /// ```
 ///
+/// # NUMBERING
+///
+/// An `<OL>` is numbered by the browser. The first `<LI>` of an `<OL>` decides the number of
+/// its first item, which we set as the `start` attribute of the `<OL>`:
+///  - `{"list-start": 3}` starts the list at 3
+///  - `{"list-continue": true}` continues after the last item of the previous `<OL>`,
+///    for example after a paragraph which split the list
+///  - else the list starts at 1
+///
+/// Two adjacent `<OL>` nodes are merged, unless the second one has a `list-start`.
+///
+/// # INDENTATION
+///
+/// The `indent` attribute nests a list item in `<UL>`/`<LI>` levels:
+///  - `{"list": "bullet", "indent": 1}` --> `<ul><li><ul><li>text</li></ul></li></ul>`
+///
+/// The `<LI>` wrapping a nested list has an operation of length 0, like the `<UL>`, so it
+/// does not appear in the delta. Items with different indent levels are never merged, so
+/// each indent level gets its own outer `<UL>`. Adjacent items of the same level are merged
+/// at every nested level. The nesting replaces the `ql-indent-` class of other blocks.
+///
+/// Numbering continues after a nested ordered list, the nested list itself starts at 1.
+///
+/// # CHECKLIST
+///
+/// A checklist item is `{"list": "checked"}` or `{"list": "unchecked"}`. Both are items of
+/// the same checklist:
+///
+/// ```html
+///     <ul class="ql-checklist">
+///        <li class="ql-checked"><input type="checkbox">Coffee</li>
+///        <li class="ql-unchecked"><input type="checkbox">Tea</li>
+///     </ul>
+/// ```
+///
+/// The `<INPUT>` check box is part of the `<LI>` element, it is not a doc-node. Its `checked`
+/// property is set when the `<LI>` is created. Changing the state is a retain of the
+/// `list` attribute, which creates a new `<LI>`, see `DocumentRoot::toggle_checklist_item()`.
 ///
 #[allow(non_camel_case_types)]
 pub struct ListBlock {
@@ -154,25 +202,41 @@ pub struct ListBlock {
     parent_tag: &'static str,
     child_tag: &'static str,
     attr_val: &'static str,
+    checklist: bool,
 }
 
 impl ListBlock {
     pub fn new_ul() -> ListBlock {
         block_format::initialise();
+        register_list_attributes();
         ListBlock {
             block_name: NAME_UL_BLOCK,
             parent_tag: UL_TAG,
             child_tag: LI_TAG,
             attr_val: LIST_BULLET,
+            checklist: false,
         }
     }
     pub fn new_ol() -> ListBlock {
         block_format::initialise();
+        register_list_attributes();
         ListBlock {
             block_name: NAME_OL_BLOCK,
             parent_tag: OL_TAG,
             child_tag: LI_TAG,
             attr_val: LIST_ORDERED,
+            checklist: false,
+        }
+    }
+    pub fn new_checklist() -> ListBlock {
+        block_format::initialise();
+        register_list_attributes();
+        ListBlock {
+            block_name: NAME_CHECKLIST_BLOCK,
+            parent_tag: UL_TAG,
+            child_tag: LI_TAG,
+            attr_val: LIST_CHECKED,
+            checklist: true,
         }
     }
 }
@@ -181,48 +245,154 @@ impl ListBlock {
     /// Similar to the link format we create aa common parent with length is 0 to collect the list
     /// list_tags (type impl FormatTrait) is needed to get to the correct parent & child tags
     /// formatter (type impl FormatTrait) is the pointer to the format in the Registry; we point to that one in the created doc_nodes
+    ///
+    /// An indented item gets a `<UL><LI>` pair per indent level, see INDENTATION above.
     fn create_list_node(
         &self,
         operation: DeltaOperation,
         formatter: Arc<dyn FormatTait + Send + Sync>,
     ) -> Result<(Arc<DocumentNode>, Arc<DocumentNode>)> {
+        let mut attr = list_node_attributes(operation.get_attributes());
+        //checked and unchecked items merge in to one checklist
+        attr.insert(LIST_ATTR_KEY, self.attr_val);
+
         //<UL>
-        let ul_el = DomElement::new(self.parent_tag);
-        block_format::apply(&ul_el, operation.get_attributes())?;
+        let ul_doc_node_ptr = self.create_ul_node(self.parent_tag, &attr, formatter.clone())?;
+
+        //<LI><UL> per indent level
+        let mut ul_node = ul_doc_node_ptr.clone();
+        for _ in 0..list_indent(operation.get_attributes()) {
+            let mut op = DeltaOperation::insert("");
+            op.set_attributes(attr.clone());
+            let wrapper = self.create_li_node(op, formatter.clone());
+            append(&ul_node, wrapper.clone());
+            ul_node = self.create_ul_node(self.parent_tag, &attr, formatter.clone())?;
+            append(&wrapper, ul_node.clone());
+        }
+
+        //<LI>
+        let li_doc_node_ptr = self.create_li_node(operation, formatter);
+        append(&ul_node, li_doc_node_ptr.clone());
+
+        //Return the pair so that the calling method may use either one without traversing the tree again
+        return Ok((ul_doc_node_ptr, li_doc_node_ptr));
+    }
+
+    /// The `<UL>` node has an empty insert, with the attributes of the list
+    fn create_ul_node(
+        &self,
+        tag: &str,
+        attr: &Attributes,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let ul_el = DomElement::new(tag);
+        block_format::apply(&ul_el, &list_dom_attributes(attr))?;
+        if self.checklist {
+            ul_el.set_class(CHECKLIST_CLASS);
+        }
         let mut op = DeltaOperation::insert("");
-        op.set_attributes(operation.get_attributes().clone());
-        let ul_doc_node = DocumentNode::new_element(ul_el, formatter.clone());
+        op.set_attributes(attr.clone());
+        let ul_doc_node = DocumentNode::new_element(ul_el, formatter);
         ul_doc_node.set_operation(op);
+        Ok(Arc::new(ul_doc_node))
+    }
 
-        //<LI>
+    /// A checklist item gets its state as class, and the check box, see CHECKLIST above
+    fn create_li_node(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Arc<DocumentNode> {
         let li_el = DomElement::new(self.child_tag);
-        let li_doc_node = DocumentNode::new_element(li_el, formatter.clone());
+        if self.checklist && operation.op_len() > 0 {
+            let checked = is_checked(operation.get_attributes());
+            li_el.set_class(if checked {
+                CHECKED_CLASS
+            } else {
+                UNCHECKED_CLASS
+            });
+            let check_box = DomElement::new(INPUT_TAG);
+            check_box.set_attribute("type", "checkbox");
+            check_box.set_attribute("contenteditable", "false");
+            check_box.set_checked(checked);
+            li_el.append_child(check_box.node());
+        }
+        let li_doc_node = DocumentNode::new_element(li_el, formatter);
         li_doc_node.set_operation(operation);
-        let li_doc_node_ptr = Arc::new(li_doc_node);
+        Arc::new(li_doc_node)
+    }
 
-        //Link UL -> LI
-        let ul_doc_node_ptr = Arc::new(ul_doc_node);
-        append(&ul_doc_node_ptr, li_doc_node_ptr.clone());
+    /// Returns true for the `list` attribute values of this format
+    fn is_list_value(&self, l_type: &str) -> bool {
+        if self.checklist {
+            return l_type == LIST_CHECKED || l_type == LIST_UNCHECKED;
+        }
+        self.attr_val == l_type
+    }
 
-        //Return the pair so that the calling method may use either one without traversing the tree again
-        return Ok((ul_doc_node_ptr, li_doc_node_ptr));
+    /// An `<LI>` which only wraps a nested list, see INDENTATION above
+    fn is_wrapper_li(&self, doc_node: &Arc<DocumentNode>) -> bool {
+        doc_node.get_doc_dom_node().get_node_name() == self.child_tag && doc_node.op_len() == 0
+    }
+
+    /// Returns the outer `<UL>` of a `<LI>`, or of a (nested) `<UL>`
+    fn top_list_node(&self, doc_node: &Arc<DocumentNode>) -> Arc<DocumentNode> {
+        let mut ul_node = if doc_node.get_doc_dom_node().get_node_name() == self.child_tag {
+            doc_node.get_parent().unwrap()
+        } else {
+            doc_node.clone()
+        };
+        while let Some(wrapper) = ul_node.get_parent() {
+            if !self.is_wrapper_li(&wrapper) {
+                break;
+            }
+            ul_node = wrapper.get_parent().unwrap();
+        }
+        ul_node
     }
 
     /// Splits of a LI node such that it is the only LI element in an UL parent block.<br>
-    /// Returned: UL node --> parent UL block of this single LI
+    /// For a nested item this is repeated for every level, up to the outer UL.<br>
+    /// Returned: UL node --> outer UL block of this single LI
     fn split_li_in_own_ul(&self, li_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
-        let mut ul_node = li_node.get_parent().unwrap();
+        let mut node = li_node.clone();
+        loop {
+            //the <UL>, or the wrapping <LI> of a nested <UL>
+            let mut container = node.get_parent().unwrap();
+
+            //isolate the <LI> block so that it sits in its own <UL> block
+            if let Some(next) = next_sibling(&node) {
+                split_block_before_child(&container, &next)?;
+            }
+            if let Some(_prev) = prev_sibling(&node) {
+                container = split_block_before_child(&container, &node)?.clone();
+            }
+            assert_eq!(container.child_count(), 1); //expecting isolated node !!
 
-        //isolate the <LI> block so that it sits in its own <UL> block
-        if let Some(next) = next_sibling(&li_node) {
-            split_block_before_child(&ul_node, &next)?;
-        }
-        if let Some(_prev) = prev_sibling(&li_node) {
-            ul_node = split_block_before_child(&ul_node, &li_node)?.clone();
+            match container.get_parent() {
+                Some(parent) if self.is_wrapper_li(&parent) || self.is_wrapper_li(&container) => {
+                    node = container;
+                }
+                _ => return Ok(container),
+            }
         }
-        assert_eq!(ul_node.child_count(), 1); //expecting isolated node !!
+    }
 
-        Ok(ul_node.clone())
+    /// Merges the right hand list into the left hand list. Nested lists are merged at every
+    /// level, so that the items end up in one inner list.
+    fn merge_list_nodes(&self, left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> Result<()> {
+        let last = left.get_children().last().cloned();
+        let first = right.get_child(0);
+        merge_block_node(left, right)?;
+        if let (Some(last), Some(first)) = (last, first) {
+            if self.is_wrapper_li(&last) && self.is_wrapper_li(&first) {
+                let inner_left = last.get_children().last().cloned().unwrap();
+                let inner_right = first.get_child(0).unwrap();
+                merge_block_node(&last, &first)?;
+                self.merge_list_nodes(&inner_left, &inner_right)?;
+            }
+        }
+        Ok(())
     }
 
     /// Merging of UL nodes:
@@ -239,13 +409,15 @@ impl ListBlock {
         if let Some(next) = next_sibling(&ul_node) {
             if next.get_doc_dom_node().get_node_name() == self.parent_tag
                 && op == next.get_operation()
+                && !self.restarts(&next)
             {
-                merge_block_node(&ul_node, &next)?;
+                self.merge_list_nodes(&ul_node, &next)?;
                 if let Some(nxt_nxt) = next_sibling(&ul_node) {
                     if nxt_nxt.get_doc_dom_node().get_node_name() == self.parent_tag
                         && op == nxt_nxt.get_operation()
+                        && !self.restarts(&nxt_nxt)
                     {
-                        merge_block_node(&ul_node, &nxt_nxt)?;
+                        self.merge_list_nodes(&ul_node, &nxt_nxt)?;
                     }
                 }
             }
@@ -255,21 +427,134 @@ impl ListBlock {
         if let Some(prev) = prev_sibling(&ul_node) {
             if prev.get_doc_dom_node().get_node_name() == self.parent_tag
                 && op == prev.get_operation()
+                && !self.restarts(&ul_node)
             {
                 ret = prev.clone();
-                merge_block_node(&prev, &ul_node)?;
+                self.merge_list_nodes(&prev, &ul_node)?;
                 if let Some(prv_prv) = prev_sibling(&prev) {
                     if prv_prv.get_doc_dom_node().get_node_name() == self.parent_tag
                         && op == prv_prv.get_operation()
+                        && !self.restarts(&prev)
                     {
                         ret = prv_prv.clone();
-                        merge_block_node(&prv_prv, &prev)?;
+                        self.merge_list_nodes(&prv_prv, &prev)?;
                     }
                 }
             }
         }
         return Ok(ret);
     }
+
+    /// An `<OL>` with a `list-start` in its first `<LI>` restarts the numbering,
+    /// so it is not merged with the previous `<OL>`
+    fn restarts(&self, ul_node: &Arc<DocumentNode>) -> bool {
+        self.parent_tag == OL_TAG && list_start(&first_item_attributes(ul_node)).is_some()
+    }
+
+    /// # renumber()
+    ///
+    /// Sets the `start` attribute of all `<OL>` children of the parent, see NUMBERING above.
+    fn renumber(&self, parent: &Arc<DocumentNode>) {
+        if self.parent_tag != OL_TAG {
+            return;
+        }
+        let mut next_number = 1;
+        let mut in_list = false;
+        let mut after_nested = false;
+        for child in parent.get_children() {
+            if child.get_doc_dom_node().get_node_name() != OL_TAG {
+                in_list = false;
+                after_nested = false;
+                continue;
+            }
+            //a nested list starts at 1, and the outer list continues after it
+            if list_indent(child.get_operation().get_attributes()) > 0 {
+                after_nested = in_list;
+                continue;
+            }
+            let attr = first_item_attributes(&child);
+            let start = if let Some(start) = list_start(&attr) {
+                start
+            } else if after_nested || attr.get(LIST_CONTINUE_ATTR_KEY) == Some(&AttrVal::Bool(true))
+            {
+                next_number
+            } else {
+                1
+            };
+
+            let ol_el = child.get_dom_element().unwrap();
+            if start == 1 {
+                ol_el.remove_attribute(OL_START);
+            } else {
+                ol_el.set_attribute(OL_START, &start.to_string());
+            }
+            next_number = start + child.child_count();
+            in_list = true;
+            after_nested = false;
+        }
+    }
+}
+
+fn register_list_attributes() {
+    BlockAttributeRegistry::register_attr(LIST_ATTR_KEY);
+    BlockAttributeRegistry::register_attr(LIST_START_ATTR_KEY);
+    BlockAttributeRegistry::register_attr(LIST_CONTINUE_ATTR_KEY);
+    BlockAttributeRegistry::register_attr(LIST_INDENT_ATTR_KEY);
+}
+
+/// The numbering attributes belong to the `<LI>`. Else two lists with different numbering
+/// attributes would never merge.
+fn list_node_attributes(attr: &Attributes) -> Attributes {
+    let mut attr = attr.clone();
+    attr.remove(LIST_START_ATTR_KEY);
+    attr.remove(LIST_CONTINUE_ATTR_KEY);
+    attr
+}
+
+/// The indent is shown by nesting the lists, see INDENTATION, and not by a `ql-indent-` class
+fn list_dom_attributes(attr: &Attributes) -> Attributes {
+    let mut attr = attr.clone();
+    attr.remove(LIST_INDENT_ATTR_KEY);
+    attr
+}
+
+/// Returns the attributes of the first `<LI>` item, skipping the `<LI>` wrapping a nested list
+fn first_item_attributes(ul_node: &Arc<DocumentNode>) -> Attributes {
+    let mut node = ul_node.clone();
+    while let Some(child) = node.get_child(0) {
+        if child.op_len() > 0 {
+            return child.get_operation().get_attributes().clone();
+        }
+        node = child;
+    }
+    Attributes::default()
+}
+
+/// Returns true for a checked checklist item
+fn is_checked(attr: &Attributes) -> bool {
+    attr.get(LIST_ATTR_KEY) == Some(&AttrVal::String(LIST_CHECKED.to_string()))
+}
+
+/// Returns the `indent` level, which may be a number or a string like `"2"`. Else 0.
+fn list_indent(attr: &Attributes) -> usize {
+    let indent = match attr.get(LIST_INDENT_ATTR_KEY) {
+        Some(AttrVal::String(s)) => s.clone(),
+        Some(val) => match val.number_val() {
+            Ok(n) => n.to_string(),
+            Err(_) => return 0,
+        },
+        None => return 0,
+    };
+    indent.parse::<usize>().unwrap_or(0)
+}
+
+/// Returns the `list-start` number, which may be a number or a string like `"3"`
+fn list_start(attr: &Attributes) -> Option<usize> {
+    let start = match attr.get(LIST_START_ATTR_KEY)? {
+        AttrVal::String(s) => s.clone(),
+        val => val.number_val().ok()?.to_string(),
+    };
+    start.parse::<usize>().ok()
 }
 
 impl FormatTait for ListBlock {
@@ -302,7 +587,7 @@ impl FormatTait for ListBlock {
             if s == "\n" || s == "" {
                 //allow "" to detect the format given to an UL doc_node
                 if let Some(AttrVal::String(l_type)) = delta.get_attributes().get(LIST_ATTR_KEY) {
-                    return Ok(self.attr_val.eq(l_type));
+                    return Ok(self.is_list_value(l_type));
                 }
             }
         }
@@ -325,34 +610,15 @@ impl FormatTait for ListBlock {
     fn clone_doc_node(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
         let name = doc_node.get_doc_dom_node().get_node_name();
         let node = if name == self.child_tag {
-            let li_el = DomElement::new(self.child_tag);
-            let li_doc_node = Arc::new(DocumentNode::new_element(
-                li_el,
-                doc_node.get_formatter().clone(),
-            ));
-            li_doc_node.set_operation(doc_node.get_operation());
-
-            // let p_parent = doc_node.get_parent().unwrap();
-            // insert_after(&p_parent, &doc_node, &ul_doc_node);
-
-            li_doc_node
+            self.create_li_node(doc_node.get_operation(), doc_node.get_formatter().clone())
         } else if name == UL_TAG || name == OL_TAG {
             // in case we are splitting text
-            let ul_el = DomElement::new(&name);
             let operation = doc_node.get_operation();
-            block_format::apply(&ul_el, operation.get_attributes())?;
-            let mut op = DeltaOperation::insert("");
-            op.set_attributes(operation.get_attributes().clone());
-            let ul_doc_node = Arc::new(DocumentNode::new_element(
-                ul_el,
+            self.create_ul_node(
+                &name,
+                operation.get_attributes(),
                 doc_node.get_formatter().clone(),
-            ));
-            ul_doc_node.set_operation(op);
-
-            // let p_parent = doc_node.get_parent().unwrap();
-            // insert_after(&p_parent, &doc_node, &ul_doc_node);
-
-            ul_doc_node
+            )?
         } else {
             assert_eq!(
                 "LIST: Hey why are we in this branch of the IF statement TEXT",
@@ -371,12 +637,21 @@ impl FormatTait for ListBlock {
         panic!("ListFormat::split_leaf() - Error. ");
     }
 
+    /// The `<UL>` contains the `<LI>` items, and a `<LI>` may contain a nested `<UL>`
+    fn accepts_block_children(&self) -> bool {
+        true
+    }
+
     fn is_same_format(&self, left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> bool {
         if let Some(AttrVal::String(l)) = left.get_operation().get_attributes().get(LIST_ATTR_KEY) {
             if let Some(AttrVal::String(r)) =
                 right.get_operation().get_attributes().get(LIST_ATTR_KEY)
             {
-                return l.eq(r);
+                let indent =
+                    |n: &Arc<DocumentNode>| list_indent(n.get_operation().get_attributes());
+                return self.is_list_value(l)
+                    && self.is_list_value(r)
+                    && indent(left) == indent(right);
             }
         }
         return false;
@@ -406,6 +681,7 @@ impl FormatTait for ListBlock {
             if update_cursor {
                 cursor.set_at(&li_node, 0);
             }
+            self.renumber(&parent);
             return Ok(ul_node);
         }
 
@@ -414,7 +690,9 @@ impl FormatTait for ListBlock {
             unlink(block_node, c);
             append(&li_node, c.clone());
         }
-        return self.merge_ul_nodes(&ul_node);
+        let ul_node = self.merge_ul_nodes(&ul_node)?;
+        self.renumber(&parent);
+        return Ok(ul_node);
     }
 
     /// Split will point to a UL block, so we expect that as input here
@@ -431,17 +709,9 @@ impl FormatTait for ListBlock {
         //assert!( (update_cursor && li_node.child_count()==0) || li_node.child_count()>0 );
         assert!((update_cursor && li_node.child_count() == 0) || !update_cursor);
 
-        let mut ul_node = li_node.get_parent().unwrap();
-        let parent = ul_node.get_parent().unwrap();
-
         //isolate the <LI> block so that it sits in its own <UL> block
-        if let Some(next) = next_sibling(&li_node) {
-            split_block_before_child(&ul_node, &next)?;
-        }
-        if let Some(_prev) = prev_sibling(&li_node) {
-            ul_node = split_block_before_child(&ul_node, &li_node)?.clone();
-        }
-        assert_eq!(ul_node.child_count(), 1); //expecting isolated node !!
+        let ul_node = self.split_li_in_own_ul(li_node)?;
+        let parent = ul_node.get_parent().unwrap();
 
         //now transform the solitary <LI> block in to a <P> block
 
@@ -453,8 +723,9 @@ impl FormatTait for ListBlock {
         let p_node = P_FORMAT.create(op, P_FORMAT.clone())?;
         insert_at_index(&parent, idx, p_node.clone());
 
-        unlink(&ul_node, &li_node);
+        unlink(&li_node.get_parent().unwrap(), &li_node);
         unlink(&parent, &ul_node);
+        self.renumber(&parent);
 
         //empty <LI> block turns into empty <P>
         if update_cursor {
@@ -488,11 +759,20 @@ impl FormatTait for ListBlock {
             block_node.get_doc_dom_node().get_node_name(),
             self.child_tag
         );
-        let ul_node = block_node.get_parent().unwrap();
-        delete_node(block_node);
-        if ul_node.child_count() == 0 {
-            delete_node(&ul_node);
+        let ul_node = self.top_list_node(block_node);
+        let parent = ul_node.get_parent().unwrap();
+
+        //delete the empty (nested) lists too
+        let mut node = block_node.clone();
+        loop {
+            let container = node.get_parent().unwrap();
+            delete_node(&node);
+            if Arc::ptr_eq(&node, &ul_node) || container.child_count() > 0 {
+                break;
+            }
+            node = container;
         }
+        self.renumber(&parent);
     }
 
     /// Returns the UL doc_node with one single block `<LI>` node which contains the doc_node from
@@ -507,13 +787,12 @@ impl FormatTait for ListBlock {
     /// So how to get that node which can be used as a pivot ...
     fn try_merge(&self, _cursor: &Cursor, li_node: &Arc<DocumentNode>) -> Result<()> {
         //assert_eq!(li_node.get_doc_dom_node().get_node_name(), self.child_tag );
-        let ul_node = if li_node.get_doc_dom_node().get_node_name() == self.child_tag {
-            li_node.get_parent().unwrap()
-        } else {
-            li_node.clone()
-        };
+        let ul_node = self.top_list_node(li_node);
 
-        self.merge_ul_nodes(&ul_node)?;
+        let ul_node = self.merge_ul_nodes(&ul_node)?;
+        if let Some(parent) = ul_node.get_parent() {
+            self.renumber(&parent);
+        }
         Ok(())
     }
 }
@@ -0,0 +1,174 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use dom::dom_element::DomElement;
+use list::list_const::{LIST_ATTR_KEY, LIST_BULLET, LIST_CHECKED, LIST_ORDERED, LIST_UNCHECKED};
+use list::{ListBlock, NAME_CHECKLIST_BLOCK, NAME_UL_BLOCK};
+use node_tree::cursor::Cursor;
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_UL_BLOCK, Arc::new(ListBlock::new_ul()))
+            .unwrap();
+        r.register_block_fmt(NAME_CHECKLIST_BLOCK, Arc::new(ListBlock::new_checklist()))
+            .unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn list(val: &str) -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, val);
+    attr
+}
+
+/// Returns the `checked` property of the check box of each item in the first list
+fn check_boxes(doc: &DocumentRoot) -> Vec<bool> {
+    let ul = doc.get_root().get_child(0).unwrap();
+    let mut checked: Vec<bool> = Vec::new();
+    for li in ul.get_children() {
+        let input = li.get_dom_element().unwrap().find_down("INPUT").unwrap();
+        checked.push(DomElement::from(input).is_checked());
+    }
+    checked
+}
+
+#[test]
+fn detect_checklist_format() -> Result<()> {
+    let format = ListBlock::new_checklist();
+    assert_eq!(format.format_name(), NAME_CHECKLIST_BLOCK);
+    assert!(format.applies(&DeltaOperation::insert_attr("\n", list(LIST_CHECKED)))?);
+    assert!(format.applies(&DeltaOperation::insert_attr("\n", list(LIST_UNCHECKED)))?);
+    assert!(!format.applies(&DeltaOperation::insert_attr("\n", list(LIST_BULLET)))?);
+    assert!(!format.applies(&DeltaOperation::insert_attr("\n", list(LIST_ORDERED)))?);
+
+    let bullet = ListBlock::new_ul();
+    assert!(!bullet.applies(&DeltaOperation::insert_attr("\n", list(LIST_CHECKED)))?);
+    assert!(!bullet.applies(&DeltaOperation::insert_attr("\n", list(LIST_UNCHECKED)))?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn checklist_create_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("checklist_create_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Coffee");
+    delta.insert_attr("\n", list(LIST_CHECKED));
+    delta.insert("Tea");
+    delta.insert_attr("\n", list(LIST_UNCHECKED));
+    delta.insert("Milk");
+    delta.insert_attr("\n", list(LIST_BULLET));
+    doc.apply_delta(delta.clone())?;
+
+    //checked and unchecked items are in one checklist, the bullet gets its own list
+    let expect = r#"<ul class="ql-checklist"><li class="ql-checked"><input type="checkbox" contenteditable="false">Coffee</li><li class="ql-unchecked"><input type="checkbox" contenteditable="false">Tea</li></ul><ul><li>Milk</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    assert_eq!(check_boxes(&doc), vec![true, false]);
+
+    delta.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn checklist_insert_text_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("checklist_insert_text_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Tea");
+    delta.insert_attr("\n", list(LIST_UNCHECKED));
+    doc.apply_delta(delta)?;
+
+    //the text goes after the check box, also at the start of the item
+    let mut delta = Delta::default();
+    delta.insert("Green ");
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ul class="ql-checklist"><li class="ql-unchecked"><input type="checkbox" contenteditable="false">Green Tea</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn checklist_toggle_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("checklist_toggle_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Coffee");
+    delta.insert_attr("\n", list(LIST_CHECKED));
+    delta.insert("Tea");
+    delta.insert_attr("\n", list(LIST_UNCHECKED));
+    delta.insert("Milk");
+    delta.insert_attr("\n", list(LIST_UNCHECKED));
+    doc.apply_delta(delta)?;
+
+    //check "Tea"
+    let cursor = Cursor::default();
+    cursor.set_retain_index(8);
+    doc.toggle_checklist_item(&cursor)?;
+
+    let mut expect = Delta::default();
+    expect.insert("Coffee");
+    expect.insert_attr("\n", list(LIST_CHECKED));
+    expect.insert("Tea");
+    expect.insert_attr("\n", list(LIST_CHECKED));
+    expect.insert("Milk");
+    expect.insert_attr("\n", list(LIST_UNCHECKED));
+    expect.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+
+    let html = r#"<ul class="ql-checklist"><li class="ql-checked"><input type="checkbox" contenteditable="false">Coffee</li><li class="ql-checked"><input type="checkbox" contenteditable="false">Tea</li><li class="ql-unchecked"><input type="checkbox" contenteditable="false">Milk</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), html);
+    assert_eq!(check_boxes(&doc), vec![true, true, false]);
+
+    //un-check "Coffee", with the cursor of the document
+    set_cursor_selection(doc.get_cursor(), 2, 0);
+    let cursor = doc.get_cursor().clone();
+    doc.toggle_checklist_item(&cursor)?;
+
+    let mut expect = Delta::default();
+    expect.insert("Coffee");
+    expect.insert_attr("\n", list(LIST_UNCHECKED));
+    expect.insert("Tea");
+    expect.insert_attr("\n", list(LIST_CHECKED));
+    expect.insert("Milk");
+    expect.insert_attr("\n", list(LIST_UNCHECKED));
+    expect.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    assert_eq!(check_boxes(&doc), vec![false, true, false]);
+    assert_eq!(doc.get_cursor().get_retain_index(), 2);
+
+    //the last paragraph is not a checklist item
+    let cursor = Cursor::default();
+    cursor.set_retain_index(16);
+    assert!(doc.toggle_checklist_item(&cursor).is_err()); //Error::NotAChecklistItem
+    Ok(())
+}
@@ -1,12 +1,23 @@
 use anyhow::Result;
 use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::util::node_morph::move_node;
 use core_formats::{P_FORMAT, TEXT_FORMAT};
 use delta::attributes::Attributes;
 use delta::delta::Delta;
+use delta::operations::DeltaOperation;
 use delta::types::attr_val::AttrVal;
-use list::list_const::{LIST_ATTR_KEY, LIST_BULLET};
+use header::{HeaderBlock, NAME_HEADER};
+use list::list_const::{
+    LIST_ATTR_KEY, LIST_BULLET, LIST_CONTINUE_ATTR_KEY, LIST_INDENT_ATTR_KEY, LIST_ORDERED,
+    LIST_START_ATTR_KEY,
+};
 use list::{ListBlock, NAME_OL_BLOCK, NAME_UL_BLOCK};
+use node_tree::doc_node::DocumentNode;
+use node_tree::tree_traverse::{collect_blocks, collect_leaves, first_node, next_node};
 use op_transform::doc_root::DocumentRoot;
+use op_transform::keyboard::KeyboardShortcut;
+use op_transform::op_delete;
+use op_transform::op_retain::set_cursor_selection;
 use op_transform::registry::Registry;
 use std::ops::Deref;
 use std::sync::{Arc, Mutex, OnceLock};
@@ -20,6 +31,8 @@ fn init_test_registry() {
     TEST_REGISTRY.get_or_init(|| {
         Registry::init_registry();
         let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_HEADER, Arc::new(HeaderBlock::new()))
+            .unwrap();
         r.register_block_fmt(NAME_UL_BLOCK, Arc::new(ListBlock::new_ul()))
             .unwrap();
         r.register_block_fmt(NAME_OL_BLOCK, Arc::new(ListBlock::new_ol()))
@@ -512,3 +525,1019 @@ fn list_insert_in_p_block_test() -> Result<()> {
     assert_eq!(doc.as_html_string(), expect);
     Ok(())
 }
+
+#[wasm_bindgen_test]
+fn list_paragraph_at_cursor_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_paragraph_at_cursor_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+
+    let mut delta = Delta::default();
+    delta.insert("Leading text\nfirst");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("second");
+    delta.insert_attr("\n", attr.clone());
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<p>Leading text</p><ul><li>first</li><li>second</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //cursor in "second"
+    set_cursor_selection(doc.get_cursor(), 21, 0);
+    let par = doc.get_paragraph_at_cursor().unwrap();
+    let ops = par.get_ops_ref();
+    assert_eq!(ops.len(), 2);
+    assert_eq!(ops[0].insert_value().str_val()?, "second");
+    assert_eq!(
+        ops[1].get_attributes().get(LIST_ATTR_KEY),
+        attr.get(LIST_ATTR_KEY)
+    );
+    assert_eq!(doc.get_paragraph_text_at_cursor(), "second");
+    assert_eq!(doc.get_paragraph_retain_range(), Some((19, 7)));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_auto_format_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_auto_format_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+
+    for c in "- first".chars() {
+        doc.apply_operation(DeltaOperation::insert(c.to_string()))?;
+    }
+    doc.apply_operation(DeltaOperation::insert("\n"))?;
+    for c in "second".chars() {
+        doc.apply_operation(DeltaOperation::insert(c.to_string()))?;
+    }
+    assert_eq!(
+        doc.as_html_string(),
+        "<ul><li>first</li><li>second</li></ul>"
+    );
+
+    let mut doc = DocumentRoot::new("list_auto_format_star_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+    for c in "* item".chars() {
+        doc.apply_operation(DeltaOperation::insert(c.to_string()))?;
+    }
+    assert_eq!(doc.as_html_string(), "<ul><li>item</li></ul>");
+
+    let mut doc = DocumentRoot::new("list_auto_format_ordered_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+    for c in "1. item".chars() {
+        doc.apply_operation(DeltaOperation::insert(c.to_string()))?;
+    }
+    assert_eq!(doc.as_html_string(), "<ol><li>item</li></ol>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_delete_across_items_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_delete_across_items_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+
+    let mut delta = Delta::default();
+    delta.insert("Leading\nfirst");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("second");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("third");
+    delta.insert_attr("\n", attr.clone());
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<p>Leading</p><ul><li>first</li><li>second</li><li>third</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //delete "rst\nse" --> 2 list items merge into one
+    let mut delta = Delta::default();
+    delta.retain(10);
+    delta.delete(6);
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<p>Leading</p><ul><li>ficond</li><li>third</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_delete_paragraph_boundary_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_delete_paragraph_boundary_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+
+    let mut delta = Delta::default();
+    delta.insert("Leading\nfirst");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("second");
+    delta.insert_attr("\n", attr.clone());
+    doc.apply_delta(delta)?;
+
+    //delete the new line of the paragraph --> paragraph text joins the first list item
+    let mut delta = Delta::default();
+    delta.retain(7);
+    delta.delete(1);
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ul><li>Leadingfirst</li><li>second</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_delete_between_lists_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_delete_between_lists_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+
+    let mut delta = Delta::default();
+    delta.insert("a");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("mid\nb");
+    delta.insert_attr("\n", attr.clone());
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ul><li>a</li></ul><p>mid</p><ul><li>b</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //delete the paragraph between the lists --> both lists merge
+    let mut delta = Delta::default();
+    delta.retain(2);
+    delta.delete(4);
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ul><li>a</li><li>b</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_insert_block_after_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_insert_block_after_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+
+    let mut delta = Delta::default();
+    delta.insert("first");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("second");
+    delta.insert_attr("\n", attr.clone());
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ul><li>first</li><li>second</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    doc.insert_block_after(2, NAME_UL_BLOCK, attr)?;
+    let expect = r#"<ul><li>first</li><li><br></li><li>second</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    assert_eq!(doc.get_cursor().get_retain_index(), 6);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_import_markdown_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_import_markdown_test");
+    doc.open()?;
+
+    doc.import_markdown("Intro\n- first\n* second\n+ third\n\n1. one\n2) two")?;
+    let expect = r#"<p>Intro</p><ul><li>first</li><li>second</li><li>third</li></ul><ol><li>one</li><li>two</li></ol>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_format_ranges_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_format_ranges_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+
+    let mut delta = Delta::default();
+    delta.insert("first");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("second");
+    delta.insert_attr("\n", attr.clone());
+    doc.apply_delta(delta)?;
+
+    //the <UL> wrapper has no length, so only the list items show
+    assert_eq!(
+        doc.get_format_ranges(),
+        vec![
+            (0, 5, NAME_TEXT.to_string()),
+            (5, 1, NAME_UL_BLOCK.to_string()),
+            (6, 6, NAME_TEXT.to_string()),
+            (12, 1, NAME_UL_BLOCK.to_string()),
+            (13, 1, NAME_P_BLOCK.to_string()),
+        ]
+    );
+    assert_eq!(
+        doc.get_attribute_ranges(LIST_ATTR_KEY),
+        vec![
+            (5, 1, AttrVal::from(LIST_BULLET)),
+            (12, 1, AttrVal::from(LIST_BULLET)),
+        ]
+    );
+    Ok(())
+}
+
+fn ordered() -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_ORDERED);
+    attr
+}
+
+#[wasm_bindgen_test]
+fn list_ordered_merge_numbering_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_ordered_merge_numbering_test");
+    doc.open()?;
+
+    let mut start = ordered();
+    start.insert(LIST_START_ATTR_KEY, 3);
+    let mut delta = Delta::default();
+    delta.insert("one");
+    delta.insert_attr("\n", start.clone());
+    delta.insert("two");
+    delta.insert_attr("\n", ordered());
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ol start="3"><li>one</li><li>two</li></ol><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //a list start in the middle restarts the numbering
+    let mut delta = Delta::default();
+    delta.retain(8);
+    delta.insert("three");
+    delta.insert_attr("\n", start);
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ol start="3"><li>one</li><li>two</li></ol><ol start="3"><li>three</li></ol><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_ordered_split_numbering_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_ordered_split_numbering_test");
+    doc.open()?;
+
+    let mut start = ordered();
+    start.insert(LIST_START_ATTR_KEY, 5);
+    let mut delta = Delta::default();
+    delta.insert("one");
+    delta.insert_attr("\n", start);
+    delta.insert("two");
+    delta.insert_attr("\n", ordered());
+    delta.insert("three");
+    delta.insert_attr("\n", ordered());
+    doc.apply_delta(delta)?;
+
+    //split the list, the second half restarts at 1
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, AttrVal::Null);
+    let mut delta = Delta::default();
+    delta.retain(7);
+    delta.retain_attr(1, attr);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ol start="5"><li>one</li></ol><p>two</p><ol><li>three</li></ol><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //the second half continues the numbering
+    let mut attr = Attributes::default();
+    attr.insert(LIST_CONTINUE_ATTR_KEY, true);
+    let mut delta = Delta::default();
+    delta.retain(13);
+    delta.retain_attr(1, attr);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let expect =
+        r#"<ol start="5"><li>one</li></ol><p>two</p><ol start="6"><li>three</li></ol><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_ordered_retain_start_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_ordered_retain_start_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("one");
+    delta.insert_attr("\n", ordered());
+    delta.insert("two");
+    delta.insert_attr("\n", ordered());
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        r#"<ol><li>one</li><li>two</li></ol><p><br></p>"#
+    );
+
+    let mut attr = Attributes::default();
+    attr.insert(LIST_START_ATTR_KEY, 4);
+    let mut delta = Delta::default();
+    delta.retain(3);
+    delta.retain_attr(1, attr);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        r#"<ol start="4"><li>one</li><li>two</li></ol><p><br></p>"#
+    );
+
+    //the numbering is part of the delta document
+    let start = AttrVal::from(4);
+    assert!(doc
+        .to_delta()
+        .get_ops()
+        .iter()
+        .any(|op| op.get_attributes().get(LIST_START_ATTR_KEY) == Some(&start)));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_accessible_text_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_accessible_text_test");
+    doc.open()?;
+
+    doc.import_markdown("- apple\n- pear\n\ntext\n\n1. one\n2. two")?;
+    assert_eq!(
+        doc.get_accessible_text(),
+        "[• apple]\n[• pear]\ntext\n[1. one]\n[2. two]"
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_split_text_lines_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_split_text_lines_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert("list", "bullet");
+    doc.apply_operation(DeltaOperation::insert_attr("first\nsecond\n", attr))?;
+    let expect = r#"<ul><li>first</li><li>second</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_convert_blocks_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_convert_blocks_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("first\nsecond");
+    doc.apply_delta(delta)?;
+
+    let mut attr = Attributes::default();
+    attr.insert("list", "bullet");
+    let count = doc.convert_blocks(NAME_P_BLOCK, NAME_UL_BLOCK, attr)?;
+    assert_eq!(count, 2);
+    let expect = r#"<ul><li>first</li><li>second</li></ul>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_block_at_retain_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_block_at_retain_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+
+    let mut delta = Delta::default();
+    delta.insert("first");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("second");
+    delta.insert_attr("\n", attr.clone());
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ul><li>first</li><li>second</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //the block is the list item, the top level block is the list
+    let item = doc.get_block_at_retain(7).unwrap();
+    assert_eq!(item.get_text_content(), "second");
+    let list = doc.get_top_level_block_at_retain(7).unwrap();
+    assert_eq!(list.get_formatter().format_name(), NAME_UL_BLOCK);
+    assert_eq!(list.child_count(), 2);
+    assert!(Arc::ptr_eq(&item.get_parent().unwrap(), &list));
+
+    //the empty paragraph after the list
+    let par = doc.get_top_level_block_at_retain(13).unwrap();
+    assert_eq!(par.get_formatter().format_name(), NAME_P_BLOCK);
+    Ok(())
+}
+
+fn indented(list: &str, indent: usize) -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, list);
+    if indent > 0 {
+        attr.insert(LIST_INDENT_ATTR_KEY, indent);
+    }
+    attr
+}
+
+#[wasm_bindgen_test]
+fn list_indent_retain_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_indent_retain_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("text\n");
+    doc.apply_delta(delta)?;
+
+    let mut delta = Delta::default();
+    delta.retain(4);
+    delta.retain_attr(1, indented(LIST_BULLET, 1));
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ul><li><ul><li>text</li></ul></li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //the wrapping <LI> does not show in the delta
+    let mut expect = Delta::default();
+    expect.insert("text");
+    expect.insert_attr("\n", indented(LIST_BULLET, 1));
+    expect.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+
+    //and back to the outer level
+    let mut attr = Attributes::default();
+    attr.insert(LIST_INDENT_ATTR_KEY, AttrVal::Null);
+    let mut delta = Delta::default();
+    delta.retain(4);
+    delta.retain_attr(1, attr);
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ul><li>text</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_indent_delete_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_indent_delete_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("a");
+    delta.insert_attr("\n", indented(LIST_BULLET, 0));
+    delta.insert("b");
+    delta.insert_attr("\n", indented(LIST_BULLET, 1));
+    delta.insert("c");
+    delta.insert_attr("\n", indented(LIST_BULLET, 1));
+    delta.insert("d");
+    delta.insert_attr("\n", indented(LIST_BULLET, 0));
+    doc.apply_delta(delta)?;
+
+    //items of the same level merge, different levels do not
+    let expect = r#"<ul><li>a</li></ul><ul><li><ul><li>b</li><li>c</li></ul></li></ul><ul><li>d</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //delete the inner item "b"
+    let mut delta = Delta::default();
+    delta.retain(2);
+    delta.delete(2);
+    doc.apply_delta(delta)?;
+
+    let expect =
+        r#"<ul><li>a</li></ul><ul><li><ul><li>c</li></ul></li></ul><ul><li>d</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //delete the last inner item "c", the outer lists merge
+    let mut delta = Delta::default();
+    delta.retain(2);
+    delta.delete(2);
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<ul><li>a</li><li>d</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    assert!(doc.validate_tree_integrity().is_empty());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_indent_numbering_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_indent_numbering_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("one");
+    delta.insert_attr("\n", indented(LIST_ORDERED, 0));
+    delta.insert("nested");
+    delta.insert_attr("\n", indented(LIST_ORDERED, 1));
+    delta.insert("two");
+    delta.insert_attr("\n", indented(LIST_ORDERED, 0));
+    doc.apply_delta(delta)?;
+
+    //the outer list continues after the nested list
+    let expect = r#"<ol><li>one</li></ol><ol><li><ol><li>nested</li></ol></li></ol><ol start="2"><li>two</li></ol><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_plain_text_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_plain_text_test");
+    doc.open()?;
+
+    let mut bullet = Attributes::default();
+    bullet.insert(LIST_ATTR_KEY, LIST_BULLET);
+    let mut ordered = Attributes::default();
+    ordered.insert(LIST_ATTR_KEY, LIST_ORDERED);
+
+    let mut delta = Delta::default();
+    delta.insert("Leading text\nfirst");
+    delta.insert_attr("\n", bullet.clone());
+    delta.insert("second");
+    delta.insert_attr("\n", bullet);
+    delta.insert("one");
+    delta.insert_attr("\n", ordered);
+    doc.apply_delta(delta)?;
+
+    assert_eq!(doc.get_plain_text(), "Leading text\nfirst\nsecond\none\n\n");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_collect_blocks_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_collect_blocks_test");
+    doc.open()?;
+
+    let mut bullet = Attributes::default();
+    bullet.insert(LIST_ATTR_KEY, LIST_BULLET);
+    let mut ordered = Attributes::default();
+    ordered.insert(LIST_ATTR_KEY, LIST_ORDERED);
+
+    let mut delta = Delta::default();
+    delta.insert("Leading text\nfirst");
+    delta.insert_attr("\n", bullet.clone());
+    delta.insert("second");
+    delta.insert_attr("\n", bullet);
+    delta.insert("one");
+    delta.insert_attr("\n", ordered);
+    doc.apply_delta(delta)?;
+
+    //the <LI> items are blocks, the <UL> and <OL> are not
+    let blocks = collect_blocks(doc.get_root());
+    let text: Vec<String> = blocks.iter().map(|b| b.get_text_content()).collect();
+    assert_eq!(text, vec!["Leading text", "first", "second", "one", ""]);
+    assert!(blocks.iter().all(|b| b.op_len() > 0));
+    assert_eq!(collect_leaves(doc.get_root()).len(), 4);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_iter_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_iter_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("one");
+    delta.insert_attr("\n", indented(LIST_BULLET, 0));
+    delta.insert("nested");
+    delta.insert_attr("\n", indented(LIST_BULLET, 1));
+    delta.insert("text\n");
+    doc.apply_delta(delta)?;
+
+    let mut nodes = Vec::new();
+    let mut dn_o = Some(first_node(doc.get_root()));
+    while let Some(doc_node) = dn_o {
+        dn_o = next_node(&doc_node);
+        nodes.push(doc_node);
+    }
+
+    //the <UL> wrappers are returned too, after their <LI> items
+    let mut count = 0;
+    for doc_node in &doc {
+        assert!(Arc::ptr_eq(&doc_node, &nodes[count]));
+        count += 1;
+    }
+    assert_eq!(count, nodes.len());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_line_start_end_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_line_start_end_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Leading text\nfirst");
+    delta.insert_attr("\n", indented(LIST_BULLET, 0));
+    delta.insert("second");
+    delta.insert_attr("\n", indented(LIST_BULLET, 0));
+    delta.insert("nested");
+    delta.insert_attr("\n", indented(LIST_BULLET, 1));
+    doc.apply_delta(delta)?;
+
+    //each list item is a line
+    let cursor = doc.get_cursor();
+    for (index, start, end) in [(15, 13, 18), (21, 19, 25), (28, 26, 32)] {
+        set_cursor_selection(cursor, index, 0);
+        cursor.line_start()?;
+        assert_eq!(cursor.get_retain_index(), start);
+        cursor.line_end()?;
+        assert_eq!(cursor.get_retain_index(), end);
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_delete_selection_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_delete_selection_test");
+    doc.open()?;
+
+    let mut bullet = Attributes::default();
+    bullet.insert(LIST_ATTR_KEY, LIST_BULLET);
+    let mut delta = Delta::default();
+    delta.insert("Leading text\nfirst");
+    delta.insert_attr("\n", bullet.clone());
+    delta.insert("second");
+    delta.insert_attr("\n", bullet);
+    doc.apply_delta(delta)?;
+
+    //from the paragraph into the first list item
+    let cursor = doc.get_cursor();
+    cursor.set_selection_by_retain_range(8, 15)?;
+    op_delete::delete_selection(cursor, false)?;
+    let expect = r#"<ul><li>Leading rst</li><li>second</li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    assert!(!cursor.is_selection());
+    assert_eq!(cursor.get_retain_index(), 8);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_delta_json_round_trip_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_delta_json_round_trip_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Leading text\nfirst");
+    delta.insert_attr("\n", indented(LIST_BULLET, 0));
+    delta.insert("nested");
+    delta.insert_attr("\n", indented(LIST_BULLET, 1));
+    delta.insert("one");
+    delta.insert_attr("\n", indented(LIST_ORDERED, 0));
+    doc.apply_delta(delta)?;
+
+    let json = doc.to_delta_json();
+    let loaded = DocumentRoot::from_delta_json("list_delta_json_loaded_test", &json)?;
+    assert_eq!(loaded.as_html_string(), doc.as_html_string());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_paste_delta_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_paste_delta_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("ab\n");
+    doc.apply_delta(delta)?;
+
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+    let mut delta = Delta::default();
+    delta.insert("one");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert("two");
+    delta.insert_attr("\n", attr);
+
+    //the paragraph ends as the last list item
+    let cursor = doc.get_cursor().clone();
+    set_cursor_selection(&cursor, 1, 0);
+    doc.paste_delta(&cursor, delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<ul><li>aone</li><li>twob</li></ul><p><br></p>"
+    );
+    assert_eq!(cursor.get_retain_index(), 8);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_formats_at_cursor_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_formats_at_cursor_test");
+    doc.open()?;
+
+    let mut text_attr = Attributes::default();
+    text_attr.insert("bold", true);
+    text_attr.insert("italic", true);
+    let mut list_attr = Attributes::default();
+    list_attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+    let mut delta = Delta::default();
+    delta.insert("plain ");
+    delta.insert_attr("bold italic", text_attr);
+    delta.insert_attr("\n", list_attr);
+    doc.apply_delta(delta)?;
+
+    //in the bold italic text
+    set_cursor_selection(doc.get_cursor(), 9, 0);
+    let (block, mut text) = doc.formats_at_cursor();
+    text.sort();
+    assert_eq!(block, Some(NAME_UL_BLOCK.to_string()));
+    assert_eq!(text, vec!["bold".to_string(), "italic".to_string()]);
+
+    //in the plain text
+    set_cursor_selection(doc.get_cursor(), 2, 0);
+    let (block, text) = doc.formats_at_cursor();
+    assert_eq!(block, Some(NAME_UL_BLOCK.to_string()));
+    assert!(text.is_empty());
+    Ok(())
+}
+
+fn create_move_test_doc(id: &str) -> Result<DocumentRoot> {
+    let mut doc = DocumentRoot::new(id);
+    doc.open()?;
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+    let mut delta = Delta::default();
+    for txt in ["a", "b", "c"] {
+        delta.insert(txt);
+        delta.insert_attr("\n", attr.clone());
+    }
+    delta.insert("x\nd");
+    delta.insert_attr("\n", attr);
+    doc.apply_delta(delta)?;
+    let expect = "<ul><li>a</li><li>b</li><li>c</li></ul><p>x</p><ul><li>d</li></ul><p><br></p>";
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(doc)
+}
+
+#[wasm_bindgen_test]
+fn list_move_node_test() -> Result<()> {
+    init_test_registry();
+
+    //within its own <UL>
+    let doc = create_move_test_doc("list_move_node_test_A")?;
+    let ul = doc.get_root().get_child(0).unwrap();
+    let first = ul.get_child(0).unwrap();
+    move_node(&first, &ul, 2)?;
+    let expect = "<ul><li>b</li><li>c</li><li>a</li></ul><p>x</p><ul><li>d</li></ul><p><br></p>";
+    assert_eq!(doc.as_html_string(), expect);
+
+    //to the other <UL>
+    let doc = create_move_test_doc("list_move_node_test_B")?;
+    let ul = doc.get_root().get_child(0).unwrap();
+    let other_ul = doc.get_root().get_child(2).unwrap();
+    let second = ul.get_child(1).unwrap();
+    move_node(&second, &other_ul, 0)?;
+    let expect = "<ul><li>a</li><li>c</li></ul><p>x</p><ul><li>b</li><li>d</li></ul><p><br></p>";
+    assert_eq!(doc.as_html_string(), expect);
+    assert_eq!(doc.get_plain_text(), "a\nc\nx\nb\nd\n\n");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_move_node_error_test() -> Result<()> {
+    init_test_registry();
+    let doc = create_move_test_doc("list_move_node_error_test")?;
+    let expect = doc.as_html_string();
+    let ul = doc.get_root().get_child(0).unwrap();
+    let item = ul.get_child(0).unwrap();
+    let par = doc.get_root().get_child(1).unwrap();
+
+    //a block in a paragraph
+    assert!(move_node(&item, &par, 0).is_err()); //Error::InvalidParentFormat
+
+    //into its own sub tree
+    assert!(move_node(&ul, &item, 0).is_err()); //Error::MoveIntoDescendant
+
+    //the document root
+    assert!(move_node(doc.get_root(), &ul, 0).is_err()); //Error::MoveDocRoot
+
+    //beyond the last child
+    assert!(move_node(&item, &ul, 3).is_err()); //Error::ChildIndexOutOfRange
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_move_node_into_item_test() -> Result<()> {
+    init_test_registry();
+    let doc = create_move_test_doc("list_move_node_into_item_test")?;
+    let expect = doc.as_html_string();
+    let ul = doc.get_root().get_child(0).unwrap();
+    let second = ul.get_child(1).unwrap();
+    let par = doc.get_root().get_child(1).unwrap();
+
+    //a paragraph in a list item
+    assert!(move_node(&par, &second, 0).is_err()); //Error::InvalidParentFormat
+    assert_eq!(doc.as_html_string(), expect);
+
+    //a text node in a list item, but not in the <UL>
+    let text = ul.get_child(0).unwrap().get_child(0).unwrap();
+    assert!(move_node(&text, &ul, 0).is_err()); //Error::InvalidParentFormat
+    move_node(&text, &second, 0)?;
+    assert_eq!(second.get_text_content(), "ab");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_text_content_test() -> Result<()> {
+    init_test_registry();
+    let doc = create_move_test_doc("list_text_content_test")?;
+
+    //all <LI> items of the <UL>, the paragraph after the list is not included
+    let ul = doc.get_root().get_child(0).unwrap();
+    assert_eq!(ul.get_operation().op_len(), 0);
+    assert_eq!(ul.get_text_content(), "abc");
+    assert_eq!(ul.get_child(1).unwrap().get_text_content(), "b");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_ancestors_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_ancestors_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("a");
+    delta.insert_attr("\n", indented(LIST_BULLET, 0));
+    delta.insert("b");
+    delta.insert_attr("\n", indented(LIST_BULLET, 1));
+    doc.apply_delta(delta)?;
+    let expect = r#"<ul><li>a</li><li><ul><li>b</li></ul></li></ul><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    let names = |n: &Arc<DocumentNode>| -> Vec<String> {
+        n.ancestors()
+            .iter()
+            .map(|a| a.get_doc_dom_node().get_node_name())
+            .collect()
+    };
+
+    //the document root is not included
+    let leaves = collect_leaves(doc.get_root());
+    let a = leaves.first().unwrap();
+    assert_eq!(names(a), vec!["LI", "UL"]);
+    assert_eq!(a.depth(), 2);
+
+    //the <LI> of the nested list
+    let b = leaves.last().unwrap();
+    assert_eq!(names(b), vec!["LI", "UL", "LI", "UL"]);
+    let item = b.get_parent().unwrap();
+    assert_eq!(item.depth(), 3);
+
+    let ul = doc.get_root().get_child(0).unwrap();
+    assert_eq!(ul.depth(), 0);
+    assert!(b.ancestors().iter().any(|n| Arc::ptr_eq(n, &ul)));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_backspace_empty_item_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_backspace_empty_item_test");
+    doc.open()?;
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+    let mut delta = Delta::default();
+    delta.insert("a");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert_attr("\n", attr);
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<ul><li>a</li><li><br></li></ul><p><br></p>"
+    );
+
+    //the empty list item becomes a paragraph
+    doc.get_cursor().set_selection_by_retain_range(2, 2)?;
+    let backspace = KeyboardShortcut::new("Backspace", false, false, false);
+    assert!(doc.handle_shortcut(&backspace));
+    assert_eq!(
+        doc.as_html_string(),
+        "<ul><li>a</li></ul><p><br></p><p><br></p>"
+    );
+    assert_eq!(doc.get_cursor().get_retain_index(), 2);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_delete_before_empty_item_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_delete_before_empty_item_test");
+    doc.open()?;
+    let mut attr = Attributes::default();
+    attr.insert(LIST_ATTR_KEY, LIST_BULLET);
+    let mut delta = Delta::default();
+    delta.insert("a");
+    delta.insert_attr("\n", attr.clone());
+    delta.insert_attr("\n", attr);
+    doc.apply_delta(delta)?;
+
+    //only the Backspace key asks the empty list item, an operation is applied as it is
+    set_cursor_selection(doc.get_cursor(), 1, 0);
+    doc.apply_operation(DeltaOperation::delete(1))?;
+    assert_eq!(doc.as_html_string(), "<ul><li>a</li></ul><p><br></p>");
+    assert_eq!(doc.get_cursor().get_retain_index(), 1);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn list_split_document_at_cursor_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("list_split_document_at_cursor_test");
+    doc.open()?;
+    let mut list = Attributes::default();
+    list.insert(LIST_ATTR_KEY, LIST_BULLET);
+    let mut header = Attributes::default();
+    header.insert("heading", 1);
+    let mut delta = Delta::default();
+    delta.insert("title");
+    delta.insert_attr("\n", header);
+    delta.insert("one");
+    delta.insert_attr("\n", list.clone());
+    delta.insert("two");
+    delta.insert_attr("\n", list);
+    delta.insert("text\n");
+    doc.apply_delta(delta)?;
+    let html = doc.as_html_string();
+    let full = doc.to_delta();
+
+    //at the start, between the list items, at the start of the last paragraph
+    for retain in [0, 10, 14] {
+        set_cursor_selection(doc.get_cursor(), retain, 0);
+        let (before, after) = doc.split_document_at_cursor()?;
+        let mut first = DocumentRoot::new("list_split_document_before_test");
+        let mut second = DocumentRoot::new("list_split_document_after_test");
+        if retain == 0 {
+            assert!(before.get_ops_ref().is_empty());
+            assert_eq!(after.get_ops_ref(), full.get_ops_ref());
+        } else {
+            first.load(before)?;
+        }
+        second.load(after)?;
+        assert_eq!(first.as_html_string() + &second.as_html_string(), html);
+    }
+
+    //at the end, in the last empty paragraph, the second half is empty
+    set_cursor_selection(doc.get_cursor(), 19, 0);
+    let (before, after) = doc.split_document_at_cursor()?;
+    assert_eq!(before.get_ops_ref(), full.get_ops_ref());
+    assert!(after.get_ops_ref().is_empty());
+
+    //the document is not changed
+    assert_eq!(doc.to_delta().get_ops_ref(), full.get_ops_ref());
+    assert_eq!(doc.as_html_string(), html);
+
+    //inside a list item, both halves keep the list format
+    set_cursor_selection(doc.get_cursor(), 7, 0);
+    let (before, after) = doc.split_document_at_cursor()?;
+    let mut first = DocumentRoot::new("list_split_document_item_test");
+    first.load(before)?;
+    assert_eq!(first.as_html_string(), "<h1>title</h1><ul><li>o</li></ul>");
+    let mut second = DocumentRoot::new("list_split_document_item_after_test");
+    second.load(after)?;
+    assert_eq!(
+        second.as_html_string(),
+        "<ul><li>ne</li><li>two</li></ul><p>text</p><p><br></p>"
+    );
+    Ok(())
+}
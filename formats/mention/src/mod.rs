@@ -0,0 +1,191 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::util::node_morph::delete_node;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use dom::dom_element::DomElement;
+use node_tree::cursor::Cursor;
+use node_tree::doc_node::DocumentNode;
+use node_tree::format_trait::FormatTait;
+use std::sync::Arc;
+
+pub static NAME_MENTION: &'static str = "mention"; //registry label, and insert::map key
+
+static MENTION_TAG: &'static str = "SPAN"; //html tag
+static MENTION_CLASS: &'static str = "ql-mention"; //CSS class
+static MENTION_ID: &'static str = "id"; //key in the mention map
+static MENTION_VALUE: &'static str = "value"; //key in the mention map
+static DATA_ID: &'static str = "data-id"; //html attribute
+static DATA_VALUE: &'static str = "data-value"; //html attribute
+static DENOTATION: &'static str = "@"; //shown before the value
+
+/// # MentionFormat
+///
+/// A mention of a user, or any other object with an id:
+/// ```bash
+/// {
+///   insert: { mention: { id: '42', value: 'user' } }
+/// }
+/// ```
+///
+/// Results in:
+/// ```bash
+/// <span contenteditable="false" class="ql-mention" data-id="42" data-value="user">@user</span>
+/// ```
+///
+/// A mention is atomic: it is an embed with the length 1, which can not be split. Every
+/// mention is a unique object, so two mentions of the same user are never merged.
+pub struct MentionFormat {}
+
+impl MentionFormat {
+    pub fn new() -> Self {
+        MentionFormat {}
+    }
+}
+
+/// Returns the `(id, value)` of the mention
+fn mention_data(operation: &DeltaOperation) -> Result<(String, String)> {
+    let map = operation.insert_value().map_val()?;
+    let mention = map.get(NAME_MENTION).unwrap().map_val()?;
+    let id = mention.get(MENTION_ID).unwrap().str_val()?.to_string();
+    let value = mention.get(MENTION_VALUE).unwrap().str_val()?.to_string();
+    Ok((id, value))
+}
+
+/// Creates the `<SPAN>` of the mention
+fn create_element(id: &str, value: &str) -> DomElement {
+    let dom_el = DomElement::new(MENTION_TAG);
+    dom_el.set_attribute("contenteditable", "false");
+    dom_el.set_class(MENTION_CLASS);
+    dom_el.set_attribute(DATA_ID, id);
+    dom_el.set_attribute(DATA_VALUE, value);
+    dom_el
+        .element()
+        .set_text_content(Some(&[DENOTATION, value].concat()));
+    dom_el
+}
+
+impl FormatTait for MentionFormat {
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let (id, value) = mention_data(&operation)?;
+        let doc_node = DocumentNode::new_element(create_element(&id, &value), formatter);
+        doc_node.set_operation(operation);
+        Ok(Arc::new(doc_node))
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_MENTION
+    }
+
+    fn is_text_format(&self) -> bool {
+        true
+    }
+
+    fn is_void(&self) -> bool {
+        true
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        panic!("Hey you called MentionFormat::block_remove_attr() on the TextFormatter format-trait implementation.");
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        let val = delta.insert_value();
+        if val.is_map() {
+            if let Some(av) = val.map_val()?.get(NAME_MENTION) {
+                return Ok(av.is_map());
+            }
+        }
+        Ok(false)
+    }
+
+    //A mention has no attributes
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        _attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    /// Copies the `data-id` and `data-value` of the HTML element, so the clone does not
+    /// share anything with the original.
+    fn clone_doc_node(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        let dom_el = doc_node.get_dom_element().unwrap();
+        let id = dom_el.get_attribute(DATA_ID).unwrap_or_default();
+        let value = dom_el.get_attribute(DATA_VALUE).unwrap_or_default();
+
+        let clone = DocumentNode::new_element(
+            create_element(&id, &value),
+            doc_node.get_formatter().clone(),
+        );
+        clone.set_operation(doc_node.get_operation());
+        Ok(Arc::new(clone))
+    }
+
+    fn split_leaf(&self, _cursor: &Cursor) -> Result<()> {
+        //we have length 1 (always) so it can not be split
+        panic!("MentionFormat::split_leaf() - Error.");
+    }
+
+    fn is_same_format(&self, _left: &Arc<DocumentNode>, _right: &Arc<DocumentNode>) -> bool {
+        false //every mention is a unique object
+    }
+
+    fn block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+        _delta: DeltaOperation,
+        _new_transducer: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("MentionFormat::block_transform() - Error.");
+    }
+
+    fn un_block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("MentionFormat::un_block_transform() - Error.");
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        _doc_node: &Arc<DocumentNode>,
+        _at: usize,
+        _length: usize,
+    ) -> Result<()> {
+        panic!(
+            "MentionFormat::Delete() - Mention has length 1, so use the other delete function..."
+        );
+    }
+
+    //Deletes the mention as a whole
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn try_merge(&self, _cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        Ok(())
+    }
+}
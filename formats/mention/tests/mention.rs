@@ -0,0 +1,125 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::{DeltaOperation, OpsMap};
+use mention::{MentionFormat, NAME_MENTION};
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_MENTION, Arc::new(MentionFormat::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn mention(id: &str, value: &str) -> DeltaOperation {
+    let mut data = OpsMap::default();
+    data.insert("id", id);
+    data.insert("value", value);
+    let mut mention = OpsMap::default();
+    mention.insert(NAME_MENTION, data);
+    DeltaOperation::insert_attr(mention, Attributes::default())
+}
+
+/// Creates the document `Hi @ann@bob!`
+fn create_test_mentions(doc: &mut DocumentRoot) -> Result<()> {
+    let mut delta = Delta::default();
+    delta.insert("Hi ");
+    delta.push(mention("1", "ann"));
+    delta.push(mention("2", "bob"));
+    delta.insert("!");
+
+    doc.open()?;
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<p>Hi <span contenteditable="false" class="ql-mention" data-id="1" data-value="ann">@ann</span><span contenteditable="false" class="ql-mention" data-id="2" data-value="bob">@bob</span>!</p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[test]
+fn detect_mention_format() -> Result<()> {
+    let format = MentionFormat::new();
+    assert_eq!(format.format_name(), NAME_MENTION);
+    assert!(format.applies(&mention("1", "ann"))?);
+    assert!(!format.applies(&DeltaOperation::insert("@ann"))?);
+
+    let mut img = OpsMap::default();
+    img.insert("image", "image-source.png");
+    assert!(!format.applies(&DeltaOperation::insert_attr(img, Attributes::default()))?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn mention_adjacent_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("mention_adjacent_test");
+    create_test_mentions(&mut doc)?;
+
+    //adjacent mentions are not merged, each has the length 1
+    let p = doc.get_root().get_child(0).unwrap();
+    assert_eq!(p.child_count(), 4);
+    assert_eq!(p.get_child(1).unwrap().op_len(), 1);
+    assert_eq!(p.get_child(2).unwrap().op_len(), 1);
+
+    let ops = doc.to_delta().get_ops();
+    assert_eq!(ops[1], mention("1", "ann"));
+    assert_eq!(ops[2], mention("2", "bob"));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn mention_delete_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("mention_delete_test");
+    create_test_mentions(&mut doc)?;
+
+    set_cursor_selection(doc.get_cursor(), 3, 0);
+    doc.apply_operation(DeltaOperation::delete(1))?;
+    let expect = r#"<p>Hi <span contenteditable="false" class="ql-mention" data-id="2" data-value="bob">@bob</span>!</p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //inserting the mention again gives the same HTML element
+    set_cursor_selection(doc.get_cursor(), 3, 0);
+    doc.apply_operation(mention("1", "ann"))?;
+    let expect = r#"<p>Hi <span contenteditable="false" class="ql-mention" data-id="1" data-value="ann">@ann</span><span contenteditable="false" class="ql-mention" data-id="2" data-value="bob">@bob</span>!</p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn mention_clone_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("mention_clone_test");
+    create_test_mentions(&mut doc)?;
+
+    let p = doc.get_root().get_child(0).unwrap();
+    let ann = p.get_child(1).unwrap();
+    let clone = ann.get_formatter().clone_doc_node(&ann)?;
+    assert!(!Arc::ptr_eq(&ann, &clone));
+
+    let el = clone.get_dom_element().unwrap();
+    assert_eq!(el.get_attribute("data-id"), Some("1".to_string()));
+    assert_eq!(el.get_attribute("data-value"), Some("ann".to_string()));
+    assert_eq!(clone.get_operation(), ann.get_operation());
+    Ok(())
+}
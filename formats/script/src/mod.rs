@@ -0,0 +1,330 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::format_const::{FORMAT_SUB, FORMAT_SUP};
+use core_formats::util::node_morph::{
+    delete_node, delete_text, split_text_node, try_3_way_merge_text,
+};
+use core_formats::TEXT_FORMAT;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use node_tree::cursor::{Cursor, CursorLocation};
+use node_tree::doc_node::DocumentNode;
+use node_tree::dom_doc_tree_morph::{insert_at_index, unlink};
+use node_tree::format_trait::FormatTait;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+pub static NAME_SUPERSCRIPT: &'static str = "superscript"; //registry label
+pub static NAME_SUBSCRIPT: &'static str = "subscript"; //registry label
+
+static SCRIPT_ATTR_KEY: &'static str = "script"; //attribute key
+static SCRIPT_SUPER: &'static str = "super"; //attribute value for <SUP>
+static SCRIPT_SUB: &'static str = "sub"; //attribute value for <SUB>
+
+static SUPERSCRIPT_FORMAT: Lazy<Arc<dyn FormatTait + Send + Sync>> =
+    Lazy::new(|| Arc::new(SuperscriptFormat::new()));
+static SUBSCRIPT_FORMAT: Lazy<Arc<dyn FormatTait + Send + Sync>> =
+    Lazy::new(|| Arc::new(SubscriptFormat::new()));
+
+/// # SuperscriptFormat
+///
+/// ```bash
+///   {insert: "E=mc"}
+///   {attributes: {script: "super"}, insert: "2"}
+/// ```
+///
+/// Gives as HTML:
+/// ```bash
+///     <p>E=mc<sup>2</sup></p>
+/// ```
+///
+/// The script is a text format, like `bold`, and may be combined with the other text
+/// formats. The `<SUP>` element is made by the `TextFormat`, the document node keeps the
+/// `script` attribute.
+///
+/// Register this format before the `TextFormat`, else the `TextFormat` takes the
+/// operations with both a `script` and a `bold` attribute.
+pub struct SuperscriptFormat {}
+impl SuperscriptFormat {
+    pub fn new() -> Self {
+        SuperscriptFormat {}
+    }
+}
+
+/// # SubscriptFormat
+///
+/// ```bash
+///   {insert: "H"}
+///   {attributes: {script: "sub"}, insert: "2"}
+///   {insert: "O"}
+/// ```
+///
+/// Gives as HTML:
+/// ```bash
+///     <p>H<sub>2</sub>O</p>
+/// ```
+///
+/// See the `SuperscriptFormat`.
+pub struct SubscriptFormat {}
+impl SubscriptFormat {
+    pub fn new() -> Self {
+        SubscriptFormat {}
+    }
+}
+
+/// Returns true when the attributes contain the given `script` value
+fn has_script(attr: &Attributes, script: &str) -> bool {
+    match attr.get(SCRIPT_ATTR_KEY) {
+        Some(AttrVal::String(s)) => s == script,
+        _ => false,
+    }
+}
+
+/// Returns the format for the `script` attribute, or the `TextFormat` when there is none
+fn script_format(attr: &Attributes) -> Arc<dyn FormatTait + Send + Sync> {
+    if has_script(attr, SCRIPT_SUPER) {
+        SUPERSCRIPT_FORMAT.clone()
+    } else if has_script(attr, SCRIPT_SUB) {
+        SUBSCRIPT_FORMAT.clone()
+    } else {
+        TEXT_FORMAT.clone()
+    }
+}
+
+/// Lets the `TextFormat` create the `<SUP>`, or `<SUB>`, element using its own text format
+/// `text_format`. The document node keeps the original operation.
+fn create_script(
+    operation: DeltaOperation,
+    formatter: Arc<dyn FormatTait + Send + Sync>,
+    text_format: &str,
+) -> Result<Arc<DocumentNode>> {
+    let mut attr = operation.get_attributes().clone();
+    attr.remove(SCRIPT_ATTR_KEY);
+    attr.insert(text_format, true);
+    let op = DeltaOperation::insert_attr(operation.insert_value().str_val()?, attr);
+
+    let doc_node = TEXT_FORMAT.create(op, formatter)?;
+    doc_node.set_operation(operation);
+    Ok(doc_node)
+}
+
+/// Replaces the document node with a new one having the given attributes.<br>
+/// The format is chosen by the `script` attribute, so a retain may change `<SUP>` in to `<SUB>`.
+fn apply_script(doc_node: &Arc<DocumentNode>, attr: &Attributes) -> Result<Arc<DocumentNode>> {
+    assert!(doc_node.get_formatter().is_text_format());
+    let index = doc_node.my_index_as_child().unwrap();
+
+    let format = script_format(attr);
+    let old_op = doc_node.get_operation();
+    let op = DeltaOperation::insert_attr(old_op.insert_value().str_val()?, attr.clone());
+    let new_doc_node = format.create(op, format.clone())?;
+
+    let parent = doc_node.get_parent().unwrap();
+    unlink(&parent, doc_node);
+    insert_at_index(&parent, index, new_doc_node.clone());
+    Ok(new_doc_node)
+}
+
+/// Splits the text using `split_text_node()`, which creates the right hand node as plain
+/// text. So we give the right hand node its `<SUP>`, or `<SUB>`, element again.
+///
+/// Post condition: Cursor position is BEFORE the right hand side created node
+fn split_script_at_cursor(cursor: &Cursor) -> Result<()> {
+    if let CursorLocation::At(doc_node, index) = cursor.get_location() {
+        if index == 0 {
+            return Ok(());
+        }
+        let right = split_text_node(&doc_node, index)?;
+        let right = apply_script(&right, doc_node.get_operation().get_attributes())?;
+        cursor.set_before_no_retain_update(&right);
+    }
+    Ok(())
+}
+
+/// A `<SUP>` never merges with a `<SUB>`, so the formats must match, and then the attributes
+fn is_same_script(left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> bool {
+    left.get_formatter().format_name() == right.get_formatter().format_name()
+        && left
+            .get_operation()
+            .get_attributes()
+            .is_equal(right.get_operation().get_attributes())
+}
+
+impl FormatTait for SuperscriptFormat {
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        create_script(operation, formatter, FORMAT_SUP)
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_SUPERSCRIPT
+    }
+
+    fn is_text_format(&self) -> bool {
+        true
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        panic!("Hey you called SuperscriptFormat::block_remove_attr() on a text format-trait implementation.");
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        Ok(delta.insert_value().is_string() && has_script(delta.get_attributes(), SCRIPT_SUPER))
+    }
+
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        apply_script(doc_node, attr)
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        TEXT_FORMAT.drop_line_attributes(doc_node)
+    }
+
+    fn split_leaf(&self, cursor: &Cursor) -> Result<()> {
+        split_script_at_cursor(cursor)
+    }
+
+    fn is_same_format(&self, left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> bool {
+        is_same_script(left, right)
+    }
+
+    fn block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+        _delta: DeltaOperation,
+        _new_transducer: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("SuperscriptFormat::block_transform() - Error.");
+    }
+
+    fn un_block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("SuperscriptFormat::un_block_transform() - Error.");
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        at: usize,
+        length: usize,
+    ) -> Result<()> {
+        delete_text(doc_node, at, length)
+    }
+
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn try_merge(&self, cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        try_3_way_merge_text(cursor)
+    }
+}
+
+impl FormatTait for SubscriptFormat {
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        create_script(operation, formatter, FORMAT_SUB)
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_SUBSCRIPT
+    }
+
+    fn is_text_format(&self) -> bool {
+        true
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        panic!("Hey you called SubscriptFormat::block_remove_attr() on a text format-trait implementation.");
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        Ok(delta.insert_value().is_string() && has_script(delta.get_attributes(), SCRIPT_SUB))
+    }
+
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        apply_script(doc_node, attr)
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        TEXT_FORMAT.drop_line_attributes(doc_node)
+    }
+
+    fn split_leaf(&self, cursor: &Cursor) -> Result<()> {
+        split_script_at_cursor(cursor)
+    }
+
+    fn is_same_format(&self, left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> bool {
+        is_same_script(left, right)
+    }
+
+    fn block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+        _delta: DeltaOperation,
+        _new_transducer: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("SubscriptFormat::block_transform() - Error.");
+    }
+
+    fn un_block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("SubscriptFormat::un_block_transform() - Error.");
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        at: usize,
+        length: usize,
+    ) -> Result<()> {
+        delete_text(doc_node, at, length)
+    }
+
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn try_merge(&self, cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        try_3_way_merge_text(cursor)
+    }
+}
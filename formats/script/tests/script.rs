@@ -0,0 +1,176 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use script::{SubscriptFormat, SuperscriptFormat, NAME_SUBSCRIPT, NAME_SUPERSCRIPT};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_SUPERSCRIPT, Arc::new(SuperscriptFormat::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_SUBSCRIPT, Arc::new(SubscriptFormat::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn script(val: &str) -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert("script", val);
+    attr
+}
+
+#[test]
+fn detect_script_format() -> Result<()> {
+    let sup = SuperscriptFormat::new();
+    let sub = SubscriptFormat::new();
+    assert_eq!(sup.format_name(), NAME_SUPERSCRIPT);
+    assert_eq!(sub.format_name(), NAME_SUBSCRIPT);
+
+    let op = DeltaOperation::insert_attr("2", script("super"));
+    assert!(sup.applies(&op)?);
+    assert!(!sub.applies(&op)?);
+
+    let op = DeltaOperation::insert_attr("2", script("sub"));
+    assert!(!sup.applies(&op)?);
+    assert!(sub.applies(&op)?);
+
+    assert!(!sup.applies(&DeltaOperation::insert("2"))?);
+    assert!(!sub.applies(&DeltaOperation::insert_attr("2", script("middle")))?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn script_create_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("script_create_test");
+    doc.open()?;
+
+    let mut bold = script("super");
+    bold.insert("bold", true);
+
+    let mut delta = Delta::default();
+    delta.insert("E=mc");
+    delta.insert_attr("2", script("super"));
+    delta.insert(" H");
+    delta.insert_attr("2", script("sub"));
+    delta.insert("O ");
+    delta.insert_attr("x", bold);
+    delta.insert("\n");
+    doc.apply_delta(delta.clone())?;
+
+    let html_txt =
+        r##"<p>E=mc<sup>2</sup> H<sub>2</sub>O <sup><strong>x</strong></sup></p><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    delta.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn script_insert_mid_word_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("script_insert_mid_word_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("x");
+    delta.insert_attr("abcd", script("super"));
+    delta.insert("\n");
+    doc.apply_delta(delta)?;
+
+    //the same script merges with the split word
+    let mut delta = Delta::default();
+    delta.retain(3);
+    delta.insert_attr("XY", script("super"));
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<p>x<sup>abXYcd</sup></p><p><br></p>");
+    assert_eq!(doc.get_cursor().get_retain_index(), 5);
+    assert_eq!(doc.get_root().get_child(0).unwrap().child_count(), 2);
+
+    //another script splits the word
+    let mut delta = Delta::default();
+    delta.retain(5);
+    delta.insert_attr("z", script("sub"));
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>x<sup>abXY</sup><sub>z</sub><sup>cd</sup></p><p><br></p>"
+    );
+    assert_eq!(doc.get_cursor().get_retain_index(), 6);
+
+    let mut expect = Delta::default();
+    expect.insert("x");
+    expect.insert_attr("abXY", script("super"));
+    expect.insert_attr("z", script("sub"));
+    expect.insert_attr("cd", script("super"));
+    expect.insert("\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn script_delete_boundary_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("script_delete_boundary_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert_attr("ab", script("super"));
+    delta.insert("-");
+    delta.insert_attr("cd", script("sub"));
+    delta.insert("-");
+    delta.insert_attr("ef", script("sub"));
+    delta.insert("\n");
+    doc.apply_delta(delta)?;
+
+    //a <SUP> never merges with a <SUB>
+    let mut delta = Delta::default();
+    delta.retain(2);
+    delta.delete(1);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p><sup>ab</sup><sub>cd</sub>-<sub>ef</sub></p><p><br></p>"
+    );
+
+    //but a <SUB> merges with a <SUB>
+    let mut delta = Delta::default();
+    delta.retain(4);
+    delta.delete(1);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p><sup>ab</sup><sub>cdef</sub></p><p><br></p>"
+    );
+
+    let mut expect = Delta::default();
+    expect.insert_attr("ab", script("super"));
+    expect.insert_attr("cdef", script("sub"));
+    expect.insert("\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
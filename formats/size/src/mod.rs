@@ -0,0 +1,224 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::format_const::TEXT_ATTR_SIZE;
+use core_formats::util::lookup::AttributesLookup;
+use core_formats::util::node_morph::{
+    delete_node, delete_text, split_text_node, try_3_way_merge_text,
+};
+use core_formats::TEXT_FORMAT;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use node_tree::cursor::{Cursor, CursorLocation};
+use node_tree::doc_node::DocumentNode;
+use node_tree::dom_doc_tree_morph::{insert_at_index, unlink};
+use node_tree::format_trait::FormatTait;
+use once_cell::sync::{Lazy, OnceCell};
+use std::sync::Arc;
+
+pub static NAME_SIZE: &'static str = "size"; //registry label
+
+static SIZE_UNIT: &'static str = "pt"; //unit of a numeric size
+
+static SIZE_FORMAT: Lazy<Arc<dyn FormatTait + Send + Sync>> =
+    Lazy::new(|| Arc::new(FontSizeFormat::new()));
+
+static SIZES: OnceCell<AttributesLookup> = OnceCell::new();
+pub(crate) fn initialise() {
+    if let Some(_attr) = SIZES.get() {
+        return;
+    }
+    let mut attr = AttributesLookup::new(3);
+    attr.fill_one("small", "0.75em");
+    attr.fill_one("large", "1.5em");
+    attr.fill_one("huge", "2.5em");
+    SIZES
+        .set(attr)
+        .expect("did you call size::initialise() twice?");
+}
+
+/// # FontSizeFormat
+///
+/// ```bash
+///   {insert: "big", attributes: {size: "huge"}}
+///   {insert: "print", attributes: {size: 8}}
+/// ```
+///
+/// Gives as HTML:
+/// ```bash
+///     <span style="font-size:2.5em;">big</span><span style="font-size:8pt;">print</span>
+/// ```
+///
+/// The named sizes `small`, `large`, and `huge` are translated to a CSS size. A number is a
+/// size in points. Any other string is used as CSS size, like `"12px"`.
+///
+/// The `<SPAN>` is made by the `TextFormat`, so the size may be combined with the other
+/// text formats. The document node keeps the `size` attribute as it was given.
+///
+/// Register this format before the `TextFormat`.
+pub struct FontSizeFormat {}
+impl FontSizeFormat {
+    pub fn new() -> Self {
+        initialise();
+        FontSizeFormat {}
+    }
+}
+
+/// Returns the CSS `font-size` for the `size` attribute value
+fn css_size(val: &AttrVal) -> Option<String> {
+    match val {
+        AttrVal::Null => None,
+        AttrVal::String(s) => match SIZES.get().unwrap().get(s) {
+            Some(css) => Some(css.to_string()),
+            None => Some(s.clone()),
+        },
+        val => val
+            .number_val()
+            .ok()
+            .map(|n| [n.to_string(), SIZE_UNIT.to_string()].concat()),
+    }
+}
+
+/// Returns true when the attributes contain a `size`, which is not `null`
+fn has_size(attr: &Attributes) -> bool {
+    attr.get(TEXT_ATTR_SIZE).and_then(css_size).is_some()
+}
+
+/// Replaces the document node with a new one having the given attributes.<br>
+/// Without a size the node is given to the `TextFormat`.
+fn apply_size(doc_node: &Arc<DocumentNode>, attr: &Attributes) -> Result<Arc<DocumentNode>> {
+    assert!(doc_node.get_formatter().is_text_format());
+    let index = doc_node.my_index_as_child().unwrap();
+
+    let format = if has_size(attr) {
+        SIZE_FORMAT.clone()
+    } else {
+        TEXT_FORMAT.clone()
+    };
+    let old_op = doc_node.get_operation();
+    let op = DeltaOperation::insert_attr(old_op.insert_value().str_val()?, attr.clone());
+    let new_doc_node = format.create(op, format.clone())?;
+
+    let parent = doc_node.get_parent().unwrap();
+    unlink(&parent, doc_node);
+    insert_at_index(&parent, index, new_doc_node.clone());
+    Ok(new_doc_node)
+}
+
+impl FormatTait for FontSizeFormat {
+    /// Lets the `TextFormat` create the `<SPAN>` with the CSS size
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let mut attr = operation.get_attributes().clone();
+        if let Some(css) = attr.get(TEXT_ATTR_SIZE).and_then(css_size) {
+            attr.insert(TEXT_ATTR_SIZE, css);
+        }
+        let op = DeltaOperation::insert_attr(operation.insert_value().str_val()?, attr);
+
+        let doc_node = TEXT_FORMAT.create(op, formatter)?;
+        doc_node.set_operation(operation);
+        Ok(doc_node)
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_SIZE
+    }
+
+    fn is_text_format(&self) -> bool {
+        true
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        panic!("Hey you called FontSizeFormat::block_remove_attr() on a text format-trait implementation.");
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        if delta.insert_value().is_string() && delta.insert_value().str_val()? != "\n" {
+            return Ok(has_size(delta.get_attributes()));
+        }
+        Ok(false)
+    }
+
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        apply_size(doc_node, attr)
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        TEXT_FORMAT.drop_line_attributes(doc_node)
+    }
+
+    /// `split_text_node()` creates the right hand node as plain text, so we give it the
+    /// size again.
+    ///
+    /// Post condition: Cursor position is BEFORE the right hand side created node
+    fn split_leaf(&self, cursor: &Cursor) -> Result<()> {
+        if let CursorLocation::At(doc_node, index) = cursor.get_location() {
+            if index == 0 {
+                return Ok(());
+            }
+            let right = split_text_node(&doc_node, index)?;
+            let right = apply_size(&right, doc_node.get_operation().get_attributes())?;
+            cursor.set_before_no_retain_update(&right);
+        }
+        Ok(())
+    }
+
+    //Only the size decides, the other attributes are compared by try_3_way_merge_text()
+    fn is_same_format(&self, left: &Arc<DocumentNode>, right: &Arc<DocumentNode>) -> bool {
+        left.get_operation().get_attributes().get(TEXT_ATTR_SIZE)
+            == right.get_operation().get_attributes().get(TEXT_ATTR_SIZE)
+    }
+
+    fn block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+        _delta: DeltaOperation,
+        _new_transducer: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("FontSizeFormat::block_transform() - Error.");
+    }
+
+    fn un_block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("FontSizeFormat::un_block_transform() - Error.");
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        at: usize,
+        length: usize,
+    ) -> Result<()> {
+        delete_text(doc_node, at, length)
+    }
+
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn try_merge(&self, cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        try_3_way_merge_text(cursor)
+    }
+}
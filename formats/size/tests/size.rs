@@ -0,0 +1,123 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use size::{FontSizeFormat, NAME_SIZE};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_SIZE, Arc::new(FontSizeFormat::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn size(val: &str) -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert("size", val);
+    attr
+}
+
+#[test]
+fn detect_size_format() -> Result<()> {
+    let format = FontSizeFormat::new();
+    assert_eq!(format.format_name(), NAME_SIZE);
+    assert!(format.applies(&DeltaOperation::insert_attr("big", size("huge")))?);
+    assert!(format.applies(&DeltaOperation::insert_attr("px", size("12px")))?);
+
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    attr.insert("italic", true);
+    assert!(!format.applies(&DeltaOperation::insert_attr("bold", attr))?);
+    assert!(!format.applies(&DeltaOperation::insert("plain"))?);
+
+    let mut attr = Attributes::default();
+    attr.insert("size", AttrVal::Null);
+    assert!(!format.applies(&DeltaOperation::insert_attr("null", attr))?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn size_create_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("size_create_test");
+    doc.open()?;
+
+    let mut points = Attributes::default();
+    points.insert("size", 8);
+    let mut bold = size("large");
+    bold.insert("bold", true);
+
+    let mut delta = Delta::default();
+    delta.insert_attr("print", points);
+    delta.insert_attr("12px", size("12px"));
+    delta.insert_attr("bold", bold);
+    delta.insert("\n");
+    doc.apply_delta(delta.clone())?;
+
+    let html_txt = r##"<p><span style="font-size:8pt;">print</span><span style="font-size:12px;">12px</span><strong style="font-size:1.5em;">bold</strong></p><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+
+    delta.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn size_retain_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("size_retain_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("plain ");
+    delta.insert_attr("big", size("huge"));
+    delta.insert_attr("small", size("small"));
+    delta.insert(" end\n");
+    doc.apply_delta(delta)?;
+
+    //different sizes do not merge
+    let html_txt = r##"<p>plain <span style="font-size:2.5em;">big</span><span style="font-size:0.75em;">small</span> end</p><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    assert_eq!(doc.get_root().get_child(0).unwrap().child_count(), 4);
+
+    //without a size the word merges with the plain text
+    let mut attr = Attributes::default();
+    attr.insert("size", AttrVal::Null);
+    let mut delta = Delta::default();
+    delta.retain(6);
+    delta.retain_attr(3, attr);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let html_txt =
+        r##"<p>plain big<span style="font-size:0.75em;">small</span> end</p><p><br></p>"##;
+    assert_eq!(doc.as_html_string(), html_txt);
+    assert_eq!(doc.get_root().get_child(0).unwrap().child_count(), 3);
+
+    let mut expect = Delta::default();
+    expect.insert("plain big");
+    expect.insert_attr("small", size("small"));
+    expect.insert(" end\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
@@ -77,6 +77,10 @@ impl FormatTait for SoftBreak {
         true
     }
 
+    fn is_void(&self) -> bool {
+        true
+    }
+
     fn block_remove_attr(&self) -> Attributes {
         let mut attr = Attributes::default();
         attr.insert(BREAK, Null);
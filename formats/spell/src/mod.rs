@@ -0,0 +1,27 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+/// # SpellChecker
+///
+/// Interface to a spell checker, like a dictionary lookup or a remote service.
+/// The editor calls the spell checker with the text of one text node at a time.
+pub trait SpellChecker {
+    /// Returns the byte ranges `(start, end)` of the misspelled words in the text.
+    /// The end is exclusive, and both shall be on a character boundary.
+    fn check(&self, text: &str) -> Vec<(usize, usize)>;
+}
+
+/// # SpellingError
+///
+/// A misspelled word in the document, with the retain index of its first character, and
+/// the retain index just behind its last character.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpellingError {
+    pub retain_start: usize,
+    pub retain_end: usize,
+    pub word: String,
+}
@@ -0,0 +1,162 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::util::node_morph::delete_node;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use dom::dom_element::DomElement;
+use node_tree::cursor::Cursor;
+use node_tree::doc_node::DocumentNode;
+use node_tree::format_trait::FormatTait;
+use std::sync::Arc;
+
+pub static NAME_TEMPLATE_VAR: &'static str = "template-var"; //registry label
+
+static TEMPLATE_VAR_TAG: &'static str = "SPAN"; //html tag
+static TEMPLATE_VAR_CLASS: &'static str = "ql-template-var"; //CSS class
+
+/// # TemplateVarFormat
+///
+/// A template variable is a non editable placeholder, like a computed field:
+/// ```bash
+/// {
+///   insert: { template-var: 'user.name' }
+/// }
+/// ```
+///
+/// Results in:
+/// ```bash
+/// <span contenteditable="false" class="ql-template-var">{{user.name}}</span>
+/// ```
+///
+/// The delta format gives every embed the length 1. So like an image the template variable
+/// is one unit for the cursor: the cursor jumps over it, and a delete removes it as a whole.
+/// Note that the length is not the length of the shown text `{{user.name}}`.
+pub struct TemplateVarFormat {}
+
+impl TemplateVarFormat {
+    pub fn new() -> Self {
+        TemplateVarFormat {}
+    }
+}
+
+impl FormatTait for TemplateVarFormat {
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let name = operation
+            .insert_value()
+            .map_val()?
+            .get(NAME_TEMPLATE_VAR)
+            .unwrap()
+            .str_val()?
+            .to_string();
+
+        let dom_el = DomElement::new(TEMPLATE_VAR_TAG);
+        dom_el.set_attribute("contenteditable", "false");
+        dom_el.set_class(TEMPLATE_VAR_CLASS);
+        dom_el
+            .element()
+            .set_text_content(Some(&["{{", &name, "}}"].concat()));
+
+        let doc_node = DocumentNode::new_element(dom_el, formatter);
+        doc_node.set_operation(operation);
+        Ok(Arc::new(doc_node))
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_TEMPLATE_VAR
+    }
+
+    fn is_text_format(&self) -> bool {
+        true
+    }
+
+    fn is_void(&self) -> bool {
+        true
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        panic!("Hey you called TemplateVarFormat::block_remove_attr() on the TextFormatter format-trait implementation.");
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        let val = delta.insert_value();
+        if val.is_map() {
+            if let Some(av) = val.map_val()?.get(NAME_TEMPLATE_VAR) {
+                return Ok(av.is_string());
+            }
+        }
+        Ok(false)
+    }
+
+    //A template variable has no attributes
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        _attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn split_leaf(&self, _cursor: &Cursor) -> Result<()> {
+        //we have length 1 (always) so it can not be split
+        panic!("TemplateVarFormat::split_leaf() - Error.");
+    }
+
+    fn is_same_format(&self, _left: &Arc<DocumentNode>, _right: &Arc<DocumentNode>) -> bool {
+        false //every variable is a unit of its own
+    }
+
+    fn block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+        _delta: DeltaOperation,
+        _new_transducer: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("TemplateVarFormat::block_transform() - Error.");
+    }
+
+    fn un_block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("TemplateVarFormat::un_block_transform() - Error.");
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        _doc_node: &Arc<DocumentNode>,
+        _at: usize,
+        _length: usize,
+    ) -> Result<()> {
+        panic!("TemplateVarFormat::Delete() - Variable has length 1, so use the other delete function...");
+    }
+
+    //Deletes the variable as a whole
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn try_merge(&self, _cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        Ok(())
+    }
+}
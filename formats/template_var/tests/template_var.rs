@@ -0,0 +1,109 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::{DeltaOperation, OpsMap};
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use template_var::{TemplateVarFormat, NAME_TEMPLATE_VAR};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_TEMPLATE_VAR, Arc::new(TemplateVarFormat::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn template_var(name: &str) -> DeltaOperation {
+    let mut var = OpsMap::default();
+    var.insert(NAME_TEMPLATE_VAR, name);
+    DeltaOperation::insert_attr(var, Attributes::default())
+}
+
+/// Creates the document `Hello {{user.name}}!`
+fn create_test_var(doc: &mut DocumentRoot) -> Result<()> {
+    let mut delta = Delta::default();
+    delta.insert("Hello ");
+    delta.push(template_var("user.name"));
+    delta.insert("!");
+
+    doc.open()?;
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<p>Hello <span contenteditable="false" class="ql-template-var">{{user.name}}</span>!</p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn template_var_create_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("template_var_create_test");
+    create_test_var(&mut doc)?;
+
+    let ops = doc.to_delta().get_ops();
+    assert_eq!(ops[1], template_var("user.name"));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn template_var_cursor_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("template_var_cursor_test");
+    create_test_var(&mut doc)?;
+
+    //the variable has length 1, so we insert behind it
+    set_cursor_selection(doc.get_cursor(), 7, 0);
+    doc.apply_operation(DeltaOperation::insert(","))?;
+    let expect = r#"<p>Hello <span contenteditable="false" class="ql-template-var">{{user.name}}</span>,!</p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    assert_eq!(doc.get_cursor().get_retain_index(), 8);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn template_var_delete_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("template_var_delete_test");
+    create_test_var(&mut doc)?;
+
+    set_cursor_selection(doc.get_cursor(), 6, 0);
+    doc.apply_operation(DeltaOperation::delete(1))?;
+    assert_eq!(doc.as_html_string(), "<p>Hello !</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn get_template_vars_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("get_template_vars_test");
+    doc.open()?;
+    assert!(doc.get_template_vars().is_empty());
+
+    let mut delta = Delta::default();
+    delta.push(template_var("user.name"));
+    delta.insert(" and ");
+    delta.push(template_var("user.email"));
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.get_template_vars(),
+        vec![("user.name".to_string(), 0), ("user.email".to_string(), 6)]
+    );
+    Ok(())
+}
@@ -0,0 +1,235 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::Result;
+use core_formats::util::lookup::{AttributesLookup, Attributor};
+use core_formats::util::node_morph::delete_node;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use dom::dom_element::DomElement;
+use node_tree::cursor::Cursor;
+use node_tree::doc_node::DocumentNode;
+use node_tree::format_trait::FormatTait;
+use once_cell::sync::OnceCell;
+use std::sync::Arc;
+
+pub static NAME_VIDEO: &'static str = "video"; //registry label
+
+static VIDEO_TAG: &'static str = "VIDEO"; //html tag of a direct video link
+static IFRAME_TAG: &'static str = "IFRAME"; //html tag of a video platform player
+static VIDEO_CLASS: &'static str = "ql-video"; //CSS class
+
+//Hosts of which we show the video in the player of the platform
+static PLAYER_HOSTS: [&'static str; 4] =
+    ["youtube.com", "youtu.be", "vimeo.com", "player.vimeo.com"];
+
+static ATTRIBUTES: OnceCell<AttributesLookup> = OnceCell::new();
+pub(crate) fn initialise() {
+    if let Some(_attr) = ATTRIBUTES.get() {
+        return;
+    }
+    let mut attr = AttributesLookup::new(2);
+    attr.fill_one("width", "width");
+    attr.fill_one("height", "height");
+    ATTRIBUTES
+        .set(attr)
+        .expect("did you call video::initialise() twice?");
+}
+
+/// # VideoFormat
+///
+/// Insert an embedded video:
+/// ```bash
+/// {
+///   insert: { video: 'https://www.youtube.com/embed/dQw4w9WgXcQ' },
+///   attributes: { width: 560, height: 315 }
+/// }
+/// ```
+///
+/// Results in:
+/// ```bash
+/// <iframe frameborder="0" allowfullscreen="true" class="ql-video"
+///     src="https://www.youtube.com/embed/dQw4w9WgXcQ" width="560" height="315"></iframe>
+/// ```
+///
+/// A YouTube or Vimeo URL is shown in the player of the platform, an `<IFRAME>`. Any other URL
+/// is a direct link to a video file, which is shown in a `<VIDEO>` element with controls.
+///
+/// Like an image the video is an embed with the length 1. Videos are never merged.
+pub struct VideoFormat {}
+
+impl VideoFormat {
+    pub fn new() -> Self {
+        initialise();
+        VideoFormat {}
+    }
+}
+
+/// Returns true when the URL is a video on a platform with its own player
+fn is_player_url(url: &str) -> bool {
+    let host = url
+        .split("://")
+        .last()
+        .unwrap_or(url)
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or("");
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    PLAYER_HOSTS.iter().any(|&h| h == host)
+}
+
+/// Sets the HTML attributes; a `null` value removes the attribute
+fn set_attributes(dom_el: &DomElement, attr: &Attributes) -> Result<()> {
+    for (k, v) in Attributor::selected(attr, ATTRIBUTES.get().unwrap()) {
+        match v {
+            AttrVal::Null => dom_el.remove_attribute(k),
+            AttrVal::String(s) => dom_el.set_attribute(k, s),
+            v => dom_el.set_attribute(k, &v.number_val()?.to_string()),
+        }
+    }
+    Ok(())
+}
+
+impl FormatTait for VideoFormat {
+    fn create(
+        &self,
+        operation: DeltaOperation,
+        formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        let url = operation
+            .insert_value()
+            .map_val()?
+            .get(NAME_VIDEO)
+            .unwrap()
+            .str_val()?
+            .to_string();
+
+        let dom_el = if is_player_url(&url) {
+            let dom_el = DomElement::new(IFRAME_TAG);
+            dom_el.set_attribute("frameborder", "0");
+            dom_el.set_attribute("allowfullscreen", "true");
+            dom_el
+        } else {
+            let dom_el = DomElement::new(VIDEO_TAG);
+            dom_el.set_attribute("controls", "true");
+            dom_el
+        };
+        dom_el.set_class(VIDEO_CLASS);
+        dom_el.set_attribute("src", &url);
+        set_attributes(&dom_el, operation.get_attributes())?;
+
+        let doc_node = DocumentNode::new_element(dom_el, formatter);
+        doc_node.set_operation(operation);
+        Ok(Arc::new(doc_node))
+    }
+
+    fn format_name(&self) -> &'static str {
+        NAME_VIDEO
+    }
+
+    fn is_text_format(&self) -> bool {
+        true
+    }
+
+    fn is_void(&self) -> bool {
+        true
+    }
+
+    fn block_remove_attr(&self) -> Attributes {
+        panic!("Hey you called VideoFormat::block_remove_attr() on the TextFormatter format-trait implementation.");
+    }
+
+    fn applies(&self, delta: &DeltaOperation) -> Result<bool> {
+        let val = delta.insert_value();
+        if val.is_map() {
+            if let Some(av) = val.map_val()?.get(NAME_VIDEO) {
+                return Ok(av.is_string());
+            }
+        }
+        Ok(false)
+    }
+
+    /// Unlike the image, we keep the attributes in the operation too. So that a retain
+    /// shows up in `to_delta()`.
+    fn apply_line_attributes(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+        attr: &Attributes,
+        _formatter: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        set_attributes(doc_node.get_dom_element().unwrap(), attr)?;
+        let operation = DeltaOperation::insert_attr(
+            doc_node.get_operation().insert_value().clone(),
+            attr.clone(),
+        );
+        doc_node.set_operation(operation);
+        Ok(doc_node.clone())
+    }
+
+    fn drop_line_attributes(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        let dom_el = doc_node.get_dom_element().unwrap();
+        for key in Attributor::all_key(ATTRIBUTES.get().unwrap()) {
+            dom_el.remove_attribute(key);
+        }
+        let operation = DeltaOperation::insert_attr(
+            doc_node.get_operation().insert_value().clone(),
+            Attributes::default(),
+        );
+        doc_node.set_operation(operation);
+        Ok(doc_node.clone())
+    }
+
+    fn split_leaf(&self, _cursor: &Cursor) -> Result<()> {
+        //we have length 1 (always) so it can not be split
+        panic!("VideoFormat::split_leaf() - Error.");
+    }
+
+    fn is_same_format(&self, _left: &Arc<DocumentNode>, _right: &Arc<DocumentNode>) -> bool {
+        false //never merge 2 videos
+    }
+
+    fn block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+        _delta: DeltaOperation,
+        _new_transducer: Arc<dyn FormatTait + Send + Sync>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("VideoFormat::block_transform() - Error.");
+    }
+
+    fn un_block_transform(
+        &self,
+        _cursor: &Cursor,
+        _block_node: &Arc<DocumentNode>,
+    ) -> Result<Arc<DocumentNode>> {
+        panic!("VideoFormat::un_block_transform() - Error.");
+    }
+
+    fn delete_leaf_segment(
+        &self,
+        _doc_node: &Arc<DocumentNode>,
+        _at: usize,
+        _length: usize,
+    ) -> Result<()> {
+        panic!("VideoFormat::Delete() - Video has length 1, so use the other delete function...");
+    }
+
+    //Deletes the video as a whole
+    fn delete_node(&self, doc_node: &Arc<DocumentNode>) {
+        delete_node(doc_node);
+    }
+
+    fn isolate(&self, doc_node: &Arc<DocumentNode>) -> Result<Arc<DocumentNode>> {
+        Ok(doc_node.clone())
+    }
+
+    fn try_merge(&self, _cursor: &Cursor, _block_node: &Arc<DocumentNode>) -> Result<()> {
+        Ok(())
+    }
+}
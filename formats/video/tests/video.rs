@@ -0,0 +1,161 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::{DeltaOperation, OpsMap};
+use delta::types::attr_val::AttrVal;
+use node_tree::format_trait::FormatTait;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use video::{VideoFormat, NAME_VIDEO};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry registers only the BASIC formats required for testing in this module
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_VIDEO, Arc::new(VideoFormat::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn video(url: &str, attr: Attributes) -> DeltaOperation {
+    let mut video = OpsMap::default();
+    video.insert(NAME_VIDEO, url);
+    DeltaOperation::insert_attr(video, attr)
+}
+
+fn size(width: &str, height: &str) -> Attributes {
+    let mut attr = Attributes::default();
+    attr.insert("width", width);
+    attr.insert("height", height);
+    attr
+}
+
+#[test]
+fn detect_video_format() -> Result<()> {
+    let format = VideoFormat::new();
+    assert_eq!(format.format_name(), NAME_VIDEO);
+    assert!(format.applies(&video("movie.mp4", Attributes::default()))?);
+    assert!(!format.applies(&DeltaOperation::insert("movie.mp4"))?);
+
+    let mut img = OpsMap::default();
+    img.insert("image", "image-source.png");
+    assert!(!format.applies(&DeltaOperation::insert_attr(img, Attributes::default()))?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn video_create_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("video_create_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.push(video(
+        "https://www.youtube.com/embed/abc",
+        size("560", "315"),
+    ));
+    delta.push(video(
+        "https://example.com/movie.mp4",
+        Attributes::default(),
+    ));
+    delta.insert("\n");
+    doc.apply_delta(delta.clone())?;
+
+    let expect = r#"<p><iframe frameborder="0" allowfullscreen="true" class="ql-video" src="https://www.youtube.com/embed/abc" width="560" height="315"></iframe><video controls="true" class="ql-video" src="https://example.com/movie.mp4"></video></p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    let p = doc.get_root().get_child(0).unwrap();
+    assert_eq!(p.child_count(), 2); //videos do not merge
+    assert_eq!(p.get_child(0).unwrap().op_len(), 1);
+
+    delta.insert("\n");
+    assert_eq!(doc.to_delta().get_ops(), delta.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn video_vimeo_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("video_vimeo_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.push(video(
+        "https://player.vimeo.com/video/123",
+        Attributes::default(),
+    ));
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<p><iframe frameborder="0" allowfullscreen="true" class="ql-video" src="https://player.vimeo.com/video/123"></iframe></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn video_delete_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("video_delete_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("a");
+    delta.push(video("movie.mp4", Attributes::default()));
+    delta.insert("b\n");
+    doc.apply_delta(delta)?;
+
+    set_cursor_selection(doc.get_cursor(), 1, 0);
+    doc.apply_operation(DeltaOperation::delete(1))?;
+    assert_eq!(doc.as_html_string(), "<p>ab</p><p><br></p>");
+
+    let mut expect = Delta::default();
+    expect.insert("ab\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn video_retain_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("video_retain_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.push(video("movie.mp4", size("560", "315")));
+    delta.insert("\n");
+    doc.apply_delta(delta)?;
+
+    //change the width, and remove the height
+    let mut attr = Attributes::default();
+    attr.insert("width", "640");
+    attr.insert("height", AttrVal::Null);
+    let mut delta = Delta::default();
+    delta.retain_attr(1, attr);
+    doc.reset_cursor();
+    doc.apply_delta(delta)?;
+
+    let expect = r#"<p><video controls="true" class="ql-video" src="movie.mp4" width="640"></video></p><p><br></p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    let mut width = Attributes::default();
+    width.insert("width", "640");
+    let mut expect = Delta::default();
+    expect.push(video("movie.mp4", width));
+    expect.insert("\n\n");
+    assert_eq!(doc.to_delta().get_ops(), expect.get_ops());
+    Ok(())
+}
@@ -8,18 +8,20 @@
 use crate::doc_node::DocumentNode;
 use crate::error::Error::{
     AdvanceBeyondEnd, BackspaceBeyondStart, NonEmptyBlockCanNotTraversePrev,
-    UnexepectedCursorPosNone,
+    RetainRangeOutOfBounds, UnexepectedCursorPosNone,
 };
 use crate::tree_traverse::{
-    get_root, is_doc_root, next_node, next_node_non_zero_length, prev_node,
+    find_by_retain_index, get_root, is_doc_root, next_node, next_node_non_zero_length, prev_node,
     prev_node_non_zero_length,
 };
 use anyhow::Result;
 use log::error;
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Debug, PartialEq, Default)]
 pub enum LocationIdentifyer {
@@ -69,11 +71,22 @@ impl CursorLocation {
 }
 
 /// no selection selected if this Cursor::end equals Location::None
-#[derive(Clone, PartialEq, Default)]
+#[derive(Clone, Default)]
 pub struct Cursor {
     retain: RefCell<usize>,
     start: RefCell<CursorLocation>,
     stop: RefCell<CursorLocation>,
+    defer_retain_update: RefCell<bool>, //see set_defer_retain_update()
+    dirty: RefCell<bool>,               //see ensure_retain_index()
+}
+
+/// Cursors are equal when they point to the same location
+impl PartialEq for Cursor {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_retain_index() == other.get_retain_index()
+            && self.start == other.start
+            && self.stop == other.stop
+    }
 }
 
 ///Implementation of collapsed cursor --> single point, no selection
@@ -84,12 +97,25 @@ impl Cursor {
             retain: RefCell::new(0),
             start: RefCell::new(CursorLocation::None),
             stop: RefCell::new(CursorLocation::None),
+            defer_retain_update: RefCell::new(false),
+            dirty: RefCell::new(false),
         }
     }
 
+    /// # reset()
+    ///
+    /// The cursor does not point to any document node anymore; retain index 0, and no selection.
+    pub fn reset(&self) {
+        *self.retain.borrow_mut() = 0;
+        *self.dirty.borrow_mut() = false;
+        *self.start.borrow_mut() = CursorLocation::None;
+        *self.stop.borrow_mut() = CursorLocation::None;
+    }
+
     /// Set() clones the position of the input cursor
     pub fn from(&self, cursor: &Cursor) {
-        *self.retain.borrow_mut() = *cursor.retain.borrow();
+        *self.retain.borrow_mut() = cursor.get_retain_index();
+        *self.dirty.borrow_mut() = false;
         *self.start.borrow_mut() = cursor.start.borrow().clone();
         *self.stop.borrow_mut() = cursor.stop.borrow().clone();
     }
@@ -148,8 +174,7 @@ impl Cursor {
             panic!("Setting cursor after a block node that is the last in the document --> seems wrong");
         }
 
-        //FIXME: Very expensive in case we find the end of document, is there a smarter way?
-        *self.retain.borrow_mut() = self.calculate_retain_index();
+        self.update_retain_index();
         //error!( "set_cursor_to_doc_node_edge - END with doc_node = {}", self);
     }
 
@@ -174,8 +199,7 @@ impl Cursor {
     pub fn set_after(&self, doc_node: &Arc<DocumentNode>) {
         assert!(doc_node.get_formatter().is_text_format());
         *self.start.borrow_mut() = CursorLocation::After(doc_node.clone());
-        //FIXME: Very expensive: op_transform actions know what the index is
-        *self.retain.borrow_mut() = self.calculate_retain_index();
+        self.update_retain_index();
     }
 
     pub fn set_after_no_retain_update(&self, doc_node: &Arc<DocumentNode>) {
@@ -189,8 +213,7 @@ impl Cursor {
     pub fn set_before(&self, doc_node: &Arc<DocumentNode>) {
         assert!(doc_node.get_formatter().is_text_format());
         *self.start.borrow_mut() = CursorLocation::Before(doc_node.clone());
-        //FIXME: Very expensive: op_transform actions know what the index is
-        *self.retain.borrow_mut() = self.calculate_retain_index();
+        self.update_retain_index();
     }
 
     pub fn set_before_no_retain_update(&self, doc_node: &Arc<DocumentNode>) {
@@ -220,8 +243,7 @@ impl Cursor {
 
         //Finally we do the setting ...
         *self.start.borrow_mut() = CursorLocation::At(doc_node.clone(), index);
-        //FIXME: Very expensive: op_transform actions know what the index is
-        *self.retain.borrow_mut() = self.calculate_retain_index();
+        self.update_retain_index();
     }
 
     pub fn set_at_no_retain_update(&self, doc_node: &Arc<DocumentNode>, index: usize) {
@@ -366,11 +388,10 @@ impl Cursor {
 
     pub fn set_select_start(&self, location: CursorLocation) {
         self.start.replace(location);
-        //FIXME: Very expensive: op_transform actions know what the index is
         if *self.start.borrow() == CursorLocation::None {
-            *self.retain.borrow_mut() = 0;
+            self.set_retain_index(0);
         } else {
-            *self.retain.borrow_mut() = self.calculate_retain_index();
+            self.update_retain_index();
         }
     }
 
@@ -385,15 +406,100 @@ impl Cursor {
     pub fn get_select_stop(&self) -> CursorLocation {
         self.stop.borrow().clone()
     }
+
+    /// # set_selection_by_retain_range()
+    ///
+    /// Selects the retain range `[start, end)` of the document the cursor points to.
+    /// When `start == end` the cursor is collapsed at `start`.
+    ///
+    /// The start must be before the end of the document, since there is no cursor location
+    /// after the last new line. The end may be the end of the document.
+    ///
+    /// Returns an error if the cursor does not point to a document, or when the range
+    /// is not in the document.
+    pub fn set_selection_by_retain_range(&self, start: usize, end: usize) -> Result<()> {
+        if !self.valid() {
+            return Err(UnexepectedCursorPosNone.into());
+        }
+        if start > end {
+            return Err(RetainRangeOutOfBounds { start, end }.into());
+        }
+        let root = get_root(&self.get_doc_node());
+        let (start_node, start_offset) =
+            find_by_retain_index(&root, start).ok_or(RetainRangeOutOfBounds { start, end })?;
+        if !start_node.get_formatter().is_text_format() && start_offset > 0 {
+            //the end of the document
+            return Err(RetainRangeOutOfBounds { start, end }.into());
+        }
+        let stop = if start == end {
+            CursorLocation::None
+        } else {
+            let (dn, offset) =
+                find_by_retain_index(&root, end).ok_or(RetainRangeOutOfBounds { start, end })?;
+            retain_location(dn, offset)
+        };
+
+        self.set_select_start(retain_location(start_node, start_offset));
+        self.set_retain_index(start);
+        self.set_select_stop(stop);
+        Ok(())
+    }
+}
+
+/// Returns the cursor location for the node, and offset, of `find_by_retain_index()`
+pub(crate) fn retain_location(doc_node: Arc<DocumentNode>, offset: usize) -> CursorLocation {
+    if !doc_node.get_formatter().is_text_format() {
+        //an empty block, or the end of the document
+        CursorLocation::At(doc_node, offset)
+    } else if offset == 0 {
+        CursorLocation::Before(doc_node)
+    } else if offset == doc_node.op_len() {
+        CursorLocation::After(doc_node)
+    } else {
+        CursorLocation::At(doc_node, offset)
+    }
 }
 
 impl Cursor {
     pub fn get_retain_index(&self) -> usize {
+        self.ensure_retain_index();
         *self.retain.borrow()
     }
 
     pub fn set_retain_index(&self, index: usize) {
         *self.retain.borrow_mut() = index;
+        *self.dirty.borrow_mut() = false;
+    }
+
+    /// # set_defer_retain_update()
+    ///
+    /// While deferred, setting the cursor location with `set_at()`, `set_before()`,
+    /// `set_after()`, `set_cursor_to_doc_node_edge()` or `set_select_start()` keeps the
+    /// retain index as it is. The caller keeps track of the retain index, or calculates it
+    /// after the deferral ends.
+    ///
+    /// Calculating the retain index walks the document up to the cursor, which is expensive
+    /// when applying many operations.
+    pub fn set_defer_retain_update(&self, defer: bool) {
+        *self.defer_retain_update.borrow_mut() = defer;
+    }
+
+    /// Marks the retain index to be calculated on the next `get_retain_index()`. Moving the
+    /// cursor many times, without asking for the retain index, is then cheap.
+    fn update_retain_index(&self) {
+        if !*self.defer_retain_update.borrow() {
+            *self.dirty.borrow_mut() = true;
+        }
+    }
+
+    /// # ensure_retain_index()
+    ///
+    /// Calculates the retain index, if the cursor moved since the last calculation.
+    fn ensure_retain_index(&self) {
+        if *self.dirty.borrow() {
+            let retain = self.calculate_retain_index();
+            self.set_retain_index(retain);
+        }
     }
 
     /// # calculate_retain_index()
@@ -413,20 +519,52 @@ impl Cursor {
                 panic!("calculate_retain_index(): cursor position is NONE")
             }
         };
-        assert!(!is_doc_root(&doc_node)); //we do not accept root as input
-
-        let mut retain = r;
-        let mut dn_o = prev_node(&doc_node);
+        r + doc_node.retain_index()
+    }
+}
 
-        loop {
-            if let Some(doc_node) = dn_o {
-                assert!(!is_doc_root(&doc_node));
-                retain += doc_node.op_len();
-                dn_o = prev_node(&doc_node);
-            } else {
-                //We stop when reaching doc_root. I that case prev_node() should return None
-                return retain;
+/// Cursors are ordered by document position, i.e. by their retain index.
+///
+/// Two cursors at the same retain index, but pointing to different locations (for example
+/// `After(a)` and `Before(b)` where `b` follows `a`), are not equal. To stay consistent with
+/// `PartialEq` these cursors have no ordering, and `partial_cmp()` returns `None`.
+impl PartialOrd for Cursor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.get_retain_index().cmp(&other.get_retain_index()) {
+            Ordering::Equal => {
+                if self == other {
+                    Some(Ordering::Equal)
+                } else {
+                    None
+                }
             }
+            ord => Some(ord),
+        }
+    }
+}
+
+impl Cursor {
+    /// # min()
+    ///
+    /// Returns a copy of the cursor which comes first in the document.
+    /// If both cursors have the same retain index, a copy of `self` is returned.
+    pub fn min(&self, other: &Cursor) -> Cursor {
+        if other.get_retain_index() < self.get_retain_index() {
+            other.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// # max()
+    ///
+    /// Returns a copy of the cursor which comes last in the document.
+    /// If both cursors have the same retain index, a copy of `self` is returned.
+    pub fn max(&self, other: &Cursor) -> Cursor {
+        if other.get_retain_index() > self.get_retain_index() {
+            other.clone()
+        } else {
+            self.clone()
         }
     }
 }
@@ -435,13 +573,19 @@ impl Cursor {
     /// # advance()
     ///
     /// Advances the cursor by just 1 character. No delete of any character
+    ///
+    /// A character is a grapheme cluster, so the retain index advances by the number of
+    /// unicode scalar values of the grapheme cluster, see `grapheme_len_after()`.
     pub fn advance(&self) -> Result<()> {
+        self.ensure_retain_index();
         let loc = self.start.borrow_mut().deref().clone();
+        let mut step = 1;
         match loc {
             CursorLocation::At(doc_node, index) => {
                 if doc_node.is_text() {
-                    if index + 1 < doc_node.op_len() {
-                        self.set_at_no_retain_update(&doc_node, index + 1);
+                    step = grapheme_len_after(&doc_node, index);
+                    if index + step < doc_node.op_len() {
+                        self.set_at_no_retain_update(&doc_node, index + step);
                     } else {
                         self.set_after_no_retain_update(&doc_node);
                     }
@@ -466,7 +610,8 @@ impl Cursor {
                 assert!(doc_node.is_text());
                 if let Some(next) = next_node_non_zero_length(&doc_node) {
                     if next.is_text() {
-                        self.set_at_no_retain_update(&next, 1);
+                        step = grapheme_len_after(&next, 0);
+                        self.set_at_no_retain_update(&next, step);
                     } else if let Some(n_next) = next_node_non_zero_length(&next) {
                         if n_next.is_text() {
                             self.set_before_no_retain_update(&n_next);
@@ -480,14 +625,15 @@ impl Cursor {
             }
             CursorLocation::Before(doc_node) => {
                 assert!(doc_node.is_text());
-                self.set_at_no_retain_update(&doc_node, 1);
+                step = grapheme_len_after(&doc_node, 0);
+                self.set_at_no_retain_update(&doc_node, step);
             }
             CursorLocation::None => {
                 return Err(UnexepectedCursorPosNone.into());
             }
         };
 
-        let retain = *self.retain.borrow() + 1;
+        let retain = *self.retain.borrow() + step;
         *self.retain.borrow_mut() = retain;
         Ok(())
     }
@@ -495,15 +641,20 @@ impl Cursor {
     /// # backspace()
     ///
     /// Moves the cursor back by just 1 character. No deletion of any character!!
+    ///
+    /// A character is a grapheme cluster, like in `advance()`.
     pub fn backspace(&self) -> Result<()> {
+        self.ensure_retain_index();
         let loc = self.start.borrow_mut().deref().clone();
+        let mut step = 1;
         match loc {
             CursorLocation::At(doc_node, index) => {
                 if doc_node.is_text() {
-                    if index > 1 {
-                        self.set_at_no_retain_update(&doc_node, index - 1);
+                    step = grapheme_len_before(&doc_node, index);
+                    if index > step {
+                        self.set_at_no_retain_update(&doc_node, index - step);
                     } else {
-                        assert_eq!(index, 1); //if the index = 0, we should point "before" the node
+                        assert_eq!(index, step); //if the index = 0, we should point "before" the node
                         self.set_before_no_retain_update(&doc_node);
                     }
                 } else {
@@ -530,7 +681,8 @@ impl Cursor {
                 assert!(doc_node.get_formatter().is_text_format());
                 if let Some(prev) = prev_node_non_zero_length(&doc_node) {
                     if prev.is_text() {
-                        self.set_at_no_retain_update(&prev, prev.op_len() - 1);
+                        step = grapheme_len_before(&prev, prev.op_len());
+                        self.set_at_no_retain_update(&prev, prev.op_len() - step);
                     } else if let Some(p_prev) = prev_node_non_zero_length(&prev) {
                         if p_prev.is_text() {
                             self.set_after_no_retain_update(&p_prev);
@@ -546,8 +698,9 @@ impl Cursor {
             CursorLocation::After(doc_node) => {
                 assert!(doc_node.get_formatter().is_text_format());
                 let len = doc_node.op_len();
-                if len > 1 {
-                    self.set_at_no_retain_update(&doc_node, doc_node.op_len() - 1);
+                step = grapheme_len_before(&doc_node, len);
+                if len > step {
+                    self.set_at_no_retain_update(&doc_node, len - step);
                 } else {
                     self.set_before_no_retain_update(&doc_node);
                 }
@@ -558,8 +711,8 @@ impl Cursor {
         };
 
         let or = *self.retain.borrow();
-        if or > 0 {
-            let retain = or - 1;
+        if or >= step {
+            let retain = or - step;
             *self.retain.borrow_mut() = retain;
         } else {
             // given the above we should NEVER get here !
@@ -567,6 +720,185 @@ impl Cursor {
         }
         Ok(())
     }
+
+    /// # word_forward()
+    ///
+    /// Moves the cursor to the end of the word at, or after, the cursor. A word is a sequence
+    /// of alphanumeric, or ASCII punctuation, characters. So in the middle of a word we move
+    /// to the end of that word, and in white space we move to the end of the next word.
+    ///
+    /// At the end of a block we move to the start of the next block, and an embed (image,
+    /// video, ...) is skipped as a single step. At the end of the document nothing changes.
+    pub fn word_forward(&self) -> Result<()> {
+        match self.kind_at_cursor() {
+            CharKind::End => return Ok(()),
+            CharKind::Block | CharKind::Embed => return self.advance(),
+            _ => {}
+        }
+        while self.kind_at_cursor() == CharKind::Space {
+            self.advance()?;
+        }
+        while self.kind_at_cursor() == CharKind::Word {
+            self.advance()?;
+        }
+        Ok(())
+    }
+
+    /// # word_backward()
+    ///
+    /// Moves the cursor to the start of the word before the cursor, like `word_forward()`
+    /// in the other direction.
+    pub fn word_backward(&self) -> Result<()> {
+        match self.kind_before_cursor() {
+            CharKind::End => return Ok(()),
+            CharKind::Block | CharKind::Embed => return self.backspace(),
+            _ => {}
+        }
+        while self.kind_before_cursor() == CharKind::Space {
+            self.backspace()?;
+        }
+        while self.kind_before_cursor() == CharKind::Word {
+            self.backspace()?;
+        }
+        Ok(())
+    }
+
+    /// # line_start()
+    ///
+    /// Moves the cursor before the first leaf of the current block, like the `Home` key.
+    ///
+    /// A line is the content of one block node, so a `<P>`, a list item, or a line of a
+    /// code block. The wrapping nodes without length, like the `<UL>` or the `<PRE>`, are
+    /// skipped. When the cursor is already at the start of the block, or the block is empty,
+    /// nothing changes.
+    pub fn line_start(&self) -> Result<()> {
+        if !self.valid() {
+            return Err(UnexepectedCursorPosNone.into());
+        }
+        let doc_node = self.get_doc_node();
+        let mut first = if doc_node.is_text() && doc_node.op_len() > 0 {
+            Some(doc_node.clone())
+        } else {
+            None
+        };
+        let mut cur = doc_node;
+        while let Some(prev) = prev_node(&cur) {
+            if prev.op_len() > 0 {
+                if !prev.is_text() {
+                    break; //the previous line
+                }
+                first = Some(prev.clone());
+            }
+            cur = prev;
+        }
+        if let Some(first) = first {
+            if self.get_location() != CursorLocation::Before(first.clone()) {
+                self.set_before(&first);
+            }
+        }
+        Ok(())
+    }
+
+    /// # line_end()
+    ///
+    /// Moves the cursor after the last leaf of the current block, like the `End` key.
+    ///
+    /// See `line_start()` for what a line is. When the cursor is already at the end of the
+    /// block, or the block is empty, nothing changes.
+    pub fn line_end(&self) -> Result<()> {
+        if !self.valid() {
+            return Err(UnexepectedCursorPosNone.into());
+        }
+        let mut block = self.get_doc_node();
+        while block.is_text() || block.op_len() == 0 {
+            match next_node(&block) {
+                Some(next) => block = next,
+                None => return Ok(()),
+            }
+        }
+        if block.child_count() == 0 {
+            return Ok(()); //empty block
+        }
+        if let Some(last) = prev_node_non_zero_length(&block) {
+            if last.is_text() && self.get_location() != CursorLocation::After(last.clone()) {
+                self.set_after(&last);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the kind of the character right after the cursor
+    fn kind_at_cursor(&self) -> CharKind {
+        match self.get_location() {
+            CursorLocation::At(doc_node, index) if doc_node.is_text() => {
+                text_kind(&doc_node, index)
+            }
+            CursorLocation::At(doc_node, _index) => block_kind(&doc_node),
+            CursorLocation::Before(doc_node) => text_kind(&doc_node, 0),
+            CursorLocation::After(doc_node) => match next_node_non_zero_length(&doc_node) {
+                Some(next) if next.is_text() => text_kind(&next, 0),
+                Some(next) => block_kind(&next),
+                None => CharKind::End,
+            },
+            CursorLocation::None => CharKind::End,
+        }
+    }
+
+    /// Returns the kind of the character right before the cursor
+    fn kind_before_cursor(&self) -> CharKind {
+        let doc_node = match self.get_location() {
+            CursorLocation::At(doc_node, index) if doc_node.is_text() => {
+                return text_kind(&doc_node, index - grapheme_len_before(&doc_node, index));
+            }
+            CursorLocation::After(doc_node) => {
+                let len = doc_node.op_len();
+                return text_kind(&doc_node, len - grapheme_len_before(&doc_node, len));
+            }
+            CursorLocation::At(doc_node, _index) => doc_node,
+            CursorLocation::Before(doc_node) => doc_node,
+            CursorLocation::None => return CharKind::End,
+        };
+        match prev_node_non_zero_length(&doc_node) {
+            Some(prev) if prev.is_text() => {
+                let len = prev.op_len();
+                text_kind(&prev, len - grapheme_len_before(&prev, len))
+            }
+            Some(_prev) => CharKind::Block,
+            None => CharKind::End,
+        }
+    }
+}
+
+/// Kind of character, used for moving the cursor by words
+#[derive(Debug, PartialEq)]
+enum CharKind {
+    Word,
+    Space,
+    Block, //the new line of a block
+    Embed,
+    End, //the start, or end, of the document
+}
+
+/// Returns the kind of character `index` of the text node
+fn text_kind(doc_node: &DocumentNode, index: usize) -> CharKind {
+    let op = doc_node.get_operation();
+    match op.insert_value().str_val() {
+        Ok(s) => match s.chars().nth(index) {
+            Some(c) if c.is_alphanumeric() || c.is_ascii_punctuation() => CharKind::Word,
+            Some(_c) => CharKind::Space,
+            None => CharKind::End,
+        },
+        Err(_) => CharKind::Embed,
+    }
+}
+
+/// Returns the kind of the new line of the block node. There is no cursor location after
+/// the new line of the last block, so that new line is the end of the document.
+fn block_kind(doc_node: &Arc<DocumentNode>) -> CharKind {
+    match next_node_non_zero_length(doc_node) {
+        Some(_next) => CharKind::Block,
+        None => CharKind::End,
+    }
 }
 
 /// Display implementation for the cursor location. This is intended for debugging only.
@@ -642,6 +974,48 @@ impl Display for Cursor {
     }
 }
 
+/// # grapheme_len_after()
+///
+/// Returns the number of unicode scalar values from character `index` of the text node up
+/// to the end of the grapheme cluster at `index`. Embeds have length 1.
+fn grapheme_len_after(doc_node: &DocumentNode, index: usize) -> usize {
+    let mut pos = 0;
+    for len in grapheme_lengths(doc_node) {
+        if index < pos + len {
+            return pos + len - index;
+        }
+        pos += len;
+    }
+    1
+}
+
+/// # grapheme_len_before()
+///
+/// Returns the number of unicode scalar values from the start of the grapheme cluster
+/// before character `index` of the text node, up to `index`. Embeds have length 1.
+fn grapheme_len_before(doc_node: &DocumentNode, index: usize) -> usize {
+    let mut pos = 0;
+    for len in grapheme_lengths(doc_node) {
+        if index <= pos + len {
+            return (index - pos).max(1);
+        }
+        pos += len;
+    }
+    1
+}
+
+/// Returns the number of unicode scalar values of each grapheme cluster of the text node
+fn grapheme_lengths(doc_node: &DocumentNode) -> Vec<usize> {
+    let op = doc_node.get_operation();
+    if !op.insert_value().is_string() {
+        return Vec::new();
+    }
+    match op.insert_value().str_val() {
+        Ok(s) => s.graphemes(true).map(|g| g.chars().count()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
 /// # cursor_points_to()
 ///
 /// Returns the character to which the cursor points.
@@ -7,11 +7,12 @@
 
 use crate::dom_doc_node::DomDocNode;
 use crate::format_trait::FormatTait;
+use crate::tree_traverse::{is_doc_root, prev_node};
 use delta::operations::DeltaOperation;
 use dom::dom_element::DomElement;
 use dom::dom_text;
 use dom::dom_text::DomText;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::ops::Deref;
 use std::ptr;
 use std::sync::{Arc, Weak};
@@ -23,6 +24,8 @@ pub struct DocumentNode {
     delta_op: RefCell<DeltaOperation>,
     pub(crate) children: RefCell<Vec<Arc<DocumentNode>>>,
     pub(crate) parent: RefCell<Weak<DocumentNode>>,
+    /// Sentinel set by `unlink()` in debug builds, see `is_orphaned()`
+    pub(crate) orphaned: Cell<bool>,
 }
 
 //================================================================
@@ -49,6 +52,7 @@ impl DocumentNode {
             delta_op: RefCell::new(DeltaOperation::insert("")),
             children: RefCell::new(Vec::new()),
             parent: RefCell::new(Weak::new()),
+            orphaned: Cell::new(false),
         }
     }
 
@@ -62,6 +66,7 @@ impl DocumentNode {
             delta_op: RefCell::new(DeltaOperation::insert("")),
             children: RefCell::new(Vec::new()),
             parent: RefCell::new(Weak::new()),
+            orphaned: Cell::new(false),
         }
     }
 
@@ -72,6 +77,7 @@ impl DocumentNode {
             delta_op: RefCell::new(DeltaOperation::insert("")),
             children: RefCell::new(Vec::new()),
             parent: RefCell::new(Weak::new()),
+            orphaned: Cell::new(false),
         }
     }
 }
@@ -178,6 +184,33 @@ impl DocumentNode {
         self.parent.borrow().upgrade()
     }
 
+    /// # ancestors()
+    ///
+    /// Returns the parent, the parent of the parent, etc. up to, but without, the document
+    /// root. So a text node in a `<LI>` of a `<UL>` returns `[LI, UL]`.
+    ///
+    /// For a node which is not in a document tree we stop at the last node with a parent.
+    pub fn ancestors(&self) -> Vec<Arc<DocumentNode>> {
+        let mut ancestors = Vec::new();
+        let mut parent = self.get_parent();
+        while let Some(p) = parent {
+            if is_doc_root(&p) {
+                break;
+            }
+            parent = p.get_parent();
+            ancestors.push(p);
+        }
+        ancestors
+    }
+
+    /// # depth()
+    ///
+    /// Returns the number of ancestors, see `ancestors()`. A block in the document root
+    /// has depth 0.
+    pub fn depth(&self) -> usize {
+        self.ancestors().len()
+    }
+
     /// # get_children()
     ///
     /// Returns a vector of children.
@@ -231,11 +264,83 @@ impl DocumentNode {
         None
     }
 
+    /// # is_orphaned()
+    ///
+    /// Returns true if this node was removed from the tree, but still holds a reference
+    /// to its old parent. Traversing the tree from such a node follows stale pointers.
+    ///
+    /// In debug builds `unlink()` marks the node, so we detect it even when the old parent
+    /// is gone. In all builds we detect a parent which does not list this node as a child.
+    /// A new node, which was never linked, is not orphaned.
+    pub fn is_orphaned(&self) -> bool {
+        if self.orphaned.get() {
+            return true;
+        }
+        match self.get_parent() {
+            None => false,
+            Some(parent) => !parent
+                .children
+                .borrow()
+                .iter()
+                .any(|c| ptr::eq(c.deref(), self)),
+        }
+    }
+
+    /// # retain_index()
+    ///
+    /// Returns the retain index of this node, counted from the start of the document. This
+    /// equals the retain index of a cursor `Before` this node, see
+    /// `Cursor::calculate_retain_index()`, but without creating a cursor.
+    ///
+    /// For a block this is the index of its block operation, the new line.
+    ///
+    /// Panics for the document root, and for a node which is not in a document tree.
+    pub fn retain_index(&self) -> usize {
+        assert!(!is_doc_root(self)); //we do not accept root as input
+        let parent = self
+            .get_parent()
+            .expect("retain_index(): the document node is not in a document tree");
+        let index = self
+            .my_index_as_child()
+            .expect("retain_index(): the document node is not a child of its parent");
+        let me = parent.get_child(index).unwrap();
+
+        let mut retain: usize = 0;
+        let mut dn_o = prev_node(&me);
+        while let Some(doc_node) = dn_o {
+            assert!(!is_doc_root(&doc_node));
+            retain += doc_node.op_len();
+            dn_o = prev_node(&doc_node);
+        }
+        //We stop when reaching doc_root. In that case prev_node() returns None
+        retain
+    }
+
     pub fn is_empty_block(&self) -> bool {
         let is_block = !self.formatter.borrow().is_text_format();
         let is_empty = self.children.borrow().len() == 0;
         is_block && is_empty
     }
+
+    /// # get_text_content()
+    ///
+    /// Returns the concatenated text of all text leaves in the sub-tree of this node.
+    /// Block operations (the new lines) and embedded objects are not included.
+    pub fn get_text_content(&self) -> String {
+        let mut txt = String::new();
+        if self.formatter.borrow().is_text_format() && self.children.borrow().is_empty() {
+            let op = self.delta_op.borrow();
+            if op.insert_value().is_string() {
+                if let Ok(s) = op.insert_value().str_val() {
+                    txt.push_str(s);
+                }
+            }
+        }
+        for c in self.children.borrow().iter() {
+            txt.push_str(&c.get_text_content());
+        }
+        txt
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -5,14 +5,17 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::cursor::{Cursor, CursorLocation};
+use crate::cursor::{retain_location, Cursor, CursorLocation};
 use crate::doc_node::DocumentNode;
 use crate::dom_doc_node::{find_doc_node_from_element_node, find_doc_node_from_text_node};
+use crate::error::Error::RetainRangeOutOfBounds;
+use crate::tree_traverse::find_by_retain_index;
+use anyhow::Result;
 use dom::dom_text::DomText;
 use log::debug;
 use std::sync::Arc;
-use wasm_bindgen::UnwrapThrowExt;
-use web_sys::{Document, Node, Range, Selection, Window};
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{Document, DomRect, Element, Node, Range, Selection, Window};
 
 /// # DomCursor
 ///
@@ -141,6 +144,13 @@ impl DomCursor {
             Node::TEXT_NODE => {
                 let doc_node_o = find_doc_node_from_text_node(&start_container, &self.root_node);
                 if let Some(doc_node) = doc_node_o {
+                    //text of a block which is not a document node, like a figure caption
+                    if !doc_node.get_formatter().is_text_format() {
+                        return match doc_node.get_child(0) {
+                            Some(first) => CursorLocation::Before(first),
+                            None => CursorLocation::At(doc_node, 0),
+                        };
+                    }
                     let node_len = doc_node.get_operation().op_len();
                     if start_offset == 0 {
                         CursorLocation::Before(doc_node)
@@ -224,10 +234,57 @@ impl DomCursor {
     ///     }
     /// ```
     pub fn cursor_to_html_dom(&self, cursor: &Cursor) {
+        self.cursors_to_html_dom(std::slice::from_ref(cursor));
+    }
+
+    /// # set_selection_from_retain_range()
+    ///
+    /// Sets the HTML DOM selection to the retain range `start .. end` of the document, for
+    /// example to highlight a search match. Same as `cursor_to_html_dom()` with a cursor
+    /// selecting that range; `start == end` gives a caret.
+    ///
+    /// Returns an error if the range is not in the document.
+    pub fn set_selection_from_retain_range(&self, start: usize, end: usize) -> Result<()> {
+        if start > end {
+            return Err(RetainRangeOutOfBounds { start, end }.into());
+        }
+        let (start_node, start_offset) = find_by_retain_index(&self.root_node, start)
+            .ok_or(RetainRangeOutOfBounds { start, end })?;
+        if !start_node.get_formatter().is_text_format() && start_offset > 0 {
+            //the end of the document
+            return Err(RetainRangeOutOfBounds { start, end }.into());
+        }
+        let cursor = Cursor::new();
+        cursor.set_select_start(retain_location(start_node, start_offset));
+        if start < end {
+            let (end_node, end_offset) = find_by_retain_index(&self.root_node, end)
+                .ok_or(RetainRangeOutOfBounds { start, end })?;
+            cursor.set_select_stop(retain_location(end_node, end_offset));
+        }
+        self.cursor_to_html_dom(&cursor);
+        Ok(())
+    }
+
+    /// # cursors_to_html_dom()
+    ///
+    /// Sets the HTML DOM selection to one range per cursor, for multi caret editing.
+    /// Most browsers only show the first range.
+    pub fn cursors_to_html_dom(&self, cursors: &[Cursor]) {
         //error!("DomCursor::set_selection() {}", &cursor);
 
         let selection = self.fetch_selection();
         selection.remove_all_ranges().unwrap_throw();
+        for cursor in cursors {
+            let range = self.range_from_cursor(cursor);
+            //Repaired bug here: Add range to selection AFTER setting the range to the proper nodes !!
+            selection
+                .add_range(&range)
+                .expect("failed to add range to selection");
+        }
+    }
+
+    /// Creates the HTML DOM range for the cursor
+    fn range_from_cursor(&self, cursor: &Cursor) -> Range {
         let range = self.create_range();
 
         //Make sure we remove the selection, and just have 1 pointer.
@@ -292,10 +349,48 @@ impl DomCursor {
             }
         }
 
-        //Repaired bug here: Add range to selection AFTER setting the range to the proper nodes !!
-        selection
-            .add_range(&range)
-            .expect("failed to add range to selection");
+        range
+    }
+
+    /// # get_caret_rect()
+    ///
+    /// Returns the viewport rectangle of the caret, which is the end of the current HTML DOM
+    /// selection range. Returns `None` if there is no selection range.
+    ///
+    /// A collapsed range in an empty block, like `<p><br></p>`, has no size. Then we use the
+    /// rectangle of the element which contains the caret.
+    pub fn get_caret_rect(&self) -> Option<DomRect> {
+        let range = self.fetch_range()?;
+        let caret = range.clone_range();
+        caret.collapse_with_to_start(false);
+        let rect = caret.get_bounding_client_rect();
+        if rect.width() == 0.0 && rect.height() == 0.0 {
+            let container = caret.end_container().ok()?;
+            if let Some(element) = container.dyn_ref::<Element>() {
+                return Some(element.get_bounding_client_rect());
+            }
+            if let Some(element) = container.parent_element() {
+                return Some(element.get_bounding_client_rect());
+            }
+        }
+        Some(rect)
+    }
+
+    /// # get_selection_rect()
+    ///
+    /// Returns the viewport rectangle around the current HTML DOM selection range.
+    /// Returns `None` if there is no selection range.
+    pub fn get_selection_rect(&self) -> Option<DomRect> {
+        Some(self.fetch_range()?.get_bounding_client_rect())
+    }
+
+    /// Returns the first range of the HTML DOM selection, if any
+    fn fetch_range(&self) -> Option<Range> {
+        let selection = self.fetch_selection();
+        if selection.range_count() == 0 {
+            return None;
+        }
+        selection.get_range_at(0).ok()
     }
 
     /// # fetch_selection()
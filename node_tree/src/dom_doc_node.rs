@@ -6,7 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::doc_node::DocumentNode;
-use crate::tree_traverse::{first_node, next_node};
+use crate::tree_traverse::{first_node, get_root, next_node};
 use dom::dom_element::DomElement;
 use dom::dom_text::{DomText, TEXT_TAG};
 use log::debug;
@@ -84,6 +84,16 @@ impl From<DomText> for DomDocNode {
     }
 }
 
+/// # find_doc_node_from_text_node()
+///
+/// Searches the document node tree below `root` for the `DocumentNode` which owns the given
+/// HTML DOM text node.
+///
+/// Not all DOM text nodes have their own `DocumentNode`. In that case we walk up the HTML DOM
+/// parents of the text node, until we find one which is known in the document tree.
+///
+/// Panics when the input is not a DOM text node, or if we reach the document root element
+/// without finding a matching `DocumentNode`.
 pub fn find_doc_node_from_text_node(
     node: &Node,
     root: &Arc<DocumentNode>,
@@ -105,6 +115,12 @@ pub fn find_doc_node_from_text_node(
     }
 }
 
+/// # find_doc_node_from_element_node()
+///
+/// Searches the document node tree below `root` for the `DocumentNode` whose HTML element
+/// is the given HTML DOM element node.
+///
+/// Panics when the input is not a DOM element node, or when no matching `DocumentNode` exists.
 pub fn find_doc_node_from_element_node(
     node: &Node,
     root: &Arc<DocumentNode>,
@@ -116,22 +132,59 @@ pub fn find_doc_node_from_element_node(
     panic!("selected_node::find_doc_node_from_element_node() ...nothing found.");
 }
 
-fn find_doc_node(node: &Node, root: &Arc<DocumentNode>) -> Option<Arc<DocumentNode>> {
-    //FIXME: There are probably smarter ways to get to this node ...
-    // let html = dom_print::pretty_print(node, true );
-    // error!( "selected_node::find_doc_node() - Start node:\n{}", doc_node);
+/// # find_doc_node_by_predicate()
+///
+/// Returns the first `DocumentNode`, in document order, for which the predicate returns true.
+/// The `root` may be any node in the document, the whole document is searched. The document
+/// root itself is not tested.
+pub fn find_doc_node_by_predicate(
+    root: &Arc<DocumentNode>,
+    pred: impl Fn(&Arc<DocumentNode>) -> bool,
+) -> Option<Arc<DocumentNode>> {
+    if get_root(root).child_count() == 0 {
+        return None;
+    }
     let mut dn = Some(first_node(root));
-    while dn.is_some() {
-        let doc_node = dn.unwrap();
-        if doc_node.get_html_node().eq(node) {
-            debug!(
-                "selected_node::find_doc_node() - \n{:?}",
-                doc_node.get_operation()
-            );
+    while let Some(doc_node) = dn {
+        if pred(&doc_node) {
             return Some(doc_node);
         }
         dn = next_node(&doc_node);
     }
-    debug!("selected_node::find_doc_node() ...nothing found.");
     None
 }
+
+/// # find_doc_node_by_format()
+///
+/// Returns all `DocumentNode`, in document order, which are formatted by the `FormatTait` with
+/// the given format name. The `root` may be any node in the document.
+pub fn find_doc_node_by_format(
+    root: &Arc<DocumentNode>,
+    format_name: &str,
+) -> Vec<Arc<DocumentNode>> {
+    let mut found: Vec<Arc<DocumentNode>> = Vec::new();
+    if get_root(root).child_count() == 0 {
+        return found;
+    }
+    let mut dn = Some(first_node(root));
+    while let Some(doc_node) = dn {
+        if doc_node.get_formatter().format_name() == format_name {
+            found.push(doc_node.clone());
+        }
+        dn = next_node(&doc_node);
+    }
+    found
+}
+
+fn find_doc_node(node: &Node, root: &Arc<DocumentNode>) -> Option<Arc<DocumentNode>> {
+    //FIXME: There are probably smarter ways to get to this node ...
+    let found = find_doc_node_by_predicate(root, |doc_node| doc_node.get_html_node().eq(node));
+    match &found {
+        Some(doc_node) => debug!(
+            "selected_node::find_doc_node() - \n{:?}",
+            doc_node.get_operation()
+        ),
+        None => debug!("selected_node::find_doc_node() ...nothing found."),
+    }
+    found
+}
@@ -18,6 +18,9 @@ use std::sync::Arc;
 /// # unlink()
 ///
 /// Unlinks both the DocumentNode, and HTML dom elements from its parent
+///
+/// In debug builds the child is marked as orphaned, so traversing the tree from the unlinked
+/// node panics with a clear message. See `DocumentNode::is_orphaned()`.
 pub fn unlink(parent: &Arc<DocumentNode>, child: &Arc<DocumentNode>) {
     match &child.get_doc_dom_node() {
         DomDocNode::ElementNode(el) => {
@@ -28,6 +31,9 @@ pub fn unlink(parent: &Arc<DocumentNode>, child: &Arc<DocumentNode>) {
         }
     }
     remove_child(parent, child);
+    if cfg!(debug_assertions) {
+        child.orphaned.set(true);
+    }
 }
 
 /// # append()
@@ -44,6 +50,7 @@ pub fn append(parent: &Arc<DocumentNode>, child: Arc<DocumentNode>) {
 
             //link document node
             *child.parent.borrow_mut() = Arc::downgrade(parent);
+            child.orphaned.set(false);
             parent.children.borrow_mut().push(child);
         }
     }
@@ -67,6 +74,10 @@ pub fn insert_before(
 /// # insert_at_index()
 ///
 /// Inserts a DocumentNode and links(appends) the HTML Dom node too in the same index position
+///
+/// The HTML Dom node is inserted before the Dom node of the document node at `index`. So a
+/// HTML element which is not a document node, like the check box of a checklist item, does
+/// not shift the position.
 pub fn insert_at_index(parent: &Arc<DocumentNode>, index: usize, child: Arc<DocumentNode>) {
     //link html-dom
     match &parent.get_doc_dom_node() {
@@ -77,9 +88,11 @@ pub fn insert_at_index(parent: &Arc<DocumentNode>, index: usize, child: Arc<Docu
             if index + 1 > parent.children.borrow().len() {
                 append(parent, child);
             } else {
-                e.insert_child(index, child.get_html_node());
+                let next = parent.get_child(index).unwrap();
+                e.insert_child_before(child.get_html_node(), next.get_html_node());
                 parent.children.borrow_mut().insert(index, child.clone());
                 *child.parent.borrow_mut() = Arc::downgrade(parent);
+                child.orphaned.set(false);
             }
         }
     }
@@ -17,4 +17,14 @@ pub enum Error {
     AdvanceBeyondEnd,
     #[error("Unexpected cursor position with value: None.")]
     UnexepectedCursorPosNone,
+    #[error("Retain range = {start}..{end} is not a range in the document.")]
+    RetainRangeOutOfBounds { start: usize, end: usize },
+    #[error("The document root can not be moved.")]
+    MoveDocRoot,
+    #[error("Can not move a document node into its own sub tree.")]
+    MoveIntoDescendant,
+    #[error("Child index = {index} is beyond the {count} children of the new parent.")]
+    ChildIndexOutOfRange { index: usize, count: usize },
+    #[error("A node with format {parent} can not contain a node with format {child}.")]
+    InvalidParentFormat { parent: String, child: String },
 }
@@ -12,6 +12,28 @@ use delta::attributes::Attributes;
 use delta::operations::DeltaOperation;
 use std::sync::Arc;
 
+/// # EnterKeyBehavior
+///
+/// What the `Enter` key does in a block, see `FormatTait::enter_key_behavior()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnterKeyBehavior {
+    SplitBlock,      //a new line: the block is split at the cursor
+    InsertSoftBreak, //a soft break `<BR>` in the block, the block is not split
+    ExitBlock,       //an empty block becomes a paragraph, else the block is split
+    Inherit,         //the behavior of the parent block
+}
+
+/// # BackspaceEmptyBehavior
+///
+/// What the `Backspace` key does at the start of an empty block, see
+/// `FormatTait::backspace_at_empty_block_behavior()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackspaceEmptyBehavior {
+    ExitFormat,    //the block becomes a paragraph
+    MergeWithPrev, //the block is merged with the previous block
+    NoOp,          //nothing happens
+}
+
 /// # FormatTait
 ///
 /// All formats supported shall implement this trait.
@@ -87,6 +109,63 @@ pub trait FormatTait {
     /// MAY implement by the implementing `FormatTait`
     ///--------------------------------------------------------------
 
+    /// # is_void()
+    ///
+    /// Returns true for an atomic embed, like an image, which has length 1 and can not be
+    /// split. A void node is never split when inserting text next to it, and a delete removes
+    /// the whole node, so `split_leaf()` and `delete_leaf_segment()` are not called.
+    ///
+    /// Formats for such embeds SHOULD return true.
+    fn is_void(&self) -> bool {
+        false
+    }
+
+    /// # accepts_block_children()
+    ///
+    /// Returns true when block nodes may be children of a node of this format, like the `<LI>`
+    /// items of a `<UL>`, or the blocks of the document root. Blocks like `<P>` only contain
+    /// text nodes.
+    ///
+    /// Formats with a wrapper node around their blocks SHOULD return true. Only the zero
+    /// length wrapper takes blocks then, the blocks of the format take text, see
+    /// `node_morph::move_node()`.
+    fn accepts_block_children(&self) -> bool {
+        false
+    }
+
+    /// # enter_key_behavior()
+    ///
+    /// Returns what the `Enter` key does when the cursor is in a block of this format. The
+    /// keyboard handler of the document asks the block at the cursor, so only block formats
+    /// are asked.
+    ///
+    /// The default splits the block, like a new line in a paragraph.
+    fn enter_key_behavior(&self) -> EnterKeyBehavior {
+        EnterKeyBehavior::SplitBlock
+    }
+
+    /// # backspace_at_empty_block_behavior()
+    ///
+    /// Returns what the `Backspace` key does when the cursor is at the start of an empty block
+    /// of this format. In a block with content, `Backspace` always deletes the character in
+    /// front of the cursor.
+    ///
+    /// The default removes the block format, so an empty header becomes a paragraph.
+    fn backspace_at_empty_block_behavior(&self) -> BackspaceEmptyBehavior {
+        BackspaceEmptyBehavior::ExitFormat
+    }
+
+    /// # has_anchor_id()
+    ///
+    /// Returns true when the blocks of this format get an `id` attribute for deep links,
+    /// made from their text. The document sets the IDs after each change, so they follow
+    /// the text and are unique in the document.
+    ///
+    /// The default has no anchor ID.
+    fn has_anchor_id(&self) -> bool {
+        false
+    }
+
     /// # clone_doc_node()
     ///
     /// returns a clone of the document node.<br>
@@ -264,6 +343,10 @@ impl FormatTait for RootFormat {
         false
     }
 
+    fn accepts_block_children(&self) -> bool {
+        true
+    }
+
     fn block_remove_attr(&self) -> Attributes {
         panic!("{} -- You called: {}", RT_FORMAT_ERROR, "block_remove_attr");
     }
@@ -32,6 +32,10 @@ use std::sync::Arc;
 //==========================================================
 // Support functions not part of document impl ...
 //==========================================================
+/// Maximum depth of the document tree. Deeper trees are assumed to have a cycle in the
+/// parent / child references, so we panic instead of looping forever.
+pub const MAX_TREE_DEPTH: usize = 10_000;
+
 /// Root element of an editable tree is marked by a `<DIV>` with non empty ID attribute
 pub fn is_doc_root(doc_node: &DocumentNode) -> bool {
     if let Some(el) = doc_node.get_dom_element() {
@@ -49,10 +53,13 @@ pub fn get_root(doc_node: &Arc<DocumentNode>) -> Arc<DocumentNode> {
         return doc_node.clone();
     }
     let mut parent = doc_node.get_parent().unwrap();
+    let mut depth: usize = 0;
     loop {
         if is_doc_root(&parent) {
             return parent.clone();
         }
+        depth += 1;
+        check_depth(depth, "get_root");
         parent = if let Some(p) = parent.get_parent() {
             p
         } else {
@@ -77,11 +84,42 @@ pub fn first_node(node: &Arc<DocumentNode>) -> Arc<DocumentNode> {
 }
 #[inline(always)]
 fn first_iter_intern(doc_node: &Arc<DocumentNode>) -> Arc<DocumentNode> {
-    let c = doc_node.get_children();
-    if !c.is_empty() {
-        return first_iter_intern(c.first().unwrap());
+    let mut node = doc_node.clone();
+    let mut depth: usize = 0;
+    while let Some(c) = node.get_child(0) {
+        depth += 1;
+        check_depth(depth, "first_node");
+        node = c;
+    }
+    node
+}
+
+/// # check_depth()
+///
+/// Panics when the traversal went deeper than `MAX_TREE_DEPTH`.
+#[inline(always)]
+fn check_depth(depth: usize, function: &str) {
+    if depth > MAX_TREE_DEPTH {
+        panic!(
+            "{}(): tree depth exceeds {}, there is probably a cycle in the document tree",
+            function, MAX_TREE_DEPTH
+        );
+    }
+}
+
+/// # index_in_parent()
+///
+/// Returns the child index of the node in its parent. Panics with a clear message when the
+/// node is orphaned: it still points to a parent, which does not know about this child.
+fn index_in_parent(parent: &Arc<DocumentNode>, child: &Arc<DocumentNode>, function: &str) -> usize {
+    match parent.get_child_index(child) {
+        Some(index) => index,
+        None => panic!(
+            "{}(): the document node is orphaned, it is not a child of its parent. \
+            Was it unlinked from the tree?",
+            function
+        ),
     }
-    doc_node.clone()
 }
 
 pub fn last_block_node(root: &DocumentNode) -> Option<Arc<DocumentNode>> {
@@ -128,10 +166,15 @@ fn last_iter_intern(doc_node: &Arc<DocumentNode>) -> Option<Arc<DocumentNode>> {
 ///```
 /// Repeated calls to `Next()` should return :D, E, B, F, G, C, A
 /// We do not return root element
+///
+/// Panics if the node is orphaned, see `DocumentNode::is_orphaned()`.
 pub fn next_node(current: &Arc<DocumentNode>) -> Option<Arc<DocumentNode>> {
+    if current.is_orphaned() {
+        panic!("next_node(): the document node is orphaned. Was it unlinked from the tree?");
+    }
     if let Some(parent) = current.get_parent() {
         let children = parent.get_children();
-        let my_index = parent.get_child_index(current).unwrap();
+        let my_index = index_in_parent(&parent, current, "next_node");
         if my_index + 1 < children.len() {
             return Some(first_iter_intern(&parent.get_child(my_index + 1).unwrap()));
         }
@@ -161,6 +204,128 @@ pub fn next_node_non_zero_length(current: &Arc<DocumentNode>) -> Option<Arc<Docu
     }
 }
 
+/// # find_by_retain_index()
+///
+/// Returns the node with the retain index `idx`, and the offset of `idx` in that node.
+/// Nodes without length, like the `<UL>` of a list, are skipped.
+///
+/// The offset is at most the length of the node, so the index at the end of a text leaf
+/// gives that leaf with `offset == op_len()`. But a block boundary, the index after the
+/// new line of a block, gives the next node with offset 0.
+/// The end of the document gives the last block with `offset == op_len()`.
+///
+/// Returns `None` when the document is empty, or `idx` is beyond the end of the document.
+pub fn find_by_retain_index(
+    root: &Arc<DocumentNode>,
+    idx: usize,
+) -> Option<(Arc<DocumentNode>, usize)> {
+    if get_root(root).child_count() == 0 {
+        return None;
+    }
+    let mut retain: usize = 0;
+    let mut dn_o = Some(first_node(root));
+    while let Some(doc_node) = dn_o {
+        let len = doc_node.op_len();
+        if len > 0 && retain + len >= idx {
+            if retain + len > idx || doc_node.get_formatter().is_text_format() {
+                return Some((doc_node, idx - retain));
+            }
+            //block boundary
+            return match next_node_non_zero_length(&doc_node) {
+                Some(next) => Some((next, 0)),
+                None => Some((doc_node, len)),
+            };
+        }
+        retain += len;
+        dn_o = next_node(&doc_node);
+    }
+    None
+}
+
+/// # collect_blocks()
+///
+/// Returns all block nodes of the document, in document order. Wrappers without length,
+/// like the `<UL>` of a list, are not blocks: we return the `<LI>` items.
+pub fn collect_blocks(root: &Arc<DocumentNode>) -> Vec<Arc<DocumentNode>> {
+    collect_non_zero_length(root, false)
+}
+
+/// # collect_leaves()
+///
+/// Returns all text and embed nodes of the document, in document order. Nodes without
+/// length, like the `<A>` wrapper of a link, or a soft break, are skipped.
+pub fn collect_leaves(root: &Arc<DocumentNode>) -> Vec<Arc<DocumentNode>> {
+    collect_non_zero_length(root, true)
+}
+
+fn collect_non_zero_length(root: &Arc<DocumentNode>, text_format: bool) -> Vec<Arc<DocumentNode>> {
+    let mut nodes: Vec<Arc<DocumentNode>> = Vec::new();
+    if get_root(root).child_count() == 0 {
+        return nodes;
+    }
+    let mut dn_o = Some(first_node(root));
+    while let Some(doc_node) = dn_o {
+        if doc_node.op_len() > 0 && doc_node.get_formatter().is_text_format() == text_format {
+            nodes.push(doc_node.clone());
+        }
+        dn_o = next_node(&doc_node);
+    }
+    nodes
+}
+
+/// # DepthFirstPostIter
+///
+/// Iterator over the nodes of a document, in the same order as repeated calls to
+/// `next_node()`: children before their parent. The root itself is not returned.
+///
+/// Unlike `next_node()` we do not search the index of a node in its parent for every
+/// step, but keep a stack of the nodes still to visit.
+///
+/// Note: the iterator is not `Send`, since a `DocumentNode` holds `RefCell` and HTML DOM
+/// references, which stay in the browser thread.
+pub struct DepthFirstPostIter {
+    stack: Vec<(Arc<DocumentNode>, bool)>, //(node, children are on the stack)
+}
+
+impl DepthFirstPostIter {
+    fn new(root: &Arc<DocumentNode>) -> Self {
+        let mut iter = DepthFirstPostIter { stack: Vec::new() };
+        iter.push_children(root);
+        iter
+    }
+
+    fn push_children(&mut self, node: &Arc<DocumentNode>) {
+        for c in node.get_children().into_iter().rev() {
+            self.stack.push((c, false));
+        }
+    }
+}
+
+impl Iterator for DepthFirstPostIter {
+    type Item = Arc<DocumentNode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, expanded)) = self.stack.pop() {
+            if expanded || node.child_count() == 0 {
+                return Some(node);
+            }
+            self.stack.push((node.clone(), true));
+            self.push_children(&node);
+        }
+        None
+    }
+}
+
+/// # depth_first_post_order_iter()
+///
+/// Returns an iterator over all nodes below the root, see `DepthFirstPostIter`.
+/// ```ignore
+/// let blocks = depth_first_post_order_iter(root).filter(|n| !n.is_leaf()).count();
+/// ```
+pub fn depth_first_post_order_iter(root: &Arc<DocumentNode>) -> DepthFirstPostIter {
+    DepthFirstPostIter::new(root)
+}
+
 /// # prev_node()
 ///
 /// Get the previous document node.
@@ -181,11 +346,17 @@ pub fn next_node_non_zero_length(current: &Arc<DocumentNode>) -> Option<Arc<Docu
 /// 4) if not 3) then, go to the parent-parent previous sibling, and try 2) and 3) again
 ///
 /// Moves from node to node, depth first as shown in the header text of this file
+///
+/// Panics if the node is orphaned, see `DocumentNode::is_orphaned()`, or if the tree is
+/// deeper than `MAX_TREE_DEPTH`.
 pub fn prev_node(doc_node: &Arc<DocumentNode>) -> Option<Arc<DocumentNode>> {
     //rule 0)
     if is_doc_root(doc_node) {
         return None;
     }
+    if doc_node.is_orphaned() {
+        panic!("prev_node(): the document node is orphaned. Was it unlinked from the tree?");
+    }
 
     //Rule 1)
     if doc_node.child_count() > 0 {
@@ -196,9 +367,12 @@ pub fn prev_node(doc_node: &Arc<DocumentNode>) -> Option<Arc<DocumentNode>> {
 
     let mut parent_o = doc_node.get_parent();
     let mut child = doc_node.clone();
+    let mut depth: usize = 0;
     loop {
+        depth += 1;
+        check_depth(depth, "prev_node");
         if let Some(parent) = parent_o {
-            let my_index = parent.get_child_index(&child).unwrap();
+            let my_index = index_in_parent(&parent, &child, "prev_node");
             //rule 2)
             if my_index > 0 {
                 return parent.get_child(my_index - 1);
@@ -314,7 +488,7 @@ pub fn prev_leaf(current: &Arc<DocumentNode>) -> Option<Arc<DocumentNode>> {
 pub fn next_sibling(current: &Arc<DocumentNode>) -> Option<Arc<DocumentNode>> {
     assert!(!is_doc_root(current));
     let p = current.get_parent().unwrap();
-    let my_index = p.get_child_index(current).unwrap();
+    let my_index = index_in_parent(&p, current, "next_sibling");
     if my_index + 1 < p.child_count() {
         p.get_child(my_index + 1)
     } else {
@@ -326,10 +500,87 @@ pub fn next_sibling(current: &Arc<DocumentNode>) -> Option<Arc<DocumentNode>> {
 /// or none if the parent does not match
 pub fn prev_sibling(current: &Arc<DocumentNode>) -> Option<Arc<DocumentNode>> {
     let p = current.get_parent().unwrap();
-    let my_index = p.get_child_index(current).unwrap();
+    let my_index = index_in_parent(&p, current, "prev_sibling");
     if my_index > 0 {
         p.get_child(my_index - 1)
     } else {
         None
     }
 }
+
+/// # path_from_root()
+///
+/// Returns the nodes from the root down to, and including, the given node.
+///
+/// Panics if the node is not in the tree of the root, or if the tree is deeper than
+/// `MAX_TREE_DEPTH`.
+fn path_from_root(node: &Arc<DocumentNode>, root: &Arc<DocumentNode>) -> Vec<Arc<DocumentNode>> {
+    let mut path = vec![node.clone()];
+    let mut current = node.clone();
+    let mut depth: usize = 0;
+    while !Arc::ptr_eq(&current, root) {
+        depth += 1;
+        check_depth(depth, "path_from_root");
+        current = match current.get_parent() {
+            Some(p) => p,
+            None => panic!("path_from_root(): the node is not part of the tree of the given root"),
+        };
+        path.push(current.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// # lowest_common_ancestor()
+///
+/// Returns the deepest node which has both `a` and `b` in its sub tree. A node is part
+/// of its own sub tree, so if `a` is an ancestor of `b`, then `a` is returned.
+///
+/// Panics if one of the nodes is not in the tree of the root.
+pub fn lowest_common_ancestor(
+    a: &Arc<DocumentNode>,
+    b: &Arc<DocumentNode>,
+    root: &Arc<DocumentNode>,
+) -> Arc<DocumentNode> {
+    let path_a = path_from_root(a, root);
+    let path_b = path_from_root(b, root);
+    let mut lca = root.clone();
+    for (x, y) in path_a.iter().zip(path_b.iter()) {
+        if !Arc::ptr_eq(x, y) {
+            break;
+        }
+        lca = x.clone();
+    }
+    lca
+}
+
+/// # is_before()
+///
+/// Returns true if `a` comes before `b` in the document order of `next_node()`.
+/// This is a post-order traversal, so a child comes before its parent block.
+///
+/// We do not walk the document from `a` to `b`, but compare the children of the lowest
+/// common ancestor that lead to `a` and to `b`.
+///
+/// Returns false if `a == b`.
+pub fn is_before(a: &Arc<DocumentNode>, b: &Arc<DocumentNode>, root: &Arc<DocumentNode>) -> bool {
+    let path_a = path_from_root(a, root);
+    let path_b = path_from_root(b, root);
+    let common = path_a
+        .iter()
+        .zip(path_b.iter())
+        .take_while(|(x, y)| Arc::ptr_eq(x, y))
+        .count();
+
+    if common == path_a.len() {
+        // a == b, or a is an ancestor of b, so a comes after b
+        return false;
+    }
+    if common == path_b.len() {
+        // b is an ancestor of a
+        return true;
+    }
+    let lca = &path_a[common - 1];
+    index_in_parent(lca, &path_a[common], "is_before")
+        < index_in_parent(lca, &path_b[common], "is_before")
+}
@@ -119,4 +119,108 @@ mod test {
         }
         Ok(())
     }
+
+    #[wasm_bindgen_test]
+    fn cursor_ordering_test() -> Result<()> {
+        let doc = DocumentRoot::new("cursor_ordering_test");
+        doc.append_to_body();
+        create_text(&doc)?;
+        doc.reset_cursor();
+
+        // r#"<p>TEXT_1_1<strong>TEXT_1_2</strong>TEXT_1_3</p><p>TEXT_2_1<strong>TEXT_2_2</strong></p><p></p>"#;
+        let cursor = doc.get_cursor();
+        let start = cursor.clone();
+        for _ in 0..10 {
+            cursor.advance()?;
+        }
+        let first_block = cursor.clone();
+        for _ in 0..20 {
+            cursor.advance()?;
+        }
+        let second_block = cursor.clone();
+        assert_eq!(first_block.get_retain_index(), 10);
+        assert_eq!(second_block.get_retain_index(), 30);
+
+        assert!(start < first_block);
+        assert!(first_block < second_block);
+        assert!(second_block > start);
+        assert!(first_block <= first_block.clone());
+        assert!(first_block >= first_block.clone());
+
+        assert!(second_block.min(&start) == start);
+        assert!(start.min(&second_block) == start);
+        assert!(start.max(&second_block) == second_block);
+        assert!(first_block.max(&start) == first_block);
+
+        let mut cursors = vec![second_block.clone(), start.clone(), first_block.clone()];
+        cursors.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(cursors[0].get_retain_index(), 0);
+        assert_eq!(cursors[1].get_retain_index(), 10);
+        assert_eq!(cursors[2].get_retain_index(), 30);
+        Ok(())
+    }
+
+    /// The cursor moves over a grapheme cluster as one character. The retain index moves
+    /// by the number of unicode scalar values of the grapheme cluster.
+    #[wasm_bindgen_test]
+    fn cursor_grapheme_test() -> Result<()> {
+        let doc = DocumentRoot::new("cursor_grapheme_test");
+        doc.append_to_body();
+
+        let p_format = Arc::new(Pblock::new());
+        let t_format = Arc::new(TextFormat::new());
+        let root = doc.get_root();
+        let par = p_format.create(DeltaOperation::insert("\n"), p_format.clone())?;
+        append(&root, par.clone());
+        //family emoji: 5 scalar values, e with combining accent: 2 scalar values
+        let delta = DeltaOperation::insert("a👨‍👩‍👧e\u{301}b");
+        let t = t_format.create(delta, t_format.clone())?;
+        append(&par, t.clone());
+        doc.reset_cursor();
+
+        let cursor = doc.get_cursor();
+        for retain in [1, 6, 8, 9] {
+            cursor.advance()?;
+            assert_eq!(cursor.get_retain_index(), retain);
+        }
+        for retain in [8, 6, 1, 0] {
+            cursor.backspace()?;
+            assert_eq!(cursor.get_retain_index(), retain);
+        }
+        assert_eq!(cursor_points_to(cursor), "a");
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    fn cursor_set_selection_by_retain_range_test() -> Result<()> {
+        let doc = DocumentRoot::new("cursor_set_selection_by_retain_range_test");
+        doc.append_to_body();
+        create_text(&doc)?;
+        doc.reset_cursor();
+
+        // r#"<p>TEXT_1_1<strong>TEXT_1_2</strong>TEXT_1_3</p><p>TEXT_2_1<strong>TEXT_2_2</strong></p><p></p>"#;
+        let cursor = doc.get_cursor();
+        for (start, end) in [
+            (0, 5),   //in the first leaf
+            (8, 16),  //exactly one leaf
+            (20, 30), //across a block boundary
+            (24, 25), //only the block
+            (40, 43), //until the end of the document
+        ] {
+            cursor.set_selection_by_retain_range(start, end)?;
+            assert_eq!(cursor.get_retain_index(), start);
+            assert_eq!(cursor.selection_length(), end - start);
+        }
+
+        //collapsed
+        cursor.set_selection_by_retain_range(5, 5)?;
+        assert!(!cursor.is_selection());
+        assert_eq!(cursor.get_retain_index(), 5);
+        assert_eq!(cursor_points_to(cursor), "1");
+
+        assert!(cursor.set_selection_by_retain_range(0, 44).is_err()); //Error::RetainRangeOutOfBounds
+        assert!(cursor.set_selection_by_retain_range(10, 5).is_err()); //Error::RetainRangeOutOfBounds
+        assert!(cursor.set_selection_by_retain_range(43, 43).is_err()); //Error::RetainRangeOutOfBounds
+        Ok(())
+    }
 }
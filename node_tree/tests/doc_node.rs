@@ -224,3 +224,16 @@ fn find_text_node_from_element_test() {
     let text2 = find_dom_text(test_node.get_html_node()).unwrap();
     assert_eq!(text2.get_text(), "heLD".to_string());
 }
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "not in a document tree")]
+fn retain_index_not_in_tree_test() {
+    let t = DomText::new("hello");
+    let doc_node = DocumentNode::new_text(
+        t,
+        Arc::new(RootFormat {
+            name: "DOM_TEXT_TEST",
+        }),
+    );
+    doc_node.retain_index();
+}
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use delta::delta::Delta;
+use node_tree::dom_doc_node::{
+    find_doc_node_by_format, find_doc_node_by_predicate, find_doc_node_from_element_node,
+    find_doc_node_from_text_node,
+};
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::init_test_registry;
+use std::sync::Arc;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn create_test_document(id: &str) -> Result<DocumentRoot> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new(id);
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("abc\ndef");
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<p>abc</p><p>def</p>");
+    Ok(doc)
+}
+
+#[wasm_bindgen_test]
+fn find_by_element_test() -> Result<()> {
+    let doc = create_test_document("find_by_element_test")?;
+    let block = doc.get_root().get_child(1).unwrap();
+    let found = find_doc_node_from_element_node(block.get_html_node(), doc.get_root()).unwrap();
+    assert!(Arc::ptr_eq(&found, &block));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn find_by_text_node_test() -> Result<()> {
+    let doc = create_test_document("find_by_text_node_test")?;
+    let text = doc.get_root().get_child(0).unwrap().get_child(0).unwrap();
+    let found = find_doc_node_from_text_node(text.get_html_node(), doc.get_root()).unwrap();
+    assert!(Arc::ptr_eq(&found, &text));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn find_by_predicate_test() -> Result<()> {
+    let doc = create_test_document("find_by_predicate_test")?;
+    let found = find_doc_node_by_predicate(doc.get_root(), |dn| {
+        dn.get_operation().insert_value().str_val().unwrap_or("") == "def"
+    })
+    .unwrap();
+    let expect = doc.get_root().get_child(1).unwrap().get_child(0).unwrap();
+    assert!(Arc::ptr_eq(&found, &expect));
+
+    let found = find_doc_node_by_predicate(doc.get_root(), |dn| dn.op_len() > 3);
+    assert!(found.is_none());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn find_by_format_test() -> Result<()> {
+    let doc = create_test_document("find_by_format_test")?;
+    let blocks = find_doc_node_by_format(doc.get_root(), NAME_P_BLOCK);
+    assert_eq!(blocks.len(), 2);
+    assert!(Arc::ptr_eq(
+        &blocks[0],
+        &doc.get_root().get_child(0).unwrap()
+    ));
+
+    let texts = find_doc_node_by_format(doc.get_root(), NAME_TEXT);
+    assert_eq!(texts.len(), 2);
+
+    let none = find_doc_node_by_format(doc.get_root(), "no-such-format");
+    assert!(none.is_empty());
+    Ok(())
+}
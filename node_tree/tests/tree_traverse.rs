@@ -1,12 +1,17 @@
+use anyhow::Result;
+use delta::delta::Delta;
 use delta::operations::DeltaOperation;
 use dom::dom_element::DomElement;
 use node_tree::doc_node::DocumentNode;
-use node_tree::dom_doc_tree_morph::append;
+use node_tree::dom_doc_tree_morph::{append, unlink};
 use node_tree::format_trait::RootFormat;
 use node_tree::tree_traverse::{
-    first_node, last_block_node, next_node, next_sibling, prev_node, prev_sibling,
+    collect_blocks, collect_leaves, depth_first_post_order_iter, find_by_retain_index, first_node,
+    is_before, last_block_node, lowest_common_ancestor, next_node, next_sibling, prev_node,
+    prev_sibling,
 };
 use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::init_test_registry;
 use std::sync::Arc;
 use wasm_bindgen_test::*;
 
@@ -253,3 +258,222 @@ fn test_next_sibling_node() {
     let nxt = next_sibling(&nxt);
     assert!(nxt == None);
 }
+
+/// Creates root -> A -> (B, C), and returns (A, B, C)
+#[allow(non_snake_case)]
+fn create_orphan_document(
+    doc: &DocumentRoot,
+) -> (Arc<DocumentNode>, Arc<DocumentNode>, Arc<DocumentNode>) {
+    let A = new_el("A".to_string());
+    let B = new_el("B".to_string());
+    let C = new_el("C".to_string());
+    append(&A, B.clone());
+    append(&A, C.clone());
+    append(doc.get_root(), A.clone());
+    (A, B, C)
+}
+
+#[wasm_bindgen_test]
+#[allow(non_snake_case)]
+fn is_orphaned_test() {
+    let doc = DocumentRoot::new("is_orphaned_test");
+    let (A, B, C) = create_orphan_document(&doc);
+    assert!(!A.is_orphaned());
+    assert!(!B.is_orphaned());
+
+    unlink(&A, &B);
+    assert!(B.is_orphaned());
+    assert!(!C.is_orphaned());
+
+    append(&A, B.clone());
+    assert!(!B.is_orphaned());
+    assert_eq!(id(&next_node(&C).unwrap()), "B");
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "orphaned")]
+#[allow(non_snake_case)]
+fn next_node_orphaned_test() {
+    let doc = DocumentRoot::new("next_node_orphaned_test");
+    let (A, B, _C) = create_orphan_document(&doc);
+    unlink(&A, &B);
+    next_node(&B);
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "orphaned")]
+#[allow(non_snake_case)]
+fn prev_node_orphaned_test() {
+    let doc = DocumentRoot::new("prev_node_orphaned_test");
+    let (A, _B, C) = create_orphan_document(&doc);
+    unlink(&A, &C);
+    prev_node(&C);
+}
+
+fn find(doc: &DocumentRoot, node_id: &str) -> Arc<DocumentNode> {
+    let mut nxt = first_node(doc.get_root());
+    while id(&nxt) != node_id {
+        nxt = next_node(&nxt).unwrap();
+    }
+    nxt
+}
+
+// Next should return:	-> E,F,D,H,I,G,B,L,M,K,O,P,N,J,A
+#[wasm_bindgen_test]
+fn is_before_siblings_test() {
+    let doc = DocumentRoot::new("is_before_siblings_test");
+    create_test_document(&doc);
+    let root = doc.get_root();
+
+    let e = find(&doc, "E");
+    let f = find(&doc, "F");
+    assert!(is_before(&e, &f, root));
+    assert!(!is_before(&f, &e, root));
+    assert_eq!(id(&lowest_common_ancestor(&e, &f, root)), "D");
+}
+
+#[wasm_bindgen_test]
+fn is_before_ancestor_test() {
+    let doc = DocumentRoot::new("is_before_ancestor_test");
+    create_test_document(&doc);
+    let root = doc.get_root();
+
+    //post order: the child comes before the parent block
+    let b = find(&doc, "B");
+    let h = find(&doc, "H");
+    assert!(is_before(&h, &b, root));
+    assert!(!is_before(&b, &h, root));
+    assert_eq!(id(&lowest_common_ancestor(&b, &h, root)), "B");
+    assert_eq!(id(&lowest_common_ancestor(&h, &b, root)), "B");
+}
+
+#[wasm_bindgen_test]
+fn is_before_cousins_test() {
+    let doc = DocumentRoot::new("is_before_cousins_test");
+    create_test_document(&doc);
+    let root = doc.get_root();
+
+    let i = find(&doc, "I");
+    let l = find(&doc, "L");
+    let k = find(&doc, "K");
+    assert!(is_before(&i, &l, root));
+    assert!(!is_before(&l, &i, root));
+    assert_eq!(id(&lowest_common_ancestor(&i, &l, root)), "A");
+
+    //a parent after a cousin
+    let g = find(&doc, "G");
+    assert!(is_before(&g, &k, root));
+    assert!(!is_before(&k, &g, root));
+}
+
+#[wasm_bindgen_test]
+fn is_before_same_node_test() {
+    let doc = DocumentRoot::new("is_before_same_node_test");
+    create_test_document(&doc);
+    let root = doc.get_root();
+
+    let m = find(&doc, "M");
+    assert!(!is_before(&m, &m, root));
+    assert!(Arc::ptr_eq(&lowest_common_ancestor(&m, &m, root), &m));
+}
+
+#[wasm_bindgen_test]
+fn find_by_retain_index_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("find_by_retain_index_test");
+    let root = doc.get_root().clone();
+    assert!(find_by_retain_index(&root, 0).is_none()); //empty document
+
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("abc\ndef\n");
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<p>abc</p><p>def</p><p><br></p>");
+    let abc = root.get_child(0).unwrap().get_child(0).unwrap();
+    let def = root.get_child(1).unwrap().get_child(0).unwrap();
+    let last = root.get_child(2).unwrap();
+
+    //the start of the document
+    let (node, offset) = find_by_retain_index(&root, 0).unwrap();
+    assert!(Arc::ptr_eq(&node, &abc));
+    assert_eq!(offset, 0);
+
+    //the exact end of a leaf
+    let (node, offset) = find_by_retain_index(&root, 3).unwrap();
+    assert!(Arc::ptr_eq(&node, &abc));
+    assert_eq!(offset, 3);
+
+    //a block boundary gives the next leaf
+    let (node, offset) = find_by_retain_index(&root, 4).unwrap();
+    assert!(Arc::ptr_eq(&node, &def));
+    assert_eq!(offset, 0);
+
+    //a block boundary before an empty block gives that block
+    let (node, offset) = find_by_retain_index(&root, 8).unwrap();
+    assert!(Arc::ptr_eq(&node, &last));
+    assert_eq!(offset, 0);
+
+    //the end of the document
+    let (node, offset) = find_by_retain_index(&root, 9).unwrap();
+    assert!(Arc::ptr_eq(&node, &last));
+    assert_eq!(offset, 1);
+
+    //beyond the end of the document
+    assert!(find_by_retain_index(&root, 10).is_none());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn collect_blocks_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("collect_blocks_test");
+    let root = doc.get_root().clone();
+    assert!(collect_blocks(&root).is_empty()); //empty document
+    assert!(collect_leaves(&root).is_empty());
+
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("abc\n\ndef\n");
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>abc</p><p><br></p><p>def</p><p><br></p>"
+    );
+
+    //the soft break <BR> is not a leaf
+    let blocks = collect_blocks(&root);
+    assert_eq!(blocks.len(), 4);
+    for (i, block) in blocks.iter().enumerate() {
+        assert!(Arc::ptr_eq(block, &root.get_child(i).unwrap()));
+    }
+    let leaves = collect_leaves(&root);
+    assert_eq!(leaves.len(), 2);
+    assert!(leaves.iter().all(|n| n.op_len() > 0));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn depth_first_post_order_iter_test() {
+    let doc = DocumentRoot::new("depth_first_post_order_iter_test");
+    assert_eq!(depth_first_post_order_iter(doc.get_root()).count(), 0);
+    create_test_document(&doc);
+
+    let ids: Vec<String> = depth_first_post_order_iter(doc.get_root())
+        .map(|n| id(&n))
+        .collect();
+    assert_eq!(ids.join(","), "E,F,D,H,I,G,B,L,M,K,O,P,N,J,A");
+
+    //same as next_node()
+    let mut nodes = Vec::new();
+    let mut dn_o = Some(first_node(doc.get_root()));
+    while let Some(doc_node) = dn_o {
+        dn_o = next_node(&doc_node);
+        nodes.push(doc_node);
+    }
+    let iter_nodes: Vec<Arc<DocumentNode>> = (&doc).into_iter().collect();
+    assert_eq!(iter_nodes.len(), nodes.len());
+    assert!(iter_nodes
+        .iter()
+        .zip(nodes.iter())
+        .all(|(a, b)| Arc::ptr_eq(a, b)));
+}
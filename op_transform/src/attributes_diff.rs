@@ -0,0 +1,64 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use delta::attributes::Attributes;
+use delta::types::attr_val::AttrVal;
+
+/// # AttributesDiff
+///
+/// The `Attributes` type lives in the `delta` crate, so we add the diff and compose
+/// functions as a trait. With the trait in scope they are called as `Attributes::diff(..)`.
+///
+/// The diff is what a `retain_attr()` operation needs to change the `base` attributes of a
+/// node into the `head` attributes:
+/// ```bash
+/// base = {bold: true, italic: true}
+/// head = {bold: true, color: red}
+/// diff = {italic: null, color: red}
+/// ```
+pub trait AttributesDiff {
+    /// # diff()
+    ///
+    /// Returns the attributes which differ: the new value of keys which are added or
+    /// changed in `head`, and `AttrVal::Null` for keys which are removed from `base`.
+    fn diff(base: &Attributes, head: &Attributes) -> Attributes;
+
+    /// # compose()
+    ///
+    /// Applies the `diff` to the `base` attributes. A `AttrVal::Null` value in the diff
+    /// removes the key.
+    fn compose(base: &Attributes, diff: &Attributes) -> Attributes;
+}
+
+impl AttributesDiff for Attributes {
+    fn diff(base: &Attributes, head: &Attributes) -> Attributes {
+        let mut diff = Attributes::default();
+        for (key, val) in head.iter() {
+            if base.get(key) != Some(val) {
+                diff.insert(key.to_string(), val.clone());
+            }
+        }
+        for (key, _val) in base.iter() {
+            if !head.contains_key(key) {
+                diff.insert(key.to_string(), AttrVal::Null);
+            }
+        }
+        diff
+    }
+
+    fn compose(base: &Attributes, diff: &Attributes) -> Attributes {
+        let mut attr = base.clone();
+        for (key, val) in diff.iter() {
+            if val.is_null() {
+                attr.remove(key);
+            } else {
+                attr.insert(key.to_string(), val.clone());
+            }
+        }
+        attr
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::op_retain::set_cursor_selection;
+use crate::registry::Registry;
+use crate::{op_delete, op_retain};
+use anyhow::Result;
+use core_formats::format_const::NAME_P_BLOCK;
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use delta::types::ops_kind::OpKind;
+use std::sync::RwLockReadGuard;
+
+/// # AUTO_FORMAT_PATTERNS
+///
+/// Typed text at the start of a paragraph, and the block attribute which replaces it.
+/// The longest patterns come first, so `## ` is not seen as `# `.
+const AUTO_FORMAT_PATTERNS: [(&str, &str, AutoFormatValue); 10] = [
+    ("###### ", "heading", AutoFormatValue::Number(6)),
+    ("##### ", "heading", AutoFormatValue::Number(5)),
+    ("#### ", "heading", AutoFormatValue::Number(4)),
+    ("### ", "heading", AutoFormatValue::Number(3)),
+    ("## ", "heading", AutoFormatValue::Number(2)),
+    ("# ", "heading", AutoFormatValue::Number(1)),
+    ("- ", "list", AutoFormatValue::Str("bullet")),
+    ("* ", "list", AutoFormatValue::Str("bullet")),
+    ("1. ", "list", AutoFormatValue::Str("ordered")),
+    ("```", "code-block", AutoFormatValue::Bool(true)),
+];
+
+enum AutoFormatValue {
+    Number(i32),
+    Str(&'static str),
+    Bool(bool),
+}
+
+/// The change made by `apply_auto_format()`
+pub(crate) struct AutoFormatChange {
+    /// delta operations relative to the start of the document
+    pub(crate) changes: Vec<DeltaOperation>,
+    /// the paragraph before the change, see `DocumentRoot::undo_base_range()`
+    pub(crate) undo_base: Delta,
+}
+
+impl AutoFormatChange {
+    /// Returns the inserts and deletes of the change, each with the retain index at which
+    /// it is applied. Retains do not move text, so they are left out.
+    pub(crate) fn edits(&self) -> Vec<(usize, DeltaOperation)> {
+        let mut edits: Vec<(usize, DeltaOperation)> = Vec::new();
+        let mut retain = 0;
+        for op in self.changes.iter() {
+            match op.get_op_kind() {
+                OpKind::Retain(len) => retain += len,
+                OpKind::Delete(_) => edits.push((retain, op.clone())),
+                OpKind::Insert(_) => {
+                    edits.push((retain, op.clone()));
+                    retain += op.op_len();
+                }
+            }
+        }
+        edits
+    }
+}
+
+impl DocumentRoot {
+    /// # set_auto_format()
+    ///
+    /// Enables, or disables, auto formatting of typed patterns at the start of a paragraph:
+    ///  - `# ` .. `###### ` --> header 1 .. 6
+    ///  - `- ` or `* ` --> bullet list
+    ///  - `1. ` --> ordered list
+    ///  - ```` ``` ```` --> code block
+    ///
+    /// Patterns for formats which are not in the registry are ignored.
+    pub fn set_auto_format(&mut self, enabled: bool) {
+        self.auto_format = enabled;
+    }
+
+    pub fn is_auto_format(&self) -> bool {
+        self.auto_format
+    }
+
+    /// # apply_auto_format()
+    ///
+    /// Post insert hook for `apply_operation()`. When the cursor is right behind a pattern
+    /// at the start of a paragraph, the pattern is deleted and the block format is applied.
+    /// A pattern in a protected range, see `protect_range()`, is not changed.
+    ///
+    /// Returns the applied change, or `None` if there is no pattern.
+    ///
+    /// We use the operational transforms directly, and not `apply_operation()`, to prevent
+    /// triggering this hook again. `apply_operation()` records the change as a single undo
+    /// step.
+    pub(crate) fn apply_auto_format(
+        &self,
+        registry: &RwLockReadGuard<'static, Registry>,
+    ) -> Result<Option<AutoFormatChange>> {
+        let block = match self.block_at_cursor() {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        if block.get_formatter().format_name() != NAME_P_BLOCK {
+            return Ok(None);
+        }
+        let (start, len) = match self.get_paragraph_retain_range() {
+            Some(range) => range,
+            None => return Ok(None),
+        };
+
+        let text = block.get_text_content();
+        let retain = self.get_cursor().get_retain_index();
+        for (pattern, key, value) in AUTO_FORMAT_PATTERNS.iter() {
+            let pattern_len = pattern.chars().count();
+            if !text.starts_with(pattern) || retain != start + pattern_len {
+                continue;
+            }
+
+            let mut attr = Attributes::default();
+            match value {
+                AutoFormatValue::Number(n) => attr.insert(*key, *n),
+                AutoFormatValue::Str(s) => attr.insert(*key, *s),
+                AutoFormatValue::Bool(b) => attr.insert(*key, *b),
+            };
+            let block_op = DeltaOperation::insert_attr("\n", attr.clone());
+            match registry.block_format(&block_op) {
+                Ok(format) if format.format_name() != NAME_P_BLOCK => {}
+                _ => return Ok(None),
+            }
+
+            //a protected pattern is left as typed
+            let delete_op = DeltaOperation::delete(pattern_len);
+            if self.check_protected_ranges(start, &delete_op).is_err() {
+                return Ok(None);
+            }
+
+            let undo_base = self.undo_base_range(start, len);
+            let cursor = self.get_cursor();
+            set_cursor_selection(cursor, start, 0);
+            op_delete::delete(cursor, pattern_len, self.batch)?;
+
+            //the block operation is the last character of the paragraph
+            set_cursor_selection(cursor, start + len - pattern_len - 1, 0);
+            let mut op = DeltaOperation::retain(1);
+            op.set_attributes(attr);
+            op_retain::retain(cursor, &op, registry, self.batch)?;
+
+            set_cursor_selection(cursor, start, 0);
+            let mut changes: Vec<DeltaOperation> = Vec::new();
+            if start > 0 {
+                changes.push(DeltaOperation::retain(start));
+            }
+            changes.push(delete_op);
+            if len - pattern_len > 1 {
+                changes.push(DeltaOperation::retain(len - pattern_len - 1));
+            }
+            changes.push(op);
+            return Ok(Some(AutoFormatChange { changes, undo_base }));
+        }
+        Ok(None)
+    }
+}
@@ -10,6 +10,7 @@ use anyhow::Result;
 use dom::dom_element::DomElement;
 use log::error;
 use node_tree::doc_node::DocumentNode;
+use node_tree::tree_traverse::{first_node, next_node};
 use std::sync::Arc;
 
 ///HTML tag
@@ -44,7 +45,13 @@ pub struct AutomaticSoftBreak {}
 
 impl AutomaticSoftBreak {
     /// Uses DomElement manipulations not TreeMorph
-    pub fn insert(doc_node: &Arc<DocumentNode>) -> Result<()> {
+    ///
+    /// While `deferred`, during a batch of operations, nothing is inserted. See
+    /// `insert_deferred()`.
+    pub fn insert(doc_node: &Arc<DocumentNode>, deferred: bool) -> Result<()> {
+        if deferred {
+            return Ok(());
+        }
         //not very nice, but saves quite some tests in the op_transform module
 
         //In some cases we create a new node with a child
@@ -76,8 +83,13 @@ impl AutomaticSoftBreak {
 
     /// The soft break shall be the first child of the parent.
     /// We do a quick check on length 0
-    pub fn remove(doc_node: &Arc<DocumentNode>) -> Result<()> {
+    ///
+    /// While `deferred`, the block may not have a soft break.
+    pub fn remove(doc_node: &Arc<DocumentNode>, deferred: bool) -> Result<()> {
         assert!(!doc_node.get_formatter().is_text_format());
+        if deferred && !AutomaticSoftBreak::has_break(doc_node) {
+            return Ok(());
+        }
         let element = doc_node.get_dom_element().unwrap();
         if let Some(child) = element.get_child(0) {
             assert_eq!(child.node_name(), SOFT_BREAK_TAG);
@@ -93,7 +105,7 @@ impl AutomaticSoftBreak {
         Ok(())
     }
 
-    fn has_break(doc_node: &Arc<DocumentNode>) -> bool {
+    pub(crate) fn has_break(doc_node: &Arc<DocumentNode>) -> bool {
         assert!(!doc_node.get_formatter().is_text_format());
         let parent = doc_node.get_dom_element().unwrap();
         if let Some(child) = parent.get_child(0) {
@@ -103,4 +115,26 @@ impl AutomaticSoftBreak {
         }
         return false;
     }
+
+    /// # insert_deferred()
+    ///
+    /// Inserts a place holder in every empty block of the document which does not have one,
+    /// after the place holders were deferred during a batch of operations.
+    pub(crate) fn insert_deferred(root: &Arc<DocumentNode>) -> Result<()> {
+        if root.child_count() == 0 {
+            return Ok(());
+        }
+        let mut dn_o = Some(first_node(root));
+        while let Some(doc_node) = dn_o {
+            if doc_node.child_count() == 0
+                && doc_node.op_len() > 0
+                && !doc_node.get_formatter().is_text_format()
+                && !AutomaticSoftBreak::has_break(&doc_node)
+            {
+                AutomaticSoftBreak::insert(&doc_node, false)?;
+            }
+            dn_o = next_node(&doc_node);
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,61 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::auto_soft_break::AutomaticSoftBreak;
+use crate::doc_root::DocumentRoot;
+use crate::registry::Registry;
+use anyhow::Result;
+
+impl DocumentRoot {
+    /// # begin_batch()
+    ///
+    /// Starts applying a batch of operations. Until `end_batch()`:
+    ///  - the cursor does not re-calculate the retain index when it moves,
+    ///  - empty blocks do not get the automatic `<BR>` soft break,
+    ///  - the anchor IDs of the headers are not updated, see `FormatTait::has_anchor_id()`,
+    ///  - the browser selection is not set to the document cursor.
+    ///
+    /// `apply_operation()` and `apply_delta()` keep the retain index up to date themselves,
+    /// so a batch of operations gives the same document as applying them one by one.
+    ///
+    /// The batch belongs to this document, so editing another document meanwhile is fine.
+    /// Closing the document ends the batch, without the deferred updates.
+    pub fn begin_batch(&mut self) {
+        self.batch = true;
+        self.get_cursor().set_defer_retain_update(true);
+    }
+
+    /// # end_batch()
+    ///
+    /// Ends the batch started with `begin_batch()`, and does the deferred updates once.
+    /// Without a batch this does nothing.
+    pub fn end_batch(&mut self) -> Result<()> {
+        if !self.batch {
+            return Ok(());
+        }
+        self.batch = false;
+        AutomaticSoftBreak::insert_deferred(self.get_root())?;
+
+        let cursor = self.get_cursor();
+        cursor.set_defer_retain_update(false);
+        if cursor.valid() {
+            cursor.set_retain_index(cursor.calculate_retain_index());
+        }
+        if Registry::get_ref()?.has_anchor_ids() {
+            self.update_anchor_ids();
+        }
+        self.sync_dom_cursor();
+        Ok(())
+    }
+
+    /// # is_batch()
+    ///
+    /// True between `begin_batch()` and `end_batch()`.
+    pub fn is_batch(&self) -> bool {
+        self.batch
+    }
+}
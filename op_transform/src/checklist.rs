@@ -0,0 +1,62 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::error::Error::{NotAChecklistItem, RetainIndexOutOfRange};
+use crate::op_retain::set_cursor_selection;
+use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use node_tree::cursor::Cursor;
+
+/// Attribute key, and the values, of a checklist item
+static LIST_ATTR_KEY: &str = "list";
+static LIST_CHECKED: &str = "checked";
+static LIST_UNCHECKED: &str = "unchecked";
+
+impl DocumentRoot {
+    /// # toggle_checklist_item()
+    ///
+    /// Flips the checklist item at the cursor from checked to unchecked, or the other way
+    /// around. The block gets a retain of its `list` attribute, so the change is a normal
+    /// delta operation. The format creates the `<LI>` again, with the `checked` property of
+    /// its check box set to the new state.
+    ///
+    /// Checklist items are recognised by their `list` attribute, so the `op_transform` crate
+    /// does not need to know the list format.
+    ///
+    /// The cursor of the document is not changed.
+    pub fn toggle_checklist_item(&mut self, cursor: &Cursor) -> Result<()> {
+        let retain_idx = cursor.get_retain_index();
+        let (start, len) = self
+            .block_retain_range_at(retain_idx)
+            .ok_or(RetainIndexOutOfRange { index: retain_idx })?;
+        let end = start + len - 1; //the block operation of the item
+
+        let block_op = self
+            .node_at_retain(end)
+            .ok_or(RetainIndexOutOfRange { index: end })?
+            .get_operation();
+        let toggled = match block_op.get_attributes().get(LIST_ATTR_KEY) {
+            Some(AttrVal::String(s)) if s == LIST_CHECKED => LIST_UNCHECKED,
+            Some(AttrVal::String(s)) if s == LIST_UNCHECKED => LIST_CHECKED,
+            _ => return Err(NotAChecklistItem { index: retain_idx }.into()),
+        };
+        let mut attr = Attributes::default();
+        attr.insert(LIST_ATTR_KEY, toggled);
+        let mut op = DeltaOperation::retain(1);
+        op.set_attributes(attr);
+
+        let retain = self.get_cursor().get_retain_index();
+        let selection = self.get_cursor().selection_length();
+        set_cursor_selection(self.get_cursor(), end, 0);
+        self.apply_operation(op)?;
+        set_cursor_selection(self.get_cursor(), retain, selection);
+        Ok(())
+    }
+}
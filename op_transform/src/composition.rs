@@ -0,0 +1,109 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::{DocumentRoot, EditorMode};
+use crate::error::Error::{DocumentNotOpenForEdit, NoComposition};
+use crate::op_retain::set_cursor_selection;
+use crate::registry::Registry;
+use crate::{op_delete, op_insert};
+use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use node_tree::cursor::Cursor;
+
+/// Attribute key of the composition preview text
+pub static COMPOSING_ATTR_KEY: &str = "composing";
+
+impl DocumentRoot {
+    /// # begin_composition()
+    ///
+    /// Starts an IME composition, as for the browser `compositionstart` event. The composed
+    /// text is inserted at the retain index of the cursor.
+    ///
+    /// A composition started before is ended without a commit: its preview text is removed.
+    /// Closing the document, see `DocumentRoot::close()`, ends the composition too.
+    pub fn begin_composition(&mut self, cursor: &Cursor) -> Result<()> {
+        let mut retain = cursor.get_retain_index();
+        if let Some((start, len)) = self.composition {
+            if retain >= start + len {
+                retain -= len;
+            } else if retain > start {
+                retain = start;
+            }
+            self.remove_composition_preview()?;
+        }
+        self.composition = Some((retain, 0));
+        Ok(())
+    }
+
+    /// # update_composition()
+    ///
+    /// Replaces the preview of the composition by `text`, as for the browser
+    /// `compositionupdate` event. The preview has the attribute `"composing": true`, so it can
+    /// be styled.
+    ///
+    /// The preview is not a change of the document: there is no undo step, and the observer,
+    /// see `observe_subtree()`, is not called. The secondary cursors and the protected ranges
+    /// do move with the preview, and a preview inside a protected range fails.
+    pub fn update_composition(&mut self, text: &str) -> Result<()> {
+        let start = self.remove_composition_preview()?;
+        let mut len = 0;
+        if !text.is_empty() {
+            let mut attr = Attributes::default();
+            attr.insert(COMPOSING_ATTR_KEY, true);
+            let op = DeltaOperation::insert_attr(text, attr);
+            len = op.op_len();
+            self.check_protected_ranges(start, &op)?;
+            let registry = Registry::get_ref()?;
+            op_insert::insert(self.get_cursor(), op.clone(), &registry, self.batch)?;
+            drop(registry);
+            self.shift_cursors(start, &op);
+            self.shift_protected_ranges(start, &op);
+        }
+        self.composition = Some((start, len));
+        self.sync_dom_cursor();
+        Ok(())
+    }
+
+    /// # commit_composition()
+    ///
+    /// Ends the composition, as for the browser `compositionend` event. The preview is
+    /// removed, and `text` is inserted like any other typed text.
+    pub fn commit_composition(&mut self, text: &str) -> Result<()> {
+        self.remove_composition_preview()?;
+        self.composition = None;
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.apply_operation(DeltaOperation::insert(text))
+    }
+
+    /// # is_composing()
+    ///
+    /// True between `begin_composition()` and `commit_composition()`.
+    pub fn is_composing(&self) -> bool {
+        self.composition.is_some()
+    }
+
+    /// Deletes the preview text, and sets the cursor at the start of the composition.
+    /// Returns the retain index of the start.
+    fn remove_composition_preview(&mut self) -> Result<usize> {
+        if self.get_mode() != &EditorMode::Edit {
+            return Err(DocumentNotOpenForEdit.into());
+        }
+        let (start, len) = self.composition.ok_or(NoComposition)?;
+        set_cursor_selection(self.get_cursor(), start, 0);
+        if len > 0 {
+            op_delete::delete(self.get_cursor(), len, self.batch)?;
+            self.composition = Some((start, 0));
+            let op = DeltaOperation::delete(len);
+            self.shift_cursors(start, &op);
+            self.shift_protected_ranges(start, &op);
+        }
+        Ok(start)
+    }
+}
@@ -0,0 +1,352 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::error::Error::{
+    ChangeSetOverlap, FormatDoesNotApply, InvalidDelta, RetainIndexOutOfRange,
+};
+use crate::op_retain::set_cursor_selection;
+use crate::registry::Registry;
+use anyhow::Result;
+use delta::attributes::{compose, Attributes};
+use delta::delta::Delta;
+use delta::document::Document;
+use delta::operations::DeltaOperation;
+use log::error;
+
+impl DocumentRoot {
+    /// # insert_block_before()
+    ///
+    /// Inserts a new empty block, formatted by the format `format_name` with the given
+    /// attributes, before the block containing `retain_idx`.
+    ///
+    /// The cursor is positioned in the new block.
+    pub fn insert_block_before(
+        &mut self,
+        retain_idx: usize,
+        format_name: &str,
+        attrs: Attributes,
+    ) -> Result<()> {
+        let op = DocumentRoot::block_operation(format_name, attrs)?;
+        let (start, _len) = self
+            .block_retain_range_at(retain_idx)
+            .ok_or(RetainIndexOutOfRange { index: retain_idx })?;
+
+        set_cursor_selection(self.get_cursor(), start, 0);
+        self.apply_operation(op)?;
+        set_cursor_selection(self.get_cursor(), start, 0);
+        Ok(())
+    }
+
+    /// # insert_block_after()
+    ///
+    /// Inserts a new empty block, formatted by the format `format_name` with the given
+    /// attributes, after the block containing `retain_idx`.
+    ///
+    /// We split the block right before its own block operation, so the content stays in
+    /// the block with the same format. Then the now empty block at the end gets the new format.
+    ///
+    /// The cursor is positioned in the new block.
+    pub fn insert_block_after(
+        &mut self,
+        retain_idx: usize,
+        format_name: &str,
+        attrs: Attributes,
+    ) -> Result<()> {
+        let op = DocumentRoot::block_operation(format_name, attrs)?;
+        let (start, len) = self
+            .block_retain_range_at(retain_idx)
+            .ok_or(RetainIndexOutOfRange { index: retain_idx })?;
+        let end = start + len - 1; //the block operation of the block
+
+        let block_op = self
+            .node_at_retain(end)
+            .ok_or(RetainIndexOutOfRange { index: end })?
+            .get_operation();
+        let remove = Registry::get_ref()?.block_remove_attr(&block_op)?;
+
+        set_cursor_selection(self.get_cursor(), end, 0);
+        self.apply_operation(DeltaOperation::insert_attr(
+            "\n",
+            block_op.get_attributes().clone(),
+        ))?;
+
+        let mut retain = DeltaOperation::retain(1);
+        retain.set_attributes(compose(&remove, op.get_attributes(), true));
+        set_cursor_selection(self.get_cursor(), end + 1, 0);
+        self.apply_operation(retain)?;
+        set_cursor_selection(self.get_cursor(), end + 1, 0);
+        Ok(())
+    }
+
+    /// # convert_blocks()
+    ///
+    /// Converts all blocks with the format `from_format` to the format `to_format`, with the
+    /// given attributes. Returns the number of converted blocks.
+    ///
+    /// Each block operation gets a retain, which removes the attributes of the old format,
+    /// and adds the new attributes. Retains do not change the length of the document, so
+    /// the retain index of each block is known before the first conversion.
+    ///
+    /// The cursor is not changed.
+    pub fn convert_blocks(
+        &mut self,
+        from_format: &str,
+        to_format: &str,
+        attrs: Attributes,
+    ) -> Result<usize> {
+        let op = DocumentRoot::block_operation(to_format, attrs)?;
+        let mut blocks: Vec<(usize, Attributes)> = Vec::new();
+        {
+            let registry = Registry::get_ref()?;
+            let mut retain: usize = 0;
+            for doc_node in self.non_zero_length_nodes() {
+                let format = doc_node.get_formatter();
+                if !format.is_text_format() && format.format_name() == from_format {
+                    let remove = registry.block_remove_attr(&doc_node.get_operation())?;
+                    blocks.push((retain, compose(&remove, op.get_attributes(), true)));
+                }
+                retain += doc_node.op_len();
+            }
+        }
+
+        let retain = self.get_cursor().get_retain_index();
+        let selection = self.get_cursor().selection_length();
+        for (block_idx, attr) in blocks.iter() {
+            let mut op = DeltaOperation::retain(1);
+            op.set_attributes(attr.clone());
+            set_cursor_selection(self.get_cursor(), *block_idx, 0);
+            self.apply_operation(op)?;
+        }
+        set_cursor_selection(self.get_cursor(), retain, selection);
+        Ok(blocks.len())
+    }
+
+    /// # apply_change_set()
+    ///
+    /// Applies multiple changes `(start_retain, length, replacement)` in one call. Each change
+    /// deletes `length` characters at `start_retain`, and then inserts the `replacement` delta.
+    /// All retain indices refer to the document before any change is applied.
+    ///
+    /// The changes must be sorted by position, and must not overlap. We apply them from back
+    /// to front, so an earlier change does not shift the position of a later change.
+    ///
+    /// The change set is atomic: if one change fails, the document is restored to the content
+    /// before the first change, and the error is returned. The undo history, the secondary
+    /// cursors and the protected ranges are restored too.
+    ///
+    /// The cursor ends behind the replacement of the first change.
+    ///
+    /// An observer, see `observe_subtree()`, is called once per change, in the order applied.
+    pub fn apply_change_set(&mut self, changes: Vec<(usize, usize, Delta)>) -> Result<()> {
+        //The last new line of the document can not be changed
+        let backup = self.to_delta();
+        let last = Delta::document_length(&backup) - 1;
+        let mut end: usize = 0;
+        for (index, (start, length, _replacement)) in changes.iter().enumerate() {
+            if *start < end {
+                return Err(ChangeSetOverlap { index }.into());
+            }
+            end = start + length;
+            if end > last {
+                return Err(RetainIndexOutOfRange { index: end }.into());
+            }
+        }
+
+        //The observer only sees the change set when it is applied completely
+        let observer = self.observer.take();
+        //the rollback reopens the document, see close(), which resets these
+        let history = self.history.clone();
+        let cursors = self.cursors.clone();
+        let protected_ranges = self.protected_ranges.clone();
+        let retain = self.get_cursor().get_retain_index();
+        let selection = self.get_cursor().selection_length();
+        let mut applied: Vec<Vec<DeltaOperation>> = Vec::new();
+        for (start, length, replacement) in changes.into_iter().rev() {
+            let mut ops: Vec<DeltaOperation> = Vec::new();
+            if start > 0 {
+                ops.push(DeltaOperation::retain(start));
+            }
+            if length > 0 {
+                ops.push(DeltaOperation::delete(length));
+            }
+            ops.append(&mut replacement.get_ops());
+            applied.push(ops);
+
+            if let Err(e) = self.apply_change(start, length, replacement) {
+                error!("DocumentRoot::apply_change_set() - rollback, {}", e);
+                let restored = self.replace_content(backup);
+                self.observer = observer;
+                self.history = history;
+                self.cursors = cursors;
+                self.protected_ranges = protected_ranges;
+                set_cursor_selection(self.get_cursor(), retain, selection);
+                restored?;
+                return Err(e);
+            }
+        }
+        self.observer = observer;
+        for ops in applied.iter() {
+            self.notify_changes(ops);
+        }
+        Ok(())
+    }
+
+    fn apply_change(&mut self, start: usize, length: usize, replacement: Delta) -> Result<()> {
+        set_cursor_selection(self.get_cursor(), start, 0);
+        if length > 0 {
+            self.apply_operation(DeltaOperation::delete(length))?;
+        }
+        for op in replacement.get_ops() {
+            self.apply_operation(op)?;
+        }
+        Ok(())
+    }
+
+    /// # split_document_at()
+    ///
+    /// Returns two new documents. The first with the content before `retain_idx`, and the
+    /// second with the content from `retain_idx` to the end. This document is not changed.
+    ///
+    /// Blocks are not split: we split at the block boundary nearest to `retain_idx`. So both
+    /// documents are valid documents, ending with a block operation.
+    ///
+    /// The new documents get the ID of this document, with the suffix `-1` and `-2`.
+    /// Their cursor is at the start of the document.
+    pub fn split_document_at(&self, retain_idx: usize) -> Result<(DocumentRoot, DocumentRoot)> {
+        let (start, len) = self
+            .block_retain_range_at(retain_idx)
+            .ok_or(RetainIndexOutOfRange { index: retain_idx })?;
+        let boundary = if retain_idx - start <= start + len - retain_idx {
+            start
+        } else {
+            start + len
+        };
+
+        let (first, second) = split_delta(self.to_delta(), boundary)?;
+        let id = self
+            .get_container_element()
+            .get_attribute("id")
+            .unwrap_or_default();
+        Ok((
+            DocumentRoot::from_delta(&format!("{}-1", id), first)?,
+            DocumentRoot::from_delta(&format!("{}-2", id), second)?,
+        ))
+    }
+
+    /// # merge_document()
+    ///
+    /// Appends the content of the other document to this document. This is the inverse of
+    /// `split_document_at()`. The cursor is at the start of the document.
+    pub fn merge_document(&mut self, other: DocumentRoot) -> Result<()> {
+        let mut delta = self.to_delta();
+        for op in other.to_delta().get_ops() {
+            delta.push(op);
+        }
+        self.replace_content(delta)?;
+        self.reset_cursor();
+        Ok(())
+    }
+
+    /// Opens a new document with the content of the delta. An empty delta gives an empty document.
+    fn from_delta(id: &str, delta: Delta) -> Result<DocumentRoot> {
+        let mut doc = DocumentRoot::new(id);
+        doc.open()?;
+        if !delta.get_ops_ref().is_empty() {
+            doc.replace_content(delta)?;
+        }
+        doc.reset_cursor();
+        Ok(doc)
+    }
+
+    /// # replace_content()
+    ///
+    /// Replaces the document content by the `document` delta, as returned by `to_delta()`.
+    /// The document must end with a new line, else `InvalidDelta` is returned.
+    ///
+    /// A newly opened document already has the last `<P>` block. So the last new line of the
+    /// document is applied as a retain, to set the attributes of that last block.
+    pub(crate) fn replace_content(&mut self, document: Delta) -> Result<()> {
+        let mut ops = document.get_ops();
+        let last = ops.pop();
+
+        let mut delta = Delta::default();
+        for op in ops {
+            delta.push(op);
+        }
+        if let Some(last) = last {
+            let txt = last
+                .insert_value()
+                .str_val()
+                .unwrap_or_default()
+                .to_string();
+            let Some(text) = txt.strip_suffix('\n') else {
+                return Err(InvalidDelta {
+                    msg: "the document does not end with a new line".to_string(),
+                }
+                .into());
+            };
+            if !text.is_empty() {
+                delta.insert_attr(text, last.get_attributes().clone());
+            }
+            delta.retain_attr(1, last.get_attributes().clone());
+        }
+
+        self.open()?;
+        self.apply_delta(delta)
+    }
+
+    /// # block_operation()
+    ///
+    /// Returns the block operation `insert("\n")` with the attributes. The format with
+    /// the name `format_name` must exist, and it must handle this operation.
+    fn block_operation(format_name: &str, attrs: Attributes) -> Result<DeltaOperation> {
+        let op = DeltaOperation::insert_attr("\n", attrs);
+        let format = Registry::get_mut_ref()?.block_format_from_name(format_name)?;
+        if !format.applies(&op)? {
+            return Err(FormatDoesNotApply {
+                fmt: format_name.to_string(),
+                op,
+            }
+            .into());
+        }
+        Ok(op)
+    }
+}
+
+/// # split_delta()
+///
+/// Splits a delta document at the retain index. Text inserts are split, embeds have length 1,
+/// so they are never split.
+fn split_delta(delta: Delta, at: usize) -> Result<(Delta, Delta)> {
+    let mut first = Delta::default();
+    let mut second = Delta::default();
+    let mut retain: usize = 0;
+    for op in delta.get_ops() {
+        let len = op.op_len();
+        if retain + len <= at {
+            first.push(op);
+        } else if retain >= at {
+            second.push(op);
+        } else {
+            let txt = op.insert_value().str_val()?;
+            let split = at - retain;
+            let left: String = txt.chars().take(split).collect();
+            let right: String = txt.chars().skip(split).collect();
+            first.push(DeltaOperation::insert_attr(
+                left,
+                op.get_attributes().clone(),
+            ));
+            second.push(DeltaOperation::insert_attr(
+                right,
+                op.get_attributes().clone(),
+            ));
+        }
+        retain += len;
+    }
+    Ok((first, second))
+}
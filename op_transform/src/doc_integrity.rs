@@ -0,0 +1,124 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::auto_soft_break::AutomaticSoftBreak;
+use crate::doc_root::DocumentRoot;
+use delta::delta::Delta;
+use delta::document::Document;
+use node_tree::doc_node::DocumentNode;
+use node_tree::tree_traverse::MAX_TREE_DEPTH;
+use std::sync::Arc;
+
+impl DocumentRoot {
+    /// # validate_tree_integrity()
+    ///
+    /// Checks the invariants of the document node tree, and returns a description of each
+    /// violation found. An empty vector means the tree is valid.
+    ///
+    /// Checks:
+    ///  - every node points to the parent which lists it as a child
+    ///  - nodes without children are text nodes, or empty blocks with a `<BR>` soft break
+    ///  - nodes with children are blocks, not text nodes
+    ///  - no node has a zero length operation and no children
+    ///  - the document ends with a block operation, at retain index `document_length - 1`
+    ///
+    /// This walks the whole tree, so it is not called by the edit operations. Use it when
+    /// debugging, or in tests.
+    pub fn validate_tree_integrity(&self) -> Vec<String> {
+        let mut violations: Vec<String> = Vec::new();
+        let mut nodes: Vec<Arc<DocumentNode>> = Vec::new();
+        collect_post_order(self.get_root(), 0, &mut nodes, &mut violations);
+
+        //a closed document has no nodes
+        if nodes.is_empty() {
+            return violations;
+        }
+        for node in nodes.iter() {
+            check_node(node, &mut violations);
+        }
+
+        //the retain index calculation traverses the tree, which must be consistent for that
+        if !violations.is_empty() {
+            return violations;
+        }
+        match nodes.iter().rev().find(|n| n.op_len() > 0) {
+            Some(last) if !last.get_formatter().is_text_format() => {
+                let retain = last.retain_index();
+                let length = Delta::document_length(&self.to_delta());
+                if retain + 1 != length {
+                    violations.push(format!(
+                        "The last block is at retain index {}, but the document length is {}",
+                        retain, length
+                    ));
+                }
+            }
+            Some(last) => violations.push(format!(
+                "The document does not end with a block operation, but with: {:?}",
+                last.get_operation()
+            )),
+            None => violations.push("The document has no operations".to_string()),
+        }
+        violations
+    }
+}
+
+/// Collects the nodes below `node` in post order, like `next_node()`. We do not use
+/// `next_node()` since it panics on the broken trees we are looking for.
+fn collect_post_order(
+    node: &Arc<DocumentNode>,
+    depth: usize,
+    nodes: &mut Vec<Arc<DocumentNode>>,
+    violations: &mut Vec<String>,
+) {
+    if depth > MAX_TREE_DEPTH {
+        violations.push(format!(
+            "The tree is deeper than {}, is there a cycle?",
+            MAX_TREE_DEPTH
+        ));
+        return;
+    }
+    for child in node.get_children() {
+        match child.get_parent() {
+            None => violations.push(format!("Node without parent: {:?}", child.get_operation())),
+            Some(parent) if !Arc::ptr_eq(&parent, node) => violations.push(format!(
+                "Node is listed as child of a node which is not its parent: {:?}",
+                child.get_operation()
+            )),
+            _ => {}
+        }
+        if child.is_orphaned() {
+            violations.push(format!("Orphaned node: {:?}", child.get_operation()));
+        }
+        collect_post_order(&child, depth + 1, nodes, violations);
+        nodes.push(child);
+    }
+}
+
+fn check_node(node: &Arc<DocumentNode>, violations: &mut Vec<String>) {
+    let is_text = node.get_formatter().is_text_format();
+    if node.child_count() > 0 {
+        //a text wrapper, like the <A> of a link, has a zero length operation
+        if is_text && node.op_len() > 0 {
+            violations.push(format!(
+                "Text node with children: {:?}",
+                node.get_operation()
+            ));
+        }
+        return;
+    }
+    if node.op_len() == 0 {
+        violations.push(format!(
+            "Node with a zero length operation and no children: {:?}",
+            node.get_operation()
+        ));
+    } else if !is_text && !AutomaticSoftBreak::has_break(node) {
+        violations.push(format!(
+            "Empty block without a <BR> soft break: {:?}",
+            node.get_operation()
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use delta::types::ops_kind::OpKind;
+use std::rc::Rc;
+
+/// Function called with the change applied to the document
+pub type ChangeObserver = Rc<dyn Fn(&[DeltaOperation])>;
+
+impl DocumentRoot {
+    /// # observe_subtree()
+    ///
+    /// Registers a callback which is called after each `apply_operation()` with the change
+    /// applied to the document. For example for collaborative editing using a CRDT.
+    ///
+    /// The change is a delta relative to the start of the document:
+    ///  - insert: `[retain(position), insert(content, attributes)]`
+    ///  - delete: `[retain(position), delete(length)]`
+    ///  - format: `[retain(position), retain(length, attributes)]`
+    ///
+    /// The change is the delta as applied, which may differ from the applied operation. The
+    /// new lines of a multi line insert only get the block attributes, and the text only the
+    /// other attributes. So `insert("a\nb", {bold: true})` is reported as
+    /// `[insert("a", {bold: true}), insert("\n"), insert("b", {bold: true})]`.
+    ///
+    /// The leading retain is left out at position 0. A retain without attributes only moves
+    /// the cursor, so it is not reported. When auto formatting replaces a typed pattern, that
+    /// change is reported in a second call. See `set_auto_format()`.
+    ///
+    /// There is only one observer; a new observer replaces the previous one.
+    pub fn observe_subtree(&mut self, callback: impl Fn(&[DeltaOperation]) + 'static) {
+        self.observer = Some(Rc::new(callback));
+    }
+
+    /// # unobserve()
+    ///
+    /// Removes the callback registered with `observe_subtree()`
+    pub fn unobserve(&mut self) {
+        self.observer = None;
+    }
+
+    /// # notify_observer()
+    ///
+    /// Translates the delta applied at retain index `start` into a change for the observer.
+    pub(crate) fn notify_observer(&self, start: usize, applied: Delta) {
+        let ops = applied.get_ops();
+        let moves_only = ops.iter().all(|op| {
+            matches!(op.get_op_kind(), OpKind::Retain(_)) && op.get_attributes().is_empty()
+        });
+        if moves_only {
+            return;
+        }
+        let mut changes: Vec<DeltaOperation> = Vec::new();
+        if start > 0 {
+            changes.push(DeltaOperation::retain(start));
+        }
+        changes.extend(ops);
+        self.notify_changes(&changes);
+    }
+
+    pub(crate) fn notify_changes(&self, changes: &[DeltaOperation]) {
+        if let Some(observer) = &self.observer {
+            observer(changes);
+        }
+    }
+}
@@ -0,0 +1,565 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::error::Error::RetainIndexOutOfRange;
+use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use node_tree::cursor::CursorLocation;
+use node_tree::doc_node::DocumentNode;
+use node_tree::tree_traverse::{
+    find_by_retain_index, first_node, is_doc_root, next_node, next_node_non_zero_length,
+};
+use std::sync::Arc;
+
+/// Format name, and attribute key, of the link format in the link crate
+static LINK_FORMAT: &str = "link";
+static LINK_ATTR_KEY: &str = "link";
+/// Insert map keys of the embeds, see the image and template_var crates
+static IMAGE_EMBED: &str = "image";
+static TEMPLATE_VAR_EMBED: &str = "template-var";
+
+impl DocumentRoot {
+    /// # get_paragraph_at_cursor()
+    ///
+    /// Returns the delta of the block (paragraph, heading, list item, ...) which contains
+    /// the start of the cursor. The block operation itself is the last operation in the delta.
+    ///
+    /// For a list item only the content of that `<LI>` is returned, not the whole list.
+    pub fn get_paragraph_at_cursor(&self) -> Option<Delta> {
+        let block = self.block_at_cursor()?;
+        let mut delta = Delta::default();
+        for doc_node in collect_sub_tree(&block) {
+            if doc_node.op_len() > 0 {
+                delta.push(doc_node.get_operation());
+            }
+        }
+        Some(delta)
+    }
+
+    /// # get_paragraph_text_at_cursor()
+    ///
+    /// Returns the plain text of the block which contains the start of the cursor.
+    /// Returns an empty string if there is no such block.
+    pub fn get_paragraph_text_at_cursor(&self) -> String {
+        match self.block_at_cursor() {
+            Some(block) => block.get_text_content(),
+            None => String::new(),
+        }
+    }
+
+    /// # get_paragraph_retain_range()
+    ///
+    /// Returns `(retain_index, length)` of the block which contains the start of the cursor.
+    /// The length includes the block operation itself (the new line).
+    pub fn get_paragraph_retain_range(&self) -> Option<(usize, usize)> {
+        let block = self.block_at_cursor()?;
+        let length: usize = collect_sub_tree(&block).iter().map(|dn| dn.op_len()).sum();
+
+        let mut retain: usize = 0;
+        let mut dn_o = Some(first_node(self.get_root()));
+        while let Some(doc_node) = dn_o {
+            retain += doc_node.op_len();
+            if Arc::ptr_eq(&doc_node, &block) {
+                return Some((retain - length, length));
+            }
+            dn_o = next_node(&doc_node);
+        }
+        None
+    }
+
+    /// # formats_at_cursor()
+    ///
+    /// Returns `(block_format_name, text_attributes)` at the start of the cursor, to show the
+    /// state of the toolbar buttons. The text attributes are the attribute keys of the text
+    /// node at the cursor, which are not `null` or `false`. So `["bold", "italic"]` for bold
+    /// italic text in a list item with block format name `"UL_BLOCK"`.
+    ///
+    /// The tree is not changed, we only walk from the cursor up to its block.
+    pub fn formats_at_cursor(&self) -> (Option<String>, Vec<String>) {
+        let block = self.block_at_cursor();
+        let block_name = block
+            .as_ref()
+            .map(|b| b.get_formatter().format_name().to_string());
+
+        let mut text_formats: Vec<String> = Vec::new();
+        if block.is_some() {
+            let doc_node = self.get_cursor().get_location().doc_node();
+            if doc_node.get_formatter().is_text_format() {
+                for (key, val) in doc_node.get_operation().get_attributes().iter() {
+                    if *val != AttrVal::Null && *val != AttrVal::Bool(false) {
+                        text_formats.push(key.to_string());
+                    }
+                }
+            }
+        }
+        (block_name, text_formats)
+    }
+
+    /// # word_at_cursor()
+    ///
+    /// Returns `(word, start_retain, end_retain)` of the word at the cursor, where the end
+    /// is just after the last character. The cursor may be at the start, or at the end
+    /// of the word. Returns `None` if the cursor is in white space, or in punctuation.
+    ///
+    /// Word characters are the unicode alphanumeric characters and `_`. A word may span
+    /// several text nodes, for example when part of the word is bold.
+    pub fn word_at_cursor(&self) -> Option<(String, usize, usize)> {
+        let (block_start, chars) = self.block_chars_at_cursor()?;
+        let at = self.get_cursor().get_retain_index() - block_start;
+
+        let is_word = |i: usize| chars.get(i).is_some_and(|c| is_word_char(*c));
+        if !is_word(at) && (at == 0 || !is_word(at - 1)) {
+            return None;
+        }
+        let mut start = at;
+        while start > 0 && is_word(start - 1) {
+            start -= 1;
+        }
+        let mut end = at;
+        while is_word(end) {
+            end += 1;
+        }
+        let word: String = chars[start..end].iter().collect();
+        Some((word, block_start + start, block_start + end))
+    }
+
+    /// # sentence_at_cursor()
+    ///
+    /// Returns `(sentence, start_retain, end_retain)` of the sentence at the cursor, where
+    /// the end is just after the last character. A sentence ends with `.`, `!` or `?`
+    /// followed by white space, or at the end of the block.
+    ///
+    /// Returns `None` if the cursor is in the white space between two sentences, or in an
+    /// empty block.
+    pub fn sentence_at_cursor(&self) -> Option<(String, usize, usize)> {
+        let (block_start, chars) = self.block_chars_at_cursor()?;
+        let at = self.get_cursor().get_retain_index() - block_start;
+
+        let mut start = 0;
+        while start < chars.len() {
+            while start < chars.len() && chars[start].is_whitespace() {
+                start += 1;
+            }
+            if start == chars.len() || at < start {
+                return None;
+            }
+            let mut end = start;
+            while end < chars.len() {
+                end += 1;
+                if ['.', '!', '?'].contains(&chars[end - 1])
+                    && chars.get(end).is_none_or(|c| c.is_whitespace())
+                {
+                    break;
+                }
+            }
+            let mut last = end;
+            while chars[last - 1].is_whitespace() {
+                last -= 1;
+            }
+            if at <= last {
+                let sentence: String = chars[start..last].iter().collect();
+                return Some((sentence, block_start + start, block_start + last));
+            }
+            start = end;
+        }
+        None
+    }
+
+    /// Returns the retain index of the block at the cursor, and the characters of that block
+    /// without the new line. An embed, like an image, is an object replacement character.
+    fn block_chars_at_cursor(&self) -> Option<(usize, Vec<char>)> {
+        let block = self.block_at_cursor()?;
+        let (block_start, _length) = self.get_paragraph_retain_range()?;
+        let mut chars: Vec<char> = Vec::new();
+        for doc_node in collect_sub_tree(&block) {
+            if Arc::ptr_eq(&doc_node, &block) || doc_node.op_len() == 0 {
+                continue;
+            }
+            let op = doc_node.get_operation();
+            match op.insert_value().str_val() {
+                Ok(txt) if op.insert_value().is_string() => chars.extend(txt.chars()),
+                _ => chars.extend(std::iter::repeat('\u{FFFC}').take(op.op_len())),
+            }
+        }
+        Some((block_start, chars))
+    }
+
+    /// # get_format_ranges()
+    ///
+    /// Returns `(retain_index, length, format_name)` for all continuous ranges of the document
+    /// which have the same format, and the same attributes. So plain text and bold text are
+    /// separate ranges, both with the text format name.
+    ///
+    /// Nodes with zero length, like the `<UL>` wrapper of a list, are not reported.
+    pub fn get_format_ranges(&self) -> Vec<(usize, usize, String)> {
+        let mut ranges: Vec<(usize, usize, String)> = Vec::new();
+        let mut last_attr = Attributes::default();
+        let mut retain: usize = 0;
+        for doc_node in self.non_zero_length_nodes() {
+            let name = doc_node.get_formatter().format_name();
+            let op = doc_node.get_operation();
+            let len = doc_node.op_len();
+            match ranges.last_mut() {
+                Some(range) if range.2 == name && &last_attr == op.get_attributes() => {
+                    range.1 += len;
+                }
+                _ => {
+                    ranges.push((retain, len, name.to_string()));
+                    last_attr = op.get_attributes().clone();
+                }
+            }
+            retain += len;
+        }
+        ranges
+    }
+
+    /// # get_attribute_ranges()
+    ///
+    /// Returns `(retain_index, length, value)` for all continuous ranges of the document which
+    /// have the same value for the attribute `attr_key`. Ranges without the attribute are
+    /// not reported.
+    pub fn get_attribute_ranges(&self, attr_key: &str) -> Vec<(usize, usize, AttrVal)> {
+        let mut ranges: Vec<(usize, usize, AttrVal)> = Vec::new();
+        let mut retain: usize = 0;
+        for doc_node in self.non_zero_length_nodes() {
+            let op = doc_node.get_operation();
+            let len = doc_node.op_len();
+            if let Some(val) = op.get_attributes().get(attr_key) {
+                match ranges.last_mut() {
+                    Some(range) if range.0 + range.1 == retain && &range.2 == val => {
+                        range.1 += len;
+                    }
+                    _ => ranges.push((retain, len, val.clone())),
+                }
+            }
+            retain += len;
+        }
+        ranges
+    }
+
+    /// # get_block_at_retain()
+    ///
+    /// Returns the block node (paragraph, heading, list item, ...) which contains the retain
+    /// index. A retain index of a block operation, the `"\n"`, returns that block.
+    /// Returns `None` for a retain index beyond the end of the document.
+    pub fn get_block_at_retain(&self, retain_idx: usize) -> Option<Arc<DocumentNode>> {
+        block_of(&self.node_at_retain(retain_idx)?)
+    }
+
+    /// # get_top_level_block_at_retain()
+    ///
+    /// Returns the child of the root node which contains the retain index. For a list item
+    /// this is the `<UL>` or `<OL>` list, for a paragraph it is the paragraph itself.
+    pub fn get_top_level_block_at_retain(&self, retain_idx: usize) -> Option<Arc<DocumentNode>> {
+        let mut node = self.node_at_retain(retain_idx)?;
+        loop {
+            let parent = node.get_parent()?;
+            if is_doc_root(&parent) {
+                return Some(node);
+            }
+            node = parent;
+        }
+    }
+
+    /// # get_delta_slice()
+    ///
+    /// Returns the content of the retain range `[start, start + length)` as a delta. Text
+    /// operations are cut at both ends of the range, block operations within the range keep
+    /// their attributes. So unlike `get_paragraph_at_cursor()` the range may span blocks.
+    ///
+    /// The slice of the whole document equals `to_delta()`, an empty range gives an
+    /// empty delta.
+    pub fn get_delta_slice(&self, start: usize, length: usize) -> Delta {
+        self.get_delta_at_range(start, length)
+    }
+
+    /// # get_delta_at_range()
+    ///
+    /// Same as `get_delta_slice()`. The start of the range is found with
+    /// `find_by_retain_index()`, from there we take nodes until the length is used up.
+    ///
+    /// The delta is a document of its own, for example the content to copy. Applied to an
+    /// empty document it gives the content of the range.
+    pub fn get_delta_at_range(&self, start: usize, length: usize) -> Delta {
+        let mut delta = Delta::default();
+        let Some((mut doc_node, mut from)) = find_by_retain_index(self.get_root(), start) else {
+            return delta;
+        };
+        let mut remaining = length;
+        while remaining > 0 {
+            let len = doc_node.op_len();
+            if from < len {
+                let to = len.min(from + remaining);
+                let op = doc_node.get_operation();
+                if from == 0 && to == len {
+                    delta.push(op);
+                } else if let Ok(text) = op.insert_value().str_val() {
+                    //embeds have length 1, so only text can be cut
+                    let part: String = text.chars().skip(from).take(to - from).collect();
+                    let mut part = DeltaOperation::insert(part.as_str());
+                    part.set_attributes(op.get_attributes().clone());
+                    delta.push(part);
+                }
+                remaining -= to - from;
+            }
+            from = 0;
+            doc_node = match next_node_non_zero_length(&doc_node) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+        delta
+    }
+
+    /// # split_document_at_cursor()
+    ///
+    /// Returns the document before, and after the cursor as two deltas, see
+    /// `get_delta_at_range()`. The document itself is not changed.
+    ///
+    /// Both halves are documents of their own, see `load()`. When the cursor is inside a
+    /// block, the first half ends with a copy of that block operation, so both halves keep
+    /// the block format. At the start of the document the first half is empty, and at the
+    /// end of the document, before its last new line, the second half is empty.
+    pub fn split_document_at_cursor(&self) -> Result<(Delta, Delta)> {
+        let retain = self.get_cursor().get_retain_index();
+        let full = self.to_delta();
+        let document_length = Delta::document_length(&full);
+        if retain > document_length {
+            return Err(RetainIndexOutOfRange { index: retain }.into());
+        }
+        if retain + 1 >= document_length {
+            return Ok((full, Delta::default()));
+        }
+        let mut before = self.get_delta_at_range(0, retain);
+        let after = self.get_delta_at_range(retain, document_length - retain);
+        if let Some((block_start, _)) = self.block_retain_range_at(retain) {
+            if block_start < retain {
+                if let Some(block) = self.get_block_at_retain(retain) {
+                    before.push(block.get_operation());
+                }
+            }
+        }
+        Ok((before, after))
+    }
+
+    /// # get_plain_text()
+    ///
+    /// Returns the text of the document without formatting, with a `\n` for the end of
+    /// every block. An embed (image, mention, video, ...) is a single space, see
+    /// `get_plain_text_with_placeholder()`.
+    pub fn get_plain_text(&self) -> String {
+        self.get_plain_text_with_placeholder(" ")
+    }
+
+    /// # get_plain_text_with_placeholder()
+    ///
+    /// Same as `get_plain_text()`, where an embed is replaced by the `placeholder`.
+    pub fn get_plain_text_with_placeholder(&self, placeholder: &str) -> String {
+        let mut text = String::new();
+        if self.get_root().child_count() == 0 {
+            return text;
+        }
+        let mut dn_o = Some(first_node(self.get_root()));
+        while let Some(doc_node) = dn_o {
+            //containers, like the <A> of a link, and soft breaks have no length
+            if doc_node.op_len() > 0 {
+                if !doc_node.get_formatter().is_text_format() {
+                    text.push('\n');
+                } else {
+                    let op = doc_node.get_operation();
+                    match op.insert_value().str_val() {
+                        Ok(s) => text.push_str(s),
+                        Err(_) => text.push_str(placeholder),
+                    }
+                }
+            }
+            dn_o = next_node(&doc_node);
+        }
+        text
+    }
+
+    /// # extract_links()
+    ///
+    /// Returns `(url, start_retain, end_retain)` for all links in the document. The retain
+    /// range spans all text of the link, also when part of the link text is formatted.
+    ///
+    /// A link is the zero length `<A>` wrapper node of the link format, with the url in its
+    /// `link` attribute. In document order the wrapper comes after its children.
+    pub fn extract_links(&self) -> Vec<(String, usize, usize)> {
+        let mut links: Vec<(String, usize, usize)> = Vec::new();
+        if self.get_root().child_count() == 0 {
+            return links;
+        }
+        let mut retain: usize = 0;
+        let mut dn_o = Some(first_node(self.get_root()));
+        while let Some(doc_node) = dn_o {
+            retain += doc_node.op_len();
+            let op = doc_node.get_operation();
+            if doc_node.get_formatter().format_name() == LINK_FORMAT && doc_node.op_len() == 0 {
+                if let Some(url) = op.get_attributes().get(LINK_ATTR_KEY) {
+                    let url = url.str_val().unwrap_or_default().to_string();
+                    let len: usize = collect_sub_tree(&doc_node).iter().map(|n| n.op_len()).sum();
+                    links.push((url, retain - len, retain));
+                }
+            }
+            dn_o = next_node(&doc_node);
+        }
+        links
+    }
+
+    /// # extract_images()
+    ///
+    /// Returns `(src_url, retain_index)` for all images in the document.
+    pub fn extract_images(&self) -> Vec<(String, usize)> {
+        self.embed_values(IMAGE_EMBED)
+    }
+
+    /// # get_template_vars()
+    ///
+    /// Returns `(variable_name, retain_index)` for all template variables in the document.
+    pub fn get_template_vars(&self) -> Vec<(String, usize)> {
+        self.embed_values(TEMPLATE_VAR_EMBED)
+    }
+
+    /// Returns `(value, retain_index)` for all embeds `insert: {embed: value}` in the document
+    fn embed_values(&self, embed: &str) -> Vec<(String, usize)> {
+        let mut values: Vec<(String, usize)> = Vec::new();
+        let mut retain: usize = 0;
+        for doc_node in self.non_zero_length_nodes() {
+            let op = doc_node.get_operation();
+            if op.insert_value().is_map() {
+                if let Ok(map) = op.insert_value().map_val() {
+                    if let Some(Ok(value)) = map.get(embed).map(|v| v.str_val()) {
+                        values.push((value.to_string(), retain));
+                    }
+                }
+            }
+            retain += doc_node.op_len();
+        }
+        values
+    }
+
+    /// # non_zero_length_nodes()
+    ///
+    /// Returns all nodes which have a delta operation, in document order
+    pub(crate) fn non_zero_length_nodes(&self) -> Vec<Arc<DocumentNode>> {
+        let mut nodes: Vec<Arc<DocumentNode>> = Vec::new();
+        if self.get_root().child_count() == 0 {
+            return nodes;
+        }
+        let mut dn_o = Some(first_node(self.get_root()));
+        while let Some(doc_node) = dn_o {
+            if doc_node.op_len() > 0 {
+                nodes.push(doc_node.clone());
+            }
+            dn_o = next_node(&doc_node);
+        }
+        nodes
+    }
+
+    /// # block_retain_range_at()
+    ///
+    /// Returns `(retain_index, length)` of the block which contains the given retain index.
+    /// The length includes the block operation itself (the new line).
+    pub(crate) fn block_retain_range_at(&self, retain_idx: usize) -> Option<(usize, usize)> {
+        if self.get_root().child_count() == 0 {
+            return None;
+        }
+        let mut retain: usize = 0;
+        let mut block_start: usize = 0;
+        let mut dn_o = Some(first_node(self.get_root()));
+        while let Some(doc_node) = dn_o {
+            retain += doc_node.op_len();
+            if !doc_node.get_formatter().is_text_format() && doc_node.op_len() > 0 {
+                if retain_idx < retain {
+                    return Some((block_start, retain - block_start));
+                }
+                block_start = retain;
+            }
+            dn_o = next_node(&doc_node);
+        }
+        None
+    }
+
+    /// # node_at_retain()
+    ///
+    /// Returns the document node with non zero length, which contains the retain index.
+    pub(crate) fn node_at_retain(&self, retain_idx: usize) -> Option<Arc<DocumentNode>> {
+        if self.get_root().child_count() == 0 {
+            return None;
+        }
+        let mut retain: usize = 0;
+        let mut dn_o = Some(first_node(self.get_root()));
+        while let Some(doc_node) = dn_o {
+            retain += doc_node.op_len();
+            if retain_idx < retain {
+                return Some(doc_node);
+            }
+            dn_o = next_node(&doc_node);
+        }
+        None
+    }
+
+    /// # block_at_cursor()
+    ///
+    /// Returns the block node containing the start of the cursor.
+    pub(crate) fn block_at_cursor(&self) -> Option<Arc<DocumentNode>> {
+        if self.get_root().child_count() == 0 {
+            return None;
+        }
+        let doc_node = match self.get_cursor().get_location() {
+            CursorLocation::None => return None,
+            location => location.doc_node(),
+        };
+        block_of(&doc_node)
+    }
+}
+
+/// # block_of()
+///
+/// Walks up the tree until we find a block node. Zero length wrappers like `<A>` or `<UL>`
+/// are skipped.
+pub(crate) fn block_of(doc_node: &Arc<DocumentNode>) -> Option<Arc<DocumentNode>> {
+    let mut node = doc_node.clone();
+    loop {
+        if is_doc_root(&node) {
+            return None;
+        }
+        if !node.get_formatter().is_text_format() && node.op_len() > 0 {
+            return Some(node);
+        }
+        node = node.get_parent()?;
+    }
+}
+
+/// # is_empty_block()
+///
+/// Returns true when the block has no content. Zero length children, like the wrapper of a
+/// nested list, are not content.
+pub(crate) fn is_empty_block(block: &Arc<DocumentNode>) -> bool {
+    block.get_children().iter().all(|c| c.op_len() == 0)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// # collect_sub_tree()
+///
+/// Returns all nodes of the sub tree, in document order. So the input node comes last.
+fn collect_sub_tree(doc_node: &Arc<DocumentNode>) -> Vec<Arc<DocumentNode>> {
+    let mut nodes: Vec<Arc<DocumentNode>> = Vec::new();
+    for c in doc_node.get_children().iter() {
+        nodes.append(&mut collect_sub_tree(c));
+    }
+    nodes.push(doc_node.clone());
+    nodes
+}
@@ -1,21 +1,33 @@
 use crate::auto_soft_break::AutomaticSoftBreak;
-use crate::error::Error::DocumentNotOpenForEdit;
+use crate::doc_observer::ChangeObserver;
+use crate::error::Error::{DocumentClosed, DocumentNotOpenForEdit, InvalidDelta};
+use crate::keyboard::KeyboardShortcuts;
+use crate::multi_cursor::CursorId;
+use crate::protected_range::ProtectedRangeId;
 use crate::registry::Registry;
+use crate::undo::UndoHistory;
 use crate::{init_log, op_delete, op_insert, op_retain, set_panic_hook};
 use anyhow::Result;
+use delta::attributes::Attributes;
 use delta::delta::Delta;
 use delta::operations::DeltaOperation;
 use delta::types::ops_kind::OpKind;
+use dom::constants::DOCUMENT;
 use dom::dom_element::{get_dom_element_by_id, DomElement};
 use log::{trace, Level};
 use node_tree::cursor::Cursor;
 use node_tree::doc_node::DocumentNode;
+use node_tree::dom_cursor::DomCursor;
 use node_tree::dom_doc_tree_morph::{append, unlink};
 use node_tree::format_trait::RootFormat;
-use node_tree::tree_traverse::{first_node, last_block_node, next_node};
+use node_tree::tree_traverse::{
+    depth_first_post_order_iter, first_node, last_block_node, next_node, DepthFirstPostIter,
+};
 use node_tree::EDITOR_CLASS;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use web_sys::Node;
+use web_sys::{DocumentFragment, DomRect, Element, Node};
 
 static CONTAINTER_CLASS: &str = "ql-container";
 static STYLE_SNOW: &str = "ql-snow";
@@ -32,6 +44,9 @@ pub enum EditorMode {
 
 const DOC_ROOT_FORMAT: &str = "DOC_ROOT_FORMAT";
 
+/// Counter for the generated ID in `with_content()`
+static DOC_ROOT_COUNT: AtomicUsize = AtomicUsize::new(0);
+
 ///  # DocumentRoot
 ///
 /// The doc-root collects:
@@ -56,9 +71,20 @@ const DOC_ROOT_FORMAT: &str = "DOC_ROOT_FORMAT";
 #[derive(Clone)]
 pub struct DocumentRoot {
     mode: EditorMode,
-    cursor: Cursor,               //current location of the cursor
-    container: Arc<DocumentNode>, //container for root element
-    root: Arc<DocumentNode>,      //container for browser content
+    cursor: Cursor,                              //current location of the cursor
+    container: Arc<DocumentNode>,                //container for root element
+    root: Arc<DocumentNode>,                     //container for browser content
+    shortcuts: KeyboardShortcuts,                //keyboard shortcuts handled by on_key_down()
+    pub(crate) auto_format: bool, //format typed patterns like "# ", see set_auto_format()
+    pub(crate) observer: Option<ChangeObserver>, //see observe_subtree()
+    dom_cursor: Option<Rc<DomCursor>>, //see set_dom_cursor()
+    pub(crate) cursors: Vec<(CursorId, usize)>, //secondary cursors, see add_cursor()
+    pub(crate) next_cursor_id: usize,
+    pub(crate) protected_ranges: Vec<(ProtectedRangeId, usize, usize)>, //see protect_range()
+    pub(crate) next_protected_id: u64,
+    pub(crate) batch: bool,                         //see begin_batch()
+    pub(crate) history: UndoHistory,                //see undo()
+    pub(crate) composition: Option<(usize, usize)>, //start, preview length, see begin_composition()
 }
 
 impl DocumentRoot {
@@ -103,6 +129,17 @@ impl DocumentRoot {
             cursor: Cursor::new(),
             container: Arc::new(container),
             root: Arc::new(root),
+            shortcuts: KeyboardShortcuts::default(),
+            auto_format: false,
+            observer: None,
+            dom_cursor: None,
+            cursors: Vec::new(),
+            next_cursor_id: 0,
+            protected_ranges: Vec::new(),
+            next_protected_id: 0,
+            batch: false,
+            history: UndoHistory::default(),
+            composition: None,
         }
     }
 
@@ -201,6 +238,9 @@ impl DocumentRoot {
     }
 
     /// Open a DeltaDocument, and put its content in the selected DOM node
+    ///
+    /// An open document is closed first, so we always start with a fresh empty document,
+    /// and the cursor in its first block.
     pub fn open(&mut self) -> Result<()> {
         let registry = Registry::get_ref()?;
         if self.get_mode() != &EditorMode::Closed {
@@ -212,25 +252,68 @@ impl DocumentRoot {
         let block = format.create(op, format.clone())?;
 
         //insert_soft_break() to allow a cursor to appear in an empty <p></p> block
-        AutomaticSoftBreak::insert(&block)?;
+        AutomaticSoftBreak::insert(&block, false)?;
 
         //Note: cursor AT(dn,0) for the <P>-block is the default start for an empty document
         //In general if a new empty block is started, then also the cursor should be AT(dn,0)
         //Insert before setting cursor, since the cursor.set_at() tries to set the retain index,
         //which requires the block to be inserted in a valid document
         append(&self.get_root(), block.clone());
-        self.get_cursor().set_at(&block, 0);
+        self.cursor.reset();
+        self.cursor.set_at(&block, 0);
         Ok(())
     }
 
     /// Closes the document, and removes all DOM nodes from the HTML context.
+    ///
+    /// The cursor is reset, since it would point to the unlinked document nodes.
+    /// The undo history is cleared too, and the secondary cursors, the protected ranges and
+    /// a composition are removed, since they belong to the closed content. A batch, see
+    /// `begin_batch()`, ends without its deferred updates.
     pub fn close(&mut self) {
         for c in self.root.get_children() {
             unlink(&self.root, &c);
         }
+        self.batch = false;
+        self.cursor.set_defer_retain_update(false);
+        self.cursor.reset();
+        self.cursors.clear();
+        self.history.clear();
+        self.protected_ranges.clear();
+        self.composition = None;
         self.edit_mode(EditorMode::Closed);
     }
 
+    /// # load()
+    ///
+    /// Replaces the document content by the `document` delta, and shows it read only.
+    /// Use `set_mode(EditorMode::Edit)` to start editing the loaded document.
+    pub fn load(&mut self, document: Delta) -> Result<()> {
+        self.replace_content(document)?;
+        self.cursor.reset();
+        self.edit_mode(EditorMode::Read);
+        Ok(())
+    }
+
+    /// # view_only_apply_delta()
+    ///
+    /// Applies the delta, relative to the start of the document, to a document that is
+    /// shown read only. The mode is not changed, and the cursor is reset afterwards since
+    /// there is no user selection to keep.
+    ///
+    /// A new, or closed, document has no content to apply the delta to, see `load()`.
+    pub fn view_only_apply_delta(&mut self, delta: Delta) -> Result<()> {
+        if self.root.child_count() == 0 {
+            return Err(DocumentClosed.into());
+        }
+        let mode = self.mode.clone();
+        self.edit_mode(EditorMode::Edit);
+        let result = self.apply_delta(delta);
+        self.edit_mode(mode);
+        self.cursor.reset();
+        result
+    }
+
     /// Collects the DocumentNode tree and renders a valid DeltaDocument
     pub fn to_delta(&self) -> Delta {
         let mut delta = Delta::default();
@@ -245,6 +328,41 @@ impl DocumentRoot {
         }
         delta
     }
+
+    /// # to_delta_json()
+    ///
+    /// Returns the document, see `to_delta()`, as a JSON string like `{"ops":[...]}`.
+    pub fn to_delta_json(&self) -> String {
+        serde_json::to_string(&self.to_delta()).unwrap_or_default()
+    }
+
+    /// # from_delta_json()
+    ///
+    /// Creates a document from a JSON string, see `to_delta_json()`. The document is open
+    /// for editing.
+    ///
+    /// Returns `InvalidDelta` if the string is not a JSON delta.
+    pub fn from_delta_json(id: &str, s: &str) -> Result<DocumentRoot> {
+        let delta: Delta = match serde_json::from_str(s) {
+            Ok(delta) => delta,
+            Err(e) => return Err(InvalidDelta { msg: e.to_string() }.into()),
+        };
+        let mut doc = DocumentRoot::new(id);
+        doc.replace_content(delta)?;
+        Ok(doc)
+    }
+}
+
+/// Keyboard shortcut interface, see `on_key_down()`
+impl DocumentRoot {
+    pub fn get_keyboard_shortcuts(&self) -> &KeyboardShortcuts {
+        &self.shortcuts
+    }
+
+    /// Allows the application to add, or remove keyboard shortcuts
+    pub fn get_keyboard_shortcuts_mut(&mut self) -> &mut KeyboardShortcuts {
+        &mut self.shortcuts
+    }
 }
 
 /// CURSOR related interface
@@ -260,7 +378,12 @@ impl DocumentRoot {
     /// finds the first node in the document, and points to the first character.
     ///
     /// The end point of the cursor is not changed
+    ///
+    /// A closed document has no nodes, and the cursor is left as is.
     pub fn cursor_to_start(&self) {
+        if self.root.child_count() == 0 {
+            return;
+        }
         let first = first_node(&self.root);
         if first.get_formatter().is_text_format() {
             self.cursor.set_before(&first);
@@ -278,20 +401,80 @@ impl DocumentRoot {
     /// Implementation node:<br>
     /// The minimum document contains `<P></P>` as content. Hence the last position
     /// is just before that last `<P>`
+    ///
+    /// A closed document has no nodes, and the cursor is left as is.
     pub fn cursor_to_end(&self) {
+        if self.root.child_count() == 0 {
+            return;
+        }
         let last = last_block_node(&self.root).unwrap();
         self.cursor.set_cursor_to_doc_node_edge(&last, true);
     }
 
+    /// # set_dom_cursor()
+    ///
+    /// Registers the DOM cursor, which shows the document cursor in the browser. After every
+    /// change by `apply_operation()` the browser selection is set to the document cursor.
+    pub fn set_dom_cursor(&mut self, dom_cursor: &DomCursor) {
+        self.dom_cursor = Some(Rc::new(dom_cursor.clone()));
+    }
+
+    /// # get_caret_visual_position()
+    ///
+    /// Returns the bottom left corner `(x, y)` of the caret in viewport coordinates. It is
+    /// the anchor for a floating element like a toolbar or an autocomplete list.
+    ///
+    /// Returns `None` if there is no registered DOM cursor, see `set_dom_cursor()`, if the
+    /// document is not in edit mode, or if the cursor is not valid.
+    pub fn get_caret_visual_position(&self) -> Option<(f64, f64)> {
+        let rect = self.visual_dom_cursor()?.get_caret_rect()?;
+        Some((rect.left(), rect.bottom()))
+    }
+
+    /// # get_selection_visual_rect()
+    ///
+    /// Returns the bounding rectangle of the selection in viewport coordinates.
+    /// See `get_caret_visual_position()` for when this returns `None`.
+    pub fn get_selection_visual_rect(&self) -> Option<DomRect> {
+        self.visual_dom_cursor()?.get_selection_rect()
+    }
+
+    /// The DOM cursor, if the cursor is visible to the user
+    fn visual_dom_cursor(&self) -> Option<&Rc<DomCursor>> {
+        if self.mode != EditorMode::Edit || !self.cursor.valid() {
+            return None;
+        }
+        self.dom_cursor.as_ref()
+    }
+
+    /// The registered DOM cursor, see `set_dom_cursor()`
+    pub(crate) fn get_dom_cursor(&self) -> Option<&Rc<DomCursor>> {
+        self.dom_cursor.as_ref()
+    }
+
+    /// Sets the browser selection to the document cursor, if there is a registered DOM cursor.
+    pub(crate) fn sync_dom_cursor(&self) {
+        if let Some(dom_cursor) = &self.dom_cursor {
+            dom_cursor.cursor_to_html_dom(&self.cursor);
+        }
+    }
+
     /// Sets the cursor to the first node in the document.
     /// FIXME: Should this be a function that operates on the cursor? --> move function to cursor_test?
+    ///
+    /// A closed document has no nodes, and the cursor is left as is.
     pub fn reset_cursor(&self) {
+        if self.root.child_count() == 0 {
+            return;
+        }
         let node = first_node(&self.root);
         if node.is_leaf() {
             self.cursor.set_before(&node);
         } else {
             self.cursor.set_at(&node, 0);
         }
+        //the retain index is not updated during a batch
+        self.cursor.set_retain_index(0);
     }
 }
 
@@ -306,23 +489,68 @@ impl DocumentRoot {
     /// a few cyclic dependencies
 
     /// Applies a single DeltaOperation to the current location of the document cursor
+    ///
+    /// When there is an observer, see `observe_subtree()`, it is called with the applied change.
+    /// An insert or delete which changes a protected range, see `protect_range()`, fails.
     pub fn apply_operation(&mut self, operation: DeltaOperation) -> Result<()> {
         trace!("Document::apply_operatation({:?})", operation);
         let registry = Registry::get_ref()?;
         if self.mode != EditorMode::Edit {
             return Err(DocumentNotOpenForEdit.into());
         }
+        let start = self.get_cursor().get_retain_index();
+        self.check_protected_ranges(start, &operation)?;
+        let undo_base = self.undo_base(start, &operation);
+        //see update_anchor_ids(), only when a block with an anchor ID may change
+        let has_anchor_ids = registry.has_anchor_ids() && !self.batch;
+        let anchor_change = has_anchor_ids && self.touches_anchor_block(&operation, &registry);
+        let mut applied = Delta::default(); //the change for the observer
         match &operation.get_op_kind() {
             OpKind::Insert(_val) => {
-                for o in DocumentRoot::split_text_lines(operation)?.into_iter() {
-                    op_insert::insert(self.get_cursor(), o, &registry)?;
+                for o in DocumentRoot::split_text_lines(operation.clone(), &registry)?.into_iter() {
+                    applied.push(o.clone());
+                    op_insert::insert(self.get_cursor(), o, &registry, self.batch)?;
                 }
             }
             OpKind::Delete(len) => {
-                op_delete::delete(self.get_cursor(), *len)?;
+                op_delete::delete(self.get_cursor(), *len, self.batch)?;
+                applied.push(operation.clone());
             }
             OpKind::Retain(_len) => {
-                op_retain::retain(self.get_cursor(), &operation, &registry)?;
+                op_retain::retain(self.get_cursor(), &operation, &registry, self.batch)?;
+                applied.push(operation.clone());
+            }
+        }
+        self.shift_cursors(start, &operation);
+        self.shift_protected_ranges(start, &operation);
+
+        //an undo or redo applies the recorded auto format change itself
+        let mut auto_format_change = None;
+        let is_insert = matches!(operation.get_op_kind(), OpKind::Insert(_));
+        if is_insert && self.auto_format && !self.history.is_suspended() {
+            auto_format_change = self.apply_auto_format(&registry)?;
+        }
+
+        //the observer may want to use the registry too
+        drop(registry);
+        self.record_undo(start, &operation, &undo_base);
+        if let Some(auto) = &auto_format_change {
+            self.record_undo_changes(&auto.changes, &auto.undo_base);
+            for (edit_start, edit) in auto.edits() {
+                self.shift_cursors(edit_start, &edit);
+                self.shift_protected_ranges(edit_start, &edit);
+            }
+        }
+        if !self.batch {
+            if anchor_change || (has_anchor_ids && self.anchor_block_at_cursor()) {
+                self.update_anchor_ids();
+            }
+            self.sync_dom_cursor();
+        }
+        if self.observer.is_some() {
+            self.notify_observer(start, applied);
+            if let Some(auto) = auto_format_change {
+                self.notify_changes(&auto.changes);
             }
         }
         Ok(())
@@ -335,11 +563,15 @@ impl DocumentRoot {
     /// minimal like the delta format prescribes. The only use is that these Delta operations are
     /// more easily translated into HTML.
     ///
+    /// The attributes are split too: the `"\n"` operations only get the block attributes, see
+    /// `Registry::is_block_attribute()`, and the text operations only get the other attributes.
+    /// So `insert("hello\nworld", {bold:true})` does not result in a bold block.
+    ///
     /// Implementation note: string.split() must use  "\n" ...r##"\n"## does not work
-    fn split_text_lines(op: DeltaOperation) -> Result<Vec<DeltaOperation>> {
+    fn split_text_lines(op: DeltaOperation, registry: &Registry) -> Result<Vec<DeltaOperation>> {
         let mut delta_operations: Vec<DeltaOperation> = Vec::new();
 
-        //if we have an single character
+        //if we have an single character, or a single line
         if op.op_len() == 1 || !op.insert_value().is_string() {
             delta_operations.push(op);
             return Ok(delta_operations);
@@ -347,34 +579,107 @@ impl DocumentRoot {
 
         //so we are a string operation
         let txt = op.insert_value().str_val()?;
+        if !txt.contains('\n') {
+            delta_operations.push(op);
+            return Ok(delta_operations);
+        }
         let paragraphs: Vec<&str> = txt.split('\n').collect();
 
+        let mut block_attr = Attributes::default();
+        let mut text_attr = Attributes::default();
+        for (key, val) in op.get_attributes().iter() {
+            if registry.is_block_attribute(key, val) {
+                block_attr.insert(key.clone(), val.clone());
+            } else {
+                text_attr.insert(key.clone(), val.clone());
+            }
+        }
+
         for &p in paragraphs.iter() {
             if !p.is_empty() {
                 let mut opr = DeltaOperation::insert(p);
-                opr.set_attributes(op.get_attributes().clone());
-                delta_operations.push(opr);
-                let mut opr = DeltaOperation::insert("\n");
-                opr.set_attributes(op.get_attributes().clone());
-                delta_operations.push(opr);
-            } else {
-                let mut opr = DeltaOperation::insert("\n");
-                opr.set_attributes(op.get_attributes().clone());
+                opr.set_attributes(text_attr.clone());
                 delta_operations.push(opr);
             }
+            let mut opr = DeltaOperation::insert("\n");
+            opr.set_attributes(block_attr.clone());
+            delta_operations.push(opr);
         }
 
-        if delta_operations.len() > 1 {
-            delta_operations.pop();
-        }
+        //the last line has no new line
+        delta_operations.pop();
         Ok(delta_operations)
     }
 
+    /// # normalize_delta()
+    ///
+    /// Deltas from an external source (server sync, undo) may not be minimal. This merges
+    /// adjacent operations of the same kind with the same attributes, so the tree sees one
+    /// text insert instead of several. Text inserts with embedded `\n` are split later,
+    /// by `split_text_lines()`.
+    ///
+    /// Returns `InvalidDelta` for an empty insert, a zero length retain or delete.
+    fn normalize_delta(delta: Delta) -> Result<Delta> {
+        let mut normalized: Vec<DeltaOperation> = Vec::new();
+        for (index, op) in delta.get_ops().into_iter().enumerate() {
+            if op.op_len() == 0 {
+                let msg = match op.get_op_kind() {
+                    OpKind::Insert(_) => "empty insert",
+                    OpKind::Retain(_) => "zero length retain",
+                    OpKind::Delete(_) => "zero length delete",
+                };
+                return Err(InvalidDelta {
+                    msg: format!("{} at operation {}", msg, index),
+                }
+                .into());
+            }
+            if let Some(prev) = normalized.last_mut() {
+                if prev.get_attributes() == op.get_attributes() {
+                    match (prev.get_op_kind(), op.get_op_kind()) {
+                        (OpKind::Insert(_), OpKind::Insert(_))
+                            if prev.insert_value().is_string() && op.insert_value().is_string() =>
+                        {
+                            let mut merged = DeltaOperation::insert(format!(
+                                "{}{}",
+                                prev.insert_value().str_val()?,
+                                op.insert_value().str_val()?
+                            ));
+                            merged.set_attributes(op.get_attributes().clone());
+                            *prev = merged;
+                            continue;
+                        }
+                        (OpKind::Retain(a), OpKind::Retain(b)) => {
+                            let mut merged = DeltaOperation::retain(a + b);
+                            merged.set_attributes(op.get_attributes().clone());
+                            *prev = merged;
+                            continue;
+                        }
+                        (OpKind::Delete(a), OpKind::Delete(b)) => {
+                            *prev = DeltaOperation::delete(a + b);
+                            continue;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            normalized.push(op);
+        }
+
+        let mut delta = Delta::default();
+        for op in normalized {
+            delta.push(op);
+        }
+        Ok(delta)
+    }
+
     /// # apply_delta()
     ///
     /// Sets the cursor to the first character in the document,
     /// and inserts a delta document at that location.
     ///
+    /// The delta is normalized first, see `normalize_delta()`. A malformed delta
+    /// is rejected before any change is made to the document.
+    ///
     /// Changes cursor position to the last entry that gets updated.
     ///
     /// This assumes that the delta to be applied is a
@@ -385,6 +690,7 @@ impl DocumentRoot {
         if self.mode != EditorMode::Edit {
             return Err(DocumentNotOpenForEdit.into());
         }
+        let delta = DocumentRoot::normalize_delta(delta)?;
         self.reset_cursor();
         for op in delta.get_ops() {
             self.apply_operation(op)?;
@@ -398,6 +704,110 @@ impl DocumentRoot {
         Ok(())
     }
 
+    /// # apply_delta_at_position()
+    ///
+    /// Sets the cursor to the retain index `position`, and applies the delta at that location.
+    /// This is the same as `apply_delta()` with a delta starting with `retain(position)`, but
+    /// without moving the cursor through the document from the start.
+    ///
+    /// The delta is normalized first, see `normalize_delta()`. The retain index of the cursor
+    /// is updated by each operation, so the cursor ends after the last inserted, or retained,
+    /// character.
+    ///
+    /// Returns an error if the position is not in the document, see
+    /// `Cursor::set_selection_by_retain_range()`.
+    pub fn apply_delta_at_position(&mut self, position: usize, delta: Delta) -> Result<()> {
+        if self.mode != EditorMode::Edit {
+            return Err(DocumentNotOpenForEdit.into());
+        }
+        let delta = DocumentRoot::normalize_delta(delta)?;
+        self.cursor
+            .set_selection_by_retain_range(position, position)?;
+        for op in delta.get_ops() {
+            self.apply_operation(op)?;
+        }
+        Ok(())
+    }
+
+    /// # paste_delta()
+    ///
+    /// Pastes the clipboard `delta`, a delta document, at the `cursor`. A selection is
+    /// deleted first, see `op_delete::delete_selection()`, then the operations of the delta
+    /// are applied at the start of the selection.
+    ///
+    /// A copied document ends with a new line. Applying that new line splits the block at the
+    /// cursor, and leaves an empty `<P>` when pasting at the end of a block. So the last new
+    /// line is not inserted. Its block attributes are applied to the block in which the paste
+    /// ends, like pasting list items makes that block a list item too. Only pasting a single
+    /// new line does split the block.
+    ///
+    /// The document cursor, and `cursor`, end after the last inserted character.
+    pub fn paste_delta(&mut self, cursor: &Cursor, delta: Delta) -> Result<()> {
+        if self.mode != EditorMode::Edit {
+            return Err(DocumentNotOpenForEdit.into());
+        }
+        let delta = DocumentRoot::normalize_delta(delta)?;
+        self.cursor.from(cursor);
+        op_delete::delete_selection(self.get_cursor(), self.batch)?;
+
+        let mut ops = delta.get_ops();
+        let mut block_attr = Attributes::default();
+        if let Some(last) = ops.pop() {
+            match last.get_op_kind() {
+                OpKind::Insert(_) if last.insert_value().is_string() => {
+                    let txt = last.insert_value().str_val()?.to_string();
+                    match txt.strip_suffix('\n') {
+                        Some(text) if !(text.is_empty() && ops.is_empty()) => {
+                            if !text.is_empty() {
+                                ops.push(DeltaOperation::insert_attr(
+                                    text,
+                                    last.get_attributes().clone(),
+                                ));
+                            }
+                            let registry = Registry::get_ref()?;
+                            for (key, val) in last.get_attributes().iter() {
+                                if registry.is_block_attribute(key, val) {
+                                    block_attr.insert(key.clone(), val.clone());
+                                }
+                            }
+                        }
+                        _ => ops.push(last),
+                    }
+                }
+                _ => ops.push(last),
+            }
+        }
+        for op in ops {
+            self.apply_operation(op)?;
+        }
+
+        if !block_attr.is_empty() {
+            let retain = self.cursor.get_retain_index();
+            if let Some((start, len)) = self.block_retain_range_at(retain) {
+                let mut op = DeltaOperation::retain(1);
+                op.set_attributes(block_attr);
+                op_retain::set_cursor_selection(self.get_cursor(), start + len - 1, 0);
+                self.apply_operation(op)?;
+                op_retain::set_cursor_selection(self.get_cursor(), retain, 0);
+            }
+        }
+        cursor.from(self.get_cursor());
+        Ok(())
+    }
+
+    /// # apply_delta_and_sync_dom()
+    ///
+    /// Same as `apply_delta()`, and then sets the browser selection to the document cursor.
+    /// Uses the DOM cursor from `set_dom_cursor()`, or else a DOM cursor for this document.
+    pub fn apply_delta_and_sync_dom(&mut self, delta: Delta) -> Result<()> {
+        self.apply_delta(delta)?;
+        match &self.dom_cursor {
+            Some(dom_cursor) => dom_cursor.cursor_to_html_dom(&self.cursor),
+            None => DomCursor::new(&self.root).cursor_to_html_dom(&self.cursor),
+        }
+        Ok(())
+    }
+
     // /// Applies the delta operations in the delta, starting from the current cursor
     // /// position in the current document.
     // ///
@@ -429,18 +839,122 @@ impl DocumentRoot {
     pub fn get_container_element(&self) -> &DomElement {
         self.container.get_dom_element().unwrap()
     }
+
+    /// # container_element()
+    ///
+    /// Returns the container `<div class="ql-container">`, like `get_container_element()`.
+    pub fn container_element(&self) -> &DomElement {
+        self.get_container_element()
+    }
+
+    /// # container_dom_node()
+    ///
+    /// Returns the live HTML DOM node of the container `<div class="ql-container">`, for
+    /// example to add event listeners for a context menu.
+    pub fn container_dom_node(&self) -> &Node {
+        self.container.get_html_node()
+    }
+
+    /// # editor_dom_node()
+    ///
+    /// Returns the live HTML DOM node of the editable `<div class="ql-editor">`, which
+    /// contains the document content.
+    pub fn editor_dom_node(&self) -> &Node {
+        self.root.get_html_node()
+    }
+
+    /// # to_document_fragment()
+    ///
+    /// Returns a deep copy of the document content, with the same HTML as `as_html_string()`.
+    /// The copy is not linked to the editor, so it can be used for a preview or a print view.
+    pub fn to_document_fragment(&self) -> DocumentFragment {
+        let fragment = DOCUMENT.with(|d| d.create_document_fragment());
+        let children = self.root.get_html_node().child_nodes();
+        for i in 0..children.length() {
+            let child = children.item(i).unwrap();
+            let copy = child.clone_node_with_deep(true).unwrap();
+            fragment.append_child(&copy).unwrap();
+        }
+        fragment
+    }
+
+    /// # clone_to_element()
+    ///
+    /// Appends a deep copy of the document content to the target element,
+    /// see `to_document_fragment()`.
+    pub fn clone_to_element(&self, target: &Element) {
+        target.append_child(&self.to_document_fragment()).unwrap();
+    }
+}
+
+impl DocumentRoot {
+    /// # with_content()
+    ///
+    /// Creates a new document with a generated unique ID, see `new()`, and opens it with
+    /// the content of the delta document. The delta must end with a new line.
+    pub fn with_content(delta: Delta) -> Result<Self> {
+        let count = DOC_ROOT_COUNT.fetch_add(1, Ordering::Relaxed);
+        let mut doc = DocumentRoot::new(&format!("doc-root-{}", count));
+        doc.replace_content(delta)?;
+        Ok(doc)
+    }
+
+    /// Operations of the document content, a document without nodes has no operations
+    fn content_ops(&self) -> Vec<DeltaOperation> {
+        if self.root.child_count() == 0 {
+            return Vec::new();
+        }
+        self.to_delta().get_ops()
+    }
+}
+
+impl TryFrom<Delta> for DocumentRoot {
+    type Error = anyhow::Error;
+
+    fn try_from(delta: Delta) -> Result<Self> {
+        DocumentRoot::with_content(delta)
+    }
+}
+
+impl From<&DocumentRoot> for Delta {
+    fn from(doc: &DocumentRoot) -> Self {
+        doc.to_delta()
+    }
+}
+
+/// Iterates over all nodes of the document, see `DepthFirstPostIter`:
+/// ```ignore
+/// for doc_node in &doc { ... }
+/// ```
+impl<'a> IntoIterator for &'a DocumentRoot {
+    type Item = Arc<DocumentNode>;
+    type IntoIter = DepthFirstPostIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        depth_first_post_order_iter(&self.root)
+    }
+}
+
+/// Two documents are equal when they have the same content, ignoring the ID,
+/// the editor mode and the cursor.
+impl PartialEq for DocumentRoot {
+    fn eq(&self, other: &Self) -> bool {
+        self.content_ops() == other.content_ops()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::doc_root::DocumentRoot;
+    use crate::registry::Registry;
     use delta::delta::Delta;
 
     #[test]
     fn split_text_lines_test() -> anyhow::Result<()> {
         let mut delta = Delta::default();
         delta.insert("\nHello sweet \nworld");
-        let v = DocumentRoot::split_text_lines(delta.first().unwrap().clone())?;
+        let registry = Registry::default();
+        let v = DocumentRoot::split_text_lines(delta.first().unwrap().clone(), &registry)?;
 
         assert_eq!(v.first().unwrap().insert_value().str_val()?, "\n");
         assert_eq!(v.get(1).unwrap().insert_value().str_val()?, "Hello sweet ");
@@ -455,7 +969,8 @@ mod test {
         delta.insert("\n\n\n");
         delta.insert("\n");
 
-        let v = DocumentRoot::split_text_lines(delta.first().unwrap().clone())?;
+        let registry = Registry::default();
+        let v = DocumentRoot::split_text_lines(delta.first().unwrap().clone(), &registry)?;
 
         assert_eq!(v.first().unwrap().insert_value().str_val()?, "\n");
         assert_eq!(v.get(1).unwrap().insert_value().str_val()?, "\n");
@@ -0,0 +1,85 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+
+/// Stands in for an embed in the searched text. An embed has the length 1, so each
+/// character of the searched text is one retain index.
+static EMBED_PLACEHOLDER: &str = "\u{FFFC}";
+
+impl DocumentRoot {
+    /// # find()
+    ///
+    /// Returns `(retain_start, retain_end)` of all non-overlapping occurrences of the query,
+    /// where the end is just after the last character. The search is case sensitive.
+    ///
+    /// We search the plain text, see `get_plain_text()`, so a query matches text with
+    /// different formats, for example `"ab"` in `a<strong>b</strong>`. A query with a `\n`
+    /// matches across blocks. An embed never matches.
+    pub fn find(&self, query: &str) -> Vec<(usize, usize)> {
+        let query: Vec<char> = query.chars().collect();
+        let mut hits: Vec<(usize, usize)> = Vec::new();
+        if query.is_empty() {
+            return hits;
+        }
+        let text: Vec<char> = self
+            .get_plain_text_with_placeholder(EMBED_PLACEHOLDER)
+            .chars()
+            .collect();
+        let mut i: usize = 0;
+        while i + query.len() <= text.len() {
+            if text[i..i + query.len()] == query[..] {
+                hits.push((i, i + query.len()));
+                i += query.len();
+            } else {
+                i += 1;
+            }
+        }
+        hits
+    }
+
+    /// # replace()
+    ///
+    /// Replaces all occurrences of the query, see `find()`, by the replacement text.
+    /// Returns the number of replaced occurrences.
+    ///
+    /// Each occurrence is a delete, and an insert with the text attributes of the first
+    /// replaced character. So replacing `"ab"` in `<strong>ab</strong>c` gives bold text.
+    /// The replacements are applied as one change set, see `apply_change_set()`, so either
+    /// all occurrences are replaced or none.
+    pub fn replace(&mut self, query: &str, replacement: &str) -> Result<usize> {
+        let hits = self.find(query);
+        let mut changes: Vec<(usize, usize, Delta)> = Vec::new();
+        for (start, end) in hits.iter() {
+            let mut delta = Delta::default();
+            if !replacement.is_empty() {
+                delta.insert_attr(replacement, self.text_attributes_at(*start));
+            }
+            changes.push((*start, end - start, delta));
+        }
+        if !changes.is_empty() {
+            self.apply_change_set(changes)?;
+        }
+        Ok(hits.len())
+    }
+
+    /// Returns the attributes of the text character at the retain index. A block operation
+    /// has block attributes, which we do not want on the replacement text.
+    fn text_attributes_at(&self, retain_idx: usize) -> Attributes {
+        for op in self.get_delta_slice(retain_idx, 1).get_ops() {
+            if let Ok(text) = op.insert_value().str_val() {
+                if text != "\n" {
+                    return op.get_attributes().clone();
+                }
+            }
+        }
+        Attributes::default()
+    }
+}
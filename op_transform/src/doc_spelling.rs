@@ -0,0 +1,86 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::op_retain::set_cursor_selection;
+use anyhow::Result;
+use core_formats::format_const::TEXT_ATTR_SPELL_ERROR;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use spell::{SpellChecker, SpellingError};
+
+impl DocumentRoot {
+    /// # check_spelling()
+    ///
+    /// Calls the spell checker for the text of every text node, and returns the misspelled
+    /// words with their retain range.
+    ///
+    /// A word which is split over 2 text nodes, like `te**h**`, is checked as 2 separate words.
+    pub fn check_spelling(&self, checker: &dyn SpellChecker) -> Vec<SpellingError> {
+        let mut errors: Vec<SpellingError> = Vec::new();
+        let mut retain: usize = 0;
+        for doc_node in self.non_zero_length_nodes() {
+            let op = doc_node.get_operation();
+            if doc_node.get_formatter().is_text_format() && op.insert_value().is_string() {
+                let text = op.insert_value().str_val().unwrap_or_default();
+                for (start, end) in checker.check(text) {
+                    let retain_start = retain + text[..start].chars().count();
+                    errors.push(SpellingError {
+                        retain_start,
+                        retain_end: retain_start + text[start..end].chars().count(),
+                        word: text[start..end].to_string(),
+                    });
+                }
+            }
+            retain += doc_node.op_len();
+        }
+        errors
+    }
+
+    /// # apply_spelling_marks()
+    ///
+    /// Marks the misspelled words with the text attribute `{"spell-error": true}`, which
+    /// shows as the CSS class `ql-spell-error`. The cursor is not changed.
+    pub fn apply_spelling_marks(&mut self, errors: &[SpellingError]) -> Result<()> {
+        let mut attr = Attributes::default();
+        attr.insert(TEXT_ATTR_SPELL_ERROR, true);
+        let ranges = errors
+            .iter()
+            .map(|e| (e.retain_start, e.retain_end - e.retain_start))
+            .collect();
+        self.mark_ranges(ranges, attr)
+    }
+
+    /// # clear_spelling_marks()
+    ///
+    /// Removes all spelling marks from the document. The cursor is not changed.
+    pub fn clear_spelling_marks(&mut self) -> Result<()> {
+        let mut attr = Attributes::default();
+        attr.insert(TEXT_ATTR_SPELL_ERROR, AttrVal::Null);
+        let ranges = self
+            .get_attribute_ranges(TEXT_ATTR_SPELL_ERROR)
+            .into_iter()
+            .map(|(start, len, _val)| (start, len))
+            .collect();
+        self.mark_ranges(ranges, attr)
+    }
+
+    /// Retains the ranges `(retain_index, length)` with the attributes, and restores the cursor
+    fn mark_ranges(&mut self, ranges: Vec<(usize, usize)>, attr: Attributes) -> Result<()> {
+        let retain = self.get_cursor().get_retain_index();
+        let selection = self.get_cursor().selection_length();
+        for (start, len) in ranges.into_iter().filter(|(_start, len)| *len > 0) {
+            set_cursor_selection(self.get_cursor(), start, 0);
+            let mut op = DeltaOperation::retain(len);
+            op.set_attributes(attr.clone());
+            self.apply_operation(op)?;
+        }
+        set_cursor_selection(self.get_cursor(), retain, selection);
+        Ok(())
+    }
+}
@@ -0,0 +1,419 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use delta::delta::Delta;
+use delta::document::Document;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use delta::types::ops_kind::OpKind;
+use node_tree::doc_node::DocumentNode;
+use node_tree::tree_traverse::{first_node, next_node};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+/// Reading speed used for the reading time estimate, in words per minute
+static WORDS_PER_MINUTE: f64 = 200.0;
+
+/// # SelectionStats
+///
+/// Statistics of the document content covered by the current selection.
+///
+///  - words: number of white space separated words
+///  - chars: number of text characters, block ends (new lines) are not counted
+///  - paragraphs: number of blocks which contain selected text
+///  - format_counts: for each attribute key, the number of selected characters having that attribute
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SelectionStats {
+    pub words: usize,
+    pub chars: usize,
+    pub paragraphs: usize,
+    pub format_counts: HashMap<String, usize>,
+}
+
+/// # TextStats
+///
+/// Statistics of the whole document, see `DocumentRoot::text_statistics()`.
+///
+///  - characters: number of text characters, block ends (new lines) and embeds are not counted
+///  - words: number of white space separated words
+///  - unique_words: number of different words, ignoring case and punctuation
+///  - sentences: number of sentences, ending with `.`, `!` or `?` followed by white space
+///  - paragraphs: number of blocks which contain text
+///  - blocks: for each block format name, the number of blocks
+///  - reading_minutes: reading time estimate, at 200 words per minute
+///  - avg_words_per_paragraph: number of words divided by the number of paragraphs
+///  - longest_paragraph: number of characters of the longest paragraph
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextStats {
+    pub characters: usize,
+    pub words: usize,
+    pub unique_words: usize,
+    pub sentences: usize,
+    pub paragraphs: usize,
+    pub blocks: HashMap<String, usize>,
+    pub reading_minutes: f64,
+    pub avg_words_per_paragraph: f64,
+    pub longest_paragraph: usize,
+    block_stats: Vec<BlockStats>,
+    word_counts: HashMap<String, usize>,
+}
+
+/// Statistics of one block, used to update the `TextStats` after an operation
+#[derive(Debug, Clone, PartialEq)]
+struct BlockStats {
+    name: &'static str,
+    op_len: usize,
+    characters: usize,
+    words: usize,
+    sentences: usize,
+    unique: Vec<String>,
+}
+
+impl DocumentRoot {
+    /// # text_statistics()
+    ///
+    /// Returns the statistics of the whole document, computed in one pass over the document.
+    pub fn text_statistics(&self) -> TextStats {
+        let mut stats = TextStats::default();
+        if self.get_root().child_count() == 0 {
+            return stats;
+        }
+        for block in collect_block_stats(Some(first_node(self.get_root())), usize::MAX) {
+            stats.add_block(&block);
+            stats.block_stats.push(block);
+        }
+        stats.update_derived();
+        stats
+    }
+
+    /// # word_count()
+    ///
+    /// Returns the number of words in the document. A word is a white space separated
+    /// part of the text which has at least one alphanumeric character, so a lone `-` is
+    /// not a word. Embeds are not words.
+    pub fn word_count(&self) -> usize {
+        self.get_plain_text_with_placeholder("")
+            .split_whitespace()
+            .filter(|w| w.chars().any(char::is_alphanumeric))
+            .count()
+    }
+
+    /// # character_count()
+    ///
+    /// Returns the number of text characters in the document, block ends (new lines)
+    /// and embeds are not counted. Without `include_spaces` white space is not counted.
+    pub fn character_count(&self, include_spaces: bool) -> usize {
+        self.get_plain_text_with_placeholder("")
+            .chars()
+            .filter(|&c| c != '\n' && (include_spaces || !c.is_whitespace()))
+            .count()
+    }
+
+    /// # update_stats()
+    ///
+    /// Updates the statistics after `apply_operation()`, where `start` is the retain index
+    /// at which the operation was applied. This is the same retain index as passed to the
+    /// document observer, see `observe_subtree()`.
+    ///
+    /// Only the blocks touched by the operation are counted again, the statistics of the
+    /// other blocks are kept. When the statistics do not match the operation, then the
+    /// statistics of the whole document are computed again.
+    ///
+    /// The operation alone does not tell where it was applied, hence the `start`, and the
+    /// document to read the touched blocks from.
+    ///
+    /// Complexity: finding the touched blocks is a scan over the block lengths, O(blocks),
+    /// and finding the first touched node walks the document nodes before it, O(nodes).
+    /// Only the text of the touched blocks is read and counted again. A full
+    /// `text_statistics()` reads and counts the text of all blocks.
+    pub fn update_stats(&self, start: usize, op: &DeltaOperation, stats: &mut TextStats) {
+        let Some((first, last, count, block_start)) = stats.touched_blocks(start, op) else {
+            *stats = self.text_statistics();
+            return;
+        };
+        let blocks = collect_block_stats(self.node_at_retain(block_start), count);
+        if blocks.len() != count {
+            *stats = self.text_statistics();
+            return;
+        }
+        for block in blocks.iter() {
+            stats.add_block(block);
+        }
+        let removed: Vec<BlockStats> = stats.block_stats.splice(first..=last, blocks).collect();
+        for block in removed.iter() {
+            stats.remove_block(block);
+        }
+        stats.update_derived();
+    }
+
+    /// # get_formatted_selection_stats()
+    ///
+    /// Returns the statistics of the current selection. If there is no selection
+    /// (collapsed cursor), then the statistics of the whole document are returned.
+    pub fn get_formatted_selection_stats(&self) -> SelectionStats {
+        let mut stats = SelectionStats::default();
+        if self.get_root().child_count() == 0 {
+            return stats;
+        }
+
+        let cursor = self.get_cursor();
+        let (start, stop) = if cursor.is_selection() {
+            let start = cursor.get_retain_index();
+            (start, start + cursor.selection_length())
+        } else {
+            (0, Delta::document_length(&self.to_delta()))
+        };
+
+        let mut text = String::new();
+        let mut block_has_text = false;
+        let mut retain: usize = 0;
+        let mut dn_o = Some(first_node(self.get_root()));
+        while let Some(doc_node) = dn_o {
+            let len = doc_node.op_len();
+            if len > 0 {
+                //overlap of [retain, retain+len) with the selection [start, stop)
+                let from = retain.max(start);
+                let to = (retain + len).min(stop);
+                if doc_node.get_formatter().is_text_format() {
+                    let op = doc_node.get_operation();
+                    if from < to && op.insert_value().is_string() {
+                        let selected = to - from;
+                        if let Ok(s) = op.insert_value().str_val() {
+                            text.extend(s.chars().skip(from - retain).take(selected));
+                        }
+                        stats.chars += selected;
+                        block_has_text = true;
+                        for (key, val) in op.get_attributes().iter() {
+                            if matches!(val, AttrVal::Null | AttrVal::Bool(false)) {
+                                continue;
+                            }
+                            *stats.format_counts.entry(key.to_string()).or_insert(0) += selected;
+                        }
+                    }
+                } else {
+                    if block_has_text {
+                        stats.paragraphs += 1;
+                    }
+                    block_has_text = false;
+                    text.push('\n');
+                }
+            }
+            retain += len;
+            if retain >= stop {
+                break;
+            }
+            dn_o = next_node(&doc_node);
+        }
+        if block_has_text {
+            //selection ended half way a block
+            stats.paragraphs += 1;
+        }
+        stats.words = text.split_whitespace().count();
+        stats
+    }
+}
+
+impl TextStats {
+    /// Returns the first, and last, block touched by the operation, the number of blocks
+    /// replacing them, and the retain index of the first block. Returns `None` when the
+    /// operation is beyond the last block.
+    fn touched_blocks(
+        &self,
+        start: usize,
+        op: &DeltaOperation,
+    ) -> Option<(usize, usize, usize, usize)> {
+        let (stop, inserted) = match op.get_op_kind() {
+            OpKind::Insert(_) => {
+                let mut new_lines = 0;
+                if op.insert_value().is_string() {
+                    new_lines = op.insert_value().str_val().ok()?.matches('\n').count();
+                }
+                (start, new_lines)
+            }
+            //deleting the end of a block joins it with the next block
+            OpKind::Delete(len) => (start + len, 0),
+            OpKind::Retain(len) => (start + len.max(1) - 1, 0),
+        };
+
+        let mut first = None;
+        let mut block_start = 0;
+        let mut deleted = 0;
+        let mut retain = 0;
+        for (i, block) in self.block_stats.iter().enumerate() {
+            let end = retain + block.op_len;
+            if first.is_none() && start < end {
+                first = Some(i);
+                block_start = retain;
+            }
+            if matches!(op.get_op_kind(), OpKind::Delete(_)) && start < end && end <= stop {
+                deleted += 1;
+            }
+            if stop < end {
+                let first = first?;
+                return Some((first, i, i + 1 - first + inserted - deleted, block_start));
+            }
+            retain = end;
+        }
+        None
+    }
+
+    fn add_block(&mut self, block: &BlockStats) {
+        self.characters += block.characters;
+        self.words += block.words;
+        self.sentences += block.sentences;
+        if block.characters > 0 {
+            self.paragraphs += 1;
+        }
+        *self.blocks.entry(block.name.to_string()).or_insert(0) += 1;
+        for word in block.unique.iter() {
+            *self.word_counts.entry(word.clone()).or_insert(0) += 1;
+        }
+    }
+
+    fn remove_block(&mut self, block: &BlockStats) {
+        self.characters -= block.characters;
+        self.words -= block.words;
+        self.sentences -= block.sentences;
+        if block.characters > 0 {
+            self.paragraphs -= 1;
+        }
+        decrement(&mut self.blocks, block.name);
+        for word in block.unique.iter() {
+            decrement(&mut self.word_counts, word);
+        }
+    }
+
+    fn update_derived(&mut self) {
+        self.unique_words = self.word_counts.len();
+        self.reading_minutes = self.words as f64 / WORDS_PER_MINUTE;
+        self.avg_words_per_paragraph = match self.paragraphs {
+            0 => 0.0,
+            n => self.words as f64 / n as f64,
+        };
+        self.longest_paragraph = self
+            .block_stats
+            .iter()
+            .map(|b| b.characters)
+            .max()
+            .unwrap_or(0);
+    }
+}
+
+impl Display for TextStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Characters: {}", self.characters)?;
+        writeln!(f, "Words: {} ({} unique)", self.words, self.unique_words)?;
+        writeln!(f, "Sentences: {}", self.sentences)?;
+        writeln!(f, "Paragraphs: {}", self.paragraphs)?;
+        let mut blocks: Vec<_> = self.blocks.iter().collect();
+        blocks.sort();
+        for (name, count) in blocks {
+            writeln!(f, "Blocks {}: {}", name, count)?;
+        }
+        writeln!(f, "Reading time: {:.1} min", self.reading_minutes)?;
+        writeln!(
+            f,
+            "Average words per paragraph: {:.1}",
+            self.avg_words_per_paragraph
+        )?;
+        write!(
+            f,
+            "Longest paragraph: {} characters",
+            self.longest_paragraph
+        )
+    }
+}
+
+impl BlockStats {
+    fn new(name: &'static str, op_len: usize, text: &str) -> Self {
+        let mut unique: Vec<String> = text
+            .split_whitespace()
+            .map(|w| {
+                w.trim_matches(|c: char| !c.is_alphanumeric())
+                    .to_lowercase()
+            })
+            .filter(|w| !w.is_empty())
+            .collect();
+        unique.sort();
+        unique.dedup();
+        BlockStats {
+            name,
+            op_len,
+            characters: text.chars().count(),
+            words: text.split_whitespace().count(),
+            sentences: count_sentences(text),
+            unique,
+        }
+    }
+}
+
+/// # collect_block_stats()
+///
+/// Returns the statistics of at most `max` blocks, starting at the first node of a block.
+fn collect_block_stats(from: Option<Arc<DocumentNode>>, max: usize) -> Vec<BlockStats> {
+    let mut blocks = Vec::new();
+    let mut text = String::new();
+    let mut op_len: usize = 0;
+    let mut dn_o = from;
+    while let Some(doc_node) = dn_o {
+        if blocks.len() >= max {
+            break;
+        }
+        let len = doc_node.op_len();
+        if len > 0 {
+            op_len += len;
+            if doc_node.get_formatter().is_text_format() {
+                let op = doc_node.get_operation();
+                if op.insert_value().is_string() {
+                    if let Ok(s) = op.insert_value().str_val() {
+                        text.push_str(s);
+                    }
+                }
+            } else {
+                let name = doc_node.get_formatter().format_name();
+                blocks.push(BlockStats::new(name, op_len, &text));
+                text.clear();
+                op_len = 0;
+            }
+        }
+        dn_o = next_node(&doc_node);
+    }
+    blocks
+}
+
+/// A sentence ends with `.`, `!` or `?` followed by white space, or at the end of the block
+fn count_sentences(text: &str) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+    let mut count = 0;
+    let mut has_text = false;
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_whitespace() {
+            has_text = true;
+        }
+        let ends =
+            matches!(c, '.' | '!' | '?') && chars.get(i + 1).is_none_or(|n| n.is_whitespace());
+        if ends && has_text {
+            count += 1;
+            has_text = false;
+        }
+    }
+    if has_text {
+        count += 1;
+    }
+    count
+}
+
+fn decrement(counts: &mut HashMap<String, usize>, key: &str) {
+    if let Some(count) = counts.get_mut(key) {
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(key);
+        }
+    }
+}
@@ -24,6 +24,8 @@ pub enum Error {
     DocumentRootUniqueId { pos: String },
     #[error("It seems you are changing a document which is not editable")]
     DocumentNotOpenForEdit,
+    #[error("The document is closed, load or open the document first")]
+    DocumentClosed,
     #[error(
         "You can not delete the last block format in a document. Too many delete operations??"
     )]
@@ -36,4 +38,26 @@ pub enum Error {
     DoubleInsertionOfASoftBreak,
     #[error("I am at a loss ... trying to remove an automatic soft-break, where there is none!")]
     CanNotRemoveASoftBreak,
+    #[error("The format = {fmt} does not apply to the operation: = {op:?} ")]
+    FormatDoesNotApply { fmt: String, op: DeltaOperation },
+    #[error("Retain index = {index} is beyond the end of the document")]
+    RetainIndexOutOfRange { index: usize },
+    #[error("Change = {index} of the change set overlaps the previous change, or is not sorted")]
+    ChangeSetOverlap { index: usize },
+    #[error("Can not find a header with anchor = {anchor}")]
+    AnchorNotFound { anchor: String },
+    #[error("Invalid delta: {msg}")]
+    InvalidDelta { msg: String },
+    #[error("The retain range = {start}..{end} is protected against changes")]
+    RangeProtected { start: usize, end: usize },
+    #[error("The block transform of format = {format_name} failed: {reason}")]
+    BlockTransformFailed { format_name: String, reason: String },
+    #[error("The block at retain index = {index} is not a checklist item")]
+    NotAChecklistItem { index: usize },
+    #[error("Can not parse the HTML: {msg}")]
+    HtmlParseFailed { msg: String },
+    #[error("There is no composition, call begin_composition() first")]
+    NoComposition,
+    #[error("Undo and redo are not possible during a composition")]
+    CompositionActive,
 }
@@ -0,0 +1,159 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+
+/// Attribute keys, and embed names, of the formats in the format crates.
+static HEADER_ATTR_KEY: &str = "heading";
+static LIST_ATTR_KEY: &str = "list";
+static LIST_BULLET: &str = "bullet";
+static LIST_ORDERED: &str = "ordered";
+static LINK_ATTR_KEY: &str = "link";
+static IMAGE_EMBED: &str = "image";
+static IMAGE_ALT: &str = "alt";
+
+static ARIA_LABEL: &str = "aria-label";
+
+impl DocumentRoot {
+    /// # get_accessible_text()
+    ///
+    /// Returns the document text with markers for the structure, one line per block.
+    /// The text is meant for a visually hidden `aria-live` region, for screen readers:
+    ///  - headings: `[H1: text]`
+    ///  - list items: `[• text]` for bullets, and `[1. text]` for ordered lists
+    ///  - links: `[link: text (url)]`
+    ///  - images: `[image: alt text]`, or `[image]` without alt text
+    pub fn get_accessible_text(&self) -> String {
+        if self.get_root().child_count() == 0 {
+            return String::new();
+        }
+        let mut export = AccessibleExport::default();
+        for op in self.to_delta().get_ops() {
+            if op.op_len() > 0 {
+                export.push(&op);
+            }
+        }
+        export.finish();
+        export.lines.join("\n")
+    }
+
+    /// # set_aria_label()
+    ///
+    /// Sets the `aria-label` of the editor element `<div class="ql-editor">`.
+    pub fn set_aria_label(&self, label: &str) {
+        self.get_root()
+            .get_dom_element()
+            .unwrap()
+            .set_attribute(ARIA_LABEL, label);
+    }
+}
+
+/// Collects the text of the current block, and the lines of the finished blocks
+#[derive(Default)]
+struct AccessibleExport {
+    lines: Vec<String>,
+    line: String,
+    link: Option<(String, String)>, //(url, text) of the link being collected
+    ordered_count: usize,           //number of the previous ordered list item
+}
+
+impl AccessibleExport {
+    fn push(&mut self, op: &DeltaOperation) {
+        let attr = op.get_attributes();
+        if op.insert_value().is_map() {
+            self.flush_link();
+            self.line.push_str(&image_text(op));
+            return;
+        }
+        //plain paragraphs are merged into one operation, like "Hello\nworld\n"
+        let text = op.insert_value().str_val().unwrap_or_default();
+        let mut lines = text.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            if !line.is_empty() {
+                self.push_text(line, attr);
+            }
+            if lines.peek().is_some() {
+                self.flush_link();
+                self.end_block(attr);
+            }
+        }
+    }
+
+    fn push_text(&mut self, text: &str, attr: &Attributes) {
+        let url = attr.get(LINK_ATTR_KEY).map(string_value);
+        match (&mut self.link, url) {
+            (Some((link, link_text)), Some(url)) if *link == url => link_text.push_str(text),
+            (_, url) => {
+                self.flush_link();
+                match url {
+                    Some(url) => self.link = Some((url, text.to_string())),
+                    None => self.line.push_str(text),
+                }
+            }
+        }
+    }
+
+    /// Adds the text after the last new line, if any
+    fn finish(&mut self) {
+        self.flush_link();
+        if !self.line.is_empty() {
+            self.end_block(&Attributes::default());
+        }
+    }
+
+    fn flush_link(&mut self) {
+        if let Some((url, text)) = self.link.take() {
+            self.line.push_str(&format!("[link: {} ({})]", text, url));
+        }
+    }
+
+    fn end_block(&mut self, attr: &Attributes) {
+        let text = std::mem::take(&mut self.line);
+        let list = attr.get(LIST_ATTR_KEY).map(string_value);
+        if list.as_deref() == Some(LIST_ORDERED) {
+            self.ordered_count += 1;
+        } else {
+            self.ordered_count = 0;
+        }
+
+        let line = if let Some(level) = attr.get(HEADER_ATTR_KEY) {
+            format!("[H{}: {}]", string_value(level), text)
+        } else if list.as_deref() == Some(LIST_BULLET) {
+            format!("[• {}]", text)
+        } else if list.as_deref() == Some(LIST_ORDERED) {
+            format!("[{}. {}]", self.ordered_count, text)
+        } else {
+            text
+        };
+        self.lines.push(line);
+    }
+}
+
+fn image_text(op: &DeltaOperation) -> String {
+    let is_image = match op.insert_value().map_val() {
+        Ok(map) => map.get(IMAGE_EMBED).is_some(),
+        Err(_) => false,
+    };
+    if !is_image {
+        return String::new();
+    }
+    match op.get_attributes().get(IMAGE_ALT).map(string_value) {
+        Some(alt) if !alt.trim().is_empty() => format!("[image: {}]", alt),
+        _ => "[image]".to_string(),
+    }
+}
+
+/// String or number attribute values as text
+fn string_value(val: &AttrVal) -> String {
+    match val {
+        AttrVal::String(s) => s.clone(),
+        val => val.number_val().map(|n| n.to_string()).unwrap_or_default(),
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::attributes_diff::AttributesDiff;
+use crate::doc_root::DocumentRoot;
+use crate::html_import::html_to_delta;
+use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use delta::types::ops_kind::OpKind;
+
+impl DocumentRoot {
+    /// # generate_diff_delta()
+    ///
+    /// Returns the delta which changes the document `before_html` into the document
+    /// `after_html`. Both are HTML strings as produced by the editor, see `as_html_string()`.
+    ///
+    /// Both HTML strings are imported as a delta document. The diff keeps the longest
+    /// common start and end of the two documents: there the diff is a retain, with the
+    /// attribute changes if any. The part in between is deleted, and inserted again.
+    ///
+    /// Equal documents give an empty delta.
+    pub fn generate_diff_delta(before_html: &str, after_html: &str) -> Result<Delta> {
+        let before = html_to_delta(before_html)?;
+        let after = html_to_delta(after_html)?;
+        diff_delta(&before, &after)
+    }
+}
+
+/// # diff_delta()
+///
+/// Both deltas are documents: inserts only. We compare them per unit of length 1: one
+/// character, or one embed.
+fn diff_delta(before: &Delta, after: &Delta) -> Result<Delta> {
+    let before = units(before)?;
+    let after = units(after)?;
+
+    let prefix = before
+        .iter()
+        .zip(after.iter())
+        .take_while(|(b, a)| b.0 == a.0)
+        .count();
+    let max_suffix = before.len().min(after.len()) - prefix;
+    let suffix = before
+        .iter()
+        .rev()
+        .zip(after.iter().rev())
+        .take(max_suffix)
+        .take_while(|(b, a)| b.0 == a.0)
+        .count();
+
+    let mut ops: Vec<DeltaOperation> = Vec::new();
+    for (b, a) in before[..prefix].iter().zip(after[..prefix].iter()) {
+        push_op(&mut ops, retain_op(Attributes::diff(&b.1, &a.1)))?;
+    }
+    if before.len() - suffix > prefix {
+        push_op(
+            &mut ops,
+            DeltaOperation::delete(before.len() - suffix - prefix),
+        )?;
+    }
+    for (op, attr) in after[prefix..after.len() - suffix].iter() {
+        let mut op = op.clone();
+        op.set_attributes(attr.clone());
+        push_op(&mut ops, op)?;
+    }
+    let before_end = before[before.len() - suffix..].iter();
+    for (b, a) in before_end.zip(after[after.len() - suffix..].iter()) {
+        push_op(&mut ops, retain_op(Attributes::diff(&b.1, &a.1)))?;
+    }
+
+    //a retain without attributes at the end does not change anything
+    while let Some(last) = ops.last() {
+        if matches!(last.get_op_kind(), OpKind::Retain(_)) && last.get_attributes().is_empty() {
+            ops.pop();
+        } else {
+            break;
+        }
+    }
+
+    let mut delta = Delta::default();
+    for op in ops {
+        delta.push(op);
+    }
+    Ok(delta)
+}
+
+/// Splits the document in operations of length 1, without attributes, and their attributes
+fn units(delta: &Delta) -> Result<Vec<(DeltaOperation, Attributes)>> {
+    let mut units: Vec<(DeltaOperation, Attributes)> = Vec::new();
+    for op in delta.get_ops_ref() {
+        let attr = op.get_attributes().clone();
+        if op.insert_value().is_string() {
+            for c in op.insert_value().str_val()?.chars() {
+                units.push((DeltaOperation::insert(c.to_string().as_str()), attr.clone()));
+            }
+        } else {
+            let mut unit = op.clone();
+            unit.set_attributes(Attributes::default());
+            units.push((unit, attr));
+        }
+    }
+    Ok(units)
+}
+
+fn retain_op(attr: Attributes) -> DeltaOperation {
+    let mut op = DeltaOperation::retain(1);
+    op.set_attributes(attr);
+    op
+}
+
+/// Appends the operation, merging it with the last operation when possible
+fn push_op(ops: &mut Vec<DeltaOperation>, op: DeltaOperation) -> Result<()> {
+    let Some(last) = ops.last_mut() else {
+        ops.push(op);
+        return Ok(());
+    };
+    if last.get_attributes() != op.get_attributes() {
+        ops.push(op);
+        return Ok(());
+    }
+    let mut merged = match (last.get_op_kind(), op.get_op_kind()) {
+        (OpKind::Retain(l), OpKind::Retain(r)) => DeltaOperation::retain(l + r),
+        (OpKind::Delete(l), OpKind::Delete(r)) => DeltaOperation::delete(l + r),
+        (OpKind::Insert(_), OpKind::Insert(_))
+            if last.insert_value().is_string() && op.insert_value().is_string() =>
+        {
+            let text = [last.insert_value().str_val()?, op.insert_value().str_val()?].concat();
+            DeltaOperation::insert(text.as_str())
+        }
+        _ => {
+            ops.push(op);
+            return Ok(());
+        }
+    };
+    merged.set_attributes(op.get_attributes().clone());
+    *last = merged;
+    Ok(())
+}
@@ -0,0 +1,389 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::error::Error::HtmlParseFailed;
+use anyhow::Result;
+use core_formats::format_const::{
+    FORMAT_BOLD, FORMAT_DELETED, FORMAT_INSERTED, FORMAT_ITALIC, FORMAT_MARKED, FORMAT_SMALL,
+    FORMAT_STRIKE, FORMAT_SUB, FORMAT_SUP, FORMAT_UNDERLINE,
+};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::{DeltaOperation, OpsMap};
+use wasm_bindgen::JsCast;
+use web_sys::{DomParser, Element, Node, SupportedType};
+
+/// Attribute keys, embed names and CSS classes of the formats in the format crates.
+static HEADER_ATTR_KEY: &str = "heading";
+static LIST_ATTR_KEY: &str = "list";
+static LIST_INDENT_ATTR_KEY: &str = "indent";
+static BLOCKQUOTE_ATTR_KEY: &str = "blockquote";
+static ALIGN_ATTR_KEY: &str = "align";
+static ALIGN_CLASS_PREFIX: &str = "ql-align-";
+static CODE_BLOCK_ATTR_KEY: &str = "code-block";
+static CODE_CLASS: &str = "ql-pre";
+static CODE_ATTR_KEY: &str = "code";
+static LINK_ATTR_KEY: &str = "link";
+static IMAGE_EMBED: &str = "image";
+static IMAGE_ATTRIBUTES: [&str; 3] = ["alt", "width", "height"];
+static TEMPLATE_VAR_EMBED: &str = "template-var";
+static TEMPLATE_VAR_CLASS: &str = "ql-template-var";
+static SOFT_BREAK_EMBED: &str = "page_break";
+
+/// HTML tags of the text formats, see `core_formats::t_formats`
+static TEXT_FORMATS: [(&str, &str); 10] = [
+    ("STRONG", FORMAT_BOLD),
+    ("EM", FORMAT_ITALIC),
+    ("U", FORMAT_UNDERLINE),
+    ("S", FORMAT_STRIKE),
+    ("SUB", FORMAT_SUB),
+    ("SUP", FORMAT_SUP),
+    ("DEL", FORMAT_DELETED),
+    ("INS", FORMAT_INSERTED),
+    ("MARK", FORMAT_MARKED),
+    ("SMALL", FORMAT_SMALL),
+];
+
+const ELEMENT_NODE: u16 = 1;
+const TEXT_NODE: u16 = 3;
+
+impl DocumentRoot {
+    /// # from_html()
+    ///
+    /// Creates a document from an HTML fragment, for example HTML pasted from another
+    /// application, or HTML produced by `as_html_string()`. The document is open for editing.
+    ///
+    /// Supported elements are the elements of the formats in the format crates, like `<P>`,
+    /// `<H1>`..`<H6>`, `<UL>`, `<OL>`, `<LI>`, `<BLOCKQUOTE>`, `<A>`, `<STRONG>`, `<EM>`,
+    /// `<CODE>`, `<IMG>` and `<BR>`, and the `ql-align-..` classes of the align format. The
+    /// content of other elements is imported as plain text.
+    ///
+    /// White space is imported as the browser shows it: new lines and runs of white space in
+    /// the HTML source are a single space, and white space at the start or the end of a line
+    /// is dropped. Only a code block keeps its white space. A `<BR>` within a line is a soft
+    /// break; the `<BR>` ending a line, like in `<p><br></p>`, is not.
+    ///
+    /// Returns `HtmlParseFailed` when the browser can not parse the HTML.
+    pub fn from_html(id: &str, html: &str) -> Result<DocumentRoot> {
+        let delta = html_to_delta(html)?;
+        let mut doc = DocumentRoot::new(id);
+        doc.replace_content(delta)?;
+        Ok(doc)
+    }
+}
+
+/// # html_to_delta()
+///
+/// Imports the HTML of the known formats as a delta document. Unknown elements are skipped,
+/// but their content is imported.
+///
+/// The HTML is parsed in a separate HTML document, see `DomParser`, so images are not
+/// loaded and no event handlers of the HTML run.
+pub(crate) fn html_to_delta(html: &str) -> Result<Delta> {
+    let parsed = DomParser::new()
+        .and_then(|parser| parser.parse_from_string(html, SupportedType::TextHtml))
+        .map_err(|e| HtmlParseFailed {
+            msg: format!("{:?}", e),
+        })?;
+    let mut delta = Delta::default();
+    if let Some(body) = parsed.body() {
+        import_blocks(&body, false, &mut delta)?;
+    }
+    Ok(delta)
+}
+
+/// # Line
+///
+/// Collects the inline content of a line like the browser shows it, see `from_html()`.
+/// White space and `<BR>` elements are pending until more content follows on the line.
+#[derive(Default)]
+struct Line {
+    preformatted: bool,        //white space is kept, as in a code block
+    content: bool,             //the line has content before the pending white space
+    space: Option<Attributes>, //a pending space, with the attributes of its text
+    breaks: usize,             //pending soft breaks
+}
+
+impl Line {
+    fn preformatted() -> Self {
+        Line {
+            preformatted: true,
+            ..Default::default()
+        }
+    }
+
+    fn push_text(&mut self, delta: &mut Delta, text: &str, attr: &Attributes) {
+        if self.preformatted {
+            if !text.is_empty() {
+                insert_text(delta, text, attr.clone());
+            }
+            return;
+        }
+        let mut txt = String::new();
+        for c in text.chars() {
+            if c.is_ascii_whitespace() {
+                if self.content && self.space.is_none() {
+                    self.space = Some(attr.clone());
+                }
+                continue;
+            }
+            if txt.is_empty() {
+                self.push_pending(delta);
+            } else if self.space.take().is_some() {
+                txt.push(' ');
+            }
+            txt.push(c);
+            self.content = true;
+        }
+        if !txt.is_empty() {
+            insert_text(delta, &txt, attr.clone());
+        }
+    }
+
+    fn push_embed(&mut self, delta: &mut Delta, op: DeltaOperation) {
+        self.push_pending(delta);
+        delta.push(op);
+        self.content = true;
+    }
+
+    /// White space before a `<BR>` is not shown, nor is white space after it
+    fn push_break(&mut self) {
+        self.space = None;
+        self.content = false;
+        self.breaks += 1;
+    }
+
+    fn push_pending(&mut self, delta: &mut Delta) {
+        for _ in 0..self.breaks {
+            delta.push(soft_break_operation());
+        }
+        self.breaks = 0;
+        if let Some(attr) = self.space.take() {
+            insert_text(delta, " ", attr);
+        }
+    }
+
+    /// Ends the line with its block operation. The last `<BR>` of a line is not shown.
+    fn end(mut self, delta: &mut Delta, block_attr: Attributes) {
+        self.breaks = self.breaks.saturating_sub(1);
+        self.space = None;
+        self.push_pending(delta);
+        insert_text(delta, "\n", block_attr);
+    }
+}
+
+fn import_blocks(parent: &Node, preformatted: bool, delta: &mut Delta) -> Result<()> {
+    let children = parent.child_nodes();
+    for i in 0..children.length() {
+        let node = children.item(i).unwrap();
+        let Some(element) = node.dyn_ref::<Element>() else {
+            let text = node.text_content().unwrap_or_default();
+            if preformatted {
+                for line in text.lines().filter(|l| !l.is_empty()) {
+                    delta.insert(line);
+                    delta.insert("\n");
+                }
+            } else if !text.trim().is_empty() {
+                let mut line = Line::default();
+                line.push_text(delta, &text, &Attributes::default());
+                line.end(delta, Attributes::default());
+            }
+            continue;
+        };
+
+        let tag = element.tag_name().to_uppercase();
+        let mut block_attr = Attributes::default();
+        match tag.as_str() {
+            "P" => {}
+            "H1" | "H2" | "H3" | "H4" | "H5" | "H6" => {
+                let level = tag[1..].parse::<i32>().unwrap_or(1);
+                block_attr.insert(HEADER_ATTR_KEY, level);
+            }
+            "BLOCKQUOTE" => {
+                block_attr.insert(BLOCKQUOTE_ATTR_KEY, true);
+            }
+            "UL" | "OL" => {
+                import_list(element, 0, delta)?;
+                continue;
+            }
+            "PRE" => {
+                import_blocks(&node, true, delta)?;
+                continue;
+            }
+            "SPAN" if element.class_list().contains(CODE_CLASS) => {
+                block_attr.insert(CODE_BLOCK_ATTR_KEY, true);
+            }
+            _ => {
+                import_blocks(&node, preformatted, delta)?;
+                continue;
+            }
+        }
+        insert_align(element, &mut block_attr);
+        let mut line = if preformatted || block_attr.contains_key(CODE_BLOCK_ATTR_KEY) {
+            Line::preformatted()
+        } else {
+            Line::default()
+        };
+        import_inline(&node, &Attributes::default(), &mut line, delta)?;
+        line.end(delta, block_attr);
+    }
+    Ok(())
+}
+
+/// Each `<LI>` is a line. A nested list is a child of a `<LI>`, and is one indent deeper.
+/// The `<LI>` holding only the nested list has no line of its own.
+fn import_list(list: &Element, indent: i32, delta: &mut Delta) -> Result<()> {
+    let mut block_attr = Attributes::default();
+    let kind = if list.tag_name().eq_ignore_ascii_case("UL") {
+        "bullet"
+    } else {
+        "ordered"
+    };
+    block_attr.insert(LIST_ATTR_KEY, kind);
+    if indent > 0 {
+        block_attr.insert(LIST_INDENT_ATTR_KEY, indent);
+    }
+    insert_align(list, &mut block_attr);
+
+    let items = list.children();
+    for i in 0..items.length() {
+        let item = items.item(i).unwrap();
+        let nested = item.children();
+        let nested: Vec<Element> = (0..nested.length())
+            .filter_map(|j| nested.item(j))
+            .filter(|child| is_list(child))
+            .collect();
+        if nested.is_empty() || has_content(&item) {
+            let mut line = Line::default();
+            import_inline(&item, &Attributes::default(), &mut line, delta)?;
+            line.end(delta, block_attr.clone());
+        }
+        for child in nested.iter() {
+            import_list(child, indent + 1, delta)?;
+        }
+    }
+    Ok(())
+}
+
+/// The align format sets the class `ql-align-center`, etc.
+fn insert_align(element: &Element, attr: &mut Attributes) {
+    let classes = element.class_list();
+    for i in 0..classes.length() {
+        if let Some(class) = classes.item(i) {
+            if let Some(align) = class.strip_prefix(ALIGN_CLASS_PREFIX) {
+                attr.insert(ALIGN_ATTR_KEY, align);
+            }
+        }
+    }
+}
+
+fn is_list(element: &Element) -> bool {
+    let tag = element.tag_name().to_uppercase();
+    tag == "UL" || tag == "OL"
+}
+
+/// Returns true when the `<LI>` has content other than nested lists
+fn has_content(item: &Element) -> bool {
+    let children = item.child_nodes();
+    (0..children.length())
+        .filter_map(|i| children.item(i))
+        .any(|node| match node.dyn_ref::<Element>() {
+            Some(element) => !is_list(element),
+            None => !node.text_content().unwrap_or_default().trim().is_empty(),
+        })
+}
+
+fn import_inline(
+    parent: &Node,
+    attr: &Attributes,
+    line: &mut Line,
+    delta: &mut Delta,
+) -> Result<()> {
+    let children = parent.child_nodes();
+    for i in 0..children.length() {
+        let node = children.item(i).unwrap();
+        if node.node_type() == TEXT_NODE {
+            let text = node.text_content().unwrap_or_default();
+            line.push_text(delta, &text, attr);
+            continue;
+        }
+        if node.node_type() != ELEMENT_NODE {
+            continue;
+        }
+        let element = node.dyn_ref::<Element>().unwrap();
+        let tag = element.tag_name().to_uppercase();
+        let mut attr = attr.clone();
+        match tag.as_str() {
+            "UL" | "OL" => continue,
+            "BR" => {
+                line.push_break();
+                continue;
+            }
+            "IMG" => {
+                line.push_embed(delta, image_operation(element));
+                continue;
+            }
+            "SPAN" if element.class_list().contains(TEMPLATE_VAR_CLASS) => {
+                let text = element.text_content().unwrap_or_default();
+                let name = text.trim_start_matches("{{").trim_end_matches("}}");
+                let mut var = OpsMap::default();
+                var.insert(TEMPLATE_VAR_EMBED, name);
+                line.push_embed(
+                    delta,
+                    DeltaOperation::insert_attr(var, Attributes::default()),
+                );
+                continue;
+            }
+            "A" => {
+                let href = element.get_attribute("href").unwrap_or_default();
+                attr.insert(LINK_ATTR_KEY, href.as_str());
+            }
+            "CODE" => {
+                attr.insert(CODE_ATTR_KEY, true);
+            }
+            _ => {
+                if let Some((_tag, format)) = TEXT_FORMATS.iter().find(|(t, _f)| *t == tag) {
+                    attr.insert(*format, true);
+                }
+            }
+        }
+        import_inline(&node, &attr, line, delta)?;
+    }
+    Ok(())
+}
+
+/// The image format sets the source in the attribute `img`, a browser uses `src`
+fn image_operation(element: &Element) -> DeltaOperation {
+    let src = element
+        .get_attribute("src")
+        .or_else(|| element.get_attribute("img"))
+        .unwrap_or_default();
+    let mut img = OpsMap::default();
+    img.insert(IMAGE_EMBED, src.as_str());
+    let mut attr = Attributes::default();
+    for key in IMAGE_ATTRIBUTES {
+        if let Some(val) = element.get_attribute(key) {
+            attr.insert(key, val.as_str());
+        }
+    }
+    DeltaOperation::insert_attr(img, attr)
+}
+
+fn soft_break_operation() -> DeltaOperation {
+    let mut br = OpsMap::default();
+    br.insert(SOFT_BREAK_EMBED, true);
+    DeltaOperation::insert_attr(br, Attributes::default())
+}
+
+fn insert_text(delta: &mut Delta, text: &str, attr: Attributes) {
+    if attr.is_empty() {
+        delta.insert(text);
+    } else {
+        delta.insert_attr(text, attr);
+    }
+}
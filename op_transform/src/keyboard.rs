@@ -0,0 +1,307 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_query::is_empty_block;
+use crate::doc_root::DocumentRoot;
+use crate::op_retain::set_cursor_selection;
+use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::document::Document;
+use delta::operations::{DeltaOperation, OpsMap};
+use delta::types::attr_val::AttrVal;
+use log::error;
+use node_tree::doc_node::DocumentNode;
+use node_tree::format_trait::{BackspaceEmptyBehavior, EnterKeyBehavior};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use web_sys::KeyboardEvent;
+
+/// Insert::map key of the soft break format in the soft_break crate
+static SOFT_BREAK_EMBED: &str = "page_break";
+
+/// Function called when a keyboard shortcut is pressed
+pub type ShortcutHandler = Rc<dyn Fn(&mut DocumentRoot) -> Result<()>>;
+
+/// # KeyboardShortcut
+///
+/// Key combination which identifies a shortcut. The `key` is the `KeyboardEvent.key` value.
+/// Single character keys are stored in lower case, so `Ctrl+Shift+Z` matches the key `"z"`.
+///
+/// The `ctrl` flag matches both the control key, and the meta (command) key on Mac.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyboardShortcut {
+    pub key: String,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyboardShortcut {
+    pub fn new(key: &str, ctrl: bool, shift: bool, alt: bool) -> Self {
+        let key = if key.chars().count() == 1 {
+            key.to_lowercase()
+        } else {
+            key.to_string()
+        };
+        KeyboardShortcut {
+            key,
+            ctrl,
+            shift,
+            alt,
+        }
+    }
+}
+
+impl From<&KeyboardEvent> for KeyboardShortcut {
+    fn from(event: &KeyboardEvent) -> Self {
+        KeyboardShortcut::new(
+            &event.key(),
+            event.ctrl_key() || event.meta_key(),
+            event.shift_key(),
+            event.alt_key(),
+        )
+    }
+}
+
+/// # KeyboardShortcuts
+///
+/// Registry of keyboard shortcuts for a document. Applications can add, or replace,
+/// shortcuts using `register()`.
+///
+/// Built in shortcuts:
+///  - `Enter`: new line, or what the block at the cursor wants, see `EnterKeyBehavior`
+///  - `Backspace`, `Delete`: delete the selection, or the character before / after the cursor;
+///    for `Backspace` in an empty block, see `BackspaceEmptyBehavior`
+///  - `Ctrl+B`, `Ctrl+I`: toggle bold / italic on the selection
+///  - `Ctrl+A`: select the whole document
+///  - `Ctrl+Z`: undo, `Ctrl+Y` and `Ctrl+Shift+Z`: redo, see `DocumentRoot::undo()`
+#[derive(Clone)]
+pub struct KeyboardShortcuts {
+    handlers: HashMap<KeyboardShortcut, ShortcutHandler>,
+}
+
+impl Default for KeyboardShortcuts {
+    /// Returns the registry with the built in shortcuts
+    fn default() -> Self {
+        let mut shortcuts = KeyboardShortcuts::empty();
+        shortcuts.register(
+            KeyboardShortcut::new("Enter", false, false, false),
+            Box::new(enter),
+        );
+        shortcuts.register(
+            KeyboardShortcut::new("Backspace", false, false, false),
+            Box::new(backspace),
+        );
+        shortcuts.register(
+            KeyboardShortcut::new("Delete", false, false, false),
+            Box::new(delete_forward),
+        );
+        shortcuts.register(
+            KeyboardShortcut::new("b", true, false, false),
+            Box::new(|doc: &mut DocumentRoot| toggle_format(doc, "bold")),
+        );
+        shortcuts.register(
+            KeyboardShortcut::new("i", true, false, false),
+            Box::new(|doc: &mut DocumentRoot| toggle_format(doc, "italic")),
+        );
+        shortcuts.register(
+            KeyboardShortcut::new("a", true, false, false),
+            Box::new(select_all),
+        );
+        shortcuts.register(
+            KeyboardShortcut::new("z", true, false, false),
+            Box::new(|doc: &mut DocumentRoot| doc.undo().map(|_done| ())),
+        );
+        shortcuts.register(
+            KeyboardShortcut::new("y", true, false, false),
+            Box::new(|doc: &mut DocumentRoot| doc.redo().map(|_done| ())),
+        );
+        shortcuts.register(
+            KeyboardShortcut::new("z", true, true, false),
+            Box::new(|doc: &mut DocumentRoot| doc.redo().map(|_done| ())),
+        );
+        shortcuts
+    }
+}
+
+impl KeyboardShortcuts {
+    /// Returns a registry without any shortcut
+    pub fn empty() -> Self {
+        KeyboardShortcuts {
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// Adds a shortcut, replacing any shortcut with the same key combination
+    pub fn register(
+        &mut self,
+        shortcut: KeyboardShortcut,
+        handler: Box<dyn Fn(&mut DocumentRoot) -> Result<()>>,
+    ) {
+        self.handlers.insert(shortcut, Rc::from(handler));
+    }
+
+    pub fn remove(&mut self, shortcut: &KeyboardShortcut) {
+        self.handlers.remove(shortcut);
+    }
+
+    pub fn get(&self, shortcut: &KeyboardShortcut) -> Option<ShortcutHandler> {
+        self.handlers.get(shortcut).cloned()
+    }
+}
+
+impl DocumentRoot {
+    /// # on_key_down()
+    ///
+    /// Handles a key down event from the browser. Returns true if the event is handled,
+    /// in which case the caller should call `preventDefault()` on the event.
+    pub fn on_key_down(&mut self, event: &KeyboardEvent) -> bool {
+        self.handle_shortcut(&KeyboardShortcut::from(event))
+    }
+
+    /// # handle_shortcut()
+    ///
+    /// Executes the handler registered for the shortcut. Returns true if there is such a handler.
+    ///
+    /// A failing handler is logged, but the key is still consumed: the browser default action
+    /// would change the HTML DOM without updating the document.
+    pub fn handle_shortcut(&mut self, shortcut: &KeyboardShortcut) -> bool {
+        let handler = match self.get_keyboard_shortcuts().get(shortcut) {
+            Some(handler) => handler,
+            None => return false,
+        };
+        if let Err(e) = handler(self) {
+            error!("DocumentRoot::handle_shortcut({:?}) - {}", shortcut, e);
+        }
+        true
+    }
+}
+
+/// Deletes the current selection, and collapses the cursor.
+/// Returns true if there was a selection.
+fn delete_selection(doc: &mut DocumentRoot) -> Result<bool> {
+    let cursor = doc.get_cursor();
+    if !cursor.is_selection() {
+        return Ok(false);
+    }
+    let len = cursor.selection_length();
+    cursor.collapse();
+    doc.apply_operation(DeltaOperation::delete(len))?;
+    Ok(true)
+}
+
+/// The block at the cursor decides what the `Enter` key does, see
+/// `FormatTait::enter_key_behavior()`.
+fn enter(doc: &mut DocumentRoot) -> Result<()> {
+    delete_selection(doc)?;
+    let Some(block) = doc.block_at_cursor() else {
+        return doc.apply_operation(DeltaOperation::insert("\n"));
+    };
+    match enter_key_behavior(&block) {
+        EnterKeyBehavior::InsertSoftBreak => {
+            let mut br = OpsMap::default();
+            br.insert(SOFT_BREAK_EMBED, true);
+            doc.apply_operation(DeltaOperation::insert_attr(br, Attributes::default()))
+        }
+        //like a second Enter at the end of a list
+        EnterKeyBehavior::ExitBlock if is_empty_block(&block) => exit_block(doc, &block),
+        _ => doc.apply_operation(DeltaOperation::insert("\n")),
+    }
+}
+
+/// Removes the format of the block, the block becomes a paragraph
+fn exit_block(doc: &mut DocumentRoot, block: &Arc<DocumentNode>) -> Result<()> {
+    let retain = block.retain_index();
+    let mut op = DeltaOperation::retain(1);
+    op.set_attributes(block.get_formatter().block_remove_attr());
+    set_cursor_selection(doc.get_cursor(), retain, 0);
+    doc.apply_operation(op)?;
+    set_cursor_selection(doc.get_cursor(), retain, 0);
+    Ok(())
+}
+
+/// `Inherit` takes the behavior of the first ancestor which does not inherit
+fn enter_key_behavior(block: &Arc<DocumentNode>) -> EnterKeyBehavior {
+    let mut behavior = block.get_formatter().enter_key_behavior();
+    let mut ancestors = block.ancestors().into_iter();
+    while behavior == EnterKeyBehavior::Inherit {
+        behavior = match ancestors.next() {
+            Some(ancestor) => ancestor.get_formatter().enter_key_behavior(),
+            None => EnterKeyBehavior::SplitBlock,
+        };
+    }
+    behavior
+}
+
+/// At the start of an empty block, the block decides what the `Backspace` key does, see
+/// `FormatTait::backspace_at_empty_block_behavior()`.
+fn backspace(doc: &mut DocumentRoot) -> Result<()> {
+    if delete_selection(doc)? {
+        return Ok(());
+    }
+    if let Some(block) = doc.block_at_cursor() {
+        if is_empty_block(&block) {
+            match block.get_formatter().backspace_at_empty_block_behavior() {
+                BackspaceEmptyBehavior::NoOp => return Ok(()),
+                BackspaceEmptyBehavior::ExitFormat => return exit_block(doc, &block),
+                BackspaceEmptyBehavior::MergeWithPrev => {}
+            }
+        }
+    }
+    let retain = doc.get_cursor().get_retain_index();
+    if retain == 0 {
+        return Ok(());
+    }
+    set_cursor_selection(doc.get_cursor(), retain - 1, 0);
+    doc.apply_operation(DeltaOperation::delete(1))
+}
+
+fn delete_forward(doc: &mut DocumentRoot) -> Result<()> {
+    if delete_selection(doc)? {
+        return Ok(());
+    }
+    //The last new line of the document can not be deleted
+    let last = Delta::document_length(&doc.to_delta()) - 1;
+    if doc.get_cursor().get_retain_index() >= last {
+        return Ok(());
+    }
+    doc.apply_operation(DeltaOperation::delete(1))
+}
+
+/// Switches the format off when all selected text has the format, else switches it on.
+/// A collapsed cursor is not changed.
+fn toggle_format(doc: &mut DocumentRoot, format: &str) -> Result<()> {
+    let cursor = doc.get_cursor();
+    if !cursor.is_selection() {
+        return Ok(());
+    }
+    let start = cursor.get_retain_index();
+    let len = cursor.selection_length();
+
+    let stats = doc.get_formatted_selection_stats();
+    let mut attr = Attributes::default();
+    if stats.format_counts.get(format) == Some(&stats.chars) && stats.chars > 0 {
+        attr.insert(format, AttrVal::Null);
+    } else {
+        attr.insert(format, true);
+    }
+
+    doc.get_cursor().collapse();
+    let mut op = DeltaOperation::retain(len);
+    op.set_attributes(attr);
+    doc.apply_operation(op)?;
+    set_cursor_selection(doc.get_cursor(), start, len);
+    Ok(())
+}
+
+fn select_all(doc: &mut DocumentRoot) -> Result<()> {
+    let len = Delta::document_length(&doc.to_delta()) - 1;
+    set_cursor_selection(doc.get_cursor(), 0, len);
+    Ok(())
+}
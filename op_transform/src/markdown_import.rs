@@ -0,0 +1,333 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::registry::Registry;
+use anyhow::Result;
+use core_formats::format_const::{FORMAT_BOLD, FORMAT_ITALIC, NAME_P_BLOCK};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::{DeltaOperation, OpsMap};
+use delta::types::attr_val::AttrVal;
+use std::sync::RwLockReadGuard;
+
+/// Attribute keys, and embed names, of the formats in the format crates.
+/// We only use them when the registry has a format for them.
+static HEADER_ATTR_KEY: &str = "heading";
+static LIST_ATTR_KEY: &str = "list";
+static CODE_BLOCK_ATTR_KEY: &str = "code-block";
+static CODE_ATTR_KEY: &str = "code";
+static LINK_ATTR_KEY: &str = "link";
+static IMAGE_EMBED: &str = "image";
+static IMAGE_ALT: &str = "alt";
+
+impl DocumentRoot {
+    /// # import_markdown()
+    ///
+    /// Replaces the document content by the content of the markdown text.
+    ///
+    /// Supported elements:
+    ///  - `# Header` .. `###### Header`
+    ///  - `- `, `* ` or `+ ` bullet lists, and `1. ` ordered lists
+    ///  - `**bold**`, `_italic_`, `` `code` `` and `[text](url)` links
+    ///  - `![alt](src)` images
+    ///  - fenced code blocks, using three back ticks
+    ///  - blank lines as paragraph separator. Consecutive lines form one paragraph.
+    ///
+    /// Elements for formats which are not in the registry are imported as plain text.
+    ///
+    /// FIXME: There is no divider format yet, so a horizontal rule `---` is imported as
+    /// a paragraph with the text `---`.
+    pub fn import_markdown(&mut self, markdown: &str) -> Result<()> {
+        let delta = {
+            let registry = Registry::get_ref()?;
+            MarkdownImport::new(&registry).to_delta(markdown)
+        };
+        self.replace_content(delta)
+    }
+}
+
+/// # MarkdownImport
+///
+/// Line based markdown parser, which translates the markdown in a delta document.
+struct MarkdownImport {
+    headers: bool,
+    bullet_list: bool,
+    ordered_list: bool,
+    code_block: bool,
+    code: bool,
+    link: bool,
+    image: bool,
+}
+
+impl MarkdownImport {
+    fn new(registry: &RwLockReadGuard<'static, Registry>) -> Self {
+        let block = |key: &str, value: AttrVal| {
+            let mut attr = Attributes::default();
+            attr.insert(key, value);
+            match registry.block_format(&DeltaOperation::insert_attr("\n", attr)) {
+                Ok(format) => format.format_name() != NAME_P_BLOCK,
+                Err(_) => false,
+            }
+        };
+        let text = |key: &str, value: AttrVal| {
+            let mut attr = Attributes::default();
+            attr.insert(key, value);
+            registry
+                .line_format(&DeltaOperation::insert_attr("x", attr))
+                .is_ok()
+        };
+
+        MarkdownImport {
+            headers: block(HEADER_ATTR_KEY, AttrVal::from(1)),
+            bullet_list: block(LIST_ATTR_KEY, AttrVal::from("bullet")),
+            ordered_list: block(LIST_ATTR_KEY, AttrVal::from("ordered")),
+            code_block: block(CODE_BLOCK_ATTR_KEY, AttrVal::from(true)),
+            code: text(CODE_ATTR_KEY, AttrVal::from(true)),
+            link: text(LINK_ATTR_KEY, AttrVal::from("https://")),
+            image: match registry.line_format(&image_operation("src", "")) {
+                Ok(format) => format.format_name() == IMAGE_EMBED,
+                Err(_) => false,
+            },
+        }
+    }
+
+    /// Returns the delta document, which always ends with a new line
+    fn to_delta(&self, markdown: &str) -> Delta {
+        let mut delta = Delta::default();
+        let mut paragraph: Vec<&str> = Vec::new();
+        let mut in_code_block = false;
+
+        for line in markdown.lines() {
+            let trimmed = line.trim();
+            if in_code_block {
+                if trimmed.starts_with("```") {
+                    in_code_block = false;
+                    continue;
+                }
+                if !line.is_empty() {
+                    delta.insert(line);
+                }
+                let mut attr = Attributes::default();
+                attr.insert(CODE_BLOCK_ATTR_KEY, true);
+                delta.insert_attr("\n", attr);
+                continue;
+            }
+
+            if trimmed.starts_with("```") && self.code_block {
+                self.flush_paragraph(&mut delta, &mut paragraph);
+                in_code_block = true;
+            } else if trimmed.is_empty() {
+                self.flush_paragraph(&mut delta, &mut paragraph);
+            } else if is_horizontal_rule(trimmed) {
+                self.flush_paragraph(&mut delta, &mut paragraph);
+                delta.insert(trimmed);
+                delta.insert("\n");
+            } else if let Some((text, attr)) = self.block_line(trimmed) {
+                self.flush_paragraph(&mut delta, &mut paragraph);
+                self.inline(&mut delta, text, &Attributes::default());
+                delta.insert_attr("\n", attr);
+            } else {
+                paragraph.push(trimmed);
+            }
+        }
+        self.flush_paragraph(&mut delta, &mut paragraph);
+
+        if delta.get_ops_ref().is_empty() {
+            delta.insert("\n");
+        }
+        delta
+    }
+
+    /// Consecutive lines, without blank line in between, form one paragraph
+    fn flush_paragraph(&self, delta: &mut Delta, paragraph: &mut Vec<&str>) {
+        if paragraph.is_empty() {
+            return;
+        }
+        let text = paragraph.join(" ");
+        self.inline(delta, &text, &Attributes::default());
+        delta.insert("\n");
+        paragraph.clear();
+    }
+
+    /// Returns the text, and the block attributes, when the line is a header or a list item
+    fn block_line<'a>(&self, line: &'a str) -> Option<(&'a str, Attributes)> {
+        let mut attr = Attributes::default();
+
+        let level = line.chars().take_while(|c| *c == '#').count();
+        if self.headers && (1..=6).contains(&level) {
+            let rest = &line[level..];
+            if rest.is_empty() || rest.starts_with(' ') {
+                attr.insert(HEADER_ATTR_KEY, level as i32);
+                return Some((rest.trim().trim_end_matches('#').trim_end(), attr));
+            }
+        }
+
+        for marker in ["- ", "* ", "+ "] {
+            if self.bullet_list && line.starts_with(marker) {
+                attr.insert(LIST_ATTR_KEY, "bullet");
+                return Some((line[marker.len()..].trim_start(), attr));
+            }
+        }
+
+        let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+        if self.ordered_list && digits > 0 {
+            let rest = &line[digits..];
+            if rest.starts_with(". ") || rest.starts_with(") ") {
+                attr.insert(LIST_ATTR_KEY, "ordered");
+                return Some((rest[2..].trim_start(), attr));
+            }
+        }
+        None
+    }
+
+    /// # inline()
+    ///
+    /// Appends the text with inline formats to the delta. A delimiter without a closing
+    /// delimiter is just text.
+    fn inline(&self, delta: &mut Delta, text: &str, base: &Attributes) {
+        let chars: Vec<char> = text.chars().collect();
+        //byte index of every char, so we can slice the rest of the text
+        let offsets: Vec<usize> = text.char_indices().map(|(b, _c)| b).collect();
+        let mut txt = String::new();
+        let mut bold = false;
+        let mut italic = false;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let rest = &text[offsets[i]..];
+
+            if c == '\\' && i + 1 < chars.len() && chars[i + 1].is_ascii_punctuation() {
+                txt.push(chars[i + 1]);
+                i += 2;
+                continue;
+            }
+
+            if rest.starts_with("**") || rest.starts_with("__") {
+                let delim = &rest[..2];
+                if bold || rest[2..].contains(delim) {
+                    push_text(delta, &mut txt, base, bold, italic);
+                    bold = !bold;
+                    i += 2;
+                    continue;
+                }
+            }
+
+            if (c == '*' || c == '_') && is_emphasis(&chars, i, italic) {
+                push_text(delta, &mut txt, base, bold, italic);
+                italic = !italic;
+                i += 1;
+                continue;
+            }
+
+            if c == '`' {
+                if let Some(end) = rest[1..].find('`') {
+                    push_text(delta, &mut txt, base, bold, italic);
+                    let code = &rest[1..end + 1];
+                    let mut attr = base.clone();
+                    if self.code {
+                        attr.insert(CODE_ATTR_KEY, true);
+                    }
+                    insert_text(delta, code, attr);
+                    i += code.chars().count() + 2;
+                    continue;
+                }
+            }
+
+            if c == '!' || c == '[' {
+                if let Some((label, target, len)) = parse_link(&rest[(c == '!') as usize..]) {
+                    push_text(delta, &mut txt, base, bold, italic);
+                    if c == '!' && self.image {
+                        delta.push(image_operation(target, label));
+                    } else if c == '!' {
+                        txt.push_str(&rest[..len + 1]);
+                    } else {
+                        let mut attr = text_attributes(base, bold, italic);
+                        if self.link {
+                            attr.insert(LINK_ATTR_KEY, target);
+                        }
+                        self.inline(delta, label, &attr);
+                    }
+                    i += rest[..len + (c == '!') as usize].chars().count();
+                    continue;
+                }
+            }
+
+            txt.push(c);
+            i += 1;
+        }
+        push_text(delta, &mut txt, base, bold, italic);
+    }
+}
+
+/// `***`, `---` or `___`, possibly with spaces in between
+fn is_horizontal_rule(line: &str) -> bool {
+    let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.len() >= 3 && ['-', '*', '_'].contains(&chars[0]) && chars.iter().all(|c| *c == chars[0])
+}
+
+/// An emphasis delimiter opens at the start of a word, and closes at the end of a word.
+/// So `snake_case_name` is not emphasised.
+fn is_emphasis(chars: &[char], i: usize, open: bool) -> bool {
+    let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+    let next = chars.get(i + 1).copied();
+    if open {
+        return prev.is_some_and(|p| !p.is_whitespace())
+            && next.is_none_or(|n| !n.is_alphanumeric());
+    }
+    let opens =
+        next.is_some_and(|n| !n.is_whitespace()) && prev.is_none_or(|p| !p.is_alphanumeric());
+    opens && chars.len() > i + 2 && chars[i + 2..].contains(&chars[i])
+}
+
+/// Parses `[label](target)`, and returns the label, target and byte length
+fn parse_link(text: &str) -> Option<(&str, &str, usize)> {
+    if !text.starts_with('[') {
+        return None;
+    }
+    let label_end = text.find("](")?;
+    let target_end = label_end + 2 + text[label_end + 2..].find(')')?;
+    let label = &text[1..label_end];
+    let target = text[label_end + 2..target_end].trim();
+    Some((label, target, target_end + 1))
+}
+
+fn image_operation(src: &str, alt: &str) -> DeltaOperation {
+    let mut img = OpsMap::default();
+    img.insert(IMAGE_EMBED, src);
+    let mut attr = Attributes::default();
+    attr.insert(IMAGE_ALT, alt);
+    DeltaOperation::insert_attr(img, attr)
+}
+
+fn text_attributes(base: &Attributes, bold: bool, italic: bool) -> Attributes {
+    let mut attr = base.clone();
+    if bold {
+        attr.insert(FORMAT_BOLD, true);
+    }
+    if italic {
+        attr.insert(FORMAT_ITALIC, true);
+    }
+    attr
+}
+
+fn push_text(delta: &mut Delta, txt: &mut String, base: &Attributes, bold: bool, italic: bool) {
+    if txt.is_empty() {
+        return;
+    }
+    insert_text(delta, txt, text_attributes(base, bold, italic));
+    txt.clear();
+}
+
+fn insert_text(delta: &mut Delta, txt: &str, attr: Attributes) {
+    if attr.is_empty() {
+        delta.insert(txt);
+    } else {
+        delta.insert_attr(txt, attr);
+    }
+}
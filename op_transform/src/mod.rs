@@ -20,6 +20,9 @@ pub mod op_delete;
 pub mod op_insert;
 pub mod op_retain;
 
+// Attribute changes of a retain operation
+pub mod attributes_diff;
+
 // The basic document starts with 1 root node
 // The registry describes all formats supported.
 // This registry is a static global variable.
@@ -30,6 +33,56 @@ pub mod registry;
 
 pub mod auto_soft_break;
 
+// Secondary cursors for multi caret editing
+pub mod multi_cursor;
+
+// Ranges of the document which can not be edited
+pub mod protected_range;
+
+// Applying many operations, without intermediate updates
+pub mod batch;
+
+// Undo and redo of the changes applied to the document
+pub mod snapshot;
+pub mod undo;
+
+// Keyboard event handling, shortcuts and auto formatting of typed text
+pub mod auto_format;
+pub mod composition;
+pub mod keyboard;
+
+// Read only statistics on the document content
+// Read only queries on the document content at the cursor, or at some retain index
+pub mod doc_query;
+pub mod doc_stats;
+pub mod table_of_contents;
+
+// Find and replace of text
+pub mod doc_search;
+
+// Spell checking of the text, with a pluggable spell checker
+pub mod doc_spelling;
+
+// Debugging aid: checks the invariants of the document node tree
+pub mod doc_integrity;
+
+// Programmatic edits of the document, without managing the cursor
+pub mod doc_edit;
+pub mod markdown_import;
+
+// Checking and un-checking the items of a checklist
+pub mod checklist;
+
+// Text export of the document for screen readers
+pub mod export_accessible;
+
+// Import of HTML, and the delta of the change between two HTML documents
+pub mod html_diff;
+pub mod html_import;
+
+// Notifications of the changes applied to the document
+pub mod doc_observer;
+
 use cfg_if::cfg_if;
 extern crate web_sys;
 use log::Level;
@@ -0,0 +1,100 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::op_retain::set_cursor_selection;
+use delta::operations::DeltaOperation;
+use delta::types::ops_kind::OpKind;
+use node_tree::cursor::Cursor;
+use node_tree::tree_traverse::first_node;
+
+/// # CursorId
+///
+/// Handle of a cursor, see `DocumentRoot::add_cursor()`.
+/// The primary cursor, `DocumentRoot::get_cursor()`, has the id `CursorId::PRIMARY`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CursorId(usize);
+
+impl CursorId {
+    pub const PRIMARY: CursorId = CursorId(0);
+}
+
+impl DocumentRoot {
+    /// # add_cursor()
+    ///
+    /// Adds a secondary cursor at the retain index, for multi caret editing.
+    ///
+    /// Operations are applied at the primary cursor only. The secondary cursors are just
+    /// retain indices, which move with the changes of `apply_operation()`. They are removed
+    /// when the document is closed, see `DocumentRoot::close()`.
+    pub fn add_cursor(&mut self, retain_idx: usize) -> CursorId {
+        self.next_cursor_id += 1;
+        let id = CursorId(self.next_cursor_id);
+        self.cursors.push((id, retain_idx));
+        id
+    }
+
+    /// # remove_cursor()
+    ///
+    /// Removes the secondary cursor. An unknown id, or the primary cursor, is ignored.
+    pub fn remove_cursor(&mut self, id: CursorId) {
+        self.cursors.retain(|(c, _retain)| *c != id);
+    }
+
+    /// # get_all_cursors()
+    ///
+    /// Returns `(id, retain_index)` for all cursors. The primary cursor comes first.
+    pub fn get_all_cursors(&self) -> Vec<(CursorId, usize)> {
+        let mut cursors = vec![(CursorId::PRIMARY, self.get_cursor().get_retain_index())];
+        cursors.extend(self.cursors.iter().copied());
+        cursors
+    }
+
+    /// # show_all_cursors()
+    ///
+    /// Shows all cursors as ranges of the browser selection, using the registered DOM
+    /// cursor, see `set_dom_cursor()`. Most browsers only show the first range, which is the
+    /// primary cursor.
+    pub fn show_all_cursors(&self) {
+        let Some(dom_cursor) = self.get_dom_cursor() else {
+            return;
+        };
+        if self.get_root().child_count() == 0 {
+            return;
+        }
+        let mut cursors = vec![self.get_cursor().clone()];
+        for (_id, retain) in self.cursors.iter() {
+            let cursor = Cursor::new();
+            let node = first_node(self.get_root());
+            if node.is_leaf() {
+                cursor.set_before(&node);
+            } else {
+                cursor.set_at(&node, 0);
+            }
+            set_cursor_selection(&cursor, *retain, 0);
+            cursors.push(cursor);
+        }
+        dom_cursor.cursors_to_html_dom(&cursors);
+    }
+
+    /// # shift_cursors()
+    ///
+    /// Moves the secondary cursors after the operation is applied at retain index `start`.
+    ///  - an insert before, or at, the cursor shifts it by the insert length
+    ///  - a delete before the cursor shifts it back by the delete length
+    ///  - a delete which contains the cursor moves it to the start of the delete
+    pub(crate) fn shift_cursors(&mut self, start: usize, operation: &DeltaOperation) {
+        for (_id, retain) in self.cursors.iter_mut() {
+            match operation.get_op_kind() {
+                OpKind::Insert(_) if *retain >= start => *retain += operation.op_len(),
+                OpKind::Delete(len) if *retain >= start + len => *retain -= len,
+                OpKind::Delete(_) if *retain > start => *retain = start,
+                _ => {}
+            }
+        }
+    }
+}
@@ -6,6 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::auto_soft_break::AutomaticSoftBreak;
+use crate::doc_query::block_of;
 use crate::error::Error::{
     CanNotFindNextBlock, DeleteOperationOnEmptyDocument, DeletingLastBlock,
     UnexpectedCursorPosition,
@@ -24,7 +25,10 @@ use std::sync::Arc;
 /// Deleting a character happens "after" the cursor so
 /// there is no change in cursor location. All cursor
 /// updates will use the one without retain update ...
-pub fn delete(cursor: &Cursor, delete_len: usize) -> Result<()> {
+///
+/// During a batch of operations the soft breaks of empty blocks are `deferred`, see
+/// `DocumentRoot::begin_batch()`.
+pub fn delete(cursor: &Cursor, delete_len: usize, deferred: bool) -> Result<()> {
     let (mut dn, mut del) = match cursor.get_location() {
         CursorLocation::After(dn) => match next_node_non_zero_length(&dn) {
             None => {
@@ -55,33 +59,39 @@ pub fn delete(cursor: &Cursor, delete_len: usize) -> Result<()> {
 
     //error!( "op_transform::delete() - format name = {}", dn.get_formatter().format_name());
 
+    //Remember if we deleted a block operation, we may have to merge blocks afterwards
+    let mut block_deleted = false;
     while del > 0 {
         let ol = dn.get_operation().op_len();
+        if del >= ol && !dn.get_formatter().is_text_format() {
+            block_deleted = true;
+        }
         if del > ol {
             if let Some(next) = next_node_non_zero_length(&dn) {
                 //more loops to do and more nodes to consume
-                delete_document_node(&dn)?;
+                delete_document_node(&dn, deferred)?;
                 dn = next;
                 del = del - ol;
             } else {
                 //more deletes requested BUT no nodes to consume
                 let found = find_left_node_and_set_cursor(&dn, &cursor);
-                delete_document_node(&dn)?;
+                delete_document_node(&dn, deferred)?;
                 if !found {
                     return Err(DeleteOperationOnEmptyDocument.into());
                 }
                 //No need for merging text, there is no text after to merge ...
                 return Ok(());
             }
-        } else if del == ol {
+        } else if del == ol || dn.get_formatter().is_void() {
+            //a void node, like an image, is always deleted as a whole
             if let Some(next) = next_node_non_zero_length(&dn) {
                 //last delete action, and next nodes to the right found
-                delete_document_node(&dn)?;
+                delete_document_node(&dn, deferred)?;
                 cursor.set_cursor_to_doc_node_edge(&next, true);
                 dn = next;
             } else {
                 let found = find_left_node_and_set_cursor(&dn, &cursor);
-                delete_document_node(&dn)?;
+                delete_document_node(&dn, deferred)?;
                 if !found {
                     return Err(DeleteOperationOnEmptyDocument.into());
                 }
@@ -97,11 +107,38 @@ pub fn delete(cursor: &Cursor, delete_len: usize) -> Result<()> {
     let node = cursor.get_doc_node();
     node.get_formatter().try_merge(cursor, &node)?;
 
+    //Deleting a block operation joins 2 lines. Then the block format may have to merge with
+    //its neighbours. Example: 2 `<UL>` lists which are now only separated by the deleted `<P>`
+    if block_deleted {
+        if let Some(block) = block_of(&cursor.get_doc_node()) {
+            if !Arc::ptr_eq(&block, &node) {
+                block.get_formatter().try_merge(cursor, &block)?;
+            }
+        }
+    }
+
     //We should not stick the cursor to a DOC node with length 0
     assert!(cursor.get_doc_node().op_len() > 0);
     Ok(())
 }
 
+/// # delete_selection()
+///
+/// Deletes the selected text, for example when the user presses `Delete` or `Backspace`
+/// with a selection. The cursor is collapsed to the start of the selection, and then the
+/// selection length is deleted in one `delete()` call. So a selection spanning several
+/// blocks, like a `<P>` and a list item, is joined as with any other delete.
+///
+/// Without a selection nothing is deleted.
+pub fn delete_selection(cursor: &Cursor, deferred: bool) -> Result<()> {
+    if !cursor.is_selection() {
+        return Ok(());
+    }
+    let len = cursor.selection_length();
+    cursor.collapse();
+    delete(cursor, len, deferred)
+}
+
 /// # find_left_node_and_set_cursor()
 ///
 /// Finds previous node to put the cursor after the delete action has consumed all right
@@ -129,7 +166,7 @@ fn find_left_node_and_set_cursor(doc_node: &Arc<DocumentNode>, cursor: &Cursor)
 ///
 /// What if we delete a block_node?
 /// - then we first make it empty, and then delete the block node
-fn delete_document_node(doc_node: &Arc<DocumentNode>) -> Result<()> {
+fn delete_document_node(doc_node: &Arc<DocumentNode>, deferred: bool) -> Result<()> {
     let Some(parent) = next_block(&doc_node) else {
         return if !doc_node.is_text() {
             // Test for last block ... which we never shall delete
@@ -143,7 +180,7 @@ fn delete_document_node(doc_node: &Arc<DocumentNode>) -> Result<()> {
         doc_node.get_formatter().delete_node(&doc_node);
         //If we deleted ALL children of a parent then we should add a line break
         if parent.is_empty_block() {
-            AutomaticSoftBreak::insert(&parent)?;
+            AutomaticSoftBreak::insert(&parent, deferred)?;
         }
     } else if doc_node.child_count() == 0 {
         // we are a block ... but empty remove the block
@@ -152,7 +189,7 @@ fn delete_document_node(doc_node: &Arc<DocumentNode>) -> Result<()> {
         // we are deleting the block, but not the children --> merge children with next block
         // `parent` should now point to the NEXT block to merge the children into
         if parent.is_empty_block() {
-            AutomaticSoftBreak::remove(&parent)?;
+            AutomaticSoftBreak::remove(&parent, deferred)?;
         }
 
         let block_parent = doc_node.get_parent().unwrap();
@@ -6,9 +6,9 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::auto_soft_break::AutomaticSoftBreak;
-use crate::error::Error::UnexpectedCursorPosition;
+use crate::error::Error::{BlockTransformFailed, UnexpectedCursorPosition};
 use crate::registry::Registry;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use core_formats::util::node_morph::split_text_and_block_at_cursor;
 use delta::operations::DeltaOperation;
 use node_tree::cursor::{Cursor, CursorLocation};
@@ -60,19 +60,23 @@ use std::sync::{Arc, RwLockReadGuard};
 /// text in elements that are either `Insert('some text')` or `Insert('\n')`
 ///
 /// Retain index: The retain index changes by the length of the delta operation on the input.
+///
+/// During a batch of operations the soft breaks of empty blocks are `deferred`, see
+/// `DocumentRoot::begin_batch()`.
 pub fn insert(
     cursor: &Cursor,
     op: DeltaOperation,
     reg: &RwLockReadGuard<'static, Registry>,
+    deferred: bool,
 ) -> Result<()> {
     let retain = cursor.get_retain_index();
     cursor.set_retain_index(retain + op.op_len());
 
     if reg.is_block_fmt(&op)? {
-        insert_new_block(cursor, op, &reg)?;
+        insert_new_block(cursor, op, &reg, deferred)?;
     } else {
         let new_format = reg.line_format(&op)?;
-        insert_new_text(cursor, op, &new_format)?;
+        insert_new_text(cursor, op, &new_format, deferred)?;
     }
 
     //We should not stick the cursor to a DOC node with length 0
@@ -107,6 +111,7 @@ fn insert_new_block(
     cursor: &Cursor,
     delta: DeltaOperation,
     registry: &RwLockReadGuard<'static, Registry>,
+    deferred: bool,
 ) -> Result<()> {
     if cursor.get_doc_node().get_formatter().is_text_format() {
         //See explanation above this module on splitting of text before inserting
@@ -125,7 +130,7 @@ fn insert_new_block(
             let format = right_parent.get_formatter();
             format.isolate(&right_parent)?;
             if right_parent.is_empty_block() {
-                AutomaticSoftBreak::insert(&right_parent)?;
+                AutomaticSoftBreak::insert(&right_parent, deferred)?;
             }
             (Some(right_parent), Some(format))
         } else {
@@ -133,13 +138,22 @@ fn insert_new_block(
         };
 
         let left_format = left_parent.get_formatter();
-        let left_parent = left_format.un_block_transform(cursor, &left_parent)?;
+        let left_parent = left_format
+            .un_block_transform(cursor, &left_parent)
+            .with_context(|| BlockTransformFailed {
+                format_name: left_format.format_name().to_string(),
+                reason: "un_block_transform() of the block left of the cursor".to_string(),
+            })?;
 
         let new_format = registry.block_format(&delta)?;
-        let left_parent =
-            new_format.block_transform(cursor, &left_parent, delta, new_format.clone())?;
+        let left_parent = new_format
+            .block_transform(cursor, &left_parent, delta, new_format.clone())
+            .with_context(|| BlockTransformFailed {
+                format_name: new_format.format_name().to_string(),
+                reason: "block_transform() to the inserted block".to_string(),
+            })?;
         if left_parent.is_empty_block() {
-            AutomaticSoftBreak::insert(&left_parent)?;
+            AutomaticSoftBreak::insert(&left_parent, deferred)?;
         }
 
         //Now merge again but only if we split before ...
@@ -164,9 +178,9 @@ fn insert_new_block(
 
         //in case we are an zero length block like UL block ...
         if left.op_len() > 0 {
-            AutomaticSoftBreak::insert(&left)?;
+            AutomaticSoftBreak::insert(&left, deferred)?;
         } else {
-            AutomaticSoftBreak::insert(&prev_node_non_zero_length(&left).unwrap())?;
+            AutomaticSoftBreak::insert(&prev_node_non_zero_length(&left).unwrap(), deferred)?;
         }
 
         //Insert the new node in the right spot
@@ -202,13 +216,14 @@ fn insert_new_text(
     cursor: &Cursor,
     delta: DeltaOperation,
     format: &Arc<dyn FormatTait + Sync + Send>,
+    deferred: bool,
 ) -> Result<()> {
     // Take care of the cursor currently pointing AT[block, 0] in a block node
     // By definition this block parent node is empty, and should be populated with this new leaf
     if let CursorLocation::At(block, _idx) = cursor.get_location() {
         if !block.get_formatter().is_text_format() && block.child_count() == 0 {
             //empty blocks have soft brake, but this one is not empty anymore ...
-            AutomaticSoftBreak::remove(&block)?;
+            AutomaticSoftBreak::remove(&block, deferred)?;
 
             let doc_node = format.create(delta, format.clone())?;
             cursor.set_after_no_retain_update(&doc_node);
@@ -222,7 +237,11 @@ fn insert_new_text(
     }
 
     //See explanation above this module on splitting of text before inserting
-    cursor.get_doc_node().get_formatter().split_leaf(cursor)?;
+    //A void node, like an image, can not be split; the cursor is before, or after, it
+    let formatter = cursor.get_doc_node().get_formatter();
+    if !formatter.is_void() {
+        formatter.split_leaf(cursor)?;
+    }
 
     match cursor.get_location() {
         CursorLocation::After(doc_node) => {
@@ -6,8 +6,9 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::auto_soft_break::AutomaticSoftBreak;
+use crate::error::Error::{BlockTransformFailed, UnexpectedCursorPosition};
 use crate::registry::Registry;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use core_formats::util::node_morph::split_text_node;
 use delta::attributes::{compose, Attributes};
 use delta::operations::DeltaOperation;
@@ -22,22 +23,27 @@ use std::sync::{Arc, RwLockReadGuard};
 /// Either moves the cursor, or updates the attributes.
 ///
 /// Retain index is simply current retain index + length of the delta operation
+///
+/// During a batch of operations the soft breaks of empty blocks are `deferred`, see
+/// `DocumentRoot::begin_batch()`.
 pub fn retain(
     cursor: &Cursor,
     delta: &DeltaOperation,
     registry: &RwLockReadGuard<'static, Registry>,
+    deferred: bool,
 ) -> Result<()> {
     let retain = cursor.get_retain_index();
     cursor.set_retain_index(retain + delta.op_len());
 
     if delta.get_attributes().is_empty() {
-        retain_length(cursor, delta.op_len());
+        retain_length(cursor, delta.op_len())?;
     } else {
         retain_attributed(
             cursor,
             delta.op_len(),
             delta.get_attributes().clone(),
             registry,
+            deferred,
         )?;
     }
     Ok(())
@@ -63,13 +69,18 @@ pub fn set_cursor_selection(cursor: &Cursor, retain_index: usize, selection_leng
         cursor.set_at(&node, 0);
     };
     cursor.set_retain_index(retain_index);
-    retain_length(cursor, retain_index);
+    //the cursor is set above, so it is never at the NONE position
+    if let Err(e) = retain_length(cursor, retain_index) {
+        error!("set_cursor_selection() - {}", e);
+    }
 
     //set the end
     //----------------------------------------
     if selection_length > 0 {
         let c = cursor.clone();
-        retain_length(&c, selection_length);
+        if let Err(e) = retain_length(&c, selection_length) {
+            error!("set_cursor_selection() - {}", e);
+        }
         //we need start, since retain moves the start position of the cursor
         cursor.set_select_stop(c.get_select_start());
     } else {
@@ -77,14 +88,33 @@ pub fn set_cursor_selection(cursor: &Cursor, retain_index: usize, selection_leng
     }
 }
 
+/// # apply_format_to_selection()
+///
+/// Applies the attributes to the `length` characters from the cursor start, like making the
+/// selected text bold. This is the way for the editor to format a selection.
+///
+/// Formatting changes the document nodes of the selection, so the selection is restored from
+/// its retain range afterwards, see `Cursor::set_selection_by_retain_range()`.
+pub fn apply_format_to_selection(
+    cursor: &Cursor,
+    length: usize,
+    attr: &Attributes,
+    registry: &RwLockReadGuard<'static, Registry>,
+) -> Result<()> {
+    let start = cursor.get_retain_index();
+    cursor.collapse();
+    retain_attributed(cursor, length, attr.clone(), registry, false)?;
+    cursor.set_selection_by_retain_range(start, start + length)
+}
+
 /// # retain_length()
 ///
 /// Moves the cursor the the next location, starting from the current cursor location.
-fn retain_length(cursor: &Cursor, retain_len: usize) {
+fn retain_length(cursor: &Cursor, retain_len: usize) -> Result<()> {
     let (mut dn, mut rtn) = match cursor.get_location() {
         CursorLocation::After(dn) => match next_node_non_zero_length(&dn) {
             None => {
-                return;
+                return Ok(());
             }
             Some(doc_node) => (doc_node, retain_len),
         },
@@ -98,8 +128,11 @@ fn retain_length(cursor: &Cursor, retain_len: usize) {
                 (dn, retain_len + idx)
             }
         }
-        _ => {
-            panic!("retain(): Cursor at NONE position")
+        CursorLocation::None => {
+            return Err(UnexpectedCursorPosition {
+                pos: "None".to_string(),
+            }
+            .into())
         }
     };
     //post condition: Cursor is "before" some node to be retained and nothing has been retained yet
@@ -114,21 +147,22 @@ fn retain_length(cursor: &Cursor, retain_len: usize) {
                 None => {
                     cursor.set_cursor_to_doc_node_edge(&dn, false);
                     error!("retain_length() - We try to retain but no next node.");
-                    return;
+                    return Ok(());
                 }
                 Some(doc_node) => {
                     if rtn == 0 {
                         cursor.set_cursor_to_doc_node_edge(&doc_node, true);
-                        return;
+                        return Ok(());
                     }
                     doc_node
                 }
             };
         } else {
             cursor.set_at_no_retain_update(&dn, rtn);
-            return;
+            return Ok(());
         }
     }
+    Ok(())
 }
 
 /// # retain_attributed()
@@ -148,6 +182,7 @@ fn retain_attributed(
     retain_len: usize,
     attr: Attributes,
     registry: &RwLockReadGuard<'static, Registry>,
+    deferred: bool,
 ) -> Result<()> {
     let (mut dn, mut rtn) = match cursor.get_location() {
         CursorLocation::After(dn) => {
@@ -168,8 +203,11 @@ fn retain_attributed(
                 (right_node, retain_len)
             }
         }
-        _ => {
-            panic!("retain(): Cursor at NONE position")
+        CursorLocation::None => {
+            return Err(UnexpectedCursorPosition {
+                pos: "None".to_string(),
+            }
+            .into())
         }
     };
     //post condition: Cursor is "before" some node to be retained and nothing has been retained yet
@@ -181,7 +219,7 @@ fn retain_attributed(
             if dn.get_formatter().is_text_format() {
                 dn = retain_text_format(&dn, &attr, &registry)?;
             } else {
-                dn = retain_block_format(&dn, &attr, &cursor, &registry)?
+                dn = retain_block_format(&dn, &attr, &cursor, &registry, deferred)?
             }
             if let Some(next) = next_node_non_zero_length(&dn) {
                 //more loops to do and more nodes to consume
@@ -201,7 +239,7 @@ fn retain_attributed(
             if dn.get_formatter().is_text_format() {
                 dn = retain_text_format(&dn, &attr, &registry)?;
             } else {
-                dn = retain_block_format(&dn, &attr, &cursor, &registry)?;
+                dn = retain_block_format(&dn, &attr, &cursor, &registry, deferred)?;
             }
             rtn = 0;
             //stopped at the next loop start ...but first update the cursor
@@ -241,10 +279,15 @@ fn retain_block_format(
     attr: &Attributes,
     cursor: &Cursor,
     registry: &RwLockReadGuard<'static, Registry>,
+    deferred: bool,
 ) -> Result<Arc<DocumentNode>> {
     let new_block = doc_node
         .get_formatter()
-        .un_block_transform(&cursor, &doc_node)?;
+        .un_block_transform(&cursor, &doc_node)
+        .with_context(|| BlockTransformFailed {
+            format_name: doc_node.get_formatter().format_name().to_string(),
+            reason: "un_block_transform() of the retained block".to_string(),
+        })?;
     let operation = doc_node.get_operation(); // old block format removed from operation ...
 
     let val = operation.insert_value();
@@ -253,9 +296,14 @@ fn retain_block_format(
     let operation = DeltaOperation::insert_attr(val.clone(), attr);
 
     let format = registry.block_format(&operation)?.clone();
-    let doc_node = format.block_transform(cursor, &new_block, operation, format.clone())?;
+    let doc_node = format
+        .block_transform(cursor, &new_block, operation, format.clone())
+        .with_context(|| BlockTransformFailed {
+            format_name: format.format_name().to_string(),
+            reason: "block_transform() of the retained block".to_string(),
+        })?;
     if doc_node.child_count() == 0 {
-        AutomaticSoftBreak::insert(&doc_node)?;
+        AutomaticSoftBreak::insert(&doc_node, deferred)?;
     }
     Ok(doc_node)
 }
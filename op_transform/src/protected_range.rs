@@ -0,0 +1,91 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::error::Error::RangeProtected;
+use anyhow::Result;
+use delta::operations::DeltaOperation;
+use delta::types::ops_kind::OpKind;
+
+/// # ProtectedRangeId
+///
+/// Handle of a protected range, see `DocumentRoot::protect_range()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ProtectedRangeId(u64);
+
+impl DocumentRoot {
+    /// # protect_range()
+    ///
+    /// Protects the retain range `[start, start + length)` against changes. An insert
+    /// inside the range, or a delete which overlaps the range, fails with
+    /// `Error::RangeProtected`. Retains, changing the attributes, are allowed.
+    ///
+    /// Inserting at the start, or at the end, of the range is not inside the range.
+    /// The protection ends when the document is closed, see `DocumentRoot::close()`.
+    pub fn protect_range(&mut self, start: usize, length: usize) -> ProtectedRangeId {
+        self.next_protected_id += 1;
+        let id = ProtectedRangeId(self.next_protected_id);
+        self.protected_ranges.push((id, start, length));
+        id
+    }
+
+    /// # unprotect()
+    ///
+    /// Removes the protection of the range. An unknown id is ignored.
+    pub fn unprotect(&mut self, id: ProtectedRangeId) {
+        self.protected_ranges
+            .retain(|(p, _start, _length)| *p != id);
+    }
+
+    /// # get_protected_ranges()
+    ///
+    /// Returns `(start, length)` of all protected ranges.
+    pub fn get_protected_ranges(&self) -> Vec<(usize, usize)> {
+        self.protected_ranges
+            .iter()
+            .map(|(_id, start, length)| (*start, *length))
+            .collect()
+    }
+
+    /// # check_protected_ranges()
+    ///
+    /// Returns an error when the operation, applied at retain index `start`, changes
+    /// a protected range.
+    pub(crate) fn check_protected_ranges(&self, start: usize, op: &DeltaOperation) -> Result<()> {
+        for (_id, from, length) in self.protected_ranges.iter() {
+            let to = from + length;
+            let protected = match op.get_op_kind() {
+                OpKind::Insert(_) => *from < start && start < to,
+                OpKind::Delete(len) => start < to && *from < start + len,
+                OpKind::Retain(_) => false,
+            };
+            if protected {
+                return Err(RangeProtected {
+                    start: *from,
+                    end: to,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// # shift_protected_ranges()
+    ///
+    /// Moves the protected ranges after the operation is applied at retain index `start`.
+    /// An insert before, or at the start of, the range shifts it by the insert length,
+    /// a delete before the range shifts it back by the delete length.
+    pub(crate) fn shift_protected_ranges(&mut self, start: usize, op: &DeltaOperation) {
+        for (_id, from, _length) in self.protected_ranges.iter_mut() {
+            match op.get_op_kind() {
+                OpKind::Insert(_) if *from >= start => *from += op.op_len(),
+                OpKind::Delete(len) if *from >= start + len => *from -= len,
+                _ => {}
+            }
+        }
+    }
+}
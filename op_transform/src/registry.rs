@@ -7,12 +7,16 @@
 
 use crate::error::Error::{RegistryNoFormatForOp, RegistryNoSuchFormat, RegistryNotInitialised};
 use anyhow::Result;
+use core_formats::format_const::NAME_P_BLOCK;
 #[cfg(any(test, feature = "test_export"))]
-use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::format_const::NAME_TEXT;
+use core_formats::util::block_format::BlockAttributeRegistry;
 #[cfg(any(test, feature = "test_export"))]
 use core_formats::{P_FORMAT, TEXT_FORMAT};
 use delta::attributes::Attributes;
 use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use node_tree::doc_node::DocumentNode;
 use node_tree::format_trait::FormatTait;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
@@ -134,6 +138,82 @@ impl Registry {
         .into());
     }
 
+    /// # format_for_name()
+    ///
+    /// Returns the registered format, block or line format, with the given name.
+    pub fn format_for_name(&self, name: &str) -> Option<Arc<dyn FormatTait + Send + Sync>> {
+        self.block_formats
+            .get(name)
+            .or_else(|| self.text_formats.get(name))
+            .cloned()
+    }
+
+    /// # block_format_for_node()
+    ///
+    /// Returns the registered block format of the document node, using the name of the
+    /// format of the node. Returns `None` for a text node.
+    pub fn block_format_for_node(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+    ) -> Option<Arc<dyn FormatTait + Send + Sync>> {
+        let name = doc_node.get_formatter().format_name();
+        self.block_formats.get(name).cloned()
+    }
+
+    /// # line_format_for_node()
+    ///
+    /// Returns the registered line format of the document node, using the name of the
+    /// format of the node. Returns `None` for a block node.
+    pub fn line_format_for_node(
+        &self,
+        doc_node: &Arc<DocumentNode>,
+    ) -> Option<Arc<dyn FormatTait + Send + Sync>> {
+        let name = doc_node.get_formatter().format_name();
+        self.text_formats.get(name).cloned()
+    }
+
+    /// # all_block_format_names()
+    ///
+    /// Returns the names of the registered block formats, in the order in which they are
+    /// checked.
+    pub fn all_block_format_names(&self) -> Vec<&'static str> {
+        unique_names(&self.block_order)
+    }
+
+    /// # all_line_format_names()
+    ///
+    /// Returns the names of the registered line formats, in the order in which they are
+    /// checked.
+    pub fn all_line_format_names(&self) -> Vec<&'static str> {
+        unique_names(&self.text_order)
+    }
+
+    /// # list_formats()
+    ///
+    /// Returns the names of all registered formats: first the block formats, then the line
+    /// formats, each in the order in which they are checked.
+    pub fn list_formats(&self) -> Vec<&'static str> {
+        let mut names = self.all_block_format_names();
+        names.extend(self.all_line_format_names());
+        names
+    }
+
+    /// # has_format()
+    ///
+    /// Returns true when a block or line format with the given name is registered, see
+    /// `list_formats()`.
+    pub fn has_format(&self, name: &str) -> bool {
+        self.list_formats().contains(&name)
+    }
+
+    /// # has_anchor_ids()
+    ///
+    /// Returns true when a registered block format has anchor IDs, see
+    /// `FormatTait::has_anchor_id()`.
+    pub fn has_anchor_ids(&self) -> bool {
+        self.block_formats.values().any(|f| f.has_anchor_id())
+    }
+
     /// returns true if we detect this delta operation is a registered block format
     /// Note that this only works for formats that are "\n" for block formats.
     /// So a string operation with value = "hello\nworld" is not recognized as "block format"
@@ -171,6 +251,23 @@ impl Registry {
         .into());
     }
 
+    /// # is_block_attribute()
+    ///
+    /// Returns true if the attribute belongs to a block operation `insert("\n")`. That is a
+    /// registered block attribute like `align`, or an attribute for which a block format
+    /// other than the paragraph applies, like `heading`.
+    pub fn is_block_attribute(&self, key: &str, val: &AttrVal) -> bool {
+        if BlockAttributeRegistry::is_registered(key) {
+            return true;
+        }
+        let mut attr = Attributes::default();
+        attr.insert(key, val.clone());
+        match self.block_format(&DeltaOperation::insert_attr("\n", attr)) {
+            Ok(format) => format.format_name() != NAME_P_BLOCK,
+            Err(_) => false,
+        }
+    }
+
     /// returns true if we detect this delta operation is a registered text format
     pub fn line_format(&self, op: &DeltaOperation) -> Result<Arc<dyn FormatTait + Send + Sync>> {
         for t in self.text_order.iter() {
@@ -207,6 +304,17 @@ impl Registry {
     }
 }
 
+/// A format registered twice is checked at its first position, so we list it once
+fn unique_names(order: &[&'static str]) -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = Vec::new();
+    for name in order.iter() {
+        if !names.contains(name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
 /// The test registry registers only the BASIC formats: text & paragraph.
 /// Use by adding this crate as a dev-dependency with feature = test_export enabled.
 /// The modules which use this feature, should guard the use xxx with the same
@@ -0,0 +1,43 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::op_retain::set_cursor_selection;
+use anyhow::Result;
+use delta::delta::Delta;
+
+/// # DocumentSnapshot
+///
+/// The content, and cursor position, of a document at some point in time.
+/// See `DocumentRoot::snapshot()`.
+#[derive(Clone, Debug)]
+pub struct DocumentSnapshot {
+    pub delta: Delta,
+    pub cursor_retain: usize,
+}
+
+impl DocumentRoot {
+    /// # snapshot()
+    ///
+    /// Returns the document, see `to_delta()`, and the retain index of the cursor.
+    pub fn snapshot(&self) -> DocumentSnapshot {
+        DocumentSnapshot {
+            delta: self.to_delta(),
+            cursor_retain: self.get_cursor().get_retain_index(),
+        }
+    }
+
+    /// # restore()
+    ///
+    /// Replaces the document content by the snapshot, and sets the cursor where it was.
+    /// The document is opened for editing, and the undo history is cleared.
+    pub fn restore(&mut self, snapshot: DocumentSnapshot) -> Result<()> {
+        self.replace_content(snapshot.delta)?;
+        set_cursor_selection(self.get_cursor(), snapshot.cursor_retain, 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::error::Error::AnchorNotFound;
+use crate::op_retain::set_cursor_selection;
+use crate::registry::Registry;
+use anyhow::Result;
+use core_formats::util::anchor::anchor_ids;
+use delta::operations::DeltaOperation;
+use delta::types::ops_kind::OpKind;
+use node_tree::cursor::CursorLocation;
+use node_tree::tree_traverse::{collect_blocks, first_node, next_node};
+
+/// Attribute key of the header block format
+static HEADER_ATTR_KEY: &str = "heading";
+/// HTML attribute of the anchor ID, see `HeaderBlock::new_with_anchors()` in the header crate
+static ID_ATTR: &str = "id";
+
+impl DocumentRoot {
+    /// # table_of_contents()
+    ///
+    /// Returns `(retain_index, header_text, level)` for each header in the document.
+    /// The retain index points to the first character of the header block.
+    ///
+    /// Header blocks are recognised by their `heading` attribute, so the `op_transform` crate
+    /// does not need to know the header format.
+    pub fn table_of_contents(&self) -> Vec<(usize, String, usize)> {
+        let mut toc: Vec<(usize, String, usize)> = Vec::new();
+        if self.get_root().child_count() == 0 {
+            return toc;
+        }
+
+        let mut retain: usize = 0;
+        let mut block_start: usize = 0;
+        let mut dn_o = Some(first_node(self.get_root()));
+        while let Some(doc_node) = dn_o {
+            if !doc_node.get_formatter().is_text_format() && doc_node.op_len() > 0 {
+                let op = doc_node.get_operation();
+                if let Some(level) = op.get_attributes().get(HEADER_ATTR_KEY) {
+                    let level = level.number_val().map(|l| l.to_string().parse::<usize>());
+                    if let Ok(Ok(level)) = level {
+                        toc.push((block_start, doc_node.get_text_content(), level));
+                    }
+                }
+                block_start = retain + doc_node.op_len();
+            }
+            retain += doc_node.op_len();
+            dn_o = next_node(&doc_node);
+        }
+        toc
+    }
+
+    /// # table_of_contents_anchors()
+    ///
+    /// Returns the anchor ID for each entry of `table_of_contents()`, in the same order.
+    ///
+    /// These are the same IDs as the `id` of the header elements, see `anchor_ids()` in
+    /// `core_formats`. Duplicate anchors get a numeric suffix: `intro`, `intro-2`, ...
+    pub fn table_of_contents_anchors(&self) -> Vec<String> {
+        let toc = self.table_of_contents();
+        anchor_ids(toc.iter().map(|(_retain, text, _level)| text.as_str()))
+    }
+
+    /// # extract_toc()
+    ///
+    /// Returns `(level, header_text, id)` for each header in the document. The IDs are
+    /// those of `table_of_contents_anchors()`, and of the header elements when the header
+    /// format has anchors, see `HeaderBlock::new_with_anchors()` in the header crate.
+    /// The ID is empty when the header has no text.
+    pub fn extract_toc(&self) -> Vec<(u8, String, String)> {
+        let mut toc: Vec<(u8, String, String)> = Vec::new();
+        for block in collect_blocks(self.get_root()) {
+            let op = block.get_operation();
+            let Some(level) = op.get_attributes().get(HEADER_ATTR_KEY) else {
+                continue;
+            };
+            let Ok(Ok(level)) = level.number_val().map(|l| l.to_string().parse::<u8>()) else {
+                continue;
+            };
+            toc.push((level, block.get_text_content(), String::new()));
+        }
+        let ids = anchor_ids(toc.iter().map(|(_level, text, _id)| text.as_str()));
+        for (entry, id) in toc.iter_mut().zip(ids) {
+            entry.2 = id;
+        }
+        toc
+    }
+
+    /// # update_anchor_ids()
+    ///
+    /// Sets the `id` of the blocks with an anchor ID, see `FormatTait::has_anchor_id()`,
+    /// from their text. The IDs are deduplicated within the document. A block without text
+    /// has no ID.
+    pub(crate) fn update_anchor_ids(&self) {
+        let blocks: Vec<_> = collect_blocks(self.get_root())
+            .into_iter()
+            .filter(|b| b.get_formatter().has_anchor_id())
+            .collect();
+        let texts: Vec<String> = blocks.iter().map(|b| b.get_text_content()).collect();
+        let ids = anchor_ids(texts.iter().map(|t| t.as_str()));
+        for (block, id) in blocks.iter().zip(ids) {
+            let Some(element) = block.get_dom_element() else {
+                continue;
+            };
+            if id.is_empty() {
+                element.remove_attribute(ID_ATTR);
+            } else if element.get_attribute(ID_ATTR).as_deref() != Some(id.as_str()) {
+                element.set_attribute(ID_ATTR, &id);
+            }
+        }
+    }
+
+    /// # touches_anchor_block()
+    ///
+    /// Returns true when the operation, applied at the cursor, may change the anchor IDs:
+    /// a block with an anchor ID is within the range of the operation, or the attributes
+    /// of the operation make one. Typing in a paragraph does not, so `update_anchor_ids()`
+    /// does not need to walk the document for each key stroke.
+    ///
+    /// Call it before the operation is applied, and check the block at the cursor again
+    /// afterwards, for an inserted block with an anchor ID.
+    pub(crate) fn touches_anchor_block(
+        &self,
+        operation: &DeltaOperation,
+        registry: &Registry,
+    ) -> bool {
+        if !operation.get_attributes().is_empty() {
+            let block_op = DeltaOperation::insert_attr("\n", operation.get_attributes().clone());
+            if let Ok(format) = registry.block_format(&block_op) {
+                if format.has_anchor_id() {
+                    return true;
+                }
+            }
+        }
+        if self.anchor_block_at_cursor() {
+            return true;
+        }
+        let len = match operation.get_op_kind() {
+            OpKind::Insert(_) => return false,
+            _ => operation.op_len(),
+        };
+        let (mut dn_o, mut remaining) = match self.get_cursor().get_location() {
+            CursorLocation::None => return false,
+            CursorLocation::At(doc_node, index) => (Some(doc_node), len + index),
+            CursorLocation::Before(doc_node) => (Some(doc_node), len),
+            CursorLocation::After(doc_node) => {
+                let remaining = len + doc_node.op_len();
+                (Some(doc_node), remaining)
+            }
+        };
+        while let Some(doc_node) = dn_o {
+            if !doc_node.get_formatter().is_text_format()
+                && doc_node.get_formatter().has_anchor_id()
+            {
+                return true;
+            }
+            if doc_node.op_len() > remaining {
+                break;
+            }
+            remaining -= doc_node.op_len();
+            dn_o = next_node(&doc_node);
+        }
+        false
+    }
+
+    /// # anchor_block_at_cursor()
+    ///
+    /// Returns true when the cursor is in a block with an anchor ID.
+    pub(crate) fn anchor_block_at_cursor(&self) -> bool {
+        self.block_at_cursor()
+            .is_some_and(|block| block.get_formatter().has_anchor_id())
+    }
+
+    /// # goto_anchor()
+    ///
+    /// Moves the cursor to the start of the header with the given anchor ID.
+    /// Any selection is collapsed.
+    pub fn goto_anchor(&self, anchor_id: &str) -> Result<()> {
+        let toc = self.table_of_contents();
+        let idx = self
+            .table_of_contents_anchors()
+            .iter()
+            .position(|a| a == anchor_id);
+        match idx {
+            Some(idx) => {
+                set_cursor_selection(self.get_cursor(), toc[idx].0, 0);
+                Ok(())
+            }
+            None => Err(AnchorNotFound {
+                anchor: anchor_id.to_string(),
+            }
+            .into()),
+        }
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright 2024 quill-core-rs Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::doc_root::DocumentRoot;
+use crate::error::Error::CompositionActive;
+use anyhow::Result;
+use delta::delta::Delta;
+use delta::operations::{DeltaOperation, OpKind};
+
+/// Number of changes kept, see `DocumentRoot::set_undo_limit()`
+const DEFAULT_UNDO_LIMIT: usize = 100;
+
+/// # UndoHistory
+///
+/// Keeps the `(inverse_delta, forward_delta)` pair of every change applied to the document.
+/// Both deltas are relative to the start of the document:
+///  - the forward delta is the change itself,
+///  - the inverse delta undoes the change, see `Delta::invert()`.
+///
+/// The oldest change is dropped when there are more than `limit` changes.
+#[derive(Clone)]
+pub struct UndoHistory {
+    undo: Vec<(Delta, Delta)>,
+    redo: Vec<(Delta, Delta)>,
+    limit: usize,
+    suspended: bool, //true while applying an undo or redo
+}
+
+impl Default for UndoHistory {
+    fn default() -> Self {
+        UndoHistory {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit: DEFAULT_UNDO_LIMIT,
+            suspended: false,
+        }
+    }
+}
+
+impl UndoHistory {
+    /// Adds a new change. A new change makes the redo stack invalid, so it is cleared.
+    pub fn push(&mut self, inverse: Delta, forward: Delta) {
+        if !self.is_recording() {
+            return;
+        }
+        self.redo.clear();
+        self.undo.push((inverse, forward));
+        self.truncate();
+    }
+
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.truncate();
+    }
+
+    pub fn get_limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// True while applying an undo or redo
+    pub(crate) fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// False while applying an undo or redo, or when the history is switched off
+    pub fn is_recording(&self) -> bool {
+        !self.suspended && self.limit > 0
+    }
+
+    fn truncate(&mut self) {
+        if self.undo.len() > self.limit {
+            let excess = self.undo.len() - self.limit;
+            self.undo.drain(..excess);
+        }
+    }
+}
+
+impl DocumentRoot {
+    /// # record_undo()
+    ///
+    /// Records the operation, applied at retain index `start`, in the undo history.
+    /// The `base` is the document before the change; it is only used for a delete, or a
+    /// retain with attributes, see `undo_base()`.
+    ///
+    /// A retain without attributes only moves the cursor, and is not recorded.
+    pub(crate) fn record_undo(&mut self, start: usize, operation: &DeltaOperation, base: &Delta) {
+        if !self.history.is_recording() {
+            return;
+        }
+        if let OpKind::Retain(_len) = operation.get_op_kind() {
+            if operation.get_attributes().is_empty() {
+                return;
+            }
+        }
+        let mut changes: Vec<DeltaOperation> = Vec::new();
+        if start > 0 {
+            changes.push(DeltaOperation::retain(start));
+        }
+        changes.push(operation.clone());
+        self.record_undo_changes(&changes, base);
+    }
+
+    /// # record_undo_changes()
+    ///
+    /// Records the delta operations, relative to the start of the document, as a single
+    /// change. The `base` is the changed part of the document before the change, see
+    /// `undo_base_range()`.
+    pub(crate) fn record_undo_changes(&mut self, changes: &[DeltaOperation], base: &Delta) {
+        if !self.history.is_recording() {
+            return;
+        }
+        let mut forward = Delta::default();
+        for op in changes.iter() {
+            forward.push(op.clone());
+        }
+        let inverse = forward.invert(base);
+        self.history.push(inverse, forward);
+    }
+
+    /// # undo_base()
+    ///
+    /// Returns the part of the document which the inverse of the operation at `start`
+    /// depends on. The inverse of an insert is a delete, which does not need the document.
+    pub(crate) fn undo_base(&self, start: usize, operation: &DeltaOperation) -> Delta {
+        match operation.get_op_kind() {
+            OpKind::Insert(_) => Delta::default(),
+            _ => self.undo_base_range(start, operation.op_len()),
+        }
+    }
+
+    /// # undo_base_range()
+    ///
+    /// Returns `retain(start)` followed by the content of the range. The inverse of a
+    /// change inside the range does not look at the retained part, so we only slice the
+    /// range itself.
+    ///
+    /// Returns an empty delta when the history does not record changes.
+    pub(crate) fn undo_base_range(&self, start: usize, length: usize) -> Delta {
+        let mut base = Delta::default();
+        if !self.history.is_recording() {
+            return base;
+        }
+        if start > 0 {
+            base.retain(start);
+        }
+        for op in self.get_delta_slice(start, length).get_ops() {
+            base.push(op);
+        }
+        base
+    }
+
+    /// # undo()
+    ///
+    /// Reverts the last change, and moves it to the redo stack.
+    /// Returns false when there is nothing to undo.
+    ///
+    /// The auto format, see `set_auto_format()`, is a change of its own. So after typing
+    /// `# ` the first undo gives back the typed `# ` in a paragraph.
+    ///
+    /// Fails with `Error::CompositionActive` during a composition, see `begin_composition()`:
+    /// the history does not know the preview text.
+    pub fn undo(&mut self) -> Result<bool> {
+        if self.is_composing() {
+            return Err(CompositionActive.into());
+        }
+        let (inverse, forward) = match self.history.undo.pop() {
+            Some(change) => change,
+            None => return Ok(false),
+        };
+        if let Err(e) = self.apply_history_delta(inverse.clone()) {
+            self.history.undo.push((inverse, forward));
+            return Err(e);
+        }
+        self.history.redo.push((inverse, forward));
+        Ok(true)
+    }
+
+    /// # redo()
+    ///
+    /// Re-applies the last change reverted by `undo()`.
+    /// Returns false when there is nothing to redo.
+    ///
+    /// Fails with `Error::CompositionActive` during a composition, like `undo()`.
+    pub fn redo(&mut self) -> Result<bool> {
+        if self.is_composing() {
+            return Err(CompositionActive.into());
+        }
+        let (inverse, forward) = match self.history.redo.pop() {
+            Some(change) => change,
+            None => return Ok(false),
+        };
+        if let Err(e) = self.apply_history_delta(forward.clone()) {
+            self.history.redo.push((inverse, forward));
+            return Err(e);
+        }
+        self.history.undo.push((inverse, forward));
+        self.history.truncate();
+        Ok(true)
+    }
+
+    /// # set_undo_limit()
+    ///
+    /// Sets the number of changes which can be undone. The oldest changes are dropped
+    /// when the history is longer. A limit of 0 switches the undo history off.
+    pub fn set_undo_limit(&mut self, limit: usize) {
+        self.history.set_limit(limit);
+    }
+
+    pub fn get_undo_history(&self) -> &UndoHistory {
+        &self.history
+    }
+
+    /// # clear_undo_history()
+    ///
+    /// Forgets all changes, for example after saving the document.
+    pub fn clear_undo_history(&mut self) {
+        self.history.clear();
+    }
+
+    fn apply_history_delta(&mut self, delta: Delta) -> Result<()> {
+        self.history.suspended = true;
+        let result = self.apply_delta(delta);
+        self.history.suspended = false;
+        result
+    }
+}
@@ -0,0 +1,86 @@
+use delta::attributes::Attributes;
+use delta::types::attr_val::AttrVal;
+use op_transform::attributes_diff::AttributesDiff;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn attributes(values: &[(&str, AttrVal)]) -> Attributes {
+    let mut attr = Attributes::default();
+    for (key, val) in values {
+        attr.insert(*key, val.clone());
+    }
+    attr
+}
+
+#[wasm_bindgen_test]
+fn diff_test() {
+    let base = attributes(&[
+        ("bold", AttrVal::from(true)),
+        ("italic", AttrVal::from(true)),
+        ("color", AttrVal::from("red")),
+    ]);
+    let head = attributes(&[
+        ("bold", AttrVal::from(true)),
+        ("color", AttrVal::from("blue")),
+        ("underline", AttrVal::from(true)),
+    ]);
+
+    let diff = Attributes::diff(&base, &head);
+    let expected = attributes(&[
+        ("italic", AttrVal::Null),
+        ("color", AttrVal::from("blue")),
+        ("underline", AttrVal::from(true)),
+    ]);
+    assert!(diff == expected);
+}
+
+#[wasm_bindgen_test]
+fn diff_empty_test() {
+    let empty = Attributes::default();
+    let bold = attributes(&[("bold", AttrVal::from(true))]);
+
+    assert!(Attributes::diff(&bold, &bold).is_empty());
+    assert!(Attributes::diff(&empty, &empty).is_empty());
+    assert!(Attributes::diff(&empty, &bold) == bold);
+    assert!(Attributes::diff(&bold, &empty) == attributes(&[("bold", AttrVal::Null)]));
+}
+
+#[wasm_bindgen_test]
+fn compose_test() {
+    let base = attributes(&[
+        ("bold", AttrVal::from(true)),
+        ("italic", AttrVal::from(true)),
+    ]);
+    let diff = attributes(&[
+        ("italic", AttrVal::Null),
+        ("color", AttrVal::from("red")),
+        ("strike", AttrVal::Null),
+    ]);
+
+    let expected = attributes(&[
+        ("bold", AttrVal::from(true)),
+        ("color", AttrVal::from("red")),
+    ]);
+    assert!(Attributes::compose(&base, &diff) == expected);
+    assert!(Attributes::compose(&base, &Attributes::default()) == base);
+}
+
+#[wasm_bindgen_test]
+fn diff_compose_round_trip_test() {
+    let cases = [
+        Attributes::default(),
+        attributes(&[("bold", AttrVal::from(true))]),
+        attributes(&[("bold", AttrVal::from(false)), ("link", AttrVal::from("a"))]),
+        attributes(&[
+            ("link", AttrVal::from("b")),
+            ("italic", AttrVal::from(true)),
+        ]),
+    ];
+    for base in cases.iter() {
+        for head in cases.iter() {
+            let diff = Attributes::diff(base, head);
+            assert!(Attributes::compose(base, &diff) == *head);
+        }
+    }
+}
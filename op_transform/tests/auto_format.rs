@@ -0,0 +1,106 @@
+use anyhow::Result;
+use code::{CodeBlock, NAME_CODE};
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry has the code block, so there is one pattern with a registered format.
+/// There is no header format.
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_CODE, Arc::new(CodeBlock::new()))
+            .unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+fn type_text(doc: &mut DocumentRoot, text: &str) -> Result<()> {
+    for c in text.chars() {
+        doc.apply_operation(DeltaOperation::insert(c.to_string()))?;
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn auto_format_disabled_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("auto_format_disabled_test");
+    doc.open()?;
+    assert!(!doc.is_auto_format());
+
+    type_text(&mut doc, "- item")?;
+    assert_eq!(doc.as_html_string(), "<p>- item</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn auto_format_mid_line_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("auto_format_mid_line_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+
+    let mut delta = Delta::default();
+    delta.insert("Hello ");
+    doc.apply_delta(delta)?;
+    doc.cursor_to_end();
+
+    type_text(&mut doc, "# not a header")?;
+    assert_eq!(doc.as_html_string(), "<p>Hello # not a header</p>");
+    Ok(())
+}
+
+/// The test registry has no header, so there is no format to apply
+#[wasm_bindgen_test]
+fn auto_format_unregistered_format_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("auto_format_unregistered_format_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+
+    type_text(&mut doc, "# title")?;
+    assert_eq!(doc.as_html_string(), "<p># title</p>");
+    Ok(())
+}
+
+/// The pattern delete and the block format are a single undo step
+#[wasm_bindgen_test]
+fn auto_format_undo_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("auto_format_undo_test");
+    doc.open()?;
+    doc.set_auto_format(true);
+
+    type_text(&mut doc, "```")?;
+    let ops = doc.to_delta().get_ops();
+    assert_eq!(ops.len(), 1);
+    assert_eq!(
+        ops[0].get_attributes().get("code-block"),
+        Some(&AttrVal::Bool(true))
+    );
+    let undo_len = doc.get_undo_history().undo_len();
+
+    assert!(doc.undo()?);
+    assert_eq!(doc.as_html_string(), "<p>```</p>");
+    assert_eq!(doc.get_undo_history().undo_len(), undo_len - 1);
+
+    assert!(doc.redo()?);
+    assert_eq!(doc.to_delta().get_ops(), ops);
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn now() -> f64 {
+    web_sys::window().unwrap().performance().unwrap().now()
+}
+
+fn insert_paragraphs(doc: &mut DocumentRoot, count: usize) -> Result<()> {
+    for i in 0..count {
+        doc.apply_operation(DeltaOperation::insert(format!("line {}", i).as_str()))?;
+        doc.apply_operation(DeltaOperation::insert("\n"))?;
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn batch_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("batch_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("abc\n");
+    doc.apply_delta(delta)?;
+
+    doc.begin_batch();
+    assert!(doc.is_batch());
+    doc.apply_operation(DeltaOperation::retain(1))?;
+    doc.apply_operation(DeltaOperation::insert("\n\n"))?;
+    doc.apply_operation(DeltaOperation::delete(1))?;
+    assert_eq!(doc.get_cursor().get_retain_index(), 3);
+    doc.end_batch()?;
+    assert!(!doc.is_batch());
+
+    assert_eq!(doc.get_cursor().get_retain_index(), 3);
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>a</p><p><br></p><p>c</p><p><br></p>"
+    );
+    assert!(doc.validate_tree_integrity().is_empty());
+
+    //ending without a batch does nothing
+    doc.end_batch()?;
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn batch_equals_single_operations_test() -> Result<()> {
+    init_test_registry();
+    const COUNT: usize = 1000;
+
+    let mut single = DocumentRoot::new("batch_single_test");
+    single.open()?;
+    let start = now();
+    insert_paragraphs(&mut single, COUNT)?;
+    let single_time = now() - start;
+
+    let mut batch = DocumentRoot::new("batch_batch_test");
+    batch.open()?;
+    let start = now();
+    batch.begin_batch();
+    insert_paragraphs(&mut batch, COUNT)?;
+    batch.end_batch()?;
+    let batch_time = now() - start;
+
+    assert!(batch_time < single_time);
+    assert_eq!(batch.as_html_string(), single.as_html_string());
+    assert_eq!(batch.to_delta(), single.to_delta());
+    assert_eq!(
+        batch.get_cursor().get_retain_index(),
+        single.get_cursor().get_retain_index()
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn batch_other_document_test() -> Result<()> {
+    init_test_registry();
+    let mut batch = DocumentRoot::new("batch_other_batch_test");
+    batch.open()?;
+    let mut other = DocumentRoot::new("batch_other_document_test");
+    other.open()?;
+
+    //the batch of one document does not defer the soft breaks of another document
+    batch.begin_batch();
+    batch.apply_operation(DeltaOperation::insert("\n"))?;
+    other.apply_operation(DeltaOperation::insert("\n"))?;
+    assert_eq!(other.as_html_string(), "<p><br></p><p><br></p>");
+    batch.end_batch()?;
+    assert_eq!(batch.as_html_string(), "<p><br></p><p><br></p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn batch_close_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("batch_close_test");
+    doc.open()?;
+    doc.begin_batch();
+    doc.apply_operation(DeltaOperation::insert("a\n"))?;
+
+    //closing ends the batch, so the next document is not deferred
+    doc.close();
+    assert!(!doc.is_batch());
+    doc.open()?;
+    doc.apply_operation(DeltaOperation::insert("\n"))?;
+    assert_eq!(doc.as_html_string(), "<p><br></p><p><br></p>");
+    assert_eq!(doc.get_cursor().get_retain_index(), 1);
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use anyhow::Result;
+use delta::operations::DeltaOperation;
+use delta::types::attr_val::AttrVal;
+use op_transform::composition::COMPOSING_ATTR_KEY;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn composition_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("composition_test");
+    doc.open()?;
+    doc.apply_operation(DeltaOperation::insert("ab"))?;
+    let undo_len = doc.get_undo_history().undo_len();
+
+    set_cursor_selection(doc.get_cursor(), 1, 0);
+    let cursor = doc.get_cursor().clone();
+    doc.begin_composition(&cursor)?;
+    assert!(doc.is_composing());
+
+    //each update replaces the preview
+    for preview in ["日", "日本", "日本語"] {
+        doc.update_composition(preview)?;
+        assert_eq!(doc.get_plain_text(), format!("a{}b\n", preview));
+    }
+    let composing = doc
+        .to_delta()
+        .get_ops()
+        .into_iter()
+        .filter(|op| op.get_attributes().get(COMPOSING_ATTR_KEY) == Some(&AttrVal::Bool(true)))
+        .count();
+    assert_eq!(composing, 1);
+
+    doc.commit_composition("日本語")?;
+    assert!(!doc.is_composing());
+    assert_eq!(doc.get_plain_text(), "a日本語b\n");
+    assert_eq!(doc.as_html_string(), "<p>a日本語b</p>");
+    assert_eq!(doc.get_cursor().get_retain_index(), 4);
+    assert!(doc
+        .to_delta()
+        .get_ops()
+        .iter()
+        .all(|op| op.get_attributes().get(COMPOSING_ATTR_KEY).is_none()));
+
+    //only the committed text is an undo step
+    assert_eq!(doc.get_undo_history().undo_len(), undo_len + 1);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn composition_error_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("composition_error_test");
+    doc.open()?;
+    assert!(doc.update_composition("日").is_err()); //Error::NoComposition
+    assert!(doc.commit_composition("日").is_err()); //Error::NoComposition
+
+    //an empty commit removes the preview
+    let cursor = doc.get_cursor().clone();
+    doc.begin_composition(&cursor)?;
+    doc.update_composition("日本")?;
+    doc.commit_composition("")?;
+    assert_eq!(doc.get_plain_text(), "\n");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn composition_restart_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("composition_restart_test");
+    doc.open()?;
+    doc.apply_operation(DeltaOperation::insert("ab"))?;
+
+    set_cursor_selection(doc.get_cursor(), 1, 0);
+    let cursor = doc.get_cursor().clone();
+    doc.begin_composition(&cursor)?;
+    doc.update_composition("日本")?;
+
+    //a new composition removes the preview of the old one
+    set_cursor_selection(doc.get_cursor(), 4, 0);
+    let cursor = doc.get_cursor().clone();
+    doc.begin_composition(&cursor)?;
+    assert_eq!(doc.get_plain_text(), "ab\n");
+    doc.commit_composition("x")?;
+    assert_eq!(doc.get_plain_text(), "abx\n");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn composition_moves_cursors_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("composition_moves_cursors_test");
+    doc.open()?;
+    doc.apply_operation(DeltaOperation::insert("ab"))?;
+    let id = doc.add_cursor(2);
+    doc.protect_range(1, 1);
+
+    set_cursor_selection(doc.get_cursor(), 1, 0);
+    let cursor = doc.get_cursor().clone();
+    doc.begin_composition(&cursor)?;
+    doc.update_composition("日本")?;
+    assert_eq!(doc.get_all_cursors()[1], (id, 4));
+    assert_eq!(doc.get_protected_ranges(), vec![(3, 1)]);
+
+    //the history does not know the preview
+    assert!(doc.undo().is_err()); //Error::CompositionActive
+    assert!(doc.redo().is_err()); //Error::CompositionActive
+
+    doc.commit_composition("日本")?;
+    assert_eq!(doc.get_all_cursors()[1], (id, 4));
+    assert_eq!(doc.get_protected_ranges(), vec![(3, 1)]);
+    assert!(doc.undo()?);
+    assert_eq!(doc.get_plain_text(), "ab\n");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn composition_restore_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("composition_restore_test");
+    doc.open()?;
+    doc.apply_operation(DeltaOperation::insert("abc"))?;
+    let snapshot = doc.snapshot();
+
+    set_cursor_selection(doc.get_cursor(), 1, 0);
+    let cursor = doc.get_cursor().clone();
+    doc.begin_composition(&cursor)?;
+    doc.update_composition("日本")?;
+
+    //the composition ends with the old content
+    doc.restore(snapshot)?;
+    assert!(!doc.is_composing());
+    assert!(doc.update_composition("日").is_err()); //Error::NoComposition
+    assert!(doc.commit_composition("日").is_err()); //Error::NoComposition
+    assert_eq!(doc.get_plain_text(), "abc\n");
+    Ok(())
+}
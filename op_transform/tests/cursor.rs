@@ -10,6 +10,7 @@ use node_tree::cursor::{Cursor, CursorLocation};
 use node_tree::doc_node::DocumentNode;
 use node_tree::tree_traverse::{first_node, last_block_node, last_leaf_node, next_node, prev_node};
 use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
 use op_transform::registry::init_test_registry;
 use std::sync::Arc;
 use wasm_bindgen_test::*;
@@ -230,3 +231,162 @@ fn doc_node_to_str(doc_node: &Arc<DocumentNode>, index: usize) -> String {
         format!("<{}>", txt)
     }
 }
+
+/// The cursor calculates the retain index only when it is asked for. Moving the cursor
+/// without asking for the retain index does not walk the document.
+#[wasm_bindgen_test]
+fn lazy_retain_index_test() -> Result<()> {
+    init_test_registry();
+    const COUNT: usize = 1000;
+    let performance = web_sys::window().unwrap().performance().unwrap();
+    let mut doc = DocumentRoot::new("lazy_retain_index_test");
+
+    //1000 paragraphs, so the retain index of the last block walks 1000 blocks
+    let mut delta = Delta::default();
+    for _ in 0..COUNT {
+        delta.insert("abc\n");
+    }
+    doc.load(delta)?;
+    let end = COUNT * 4 - 1;
+    assert_eq!(doc.to_delta().document_length(), end + 1);
+
+    let cursor = doc.get_cursor();
+    let first = first_node(doc.get_root());
+    let leaf = last_leaf_node(doc.get_root()).unwrap();
+
+    //eager: the retain index is calculated after every move
+    let start = performance.now();
+    for _ in 0..COUNT {
+        cursor.set_after(&leaf);
+        assert_eq!(cursor.calculate_retain_index(), end);
+        cursor.set_before(&first);
+        assert_eq!(cursor.calculate_retain_index(), 0);
+    }
+    let eager_time = performance.now() - start;
+
+    //lazy: the same moves, only asking for the retain index at the end
+    let start = performance.now();
+    for _ in 0..COUNT {
+        cursor.set_after(&leaf);
+        cursor.set_before(&first);
+    }
+    assert_eq!(cursor.get_retain_index(), 0);
+    cursor.set_after(&leaf);
+    assert_eq!(cursor.get_retain_index(), end);
+    let lazy_time = performance.now() - start;
+
+    assert!(lazy_time < eager_time);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn word_navigation_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("word_navigation_test");
+    doc.open()?;
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    let mut delta = Delta::default();
+    delta.insert("hello ");
+    delta.insert_attr("world", attr);
+    delta.insert(", foo\nbar  baz\n");
+    doc.apply_delta(delta)?;
+
+    let cursor = doc.get_cursor();
+    set_cursor_selection(cursor, 0, 0);
+    //the end of a word spans text nodes with different formats, and includes punctuation
+    for expected in [5, 12, 16, 17, 20, 25, 25] {
+        cursor.word_forward()?;
+        assert_eq!(cursor.get_retain_index(), expected);
+    }
+    for expected in [22, 17, 16, 13, 6, 0, 0] {
+        cursor.word_backward()?;
+        assert_eq!(cursor.get_retain_index(), expected);
+    }
+
+    //from the middle of a word
+    set_cursor_selection(cursor, 8, 0);
+    cursor.word_forward()?;
+    assert_eq!(cursor.get_retain_index(), 12);
+    set_cursor_selection(cursor, 8, 0);
+    cursor.word_backward()?;
+    assert_eq!(cursor.get_retain_index(), 6);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn line_start_end_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("line_start_end_test");
+    doc.open()?;
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    let mut delta = Delta::default();
+    delta.insert("hello ");
+    delta.insert_attr("world", attr);
+    delta.insert(" foo\nbar\n\n");
+    doc.apply_delta(delta)?;
+
+    let cursor = doc.get_cursor();
+    set_cursor_selection(cursor, 8, 0);
+    cursor.line_start()?;
+    assert_eq!(cursor.get_retain_index(), 0);
+    cursor.line_end()?;
+    assert_eq!(cursor.get_retain_index(), 15);
+
+    //already at the end, or the start, of the line
+    let location = cursor.get_location();
+    cursor.line_end()?;
+    assert!(cursor.get_location() == location);
+    set_cursor_selection(cursor, 0, 0);
+    let location = cursor.get_location();
+    cursor.line_start()?;
+    assert!(cursor.get_location() == location);
+    assert_eq!(cursor.get_retain_index(), 0);
+
+    set_cursor_selection(cursor, 17, 0);
+    cursor.line_end()?;
+    assert_eq!(cursor.get_retain_index(), 19);
+    cursor.line_start()?;
+    assert_eq!(cursor.get_retain_index(), 16);
+
+    //an empty paragraph
+    set_cursor_selection(cursor, 20, 0);
+    cursor.line_start()?;
+    assert_eq!(cursor.get_retain_index(), 20);
+    cursor.line_end()?;
+    assert_eq!(cursor.get_retain_index(), 20);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn node_retain_index_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("node_retain_index_test");
+    doc.open()?;
+
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    let mut delta = Delta::default();
+    delta.insert("Hello ");
+    delta.insert_attr("world", bold);
+    delta.insert("\nsecond\n");
+    doc.apply_delta(delta)?;
+
+    let first = first_node(doc.get_root());
+    assert_eq!(first.retain_index(), 0);
+    let middle = next_node(&first).unwrap();
+    assert_eq!(middle.retain_index(), 6);
+    let block = next_node(&middle).unwrap();
+    assert_eq!(block.retain_index(), 11);
+    let last = last_leaf_node(doc.get_root()).unwrap();
+    assert_eq!(last.retain_index(), 12);
+
+    //same as a cursor before the node
+    let cursor = Cursor::new();
+    for doc_node in [first, middle, block, last] {
+        cursor.set_before_no_retain_update(&doc_node);
+        assert_eq!(doc_node.retain_index(), cursor.calculate_retain_index());
+    }
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use anyhow::Result;
+use core_formats::format_const::NAME_P_BLOCK;
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn create_doc(id: &str) -> Result<DocumentRoot> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new(id);
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("first\nsecond");
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<p>first</p><p>second</p>");
+    Ok(doc)
+}
+
+#[wasm_bindgen_test]
+fn insert_block_before_test() -> Result<()> {
+    let mut doc = create_doc("insert_block_before_test")?;
+    doc.insert_block_before(8, NAME_P_BLOCK, Attributes::default())?;
+    assert_eq!(doc.as_html_string(), "<p>first</p><p><br></p><p>second</p>");
+    assert_eq!(doc.get_cursor().get_retain_index(), 6);
+
+    doc.insert_block_before(0, NAME_P_BLOCK, Attributes::default())?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p><br></p><p>first</p><p><br></p><p>second</p>"
+    );
+    assert_eq!(doc.get_cursor().get_retain_index(), 0);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn insert_block_after_last_block_test() -> Result<()> {
+    let mut doc = create_doc("insert_block_after_last_block_test")?;
+    doc.insert_block_after(8, NAME_P_BLOCK, Attributes::default())?;
+    assert_eq!(doc.as_html_string(), "<p>first</p><p>second</p><p><br></p>");
+    assert_eq!(doc.get_cursor().get_retain_index(), 13);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn insert_block_errors_test() -> Result<()> {
+    let mut doc = create_doc("insert_block_errors_test")?;
+    assert!(doc
+        .insert_block_before(0, "no-such-format", Attributes::default())
+        .is_err());
+    assert!(doc
+        .insert_block_after(100, NAME_P_BLOCK, Attributes::default())
+        .is_err());
+    assert_eq!(doc.as_html_string(), "<p>first</p><p>second</p>");
+    Ok(())
+}
+
+fn replacement(txt: &str) -> Delta {
+    let mut delta = Delta::default();
+    delta.insert(txt);
+    delta
+}
+
+#[wasm_bindgen_test]
+fn apply_change_set_single_test() -> Result<()> {
+    let mut doc = create_doc("apply_change_set_single_test")?;
+    doc.apply_change_set(vec![(0, 5, replacement("1st"))])?;
+    assert_eq!(doc.as_html_string(), "<p>1st</p><p>second</p>");
+    assert_eq!(doc.get_cursor().get_retain_index(), 3);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn apply_change_set_two_changes_test() -> Result<()> {
+    let mut doc = create_doc("apply_change_set_two_changes_test")?;
+    doc.apply_change_set(vec![(0, 5, replacement("1st")), (6, 6, replacement("2nd"))])?;
+    assert_eq!(doc.as_html_string(), "<p>1st</p><p>2nd</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn apply_change_set_back_to_front_test() -> Result<()> {
+    let mut doc = create_doc("apply_change_set_back_to_front_test")?;
+    //both positions refer to the original document, the first change grows the text
+    doc.apply_change_set(vec![(0, 1, replacement("FFF")), (6, 1, replacement("S"))])?;
+    assert_eq!(doc.as_html_string(), "<p>FFFirst</p><p>Second</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn apply_change_set_overlap_test() -> Result<()> {
+    let mut doc = create_doc("apply_change_set_overlap_test")?;
+    let result = doc.apply_change_set(vec![(0, 5, replacement("1st")), (3, 4, replacement("x"))]);
+    assert!(result.is_err());
+
+    let result = doc.apply_change_set(vec![(6, 1, replacement("S")), (0, 1, replacement("F"))]);
+    assert!(result.is_err());
+
+    let result = doc.apply_change_set(vec![(6, 7, replacement("S"))]);
+    assert!(result.is_err());
+    assert_eq!(doc.as_html_string(), "<p>first</p><p>second</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn apply_change_set_rollback_test() -> Result<()> {
+    let mut doc = create_doc("apply_change_set_rollback_test")?;
+    let undo_len = doc.get_undo_history().undo_len();
+    assert!(undo_len > 0);
+    doc.protect_range(0, 5);
+    doc.add_cursor(8);
+
+    //the second change is applied first, then the first change fails
+    let result = doc.apply_change_set(vec![(0, 1, replacement("F")), (6, 1, replacement("S"))]);
+    assert!(result.is_err()); //Error::RangeProtected
+    assert_eq!(doc.as_html_string(), "<p>first</p><p>second</p>");
+    assert_eq!(doc.get_undo_history().undo_len(), undo_len);
+    assert_eq!(doc.get_protected_ranges(), vec![(0, 5)]);
+    assert_eq!(doc.get_all_cursors()[1].1, 8);
+
+    //the history still undoes the changes before the change set
+    assert!(doc.undo()?);
+    assert_ne!(doc.as_html_string(), "<p>first</p><p>second</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn apply_change_set_delete_test() -> Result<()> {
+    let mut doc = create_doc("apply_change_set_delete_test")?;
+    doc.apply_change_set(vec![(1, 3, Delta::default()), (7, 4, Delta::default())])?;
+    assert_eq!(doc.as_html_string(), "<p>ft</p><p>sd</p>");
+    Ok(())
+}
+
+fn create_three_blocks(id: &str) -> Result<DocumentRoot> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new(id);
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("first\nsecond\nthird");
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>first</p><p>second</p><p>third</p>"
+    );
+    Ok(doc)
+}
+
+/// A valid document ends with a block operation
+fn assert_valid(doc: &DocumentRoot) -> Result<()> {
+    let delta = doc.to_delta();
+    let last = delta.get_ops_ref().last().unwrap();
+    assert!(last.insert_value().str_val()?.ends_with('\n'));
+    assert_eq!(doc.get_cursor().get_retain_index(), 0);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn split_document_at_text_test() -> Result<()> {
+    let doc = create_three_blocks("split_document_at_text_test")?;
+
+    //"se|cond" --> the start of the block is the nearest block boundary
+    let (first, second) = doc.split_document_at(8)?;
+    assert_eq!(first.as_html_string(), "<p>first</p>");
+    assert_eq!(second.as_html_string(), "<p>second</p><p>third</p>");
+    assert_valid(&first)?;
+    assert_valid(&second)?;
+
+    //"seco|nd" --> the end of the block is the nearest block boundary
+    let (first, second) = doc.split_document_at(10)?;
+    assert_eq!(first.as_html_string(), "<p>first</p><p>second</p>");
+    assert_eq!(second.as_html_string(), "<p>third</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn split_document_at_block_test() -> Result<()> {
+    let doc = create_three_blocks("split_document_at_block_test")?;
+    let (first, second) = doc.split_document_at(13)?;
+    assert_eq!(first.as_html_string(), "<p>first</p><p>second</p>");
+    assert_eq!(second.as_html_string(), "<p>third</p>");
+    assert_valid(&first)?;
+    assert_valid(&second)?;
+
+    //split at the start gives an empty first document
+    let (first, second) = doc.split_document_at(0)?;
+    assert_eq!(first.as_html_string(), "<p><br></p>");
+    assert_eq!(
+        second.as_html_string(),
+        "<p>first</p><p>second</p><p>third</p>"
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn split_document_with_selection_test() -> Result<()> {
+    let doc = create_three_blocks("split_document_with_selection_test")?;
+    set_cursor_selection(doc.get_cursor(), 2, 8);
+
+    let (first, second) = doc.split_document_at(6)?;
+    assert_eq!(first.as_html_string(), "<p>first</p>");
+    assert_eq!(second.as_html_string(), "<p>second</p><p>third</p>");
+    assert!(!first.get_cursor().is_selection());
+    assert!(!second.get_cursor().is_selection());
+
+    //the original document is not changed
+    assert_eq!(doc.get_cursor().get_retain_index(), 2);
+    assert_eq!(doc.get_cursor().selection_length(), 8);
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>first</p><p>second</p><p>third</p>"
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn merge_document_test() -> Result<()> {
+    let doc = create_three_blocks("merge_document_test")?;
+    let (mut first, second) = doc.split_document_at(6)?;
+    first.merge_document(second)?;
+    assert_eq!(
+        first.as_html_string(),
+        "<p>first</p><p>second</p><p>third</p>"
+    );
+    assert_valid(&first)?;
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use link::{LinkFormat, NAME_LINK};
+use node_tree::dom_doc_tree_morph::unlink;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry has the link format, which wraps its text node in an <A> node
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_LINK, Arc::new(LinkFormat::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+#[wasm_bindgen_test]
+fn valid_tree_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("valid_tree_test");
+    assert!(doc.validate_tree_integrity().is_empty());
+
+    doc.open()?;
+    assert!(doc.validate_tree_integrity().is_empty());
+
+    let mut delta = Delta::default();
+    delta.insert("Hello\n\nworld");
+    doc.apply_delta(delta)?;
+    assert!(doc.validate_tree_integrity().is_empty());
+
+    let mut delta = Delta::default();
+    delta.retain(2);
+    delta.delete(6);
+    doc.apply_delta(delta)?;
+    assert!(doc.validate_tree_integrity().is_empty());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn valid_tree_link_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("valid_tree_link_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert("link", "https://example.org");
+    let mut delta = Delta::default();
+    delta.insert("see ");
+    delta.insert_attr("example", attr);
+    delta.insert("\n");
+    doc.apply_delta(delta)?;
+    assert!(doc.as_html_string().contains("<a "));
+    assert!(doc.validate_tree_integrity().is_empty());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn broken_tree_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("broken_tree_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("Hello\nworld");
+    doc.apply_delta(delta)?;
+
+    //an empty block without <BR>
+    let p = doc.get_root().get_child(0).unwrap();
+    let text = p.get_child(0).unwrap();
+    unlink(&p, &text);
+
+    let violations = doc.validate_tree_integrity();
+    assert_eq!(violations.len(), 1);
+    assert!(violations[0].starts_with("Empty block without a <BR>"));
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use delta::types::ops_kind::OpKind;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Changes = Rc<RefCell<Vec<Vec<DeltaOperation>>>>;
+
+fn create_doc(id: &str) -> Result<(DocumentRoot, Changes)> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new(id);
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("Hello");
+    doc.apply_delta(delta)?;
+
+    let changes: Changes = Rc::new(RefCell::new(Vec::new()));
+    let c = changes.clone();
+    doc.observe_subtree(move |ops: &[DeltaOperation]| c.borrow_mut().push(ops.to_vec()));
+    Ok((doc, changes))
+}
+
+#[wasm_bindgen_test]
+fn observe_insert_test() -> Result<()> {
+    let (mut doc, changes) = create_doc("observe_insert_test")?;
+    set_cursor_selection(doc.get_cursor(), 5, 0);
+    doc.apply_operation(DeltaOperation::insert(" world"))?;
+
+    let changes = changes.borrow();
+    assert_eq!(changes.len(), 1);
+    let ops = changes.first().unwrap();
+    assert_eq!(ops.len(), 2);
+    assert!(matches!(ops[0].get_op_kind(), OpKind::Retain(5)));
+    assert_eq!(ops[1].insert_value().str_val()?, " world");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn observe_insert_lines_test() -> Result<()> {
+    let (mut doc, changes) = create_doc("observe_insert_lines_test")?;
+    set_cursor_selection(doc.get_cursor(), 5, 0);
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    doc.apply_operation(DeltaOperation::insert_attr("a\nb", attr.clone()))?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>Hello<strong>a</strong></p><p><strong>b</strong></p>"
+    );
+
+    //the new line does not get the text attributes
+    let changes = changes.borrow();
+    assert_eq!(changes.len(), 1);
+    let mut expect = vec![DeltaOperation::retain(5)];
+    expect.push(DeltaOperation::insert_attr("a", attr.clone()));
+    expect.push(DeltaOperation::insert("\n"));
+    expect.push(DeltaOperation::insert_attr("b", attr));
+    assert_eq!(changes[0], expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn observe_delete_test() -> Result<()> {
+    let (mut doc, changes) = create_doc("observe_delete_test")?;
+    set_cursor_selection(doc.get_cursor(), 1, 0);
+    doc.apply_operation(DeltaOperation::delete(2))?;
+    assert_eq!(doc.as_html_string(), "<p>Hlo</p>");
+
+    let changes = changes.borrow();
+    assert_eq!(changes.len(), 1);
+    let ops = changes.first().unwrap();
+    assert_eq!(ops.len(), 2);
+    assert!(matches!(ops[0].get_op_kind(), OpKind::Retain(1)));
+    assert!(matches!(ops[1].get_op_kind(), OpKind::Delete(2)));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn observe_format_test() -> Result<()> {
+    let (mut doc, changes) = create_doc("observe_format_test")?;
+
+    //a retain without attributes only moves the cursor
+    doc.reset_cursor();
+    doc.apply_operation(DeltaOperation::retain(1))?;
+    assert!(changes.borrow().is_empty());
+
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    let mut op = DeltaOperation::retain(3);
+    op.set_attributes(attr);
+    doc.apply_operation(op)?;
+    assert_eq!(doc.as_html_string(), "<p>H<strong>ell</strong>o</p>");
+
+    let changes = changes.borrow();
+    assert_eq!(changes.len(), 1);
+    let ops = changes.first().unwrap();
+    assert_eq!(ops.len(), 2);
+    assert!(matches!(ops[0].get_op_kind(), OpKind::Retain(1)));
+    assert!(matches!(ops[1].get_op_kind(), OpKind::Retain(3)));
+    assert!(ops[1].get_attributes().contains_key("bold"));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn unobserve_test() -> Result<()> {
+    let (mut doc, changes) = create_doc("unobserve_test")?;
+    doc.unobserve();
+    doc.apply_operation(DeltaOperation::insert("!"))?;
+    assert!(changes.borrow().is_empty());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn observe_change_set_test() -> Result<()> {
+    let (mut doc, changes) = create_doc("observe_change_set_test")?;
+    let mut replacement = Delta::default();
+    replacement.insert("J");
+    doc.apply_change_set(vec![(0, 1, replacement), (4, 1, Delta::default())])?;
+    assert_eq!(doc.as_html_string(), "<p>Jell</p>");
+
+    //back to front: first the delete at 4, then the replacement at 0
+    let changes = changes.borrow();
+    assert_eq!(changes.len(), 2);
+    assert!(matches!(changes[0][0].get_op_kind(), OpKind::Retain(4)));
+    assert!(matches!(changes[0][1].get_op_kind(), OpKind::Delete(1)));
+    assert!(matches!(changes[1][0].get_op_kind(), OpKind::Delete(1)));
+    assert_eq!(changes[1][1].insert_value().str_val()?, "J");
+    Ok(())
+}
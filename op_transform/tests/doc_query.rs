@@ -0,0 +1,403 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::types::attr_val::AttrVal;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use std::sync::Arc;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn paragraph_at_cursor_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("paragraph_at_cursor_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+
+    let mut delta = Delta::default();
+    delta.insert("first ");
+    delta.insert_attr("block", attr);
+    delta.insert("\nsecond block\nthird");
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>first <strong>block</strong></p><p>second block</p><p>third</p>"
+    );
+
+    //cursor in the bold text of the first block
+    set_cursor_selection(doc.get_cursor(), 8, 0);
+    let par = doc.get_paragraph_at_cursor().unwrap();
+    let ops = par.get_ops_ref();
+    assert_eq!(ops.len(), 3);
+    assert_eq!(ops[0].insert_value().str_val()?, "first ");
+    assert_eq!(ops[1].insert_value().str_val()?, "block");
+    assert_eq!(ops[2].insert_value().str_val()?, "\n");
+    assert_eq!(doc.get_paragraph_text_at_cursor(), "first block");
+    assert_eq!(doc.get_paragraph_retain_range(), Some((0, 12)));
+
+    //cursor at the start of the second block
+    set_cursor_selection(doc.get_cursor(), 12, 0);
+    let par = doc.get_paragraph_at_cursor().unwrap();
+    let ops = par.get_ops_ref();
+    assert_eq!(ops.len(), 2);
+    assert_eq!(ops[0].insert_value().str_val()?, "second block");
+    assert_eq!(doc.get_paragraph_text_at_cursor(), "second block");
+    assert_eq!(doc.get_paragraph_retain_range(), Some((12, 13)));
+
+    //cursor at the end of the document
+    doc.cursor_to_end();
+    assert_eq!(doc.get_paragraph_text_at_cursor(), "third");
+    assert_eq!(doc.get_paragraph_retain_range(), Some((25, 6)));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn paragraph_at_cursor_empty_block_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("paragraph_at_cursor_empty_block_test");
+    doc.open()?;
+
+    let par = doc.get_paragraph_at_cursor().unwrap();
+    assert_eq!(par.get_ops_ref().len(), 1);
+    assert_eq!(doc.get_paragraph_text_at_cursor(), "");
+    assert_eq!(doc.get_paragraph_retain_range(), Some((0, 1)));
+
+    doc.close();
+    assert!(doc.get_paragraph_at_cursor().is_none());
+    assert!(doc.get_paragraph_retain_range().is_none());
+    assert_eq!(doc.get_paragraph_text_at_cursor(), "");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn format_ranges_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("format_ranges_test");
+    doc.open()?;
+
+    //empty block
+    assert_eq!(
+        doc.get_format_ranges(),
+        vec![(0, 1, NAME_P_BLOCK.to_string())]
+    );
+
+    let mut delta = Delta::default();
+    delta.insert("hello");
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.get_format_ranges(),
+        vec![
+            (0, 5, NAME_TEXT.to_string()),
+            (5, 1, NAME_P_BLOCK.to_string())
+        ]
+    );
+
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    let mut delta = Delta::default();
+    delta.retain(5);
+    delta.insert_attr("world", attr);
+    delta.insert("\n");
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.get_format_ranges(),
+        vec![
+            (0, 5, NAME_TEXT.to_string()),
+            (5, 5, NAME_TEXT.to_string()),
+            (10, 1, NAME_P_BLOCK.to_string()),
+            (11, 1, NAME_P_BLOCK.to_string()),
+        ]
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn attribute_ranges_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("attribute_ranges_test");
+    doc.open()?;
+    assert!(doc.get_attribute_ranges("bold").is_empty());
+
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    let mut bold_italic = Attributes::default();
+    bold_italic.insert("bold", true);
+    bold_italic.insert("italic", true);
+
+    let mut delta = Delta::default();
+    delta.insert_attr("ab", bold);
+    delta.insert_attr("cd", bold_italic.clone());
+    delta.insert("ef");
+    delta.insert_attr("gh", bold_italic);
+    doc.apply_delta(delta)?;
+
+    assert_eq!(
+        doc.get_attribute_ranges("bold"),
+        vec![(0, 4, AttrVal::Bool(true)), (6, 2, AttrVal::Bool(true))]
+    );
+    assert_eq!(
+        doc.get_attribute_ranges("italic"),
+        vec![(2, 2, AttrVal::Bool(true)), (6, 2, AttrVal::Bool(true))]
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn word_at_cursor_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("word_at_cursor_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert("italic", true);
+
+    //"Hello, wonderful  world\nlast"
+    let mut delta = Delta::default();
+    delta.insert("Hello, won");
+    delta.insert_attr("der", attr);
+    delta.insert("ful  world\nlast");
+    doc.apply_delta(delta)?;
+
+    //first word in the block
+    set_cursor_selection(doc.get_cursor(), 0, 0);
+    assert_eq!(doc.word_at_cursor(), Some(("Hello".to_string(), 0, 5)));
+
+    //middle of a word, spanning the italic text
+    set_cursor_selection(doc.get_cursor(), 9, 0);
+    assert_eq!(doc.word_at_cursor(), Some(("wonderful".to_string(), 7, 16)));
+
+    //end of a word
+    set_cursor_selection(doc.get_cursor(), 16, 0);
+    assert_eq!(doc.word_at_cursor(), Some(("wonderful".to_string(), 7, 16)));
+
+    //at the punctuation, and in the white space
+    set_cursor_selection(doc.get_cursor(), 6, 0);
+    assert_eq!(doc.word_at_cursor(), None);
+    set_cursor_selection(doc.get_cursor(), 17, 0);
+    assert_eq!(doc.word_at_cursor(), None);
+
+    //last word in the block, and the word in the next block
+    set_cursor_selection(doc.get_cursor(), 23, 0);
+    assert_eq!(doc.word_at_cursor(), Some(("world".to_string(), 18, 23)));
+    set_cursor_selection(doc.get_cursor(), 26, 0);
+    assert_eq!(doc.word_at_cursor(), Some(("last".to_string(), 24, 28)));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn sentence_at_cursor_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("sentence_at_cursor_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Version 1.2 is out! Is it?  Yes\n");
+    doc.apply_delta(delta)?;
+
+    //a dot without white space does not end the sentence
+    set_cursor_selection(doc.get_cursor(), 3, 0);
+    let first = Some(("Version 1.2 is out!".to_string(), 0, 19));
+    assert_eq!(doc.sentence_at_cursor(), first);
+    set_cursor_selection(doc.get_cursor(), 19, 0);
+    assert_eq!(doc.sentence_at_cursor(), first);
+
+    set_cursor_selection(doc.get_cursor(), 22, 0);
+    assert_eq!(
+        doc.sentence_at_cursor(),
+        Some(("Is it?".to_string(), 20, 26))
+    );
+
+    //in the white space between sentences
+    set_cursor_selection(doc.get_cursor(), 27, 0);
+    assert_eq!(doc.sentence_at_cursor(), None);
+
+    //last sentence without punctuation, at the end of the block
+    set_cursor_selection(doc.get_cursor(), 31, 0);
+    assert_eq!(doc.sentence_at_cursor(), Some(("Yes".to_string(), 28, 31)));
+
+    //empty block
+    set_cursor_selection(doc.get_cursor(), 32, 0);
+    assert_eq!(doc.sentence_at_cursor(), None);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn get_block_at_retain_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("get_block_at_retain_test");
+    doc.open()?;
+    assert!(doc.get_block_at_retain(0).is_none());
+
+    let mut delta = Delta::default();
+    delta.insert("first\nsecond\n");
+    doc.apply_delta(delta)?;
+
+    //first char, the block boundary "\n" and the last char of the document
+    let first = doc.get_block_at_retain(0).unwrap();
+    assert_eq!(first.get_formatter().format_name(), NAME_P_BLOCK);
+    assert_eq!(first.get_text_content(), "first");
+    let boundary = doc.get_block_at_retain(5).unwrap();
+    assert!(Arc::ptr_eq(&first, &boundary));
+    let second = doc.get_block_at_retain(6).unwrap();
+    assert_eq!(second.get_text_content(), "second");
+    let last = doc.get_block_at_retain(12).unwrap();
+    assert!(Arc::ptr_eq(&second, &last));
+
+    //for a paragraph the top level block is the paragraph itself
+    let top = doc.get_top_level_block_at_retain(8).unwrap();
+    assert!(Arc::ptr_eq(&second, &top));
+
+    //the empty paragraph at the end, and out of range
+    assert!(doc.get_block_at_retain(13).unwrap().is_empty_block());
+    assert!(doc.get_block_at_retain(14).is_none());
+    assert!(doc.get_top_level_block_at_retain(14).is_none());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn get_delta_slice_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("get_delta_slice_test");
+    doc.open()?;
+
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    let mut delta = Delta::default();
+    delta.insert("Hello ");
+    delta.insert_attr("bold world", bold.clone());
+    delta.insert("\nsecond block\n");
+    doc.apply_delta(delta)?;
+
+    //one word
+    let mut expect = Delta::default();
+    expect.insert_attr("world", bold.clone());
+    let slice = doc.get_delta_slice(11, 5);
+    assert_eq!(slice.get_ops_ref(), expect.get_ops_ref());
+
+    //across a block boundary
+    let mut expect = Delta::default();
+    expect.insert_attr("world", bold.clone());
+    expect.insert("\nsecond");
+    let slice = doc.get_delta_slice(11, 12);
+    assert_eq!(slice.get_ops_ref(), expect.get_ops_ref());
+
+    //an entire block
+    let mut expect = Delta::default();
+    expect.insert("second block\n");
+    let slice = doc.get_delta_slice(17, 13);
+    assert_eq!(slice.get_ops_ref(), expect.get_ops_ref());
+
+    //the whole document, also when the range is beyond the end
+    let slice = doc.get_delta_slice(0, 31);
+    assert_eq!(slice.get_ops_ref(), doc.to_delta().get_ops_ref());
+    let slice = doc.get_delta_slice(0, 100);
+    assert_eq!(slice.get_ops_ref(), doc.to_delta().get_ops_ref());
+
+    //zero length
+    assert!(doc.get_delta_slice(5, 0).get_ops_ref().is_empty());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn get_plain_text_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("get_plain_text_test");
+    assert_eq!(doc.get_plain_text(), "");
+    doc.open()?;
+    assert_eq!(doc.get_plain_text(), "\n");
+
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    let mut delta = Delta::default();
+    delta.insert("Hello ");
+    delta.insert_attr("bold world", bold);
+    delta.insert("\n\nlast block\n");
+    doc.apply_delta(delta)?;
+
+    assert_eq!(doc.get_plain_text(), "Hello bold world\n\nlast block\n\n");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn get_delta_at_range_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("get_delta_at_range_test");
+    doc.open()?;
+
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    let mut delta = Delta::default();
+    delta.insert("Hello ");
+    delta.insert_attr("bold world", bold.clone());
+    delta.insert("\nsecond block\n");
+    doc.apply_delta(delta)?;
+
+    //from the start of the document
+    let mut expect = Delta::default();
+    expect.insert("Hello ");
+    expect.insert_attr("bo", bold.clone());
+    let range = doc.get_delta_at_range(0, 8);
+    assert_eq!(range.get_ops_ref(), expect.get_ops_ref());
+
+    //start and end in the middle of a node
+    let mut expect = Delta::default();
+    expect.insert("llo ");
+    expect.insert_attr("bold", bold.clone());
+    let range = doc.get_delta_at_range(2, 8);
+    assert_eq!(range.get_ops_ref(), expect.get_ops_ref());
+
+    //same as the slice
+    let range = doc.get_delta_at_range(11, 12);
+    assert_eq!(
+        range.get_ops_ref(),
+        doc.get_delta_slice(11, 12).get_ops_ref()
+    );
+
+    //beyond the end of the document
+    assert!(doc.get_delta_at_range(40, 5).get_ops_ref().is_empty());
+
+    //the range applied to an empty document
+    let range = doc.get_delta_at_range(6, 24);
+    let mut copy = DocumentRoot::new("get_delta_at_range_copy_test");
+    copy.open()?;
+    copy.apply_delta(range)?;
+    assert_eq!(
+        copy.as_html_string(),
+        "<p><strong>bold world</strong></p><p>second block</p><p><br></p>"
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn formats_at_cursor_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("formats_at_cursor_test");
+
+    //closed document
+    assert_eq!(doc.formats_at_cursor(), (None, Vec::new()));
+
+    //empty paragraph
+    doc.open()?;
+    let (block, text) = doc.formats_at_cursor();
+    assert_eq!(block, Some(NAME_P_BLOCK.to_string()));
+    assert!(text.is_empty());
+
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    let mut delta = Delta::default();
+    delta.insert("Hello ");
+    delta.insert_attr("world", bold);
+    doc.apply_delta(delta)?;
+
+    set_cursor_selection(doc.get_cursor(), 8, 0);
+    let (block, text) = doc.formats_at_cursor();
+    assert_eq!(block, Some(NAME_P_BLOCK.to_string()));
+    assert_eq!(text, vec!["bold".to_string()]);
+    Ok(())
+}
@@ -1,10 +1,22 @@
 use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::document::Document;
+use delta::operations::DeltaOperation;
 use dom::constants::DOCUMENT;
 use dom::dom_element::get_dom_element_by_id;
-use op_transform::doc_root::DocumentRoot;
+use node_tree::dom_cursor::DomCursor;
+use op_transform::doc_root::{DocumentRoot, EditorMode};
+use op_transform::error::Error;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
 use wasm_bindgen_test::wasm_bindgen_test_configure;
 use wasm_bindgen_test::*;
-use web_sys::Element;
+use web_sys::{Element, Event};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -55,3 +67,489 @@ fn bind_to_body_test() -> Result<()> {
     assert_eq!(el2.get_attribute("id").unwrap(), &*"body-root");
     Ok(())
 }
+
+#[wasm_bindgen_test]
+fn close_open_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("close_open_test");
+    doc.open()?;
+    doc.apply_operation(DeltaOperation::insert("first"))?;
+    assert_eq!(doc.as_html_string(), "<p>first</p>");
+
+    doc.close();
+    assert!(doc.get_mode() == &EditorMode::Closed);
+    assert!(!doc.get_cursor().valid());
+    assert_eq!(doc.get_cursor().get_retain_index(), 0);
+    assert_eq!(doc.as_html_string(), "");
+
+    doc.open()?;
+    assert_eq!(doc.get_cursor().get_retain_index(), 0);
+    doc.apply_operation(DeltaOperation::insert("second"))?;
+    assert_eq!(doc.as_html_string(), "<p>second</p>");
+
+    //open() on an open document starts a fresh document too
+    set_cursor_selection(doc.get_cursor(), 0, 3);
+    doc.open()?;
+    assert!(!doc.get_cursor().is_selection());
+    doc.apply_operation(DeltaOperation::insert("third"))?;
+    assert_eq!(doc.as_html_string(), "<p>third</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn apply_delta_merges_inserts_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("apply_delta_merges_inserts_test");
+    doc.open()?;
+
+    let inserts = Rc::new(RefCell::new(Vec::new()));
+    let i = inserts.clone();
+    doc.observe_subtree(move |ops: &[DeltaOperation]| i.borrow_mut().push(ops.to_vec()));
+
+    let mut delta = Delta::default();
+    delta.push(DeltaOperation::insert("Hello"));
+    delta.push(DeltaOperation::insert(" "));
+    delta.push(DeltaOperation::insert("world"));
+    doc.apply_delta(delta)?;
+
+    assert_eq!(doc.as_html_string(), "<p>Hello world</p>");
+    let inserts = inserts.borrow();
+    assert_eq!(inserts.len(), 1);
+    assert_eq!(inserts[0][0].insert_value().str_val()?, "Hello world");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn apply_delta_empty_delete_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("apply_delta_empty_delete_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.push(DeltaOperation::insert("Hello"));
+    delta.push(DeltaOperation::delete(0));
+    assert!(doc.apply_delta(delta).is_err()); //Error::InvalidDelta
+
+    //nothing is applied
+    assert_eq!(doc.as_html_string(), "<p><br></p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn apply_delta_zero_retain_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("apply_delta_zero_retain_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.push(DeltaOperation::retain(0));
+    delta.push(DeltaOperation::insert("Hello"));
+    assert!(doc.apply_delta(delta).is_err()); //Error::InvalidDelta
+    assert_eq!(doc.as_html_string(), "<p><br></p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn load_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("load_test");
+
+    //a new document has no nodes
+    doc.cursor_to_start();
+    doc.cursor_to_end();
+    doc.reset_cursor();
+
+    let mut delta = Delta::default();
+    delta.insert("Hello\nworld\n");
+    doc.load(delta)?;
+    assert!(doc.get_mode() == &EditorMode::Read);
+    assert_eq!(doc.as_html_string(), "<p>Hello</p><p>world</p>");
+
+    doc.cursor_to_start();
+    assert_eq!(doc.get_cursor().get_retain_index(), 0);
+    doc.cursor_to_end();
+    assert_eq!(doc.get_cursor().get_retain_index(), 11);
+
+    //not editable
+    assert!(doc.apply_operation(DeltaOperation::insert("x")).is_err());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn view_only_apply_delta_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("view_only_apply_delta_test");
+
+    let mut delta = Delta::default();
+    delta.insert("Hello");
+    assert!(doc.view_only_apply_delta(delta.clone()).is_err()); //Error::DocumentClosed
+
+    let mut document = Delta::default();
+    document.insert("world\n");
+    doc.load(document)?;
+    doc.view_only_apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<p>Helloworld</p>");
+    assert!(doc.get_mode() == &EditorMode::Read);
+    assert!(!doc.get_cursor().valid());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn to_document_fragment_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("to_document_fragment_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("Hello\nworld");
+    doc.apply_delta(delta)?;
+
+    let fragment = doc.to_document_fragment();
+    let container: Element = DOCUMENT.with(|d| d.create_element("DIV").unwrap());
+    container.append_child(&fragment).unwrap();
+    assert_eq!(container.inner_html(), doc.as_html_string());
+
+    //the copy does not follow the editor
+    doc.apply_operation(DeltaOperation::insert("!"))?;
+    assert_eq!(container.inner_html(), "<p>Hello</p><p>world</p>");
+    assert_ne!(container.inner_html(), doc.as_html_string());
+
+    let target: Element = DOCUMENT.with(|d| d.create_element("DIV").unwrap());
+    doc.clone_to_element(&target);
+    assert_eq!(target.inner_html(), doc.as_html_string());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn dom_cursor_sync_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("dom_cursor_sync_test");
+    doc.append_to_body();
+    doc.open()?;
+
+    let dom_cursor = DomCursor::new(doc.get_root());
+    doc.set_dom_cursor(&dom_cursor);
+
+    doc.apply_operation(DeltaOperation::insert("Hello world"))?;
+    assert_eq!(doc.get_cursor().get_retain_index(), 11);
+    assert_eq!(
+        dom_cursor.cursor_from_html_dom().calculate_retain_index(),
+        doc.get_cursor().get_retain_index()
+    );
+
+    set_cursor_selection(doc.get_cursor(), 5, 0);
+    doc.apply_operation(DeltaOperation::delete(6))?;
+    assert_eq!(doc.as_html_string(), "<p>Hello</p>");
+    assert_eq!(
+        dom_cursor.cursor_from_html_dom().calculate_retain_index(),
+        doc.get_cursor().get_retain_index()
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn dom_cursor_set_selection_from_retain_range_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("dom_cursor_set_selection_from_retain_range_test");
+    doc.append_to_body();
+    doc.open()?;
+    doc.apply_operation(DeltaOperation::insert("Hello world"))?;
+
+    let dom_cursor = DomCursor::new(doc.get_root());
+    dom_cursor.set_selection_from_retain_range(3, 8)?;
+    let cursor = dom_cursor.cursor_from_html_dom();
+    assert_eq!(cursor.get_retain_index(), 3);
+    assert_eq!(cursor.selection_length(), 5);
+
+    assert!(dom_cursor.set_selection_from_retain_range(8, 3).is_err()); //Error::RetainRangeOutOfBounds
+    assert!(dom_cursor.set_selection_from_retain_range(40, 45).is_err()); //Error::RetainRangeOutOfBounds
+    doc.close();
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn apply_delta_and_sync_dom_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("apply_delta_and_sync_dom_test");
+    doc.append_to_body();
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Hello\nworld");
+    doc.apply_delta_and_sync_dom(delta)?;
+
+    let dom_cursor = DomCursor::new(doc.get_root());
+    assert_eq!(
+        dom_cursor.cursor_from_html_dom().calculate_retain_index(),
+        doc.get_cursor().get_retain_index()
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn try_from_delta_test() -> Result<()> {
+    init_test_registry();
+    let mut delta = Delta::default();
+    delta.insert("Hello\nworld\n");
+    let doc = DocumentRoot::try_from(delta)?;
+    assert!(doc.get_mode() == &EditorMode::Edit);
+    assert_eq!(doc.as_html_string(), "<p>Hello</p><p>world</p>");
+    assert_eq!(Delta::from(&doc).get_ops(), doc.to_delta().get_ops());
+
+    let copy = DocumentRoot::try_from(Delta::from(&doc))?;
+    assert_eq!(copy.as_html_string(), "<p>Hello</p><p>world</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn partial_eq_test() -> Result<()> {
+    init_test_registry();
+    let mut delta = Delta::default();
+    delta.insert("Hello\n");
+    let doc_1 = DocumentRoot::with_content(delta.clone())?;
+    let doc_2 = DocumentRoot::with_content(delta)?;
+    assert!(doc_1 == doc_2);
+
+    let mut other = Delta::default();
+    other.insert("world\n");
+    let doc_3 = DocumentRoot::with_content(other)?;
+    assert!(doc_1 != doc_3);
+
+    //documents without nodes
+    assert!(DocumentRoot::new("eq_1") == DocumentRoot::new("eq_2"));
+    assert!(DocumentRoot::new("eq_3") != doc_1);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn get_caret_visual_position_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("get_caret_visual_position_test");
+    doc.append_to_body();
+    doc.open()?;
+    assert!(doc.get_caret_visual_position().is_none());
+
+    let dom_cursor = DomCursor::new(doc.get_root());
+    doc.set_dom_cursor(&dom_cursor);
+    doc.apply_operation(DeltaOperation::insert("Hello world"))?;
+
+    let bounds = doc
+        .get_root()
+        .get_dom_element()
+        .unwrap()
+        .element()
+        .get_bounding_client_rect();
+    let (x, y) = doc.get_caret_visual_position().unwrap();
+    assert!(x >= bounds.left() && x <= bounds.right());
+    assert!(y >= bounds.top() && y <= bounds.bottom());
+
+    set_cursor_selection(doc.get_cursor(), 0, 5);
+    dom_cursor.cursor_to_html_dom(doc.get_cursor());
+    let rect = doc.get_selection_visual_rect().unwrap();
+    assert!(rect.left() >= bounds.left() && rect.right() <= bounds.right());
+    assert!(rect.top() >= bounds.top() && rect.bottom() <= bounds.bottom());
+
+    doc.close();
+    assert!(doc.get_caret_visual_position().is_none());
+    assert!(doc.get_selection_visual_rect().is_none());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn split_text_lines_attributes_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("split_text_lines_attributes_test");
+    doc.open()?;
+
+    doc.apply_operation(DeltaOperation::insert("plain\ntext"))?;
+    assert_eq!(doc.as_html_string(), "<p>plain</p><p>text</p>");
+
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    doc.apply_operation(DeltaOperation::insert_attr("hello\nworld", attr))?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>plain</p><p>text<strong>hello</strong></p><p><strong>world</strong></p>"
+    );
+
+    //the block operations are not bold
+    for op in doc.to_delta().get_ops() {
+        if op.insert_value().is_string() && op.insert_value().str_val()? == "\n" {
+            assert!(!op.get_attributes().contains_key("bold"));
+        }
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn container_dom_node_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("container_dom_node_test");
+    doc.append_to_body();
+    doc.open()?;
+
+    //the nodes are the live nodes in the HTML DOM
+    let container = get_dom_element_by_id("container_dom_node_test").unwrap();
+    assert!(doc
+        .container_dom_node()
+        .is_same_node(Some(container.node())));
+    assert!(doc
+        .container_element()
+        .node()
+        .is_same_node(Some(container.node())));
+    let editor = doc.editor_dom_node();
+    assert!(editor
+        .parent_node()
+        .unwrap()
+        .is_same_node(Some(doc.container_dom_node())));
+
+    let clicks = Rc::new(RefCell::new(0));
+    let count = clicks.clone();
+    let listener = Closure::<dyn FnMut()>::new(move || *count.borrow_mut() += 1);
+    doc.container_dom_node()
+        .add_event_listener_with_callback("click", listener.as_ref().unchecked_ref())
+        .unwrap();
+    doc.editor_dom_node()
+        .add_event_listener_with_callback("click", listener.as_ref().unchecked_ref())
+        .unwrap();
+
+    let click = Event::new("click").unwrap();
+    doc.editor_dom_node().dispatch_event(&click).unwrap();
+    assert_eq!(*clicks.borrow(), 1);
+    let click = Event::new("click").unwrap();
+    doc.container_dom_node().dispatch_event(&click).unwrap();
+    assert_eq!(*clicks.borrow(), 2);
+    doc.close();
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn apply_delta_at_position_test() -> Result<()> {
+    init_test_registry();
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    let mut italic = Attributes::default();
+    italic.insert("italic", true);
+
+    let mut change = Delta::default();
+    change.insert_attr("new", bold);
+    change.delete(2);
+    change.retain(3);
+    change.retain_attr(2, italic);
+    change.insert("!");
+
+    let mut at_position = DocumentRoot::new("apply_delta_at_position_test");
+    let mut from_start = DocumentRoot::new("apply_delta_at_position_from_start_test");
+    for doc in [&mut at_position, &mut from_start] {
+        doc.open()?;
+        let mut delta = Delta::default();
+        delta.insert("first line\nsecond line\n");
+        doc.apply_delta(delta)?;
+    }
+
+    at_position.apply_delta_at_position(6, change.clone())?;
+    let mut delta = Delta::default();
+    delta.retain(6);
+    for op in change.get_ops() {
+        delta.push(op);
+    }
+    from_start.apply_delta(delta)?;
+
+    assert_eq!(at_position.as_html_string(), from_start.as_html_string());
+    assert_eq!(at_position.to_delta(), from_start.to_delta());
+    assert_eq!(
+        at_position.get_cursor().get_retain_index(),
+        from_start.get_cursor().get_retain_index()
+    );
+    assert_eq!(at_position.get_cursor().get_retain_index(), 15);
+
+    //there is no cursor position after the last new line
+    let len = at_position.to_delta().document_length();
+    assert!(at_position.apply_delta_at_position(len, change).is_err()); //Error::RetainRangeOutOfBounds
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn delta_json_round_trip_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("delta_json_round_trip_test");
+    doc.open()?;
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    let mut delta = Delta::default();
+    delta.insert("plain ");
+    delta.insert_attr("bold", bold);
+    delta.insert("\nsecond line\n");
+    doc.apply_delta(delta)?;
+
+    let json = doc.to_delta_json();
+    let loaded = DocumentRoot::from_delta_json("delta_json_round_trip_loaded_test", &json)?;
+    assert_eq!(loaded.as_html_string(), doc.as_html_string());
+    assert_eq!(loaded.to_delta(), doc.to_delta());
+    assert!(loaded.get_mode() == &EditorMode::Edit);
+
+    let not_json = DocumentRoot::from_delta_json("delta_json_error_test", "{\"ops\":[");
+    assert!(not_json.is_err()); //Error::InvalidDelta
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn delta_json_without_new_line_test() {
+    init_test_registry();
+    let no_new_line = [
+        r#"{"ops":[{"insert":"é"}]}"#,
+        r#"{"ops":[{"insert":"abc"}]}"#,
+        r#"{"ops":[{"insert":""}]}"#,
+        r#"{"ops":[{"insert":{"image":"cat.png"}}]}"#,
+    ];
+    for json in no_new_line {
+        let err = DocumentRoot::from_delta_json("delta_json_without_new_line_test", json)
+            .err()
+            .unwrap();
+        assert!(
+            matches!(
+                err.downcast_ref::<Error>(),
+                Some(Error::InvalidDelta { .. })
+            ),
+            "{}",
+            json
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn paste_delta_empty_document_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("paste_delta_empty_document_test");
+    doc.open()?;
+
+    //the last new line does not add an empty paragraph
+    let mut delta = Delta::default();
+    delta.insert("first\nsecond\n");
+    let cursor = doc.get_cursor().clone();
+    doc.paste_delta(&cursor, delta)?;
+    assert_eq!(doc.as_html_string(), "<p>first</p><p>second</p>");
+    assert_eq!(cursor.get_retain_index(), 12);
+    assert_eq!(doc.get_cursor().get_retain_index(), 12);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn paste_delta_over_selection_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("paste_delta_over_selection_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("one\ntwo\nthree\n");
+    doc.apply_delta(delta)?;
+
+    //select "e\ntwo\nt"
+    let cursor = doc.get_cursor().clone();
+    set_cursor_selection(&cursor, 2, 7);
+    let mut delta = Delta::default();
+    delta.insert("X");
+    doc.paste_delta(&cursor, delta)?;
+    assert_eq!(doc.as_html_string(), "<p>onXhree</p><p><br></p>");
+    assert!(!cursor.is_selection());
+    assert_eq!(cursor.get_retain_index(), 3);
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Creates the document `say hello world, hello` where `hello` crosses a bold/italic boundary
+fn create_test_doc(doc: &mut DocumentRoot) -> Result<()> {
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    let mut italic = Attributes::default();
+    italic.insert("italic", true);
+
+    let mut delta = Delta::default();
+    delta.insert("say ");
+    delta.insert_attr("hel", bold);
+    delta.insert_attr("lo wor", italic);
+    delta.insert("ld, hello\nsecond\n");
+
+    doc.open()?;
+    doc.apply_delta(delta)?;
+    let expect = "<p>say <strong>hel</strong><em>lo wor</em>ld, hello</p><p>second</p><p><br></p>";
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn find_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("find_test");
+    create_test_doc(&mut doc)?;
+
+    //across the bold/italic, and the italic/plain boundary
+    assert_eq!(doc.find("hello"), vec![(4, 9), (17, 22)]);
+    assert_eq!(doc.find("world"), vec![(10, 15)]);
+
+    //across a block boundary
+    assert_eq!(doc.find("hello\nsec"), vec![(17, 26)]);
+
+    //non-overlapping
+    let mut delta = Delta::default();
+    delta.insert("aaaa\n");
+    doc.open()?;
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.find("aa"), vec![(0, 2), (2, 4)]);
+
+    assert!(doc.find("b").is_empty());
+    assert!(doc.find("").is_empty());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn replace_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("replace_test");
+    create_test_doc(&mut doc)?;
+
+    //the replacement gets the format of the first replaced character
+    assert_eq!(doc.replace("hello", "bye")?, 2);
+    let expect = "<p>say <strong>bye</strong><em> wor</em>ld, bye</p><p>second</p><p><br></p>";
+    assert_eq!(doc.as_html_string(), expect);
+    assert!(doc.find("hello").is_empty());
+
+    //an empty replacement deletes
+    assert_eq!(doc.replace(" wor", "")?, 1);
+    let expect = "<p>say <strong>bye</strong>ld, bye</p><p>second</p><p><br></p>";
+    assert_eq!(doc.as_html_string(), expect);
+
+    assert_eq!(doc.replace("missing", "x")?, 0);
+    Ok(())
+}
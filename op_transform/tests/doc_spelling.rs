@@ -0,0 +1,72 @@
+use anyhow::Result;
+use delta::delta::Delta;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use spell::{SpellChecker, SpellingError};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Only knows that "teh" is wrong
+struct MockChecker {}
+
+impl SpellChecker for MockChecker {
+    fn check(&self, text: &str) -> Vec<(usize, usize)> {
+        text.match_indices("teh")
+            .map(|(start, word)| (start, start + word.len()))
+            .collect()
+    }
+}
+
+#[wasm_bindgen_test]
+fn check_spelling_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("check_spelling_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("Über teh cat\nand teh dog");
+    doc.apply_delta(delta)?;
+
+    let errors = doc.check_spelling(&MockChecker {});
+    assert_eq!(
+        errors,
+        vec![
+            SpellingError {
+                retain_start: 5,
+                retain_end: 8,
+                word: "teh".to_string()
+            },
+            SpellingError {
+                retain_start: 17,
+                retain_end: 20,
+                word: "teh".to_string()
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn spelling_marks_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("spelling_marks_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("I saw teh cat");
+    doc.apply_delta(delta)?;
+    set_cursor_selection(doc.get_cursor(), 2, 0);
+
+    let errors = doc.check_spelling(&MockChecker {});
+    doc.apply_spelling_marks(&errors)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>I saw <span class=\"ql-spell-error\">teh</span> cat</p>"
+    );
+    assert_eq!(doc.get_cursor().get_retain_index(), 2);
+
+    doc.clear_spelling_marks()?;
+    assert_eq!(doc.as_html_string(), "<p>I saw teh cat</p>");
+    assert_eq!(doc.get_cursor().get_retain_index(), 2);
+    Ok(())
+}
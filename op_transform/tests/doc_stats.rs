@@ -0,0 +1,227 @@
+use anyhow::Result;
+use core_formats::format_const::NAME_P_BLOCK;
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::doc_stats::TextStats;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn stats_no_selection_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("stats_no_selection_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("Hello sweet world\nsecond line");
+    doc.apply_delta(delta)?;
+    doc.get_cursor().collapse();
+
+    let stats = doc.get_formatted_selection_stats();
+    assert_eq!(stats.words, 5);
+    assert_eq!(stats.chars, 28);
+    assert_eq!(stats.paragraphs, 2);
+    assert!(stats.format_counts.is_empty());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn stats_single_format_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("stats_single_format_test");
+    doc.open()?;
+
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    let mut delta = Delta::default();
+    delta.insert("Hello ");
+    delta.insert_attr("sweet", attr);
+    delta.insert(" world");
+    doc.apply_delta(delta)?;
+
+    //select "lo sweet w"
+    set_cursor_selection(doc.get_cursor(), 3, 10);
+    let stats = doc.get_formatted_selection_stats();
+    assert_eq!(stats.words, 3);
+    assert_eq!(stats.chars, 10);
+    assert_eq!(stats.paragraphs, 1);
+    assert_eq!(stats.format_counts.get("bold"), Some(&5));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn stats_mixed_format_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("stats_mixed_format_test");
+    doc.open()?;
+
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    let mut bold_italic = Attributes::default();
+    bold_italic.insert("bold", true);
+    bold_italic.insert("italic", true);
+
+    let mut delta = Delta::default();
+    delta.insert_attr("abc", bold);
+    delta.insert_attr("def", bold_italic);
+    delta.insert("ghi");
+    doc.apply_delta(delta)?;
+
+    //select "bcdefg"
+    set_cursor_selection(doc.get_cursor(), 1, 6);
+    let stats = doc.get_formatted_selection_stats();
+    assert_eq!(stats.words, 1);
+    assert_eq!(stats.chars, 6);
+    assert_eq!(stats.format_counts.get("bold"), Some(&5));
+    assert_eq!(stats.format_counts.get("italic"), Some(&3));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn stats_multi_block_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("stats_multi_block_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("first block\nsecond block\nthird block");
+    doc.apply_delta(delta)?;
+
+    //select "block\nsecond block\nthird"
+    set_cursor_selection(doc.get_cursor(), 6, 24);
+    let stats = doc.get_formatted_selection_stats();
+    assert_eq!(stats.words, 4);
+    assert_eq!(stats.chars, 22);
+    assert_eq!(stats.paragraphs, 3);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn text_statistics_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("text_statistics_test");
+    doc.open()?;
+    assert_eq!(doc.text_statistics(), TextStats::default());
+
+    let mut delta = Delta::default();
+    delta.insert("Hello world. Hello again!\n\nVersion 1.2 is out\n");
+    doc.apply_delta(delta)?;
+
+    let stats = doc.text_statistics();
+    assert_eq!(stats.characters, 43);
+    assert_eq!(stats.words, 8);
+    assert_eq!(stats.unique_words, 7);
+    assert_eq!(stats.sentences, 3);
+    assert_eq!(stats.paragraphs, 2);
+    assert_eq!(stats.blocks.get(NAME_P_BLOCK), Some(&4));
+    assert_eq!(stats.reading_minutes, 0.04);
+    assert_eq!(stats.avg_words_per_paragraph, 4.0);
+    assert_eq!(stats.longest_paragraph, 25);
+
+    let expect = "Characters: 43\nWords: 8 (7 unique)\nSentences: 3\nParagraphs: 2\n\
+        Blocks F_P-BLOCK: 4\nReading time: 0.0 min\nAverage words per paragraph: 4.0\n\
+        Longest paragraph: 25 characters";
+    assert_eq!(stats.to_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn update_stats_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("update_stats_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("first block\nsecond block\nthird block\n");
+    doc.apply_delta(delta)?;
+    let mut stats = doc.text_statistics();
+
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    let mut retain = DeltaOperation::retain(8);
+    retain.set_attributes(bold);
+
+    let edits = vec![
+        //insert text, insert a new line, insert several blocks
+        (6, DeltaOperation::insert("new ")),
+        (3, DeltaOperation::insert("\n")),
+        (20, DeltaOperation::insert("one. Two!\nthree\n")),
+        //delete text, and delete across blocks joining them
+        (0, DeltaOperation::delete(2)),
+        (5, DeltaOperation::delete(12)),
+        //retain with attributes, and an insert at the start of the document
+        (2, retain),
+        (0, DeltaOperation::insert("start ")),
+    ];
+    for (start, op) in edits {
+        set_cursor_selection(doc.get_cursor(), start, 0);
+        doc.apply_operation(op.clone())?;
+        doc.update_stats(start, &op, &mut stats);
+        assert_eq!(stats, doc.text_statistics());
+    }
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn word_count_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("word_count_test");
+    assert_eq!(doc.word_count(), 0);
+    assert_eq!(doc.character_count(true), 0);
+
+    //only empty paragraphs
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("\n\n\n");
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.word_count(), 0);
+    assert_eq!(doc.character_count(true), 0);
+    assert_eq!(doc.character_count(false), 0);
+
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    let mut delta = Delta::default();
+    delta.insert("Hello, ");
+    delta.insert_attr("sweet", attr);
+    delta.insert(" world - again\nnext  line\n");
+    doc.open()?;
+    doc.apply_delta(delta)?;
+
+    //the `-` is not a word
+    assert_eq!(doc.word_count(), 6);
+    assert_eq!(doc.character_count(true), 36);
+    assert_eq!(doc.character_count(false), 30);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn word_count_large_document_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("word_count_large_document_test");
+    doc.open()?;
+
+    //1000 paragraphs of 10 words
+    let mut delta = Delta::default();
+    for _ in 0..1000 {
+        delta.insert("one two three four five six seven eight nine ten\n");
+    }
+    doc.apply_delta(delta)?;
+
+    let performance = web_sys::window().unwrap().performance().unwrap();
+    let start = performance.now();
+    assert_eq!(doc.word_count(), 10_000);
+    let words_time = performance.now() - start;
+
+    let start = performance.now();
+    assert_eq!(doc.character_count(false), 39_000);
+    let chars_time = performance.now() - start;
+
+    assert!(words_time < 10.0);
+    assert!(chars_time < 10.0);
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use op_transform::error::Error;
+
+#[test]
+fn error_display_test() {
+    let err = Error::BlockTransformFailed {
+        format_name: "F_P-BLOCK".to_string(),
+        reason: "block_transform() to the inserted block".to_string(),
+    };
+    assert_eq!(
+        err.to_string(),
+        "The block transform of format = F_P-BLOCK failed: block_transform() to the inserted block"
+    );
+
+    let err = Error::RetainIndexOutOfRange { index: 12 };
+    assert_eq!(
+        err.to_string(),
+        "Retain index = 12 is beyond the end of the document"
+    );
+
+    let err = Error::InvalidDelta {
+        msg: "delete(0)".to_string(),
+    };
+    assert_eq!(err.to_string(), "Invalid delta: delete(0)");
+}
+
+#[test]
+fn error_downcast_test() {
+    let err: anyhow::Error = Error::RetainIndexOutOfRange { index: 12 }.into();
+    assert!(matches!(
+        err.downcast_ref::<Error>(),
+        Some(Error::RetainIndexOutOfRange { index: 12 })
+    ));
+
+    //the typed error is found, also when it is added as context
+    let err = anyhow::anyhow!("format failure").context(Error::BlockTransformFailed {
+        format_name: "F_P-BLOCK".to_string(),
+        reason: "un_block_transform()".to_string(),
+    });
+    assert!(matches!(
+        err.downcast_ref::<Error>(),
+        Some(Error::BlockTransformFailed { .. })
+    ));
+    assert_eq!(err.root_cause().to_string(), "format failure");
+}
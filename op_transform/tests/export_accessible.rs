@@ -0,0 +1,40 @@
+use anyhow::Result;
+use delta::delta::Delta;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn accessible_text_paragraph_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("accessible_text_paragraph_test");
+    assert_eq!(doc.get_accessible_text(), "");
+
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("Hello\nworld");
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.get_accessible_text(), "Hello\nworld");
+
+    //an empty paragraph is an empty line
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("Hello\n\nworld");
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.get_accessible_text(), "Hello\n\nworld");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn set_aria_label_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("set_aria_label_test");
+    doc.open()?;
+    doc.set_aria_label("Document editor");
+    assert!(doc
+        .as_outer_html_string()
+        .contains(r#"aria-label="Document editor""#));
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use op_transform::doc_root::DocumentRoot;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn retain_attr(len: usize, key: &str, val: i32) -> DeltaOperation {
+    let mut attr = Attributes::default();
+    attr.insert(key, val);
+    let mut op = DeltaOperation::retain(len);
+    op.set_attributes(attr);
+    op
+}
+
+#[wasm_bindgen_test]
+fn diff_no_change_test() -> Result<()> {
+    let html = "<p>Hello <strong>world</strong></p><h1>Title</h1>";
+    let delta = DocumentRoot::generate_diff_delta(html, html)?;
+    assert!(delta.get_ops().is_empty());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn diff_insert_test() -> Result<()> {
+    let delta = DocumentRoot::generate_diff_delta("<p>Hello</p>", "<p>Hello world</p>")?;
+    assert_eq!(
+        delta.get_ops(),
+        vec![DeltaOperation::retain(5), DeltaOperation::insert(" world")]
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn diff_delete_test() -> Result<()> {
+    let delta = DocumentRoot::generate_diff_delta("<p>Hello world</p>", "<p>Hello</p>")?;
+    assert_eq!(
+        delta.get_ops(),
+        vec![DeltaOperation::retain(5), DeltaOperation::delete(6)]
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn diff_format_test() -> Result<()> {
+    let before = "<p>Hello world</p>";
+    let after = "<p>Hello <strong>world</strong></p>";
+    let delta = DocumentRoot::generate_diff_delta(before, after)?;
+
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    let mut bold = DeltaOperation::retain(5);
+    bold.set_attributes(attr);
+    assert_eq!(delta.get_ops(), vec![DeltaOperation::retain(6), bold]);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn diff_block_test() -> Result<()> {
+    let before = "<p>Title</p><p>text</p>";
+    let after = "<h1>Title</h1><p>text</p>";
+    let delta = DocumentRoot::generate_diff_delta(before, after)?;
+    assert_eq!(
+        delta.get_ops(),
+        vec![DeltaOperation::retain(5), retain_attr(1, "heading", 1)]
+    );
+    Ok(())
+}
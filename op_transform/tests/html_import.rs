@@ -0,0 +1,93 @@
+use anyhow::Result;
+use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
+use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::{DeltaOperation, OpsMap};
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::Registry;
+use soft_break::{SoftBreak, NAME_SOFT_BREAK};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The test registry has the soft break format, for the `<BR>` within a line
+static TEST_REGISTRY: OnceLock<Mutex<usize>> = OnceLock::new();
+fn init_test_registry() {
+    TEST_REGISTRY.get_or_init(|| {
+        Registry::init_registry();
+        let mut r = Registry::get_mut_ref().unwrap();
+        r.register_block_fmt(NAME_P_BLOCK, P_FORMAT.deref().clone())
+            .unwrap();
+        r.register_line_fmt(NAME_SOFT_BREAK, Arc::new(SoftBreak::new()))
+            .unwrap();
+        r.register_line_fmt(NAME_TEXT, TEXT_FORMAT.deref().clone())
+            .unwrap();
+        Mutex::new(1)
+    });
+}
+
+#[wasm_bindgen_test]
+fn from_html_test() -> Result<()> {
+    init_test_registry();
+    let html = "<p>plain <strong>bold</strong> and <em>italic</em></p><p>second</p><p><br></p>";
+    let doc = DocumentRoot::from_html("from_html_test", html)?;
+    assert_eq!(doc.as_html_string(), html);
+    assert_eq!(doc.get_plain_text(), "plain bold and italic\nsecond\n\n");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn from_html_unknown_tags_test() -> Result<()> {
+    init_test_registry();
+    let html = "<p>one <blink>two</blink></p><div>three</div>";
+    let doc = DocumentRoot::from_html("from_html_unknown_tags_test", html)?;
+    assert_eq!(doc.as_html_string(), "<p>one two</p><p>three</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn from_html_round_trip_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("from_html_round_trip_test");
+    doc.open()?;
+    doc.import_markdown("first **bold** _italic_\n\nsecond")?;
+
+    let html = doc.as_html_string();
+    let loaded = DocumentRoot::from_html("from_html_round_trip_loaded_test", &html)?;
+    assert_eq!(loaded.as_html_string(), html);
+    assert_eq!(loaded.to_delta(), doc.to_delta());
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn from_html_white_space_test() -> Result<()> {
+    init_test_registry();
+    let html = "<p>\n  one\n  <strong>two </strong> three\n</p>\n<p>  four\tfive  </p>\n";
+    let doc = DocumentRoot::from_html("from_html_white_space_test", html)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>one <strong>two </strong>three</p><p>four five</p>"
+    );
+    assert_eq!(doc.get_plain_text(), "one two three\nfour five\n");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn from_html_soft_break_test() -> Result<()> {
+    init_test_registry();
+    let html = "<p>a <br> b</p><p>c<br></p><p><br></p>";
+    let doc = DocumentRoot::from_html("from_html_soft_break_test", html)?;
+    assert_eq!(doc.as_html_string(), "<p>a<br>b</p><p>c</p><p><br></p>");
+
+    let mut br = OpsMap::default();
+    br.insert("page_break", true);
+    let mut delta = Delta::default();
+    delta.insert("a");
+    delta.push(DeltaOperation::insert_attr(br, Attributes::default()));
+    delta.insert("b\nc\n\n");
+    assert_eq!(doc.to_delta(), delta);
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use anyhow::Result;
+use delta::delta::Delta;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::keyboard::KeyboardShortcut;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+use web_sys::{KeyboardEvent, KeyboardEventInit};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn create_doc(id: &str) -> Result<DocumentRoot> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new(id);
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("Hello world");
+    doc.apply_delta(delta)?;
+    assert_eq!(doc.as_html_string(), "<p>Hello world</p>");
+    Ok(doc)
+}
+
+fn key(key: &str, ctrl: bool, shift: bool) -> KeyboardEvent {
+    let mut init = KeyboardEventInit::new();
+    init.key(key).ctrl_key(ctrl).shift_key(shift);
+    KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn enter_key_test() -> Result<()> {
+    let mut doc = create_doc("enter_key_test")?;
+    set_cursor_selection(doc.get_cursor(), 5, 0);
+    assert!(doc.on_key_down(&key("Enter", false, false)));
+    assert_eq!(doc.as_html_string(), "<p>Hello</p><p> world</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn backspace_key_test() -> Result<()> {
+    let mut doc = create_doc("backspace_key_test")?;
+    set_cursor_selection(doc.get_cursor(), 5, 0);
+    assert!(doc.on_key_down(&key("Backspace", false, false)));
+    assert_eq!(doc.as_html_string(), "<p>Hell world</p>");
+    assert_eq!(doc.get_cursor().get_retain_index(), 4);
+
+    //delete a selection
+    set_cursor_selection(doc.get_cursor(), 0, 5);
+    assert!(doc.on_key_down(&key("Backspace", false, false)));
+    assert_eq!(doc.as_html_string(), "<p>world</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn delete_key_test() -> Result<()> {
+    let mut doc = create_doc("delete_key_test")?;
+    set_cursor_selection(doc.get_cursor(), 5, 0);
+    assert!(doc.on_key_down(&key("Delete", false, false)));
+    assert_eq!(doc.as_html_string(), "<p>Helloworld</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn toggle_bold_italic_key_test() -> Result<()> {
+    let mut doc = create_doc("toggle_bold_italic_key_test")?;
+    set_cursor_selection(doc.get_cursor(), 6, 5);
+    assert!(doc.on_key_down(&key("b", true, false)));
+    assert_eq!(doc.as_html_string(), "<p>Hello <strong>world</strong></p>");
+
+    //selection is restored, so we can toggle it off again
+    assert!(doc.on_key_down(&key("b", true, false)));
+    assert_eq!(doc.as_html_string(), "<p>Hello world</p>");
+
+    assert!(doc.on_key_down(&key("i", true, false)));
+    assert_eq!(doc.as_html_string(), "<p>Hello <em>world</em></p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn undo_redo_key_test() -> Result<()> {
+    let mut doc = create_doc("undo_redo_key_test")?;
+    set_cursor_selection(doc.get_cursor(), 5, 0);
+    assert!(doc.on_key_down(&key("Backspace", false, false)));
+    assert_eq!(doc.as_html_string(), "<p>Hell world</p>");
+
+    assert!(doc.on_key_down(&key("z", true, false)));
+    assert_eq!(doc.as_html_string(), "<p>Hello world</p>");
+    assert!(doc.on_key_down(&key("y", true, false)));
+    assert_eq!(doc.as_html_string(), "<p>Hell world</p>");
+
+    //with shift the browser gives the upper case key
+    assert!(doc.on_key_down(&key("z", true, false)));
+    assert!(doc.on_key_down(&key("Z", true, true)));
+    assert_eq!(doc.as_html_string(), "<p>Hell world</p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn select_all_key_test() -> Result<()> {
+    let mut doc = create_doc("select_all_key_test")?;
+    assert!(doc.on_key_down(&key("a", true, false)));
+    assert!(doc.get_cursor().is_selection());
+    assert_eq!(doc.get_cursor().get_retain_index(), 0);
+    assert_eq!(doc.get_cursor().selection_length(), 11);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn custom_shortcut_test() -> Result<()> {
+    let mut doc = create_doc("custom_shortcut_test")?;
+    assert!(!doc.on_key_down(&key("q", true, true)));
+
+    let shortcut = KeyboardShortcut::new("Q", true, true, false);
+    doc.get_keyboard_shortcuts_mut().register(
+        shortcut.clone(),
+        Box::new(|doc: &mut DocumentRoot| {
+            doc.cursor_to_end();
+            let mut delta = Delta::default();
+            delta.retain(11);
+            delta.insert("!");
+            doc.apply_delta(delta)
+        }),
+    );
+    assert!(doc.on_key_down(&key("Q", true, true)));
+    assert_eq!(doc.as_html_string(), "<p>Hello world!</p>");
+
+    doc.get_keyboard_shortcuts_mut().remove(&shortcut);
+    assert!(!doc.handle_shortcut(&shortcut));
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anyhow::Result;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn import(id: &str, markdown: &str) -> Result<DocumentRoot> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new(id);
+    doc.open()?;
+    doc.import_markdown(markdown)?;
+    Ok(doc)
+}
+
+#[wasm_bindgen_test]
+fn import_paragraphs_test() -> Result<()> {
+    let doc = import(
+        "import_paragraphs_test",
+        "First line\ncontinues here\n\n\nSecond paragraph",
+    )?;
+    let expect = "<p>First line continues here</p><p>Second paragraph</p>";
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn import_bold_italic_test() -> Result<()> {
+    let doc = import(
+        "import_bold_italic_test",
+        "Hello **bold** and _italic_, or *italic* and __bold__",
+    )?;
+    let expect = "<p>Hello <strong>bold</strong> and <em>italic</em>, or <em>italic</em> and <strong>bold</strong></p>";
+    assert_eq!(doc.as_html_string(), expect);
+
+    let doc = import(
+        "import_no_emphasis_test",
+        "snake_case_name, 2 * 3 and **open",
+    )?;
+    let expect = "<p>snake_case_name, 2 * 3 and **open</p>";
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn import_unsupported_test() -> Result<()> {
+    //only the paragraph and text formats are registered
+    let doc = import(
+        "import_unsupported_test",
+        "# Title\n- item\n`code` [link](https://x.y) ![alt](src.png)\n\n---",
+    )?;
+    let expect = "<p># Title - item code link ![alt](src.png)</p><p>---</p>";
+    assert_eq!(doc.as_html_string(), expect);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn import_empty_test() -> Result<()> {
+    let doc = import("import_empty_test", "")?;
+    assert_eq!(doc.as_html_string(), "<p><br></p>");
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use node_tree::dom_cursor::DomCursor;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::multi_cursor::CursorId;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn create_test_doc(id: &str) -> Result<DocumentRoot> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new(id);
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("Hello sweet world");
+    doc.apply_delta(delta)?;
+    Ok(doc)
+}
+
+#[wasm_bindgen_test]
+fn add_remove_cursor_test() -> Result<()> {
+    let mut doc = create_test_doc("add_remove_cursor_test")?;
+    let first = doc.add_cursor(6);
+    let second = doc.add_cursor(12);
+    assert!(first != second);
+    assert_eq!(
+        doc.get_all_cursors(),
+        vec![(CursorId::PRIMARY, 17), (first, 6), (second, 12)]
+    );
+
+    doc.remove_cursor(first);
+    assert_eq!(
+        doc.get_all_cursors(),
+        vec![(CursorId::PRIMARY, 17), (second, 12)]
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn close_removes_cursors_test() -> Result<()> {
+    let mut doc = create_test_doc("close_removes_cursors_test")?;
+    doc.add_cursor(6);
+
+    //the new content has only the primary cursor
+    doc.open()?;
+    assert_eq!(doc.get_all_cursors(), vec![(CursorId::PRIMARY, 0)]);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn cursor_shift_insert_test() -> Result<()> {
+    let mut doc = create_test_doc("cursor_shift_insert_test")?;
+    let before = doc.add_cursor(2);
+    let at = doc.add_cursor(6);
+    let after = doc.add_cursor(12);
+
+    set_cursor_selection(doc.get_cursor(), 6, 0);
+    doc.apply_operation(DeltaOperation::insert("very "))?;
+    assert_eq!(doc.as_html_string(), "<p>Hello very sweet world</p>");
+    assert_eq!(
+        doc.get_all_cursors(),
+        vec![(CursorId::PRIMARY, 11), (before, 2), (at, 11), (after, 17)]
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn cursor_shift_delete_test() -> Result<()> {
+    let mut doc = create_test_doc("cursor_shift_delete_test")?;
+    let before = doc.add_cursor(2);
+    let inside = doc.add_cursor(8);
+    let after = doc.add_cursor(14);
+
+    set_cursor_selection(doc.get_cursor(), 6, 0);
+    doc.apply_operation(DeltaOperation::delete(6))?;
+    assert_eq!(doc.as_html_string(), "<p>Hello world</p>");
+    assert_eq!(
+        doc.get_all_cursors(),
+        vec![(CursorId::PRIMARY, 6), (before, 2), (inside, 6), (after, 8)]
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn show_all_cursors_test() -> Result<()> {
+    let mut doc = create_test_doc("show_all_cursors_test")?;
+    doc.append_to_body();
+    let dom_cursor = DomCursor::new(doc.get_root());
+    doc.set_dom_cursor(&dom_cursor);
+    doc.add_cursor(6);
+
+    set_cursor_selection(doc.get_cursor(), 2, 0);
+    doc.show_all_cursors();
+
+    //the first range is the primary cursor
+    assert_eq!(
+        dom_cursor.cursor_from_html_dom().calculate_retain_index(),
+        2
+    );
+    Ok(())
+}
@@ -38,7 +38,7 @@ fn op_delete_start_test() -> Result<()> {
     );
 
     //--------------------------------------------------------------------------
-    op_delete::delete(&cursor, 4)?;
+    op_delete::delete(&cursor, 4, false)?;
     let expect = r#"<p>_1_1<strong>TEXT_1_2</strong>TEXT_1_3</p><p>TEXT_2_1<strong>TEXT_2_2</strong></p><p><br></p>"#;
     assert_eq!(doc.as_html_string(), expect);
 
@@ -50,7 +50,7 @@ fn op_delete_start_test() -> Result<()> {
     );
 
     //--------------------------------------------------------------------------
-    op_delete::delete(&cursor, 4)?;
+    op_delete::delete(&cursor, 4, false)?;
     let expect = r#"<p><strong>TEXT_1_2</strong>TEXT_1_3</p><p>TEXT_2_1<strong>TEXT_2_2</strong></p><p><br></p>"#;
     assert_eq!(doc.as_html_string(), expect);
 
@@ -78,7 +78,7 @@ fn op_delete_ending_test() -> Result<()> {
     let cursor = doc.get_cursor();
     let p = doc.get_root().get_children().get(last).unwrap().clone();
     cursor.set_at(&p, 0);
-    let result = op_delete::delete(&cursor, 1);
+    let result = op_delete::delete(&cursor, 1, false);
     assert!(result.is_err()); //Error::DeletingLastBlock
 
     //THIS IS WRONG --> WE SHOULD AND CAN NOT DELETE THE LAST <p> BLOCK IN A DOCUMENT
@@ -112,7 +112,7 @@ fn op_delete_middle_test() -> Result<()> {
     cursor.set_before(&strong);
 
     //--------------------------------------------------------------------------
-    op_delete::delete(&cursor, 4)?;
+    op_delete::delete(&cursor, 4, false)?;
     let expect = r#"<p>TEXT_1_1<strong>_1_2</strong>TEXT_1_3</p><p>TEXT_2_1<strong>TEXT_2_2</strong></p><p><br></p>"#;
     assert_eq!(doc.as_html_string(), expect);
 
@@ -124,7 +124,7 @@ fn op_delete_middle_test() -> Result<()> {
     );
 
     //--------------------------------------------------------------------------
-    op_delete::delete(&cursor, 4)?;
+    op_delete::delete(&cursor, 4, false)?;
     let expect = r#"<p>TEXT_1_1TEXT_1_3</p><p>TEXT_2_1<strong>TEXT_2_2</strong></p><p><br></p>"#;
     assert_eq!(doc.as_html_string(), expect);
 
@@ -154,7 +154,7 @@ fn op_delete_block_node_test() -> Result<()> {
     //error!("Before: {}", &cursor);
 
     //--------------------------------------------------------------------------
-    op_delete::delete(&cursor, 1)?;
+    op_delete::delete(&cursor, 1, false)?;
     let expect = r#"<p>TEXT_1_1<strong>TEXT_1_2</strong>TEXT_1_3TEXT_2_1<strong>TEXT_2_2</strong></p><p><br></p>"#;
     assert_eq!(doc.as_html_string(), expect);
 
@@ -6,7 +6,9 @@ use delta::attributes::Attributes;
 use delta::delta::Delta;
 use delta::types::attr_val::AttrVal::Null;
 use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::apply_format_to_selection;
 use op_transform::registry::init_test_registry;
+use op_transform::registry::Registry;
 use wasm_bindgen_test::*;
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -145,3 +147,28 @@ fn retain_formatted_block_test() -> Result<()> {
     assert_eq!(doc.as_html_string(), expect);
     Ok(())
 }
+
+#[wasm_bindgen_test]
+fn apply_format_to_selection_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("apply_format_to_selection_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("word1 word2 word3");
+    doc.apply_delta(delta)?;
+
+    let cursor = doc.get_cursor();
+    cursor.set_selection_by_retain_range(6, 11)?;
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    apply_format_to_selection(cursor, 11 - 6, &attr, &Registry::get_ref()?)?;
+    let expect = r#"<p>word1 <strong>word2</strong> word3</p>"#;
+    assert_eq!(doc.as_html_string(), expect);
+
+    //the selection is restored
+    assert!(cursor.is_selection());
+    assert_eq!(cursor.get_retain_index(), 6);
+    assert_eq!(cursor.selection_length(), 5);
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn protected_range_insert_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("protected_range_insert_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("free fixed free\n");
+    doc.apply_delta(delta)?;
+    let id = doc.protect_range(5, 5);
+
+    //typing inside the protected range
+    set_cursor_selection(doc.get_cursor(), 7, 0);
+    assert!(doc.apply_operation(DeltaOperation::insert("x")).is_err()); //Error::RangeProtected
+    assert_eq!(doc.as_html_string(), "<p>free fixed free</p><p><br></p>");
+
+    //typing at the boundaries is allowed
+    set_cursor_selection(doc.get_cursor(), 10, 0);
+    doc.apply_operation(DeltaOperation::insert("!"))?;
+    assert_eq!(doc.get_protected_ranges(), vec![(5, 5)]);
+
+    //insert before the range shifts the range
+    set_cursor_selection(doc.get_cursor(), 5, 0);
+    doc.apply_operation(DeltaOperation::insert("new "))?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>free new fixed! free</p><p><br></p>"
+    );
+    assert_eq!(doc.get_protected_ranges(), vec![(9, 5)]);
+    set_cursor_selection(doc.get_cursor(), 10, 0);
+    assert!(doc.apply_operation(DeltaOperation::insert("x")).is_err()); //Error::RangeProtected
+
+    doc.unprotect(id);
+    assert!(doc.get_protected_ranges().is_empty());
+    doc.apply_operation(DeltaOperation::insert("x"))?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p>free new fxixed! free</p><p><br></p>"
+    );
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn protected_range_delete_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("protected_range_delete_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("free fixed free\n");
+    doc.apply_delta(delta)?;
+    doc.protect_range(5, 5);
+
+    //deletion across the protected boundary
+    set_cursor_selection(doc.get_cursor(), 3, 0);
+    assert!(doc.apply_operation(DeltaOperation::delete(3)).is_err()); //Error::RangeProtected
+    set_cursor_selection(doc.get_cursor(), 9, 0);
+    assert!(doc.apply_operation(DeltaOperation::delete(2)).is_err()); //Error::RangeProtected
+    assert_eq!(doc.as_html_string(), "<p>free fixed free</p><p><br></p>");
+
+    //a retain over the protected range is allowed
+    let mut delta = Delta::default();
+    let mut attr = Attributes::default();
+    attr.insert("bold", true);
+    delta.retain_attr(15, attr);
+    doc.apply_delta(delta)?;
+    assert_eq!(
+        doc.as_html_string(),
+        "<p><strong>free fixed free</strong></p><p><br></p>"
+    );
+
+    //delete before the range shifts the range
+    set_cursor_selection(doc.get_cursor(), 0, 0);
+    doc.apply_operation(DeltaOperation::delete(5))?;
+    assert_eq!(doc.get_protected_ranges(), vec![(0, 5)]);
+    assert!(doc.apply_operation(DeltaOperation::delete(1)).is_err()); //Error::RangeProtected
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn protected_range_close_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("protected_range_close_test");
+    doc.open()?;
+
+    let mut delta = Delta::default();
+    delta.insert("fixed\n");
+    doc.apply_delta(delta)?;
+    doc.protect_range(0, 5);
+
+    //the new content is not protected
+    doc.open()?;
+    assert!(doc.get_protected_ranges().is_empty());
+    doc.apply_operation(DeltaOperation::insert("free"))?;
+    assert_eq!(doc.as_html_string(), "<p>free</p>");
+    Ok(())
+}
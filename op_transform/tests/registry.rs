@@ -1,9 +1,15 @@
 use anyhow::Result;
 use core_formats::format_const::{NAME_P_BLOCK, NAME_TEXT};
 use core_formats::{P_FORMAT, TEXT_FORMAT};
+use delta::delta::Delta;
 use delta::operations::DeltaOperation;
-use op_transform::registry::Registry;
+use node_tree::tree_traverse::first_node;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::registry::{init_test_registry, Registry};
 use std::ops::Deref;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
 
 #[test]
 fn registry_block_format_test() -> Result<()> {
@@ -19,3 +25,55 @@ fn registry_block_format_test() -> Result<()> {
     assert!(!registry.is_block_fmt(&op)?);
     Ok(())
 }
+
+#[test]
+fn registry_format_for_name_test() -> Result<()> {
+    init_test_registry();
+    let registry = Registry::get_ref()?;
+
+    let format = registry.format_for_name(NAME_P_BLOCK).unwrap();
+    assert_eq!(format.format_name(), NAME_P_BLOCK);
+    assert!(!format.is_text_format());
+    let format = registry.format_for_name(NAME_TEXT).unwrap();
+    assert_eq!(format.format_name(), NAME_TEXT);
+    assert!(format.is_text_format());
+    assert!(registry.format_for_name("no-such-format").is_none());
+
+    assert_eq!(registry.all_block_format_names(), vec![NAME_P_BLOCK]);
+    assert_eq!(registry.all_line_format_names(), vec![NAME_TEXT]);
+    Ok(())
+}
+
+#[test]
+fn registry_list_formats_test() -> Result<()> {
+    init_test_registry();
+    let registry = Registry::get_ref()?;
+
+    assert_eq!(registry.list_formats(), vec![NAME_P_BLOCK, NAME_TEXT]);
+    assert!(registry.has_format(NAME_P_BLOCK));
+    assert!(registry.has_format(NAME_TEXT));
+    assert!(!registry.has_format("no-such-format"));
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn registry_format_for_node_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("registry_format_for_node_test");
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("text\n");
+    doc.apply_delta(delta)?;
+
+    let registry = Registry::get_ref()?;
+    let text = first_node(doc.get_root());
+    let format = registry.line_format_for_node(&text).unwrap();
+    assert_eq!(format.format_name(), NAME_TEXT);
+    assert!(registry.block_format_for_node(&text).is_none());
+
+    let block = text.get_parent().unwrap();
+    let format = registry.block_format_for_node(&block).unwrap();
+    assert_eq!(format.format_name(), NAME_P_BLOCK);
+    assert!(registry.line_format_for_node(&block).is_none());
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anyhow::Result;
+use delta::attributes::Attributes;
+use delta::operations::DeltaOperation;
+use op_transform::doc_root::{DocumentRoot, EditorMode};
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn snapshot_restore_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("snapshot_restore_test");
+    doc.open()?;
+    doc.apply_operation(DeltaOperation::insert("Hello\nworld"))?;
+    let mut bold = Attributes::default();
+    bold.insert("bold", true);
+    set_cursor_selection(doc.get_cursor(), 0, 0);
+    let mut op = DeltaOperation::retain(5);
+    op.set_attributes(bold);
+    doc.apply_operation(op)?;
+    set_cursor_selection(doc.get_cursor(), 8, 0);
+
+    let snapshot = doc.snapshot();
+    let html = doc.as_html_string();
+    assert_eq!(html, "<p><strong>Hello</strong></p><p>world</p>");
+    assert_eq!(snapshot.cursor_retain, 8);
+
+    //more edits
+    doc.apply_operation(DeltaOperation::insert("wide "))?;
+    set_cursor_selection(doc.get_cursor(), 0, 0);
+    doc.apply_operation(DeltaOperation::delete(3))?;
+    assert_ne!(doc.as_html_string(), html);
+
+    doc.restore(snapshot.clone())?;
+    assert_eq!(doc.as_html_string(), html);
+    assert_eq!(doc.to_delta().get_ops_ref(), snapshot.delta.get_ops_ref());
+    assert_eq!(doc.get_cursor().get_retain_index(), 8);
+    assert!(doc.get_mode() == &EditorMode::Edit);
+    assert_eq!(doc.get_undo_history().undo_len(), 0);
+    Ok(())
+}
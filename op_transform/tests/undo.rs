@@ -0,0 +1,107 @@
+use anyhow::Result;
+use delta::delta::Delta;
+use delta::operations::DeltaOperation;
+use op_transform::doc_root::DocumentRoot;
+use op_transform::op_retain::set_cursor_selection;
+use op_transform::registry::init_test_registry;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn create_test_doc(doc: &mut DocumentRoot) -> Result<()> {
+    doc.open()?;
+    let mut delta = Delta::default();
+    delta.insert("hello\n");
+    doc.apply_delta(delta)?;
+    doc.clear_undo_history();
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn undo_insert_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("undo_insert_test");
+    create_test_doc(&mut doc)?;
+    let before = doc.to_delta();
+
+    set_cursor_selection(doc.get_cursor(), 5, 0);
+    doc.apply_operation(DeltaOperation::insert(" world"))?;
+    assert_eq!(doc.as_html_string(), "<p>hello world</p><p><br></p>");
+    assert_eq!(doc.get_undo_history().undo_len(), 1);
+
+    assert!(doc.undo()?);
+    assert_eq!(doc.as_html_string(), "<p>hello</p><p><br></p>");
+    assert_eq!(doc.to_delta(), before);
+    assert_eq!(doc.get_undo_history().redo_len(), 1);
+
+    assert!(doc.redo()?);
+    assert_eq!(doc.as_html_string(), "<p>hello world</p><p><br></p>");
+
+    //nothing left to redo
+    assert!(!doc.redo()?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn undo_delete_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("undo_delete_test");
+    create_test_doc(&mut doc)?;
+
+    set_cursor_selection(doc.get_cursor(), 1, 0);
+    doc.apply_operation(DeltaOperation::delete(3))?;
+    assert_eq!(doc.as_html_string(), "<p>ho</p><p><br></p>");
+
+    assert!(doc.undo()?);
+    assert_eq!(doc.as_html_string(), "<p>hello</p><p><br></p>");
+
+    //nothing left to undo
+    assert!(!doc.undo()?);
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn undo_insert_clears_redo_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("undo_insert_clears_redo_test");
+    create_test_doc(&mut doc)?;
+
+    set_cursor_selection(doc.get_cursor(), 5, 0);
+    doc.apply_operation(DeltaOperation::insert("!"))?;
+    assert!(doc.undo()?);
+    assert_eq!(doc.get_undo_history().redo_len(), 1);
+
+    //a new change makes the redo of the old change invalid
+    set_cursor_selection(doc.get_cursor(), 0, 0);
+    doc.apply_operation(DeltaOperation::insert("x"))?;
+    assert_eq!(doc.as_html_string(), "<p>xhello</p><p><br></p>");
+    assert_eq!(doc.get_undo_history().redo_len(), 0);
+    assert!(!doc.redo()?);
+    assert_eq!(doc.as_html_string(), "<p>xhello</p><p><br></p>");
+
+    assert!(doc.undo()?);
+    assert_eq!(doc.as_html_string(), "<p>hello</p><p><br></p>");
+    assert!(doc.redo()?);
+    assert_eq!(doc.as_html_string(), "<p>xhello</p><p><br></p>");
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+fn undo_limit_test() -> Result<()> {
+    init_test_registry();
+    let mut doc = DocumentRoot::new("undo_limit_test");
+    create_test_doc(&mut doc)?;
+    doc.set_undo_limit(2);
+
+    for c in ["a", "b", "c"] {
+        set_cursor_selection(doc.get_cursor(), 0, 0);
+        doc.apply_operation(DeltaOperation::insert(c))?;
+    }
+    assert_eq!(doc.get_undo_history().undo_len(), 2);
+
+    assert!(doc.undo()?);
+    assert!(doc.undo()?);
+    assert!(!doc.undo()?);
+    assert_eq!(doc.as_html_string(), "<p>ahello</p><p><br></p>");
+    Ok(())
+}